@@ -0,0 +1,726 @@
+// <auto-generated>
+//     @generated by rocketpack-compiler
+// </auto-generated>
+#nullable enable
+
+// Encoder and decoder for the rocketpack wire format, byte-compatible with `RocketPackBytesEncoder` /
+// `RocketPackBytesDecoder`. The methods mirror the Rust `RocketPackEncoder` / `RocketPackDecoder` traits.
+
+using System;
+using System.Buffers.Binary;
+using System.Collections.Generic;
+using System.Text;
+
+namespace Omnius.RocketPack.Runtime
+{
+    public sealed class RocketPackEncoderException : Exception
+    {
+        public RocketPackEncoderException(string message)
+            : base(message)
+        {
+        }
+    }
+
+    public sealed class RocketPackDecoderException : Exception
+    {
+        public RocketPackDecoderException(string message)
+            : base(message)
+        {
+        }
+    }
+
+    public sealed class RocketPackEncoder
+    {
+        private const ulong TagPositiveBignum = 2;
+        private const ulong TagNegativeBignum = 3;
+
+        private byte[] _buf = new byte[64];
+        private int _len;
+
+        public static byte[] Encode<T>(T value, Action<RocketPackEncoder, T> pack)
+        {
+            var encoder = new RocketPackEncoder();
+            pack(encoder, value);
+            return encoder.ToArray();
+        }
+
+        public byte[] ToArray()
+        {
+            var result = new byte[_len];
+            Array.Copy(_buf, result, _len);
+            return result;
+        }
+
+        public void WriteBool(bool value) => WriteByte(value ? (byte)0xf5 : (byte)0xf4);
+
+        public void WriteU8(byte value) => WriteHead(0, value);
+
+        public void WriteU16(ushort value) => WriteHead(0, value);
+
+        public void WriteU32(uint value) => WriteHead(0, value);
+
+        public void WriteU64(ulong value) => WriteHead(0, value);
+
+        public void WriteI8(sbyte value) => WriteI64(value);
+
+        public void WriteI16(short value) => WriteI64(value);
+
+        public void WriteI32(int value) => WriteI64(value);
+
+        public void WriteI64(long value)
+        {
+            if (value >= 0)
+            {
+                WriteHead(0, (ulong)value);
+            }
+            else
+            {
+                WriteHead(1, (ulong)(-1 - value));
+            }
+        }
+
+#if NET7_0_OR_GREATER
+        // Values that fit in 64 bits are plain integers, larger ones a positive bignum (tag 2).
+        public void WriteU128(UInt128 value)
+        {
+            if (value <= ulong.MaxValue)
+            {
+                WriteHead(0, (ulong)value);
+                return;
+            }
+
+            WriteTag(TagPositiveBignum);
+            WriteBytes(BignumBytes(value));
+        }
+
+        // Values in the `long` range are plain integers, others a positive or negative bignum (tag 2 or 3).
+        public void WriteI128(Int128 value)
+        {
+            if (value >= long.MinValue && value <= long.MaxValue)
+            {
+                WriteI64((long)value);
+                return;
+            }
+            if (value >= Int128.Zero)
+            {
+                WriteU128((UInt128)value);
+                return;
+            }
+
+            WriteTag(TagNegativeBignum);
+            WriteBytes(BignumBytes((UInt128)(-1 - value)));
+        }
+
+        // Big-endian bytes without leading zeros, as bignums are written in preferred serialization.
+        private static byte[] BignumBytes(UInt128 value)
+        {
+            var bytes = new List<byte>();
+            while (value != UInt128.Zero)
+            {
+                bytes.Insert(0, (byte)(value & byte.MaxValue));
+                value >>= 8;
+            }
+            return bytes.ToArray();
+        }
+#endif
+
+#if NET5_0_OR_GREATER
+        public void WriteF16(Half value)
+        {
+            Reserve(3);
+            _buf[_len++] = 0xf9;
+            BinaryPrimitives.WriteInt16BigEndian(_buf.AsSpan(_len), BitConverter.HalfToInt16Bits(value));
+            _len += 2;
+        }
+#endif
+
+        public void WriteF32(float value)
+        {
+            Reserve(5);
+            _buf[_len++] = 0xfa;
+            BinaryPrimitives.WriteInt32BigEndian(_buf.AsSpan(_len), BitConverter.SingleToInt32Bits(value));
+            _len += 4;
+        }
+
+        public void WriteF64(double value)
+        {
+            Reserve(9);
+            _buf[_len++] = 0xfb;
+            BinaryPrimitives.WriteInt64BigEndian(_buf.AsSpan(_len), BitConverter.DoubleToInt64Bits(value));
+            _len += 8;
+        }
+
+        public void WriteBytes(byte[] value)
+        {
+            WriteHead(2, (ulong)value.Length);
+            WriteRaw(value);
+        }
+
+        public void WriteString(string value)
+        {
+            var bytes = Encoding.UTF8.GetBytes(value);
+            WriteHead(3, (ulong)bytes.Length);
+            WriteRaw(bytes);
+        }
+
+        public void WriteArray(int len) => WriteHead(4, (ulong)len);
+
+        public void WriteMap(int len) => WriteHead(5, (ulong)len);
+
+        public void WriteNull() => WriteByte(0xf6);
+
+        public void WriteTag(ulong tag) => WriteHead(6, tag);
+
+        public void WriteStruct<T>(T value, Action<RocketPackEncoder, T> pack) => pack(this, value);
+
+        // Writes the major type with the argument in its shortest form.
+        private void WriteHead(int major, ulong value)
+        {
+            var prefix = (byte)(major << 5);
+            if (value <= 23)
+            {
+                WriteByte((byte)(prefix | (byte)value));
+            }
+            else if (value <= byte.MaxValue)
+            {
+                Reserve(2);
+                _buf[_len++] = (byte)(prefix | 24);
+                _buf[_len++] = (byte)value;
+            }
+            else if (value <= ushort.MaxValue)
+            {
+                Reserve(3);
+                _buf[_len++] = (byte)(prefix | 25);
+                BinaryPrimitives.WriteUInt16BigEndian(_buf.AsSpan(_len), (ushort)value);
+                _len += 2;
+            }
+            else if (value <= uint.MaxValue)
+            {
+                Reserve(5);
+                _buf[_len++] = (byte)(prefix | 26);
+                BinaryPrimitives.WriteUInt32BigEndian(_buf.AsSpan(_len), (uint)value);
+                _len += 4;
+            }
+            else
+            {
+                Reserve(9);
+                _buf[_len++] = (byte)(prefix | 27);
+                BinaryPrimitives.WriteUInt64BigEndian(_buf.AsSpan(_len), value);
+                _len += 8;
+            }
+        }
+
+        private void WriteByte(byte value)
+        {
+            Reserve(1);
+            _buf[_len++] = value;
+        }
+
+        private void WriteRaw(byte[] value)
+        {
+            Reserve(value.Length);
+            Array.Copy(value, 0, _buf, _len, value.Length);
+            _len += value.Length;
+        }
+
+        private void Reserve(int additional)
+        {
+            var required = _len + additional;
+            if (required <= _buf.Length)
+            {
+                return;
+            }
+
+            var capacity = _buf.Length * 2;
+            while (capacity < required)
+            {
+                capacity *= 2;
+            }
+            Array.Resize(ref _buf, capacity);
+        }
+    }
+
+    public sealed class RocketPackDecoder
+    {
+        private const ulong TagPositiveBignum = 2;
+        private const ulong TagNegativeBignum = 3;
+        private const int MaxDepth = 128;
+
+        private static readonly UTF8Encoding StrictUtf8 = new UTF8Encoding(false, true);
+
+        private readonly byte[] _buf;
+        private int _pos;
+        private int _depth;
+
+        public RocketPackDecoder(byte[] buf)
+        {
+            _buf = buf;
+        }
+
+        public static T Decode<T>(ReadOnlySpan<byte> bytes, Func<RocketPackDecoder, T> unpack) => unpack(new RocketPackDecoder(bytes.ToArray()));
+
+        public int Position => _pos;
+
+        public int Remaining => _buf.Length - _pos;
+
+        public bool ReadBool()
+        {
+            var position = _pos;
+            switch (ReadByte())
+            {
+                case 0xf4:
+                    return false;
+                case 0xf5:
+                    return true;
+                default:
+                    throw Mismatch(position, "bool");
+            }
+        }
+
+        public byte ReadU8() => (byte)ReadUnsigned(byte.MaxValue, "u8");
+
+        public ushort ReadU16() => (ushort)ReadUnsigned(ushort.MaxValue, "u16");
+
+        public uint ReadU32() => (uint)ReadUnsigned(uint.MaxValue, "u32");
+
+        public ulong ReadU64() => ReadUnsigned(ulong.MaxValue, "u64");
+
+        public sbyte ReadI8() => (sbyte)ReadSigned(sbyte.MinValue, sbyte.MaxValue, "i8");
+
+        public short ReadI16() => (short)ReadSigned(short.MinValue, short.MaxValue, "i16");
+
+        public int ReadI32() => (int)ReadSigned(int.MinValue, int.MaxValue, "i32");
+
+        public long ReadI64() => ReadSigned(long.MinValue, long.MaxValue, "i64");
+
+#if NET7_0_OR_GREATER
+        // Reads a plain unsigned integer or a positive bignum (tag 2).
+        public UInt128 ReadU128()
+        {
+            var position = _pos;
+            if (PeekMajor() != 6)
+            {
+                return ReadU64();
+            }
+
+            if (ReadTag() != TagPositiveBignum)
+            {
+                throw Mismatch(position, "u128");
+            }
+            return BignumFromBytes(position, ReadBytes(), "u128");
+        }
+
+        // Reads a plain integer or a positive or negative bignum (tag 2 or 3).
+        public Int128 ReadI128()
+        {
+            var position = _pos;
+            var major = PeekMajor();
+            if (major == 0)
+            {
+                return ReadU64();
+            }
+            if (major == 1)
+            {
+                var (_, argument) = ReadHead();
+                return -1 - (Int128)argument;
+            }
+            if (major != 6)
+            {
+                throw Mismatch(position, "i128");
+            }
+
+            var tag = ReadTag();
+            if (tag != TagPositiveBignum && tag != TagNegativeBignum)
+            {
+                throw Mismatch(position, "i128");
+            }
+            var magnitude = BignumFromBytes(position, ReadBytes(), "i128");
+            if (magnitude > (UInt128)Int128.MaxValue)
+            {
+                throw OutOfRange(position, "i128");
+            }
+            return tag == TagPositiveBignum ? (Int128)magnitude : -1 - (Int128)magnitude;
+        }
+
+        private static UInt128 BignumFromBytes(int position, byte[] bytes, string name)
+        {
+            var value = UInt128.Zero;
+            foreach (var b in bytes)
+            {
+                if (value >> 120 != UInt128.Zero)
+                {
+                    throw OutOfRange(position, name);
+                }
+                value = (value << 8) | b;
+            }
+            return value;
+        }
+#endif
+
+#if NET5_0_OR_GREATER
+        public Half ReadF16()
+        {
+            var position = _pos;
+            if (ReadByte() != 0xf9)
+            {
+                throw Mismatch(position, "f16");
+            }
+            return ReadHalf();
+        }
+
+        private Half ReadHalf() => BitConverter.Int16BitsToHalf(BinaryPrimitives.ReadInt16BigEndian(ReadRaw(2)));
+#endif
+
+        // Narrower floats are widened, as the Rust decoder does.
+        public float ReadF32()
+        {
+            var position = _pos;
+            switch (ReadByte())
+            {
+#if NET5_0_OR_GREATER
+                case 0xf9:
+                    return (float)ReadHalf();
+#endif
+                case 0xfa:
+                    return BitConverter.Int32BitsToSingle(BinaryPrimitives.ReadInt32BigEndian(ReadRaw(4)));
+                default:
+                    throw Mismatch(position, "f32");
+            }
+        }
+
+        public double ReadF64()
+        {
+            var position = _pos;
+            switch (ReadByte())
+            {
+#if NET5_0_OR_GREATER
+                case 0xf9:
+                    return (double)ReadHalf();
+#endif
+                case 0xfa:
+                    return BitConverter.Int32BitsToSingle(BinaryPrimitives.ReadInt32BigEndian(ReadRaw(4)));
+                case 0xfb:
+                    return BitConverter.Int64BitsToDouble(BinaryPrimitives.ReadInt64BigEndian(ReadRaw(8)));
+                default:
+                    throw Mismatch(position, "f64");
+            }
+        }
+
+        public byte[] ReadBytes()
+        {
+            var len = ReadLength(2, "bytes", 1);
+            return ReadRaw(len).ToArray();
+        }
+
+        public string ReadString()
+        {
+            var position = _pos;
+            var len = ReadLength(3, "string", 1);
+            try
+            {
+                return StrictUtf8.GetString(_buf, ReadOffset(len), len);
+            }
+            catch (ArgumentException)
+            {
+                throw new RocketPackDecoderException($"invalid utf-8 (position: {position})");
+            }
+        }
+
+        public int ReadArray() => ReadLength(4, "array", 1);
+
+        public int ReadMap() => ReadLength(5, "map", 2);
+
+        public void ReadNull()
+        {
+            var position = _pos;
+            if (ReadByte() != 0xf6)
+            {
+                throw Mismatch(position, "null");
+            }
+        }
+
+        public ulong ReadTag()
+        {
+            var position = _pos;
+            var (major, argument) = ReadHead();
+            if (major != 6)
+            {
+                throw Mismatch(position, "tag");
+            }
+            return argument;
+        }
+
+        public T ReadStruct<T>(Func<RocketPackDecoder, T> unpack)
+        {
+            if (_depth >= MaxDepth)
+            {
+                throw new RocketPackDecoderException($"depth limit exceeded (position: {_pos}, limit: {MaxDepth})");
+            }
+
+            _depth++;
+            var value = unpack(this);
+            _depth--;
+            return value;
+        }
+
+        public void SkipField()
+        {
+            long remain = 1;
+
+            while (remain > 0)
+            {
+                var position = _pos;
+                var major = PeekMajor();
+                var info = _buf[_pos] & 0x1f;
+
+                if (major == 7)
+                {
+                    _pos++;
+                    switch (info)
+                    {
+                        case 20:
+                        case 21:
+                        case 22:
+                            break;
+                        case 25:
+                            ReadRaw(2);
+                            break;
+                        case 26:
+                            ReadRaw(4);
+                            break;
+                        case 27:
+                            ReadRaw(8);
+                            break;
+                        default:
+                            throw Mismatch(position, "field");
+                    }
+                }
+                else
+                {
+                    var (_, argument) = ReadHead();
+                    switch (major)
+                    {
+                        case 2:
+                        case 3:
+                            ReadRaw(ToLength(position, argument));
+                            break;
+                        case 4:
+                            remain += ToLength(position, argument);
+                            break;
+                        case 5:
+                            remain += 2L * ToLength(position, argument);
+                            break;
+                        // The tagged value follows the tag and still has to be skipped.
+                        case 6:
+                            remain += 1;
+                            break;
+                    }
+                }
+
+                remain--;
+            }
+        }
+
+        // Reads a collection or string header, rejecting lengths the remaining input cannot hold.
+        private int ReadLength(int expectedMajor, string name, int minItemSize)
+        {
+            var position = _pos;
+            var (major, argument) = ReadHead();
+            if (major != expectedMajor)
+            {
+                throw Mismatch(position, name);
+            }
+
+            var len = ToLength(position, argument);
+            if ((long)len * minItemSize > Remaining)
+            {
+                throw new RocketPackDecoderException($"unexpected eof (position: {position})");
+            }
+            return len;
+        }
+
+        private ulong ReadUnsigned(ulong max, string name)
+        {
+            var position = _pos;
+            var (major, argument) = ReadHead();
+            if (major != 0)
+            {
+                throw Mismatch(position, name);
+            }
+            if (argument > max)
+            {
+                throw OutOfRange(position, name);
+            }
+            return argument;
+        }
+
+        private long ReadSigned(long min, long max, string name)
+        {
+            var position = _pos;
+            var (major, argument) = ReadHead();
+            if (major != 0 && major != 1)
+            {
+                throw Mismatch(position, name);
+            }
+            if (argument > long.MaxValue)
+            {
+                throw OutOfRange(position, name);
+            }
+
+            var value = major == 0 ? (long)argument : -1 - (long)argument;
+            if (value < min || value > max)
+            {
+                throw OutOfRange(position, name);
+            }
+            return value;
+        }
+
+        // Reads an initial byte of major type 0-6 and its argument.
+        private (int Major, ulong Argument) ReadHead()
+        {
+            var position = _pos;
+            var initial = ReadByte();
+            var major = initial >> 5;
+            var info = initial & 0x1f;
+            if (major == 7)
+            {
+                throw Mismatch(position, "integer");
+            }
+
+            if (info <= 23)
+            {
+                return (major, (ulong)info);
+            }
+            switch (info)
+            {
+                case 24:
+                    return (major, ReadByte());
+                case 25:
+                    return (major, BinaryPrimitives.ReadUInt16BigEndian(ReadRaw(2)));
+                case 26:
+                    return (major, BinaryPrimitives.ReadUInt32BigEndian(ReadRaw(4)));
+                case 27:
+                    return (major, BinaryPrimitives.ReadUInt64BigEndian(ReadRaw(8)));
+                default:
+                    throw Mismatch(position, "integer");
+            }
+        }
+
+        private int PeekMajor()
+        {
+            if (_pos >= _buf.Length)
+            {
+                throw new RocketPackDecoderException("unexpected eof");
+            }
+            return _buf[_pos] >> 5;
+        }
+
+        private byte ReadByte()
+        {
+            if (_pos >= _buf.Length)
+            {
+                throw new RocketPackDecoderException("unexpected eof");
+            }
+            return _buf[_pos++];
+        }
+
+        private ReadOnlySpan<byte> ReadRaw(int len) => new ReadOnlySpan<byte>(_buf, ReadOffset(len), len);
+
+        // Consumes `len` bytes and returns where they start.
+        private int ReadOffset(int len)
+        {
+            if (len > Remaining)
+            {
+                throw new RocketPackDecoderException($"unexpected eof (position: {_pos})");
+            }
+            var offset = _pos;
+            _pos += len;
+            return offset;
+        }
+
+        private static int ToLength(int position, ulong value)
+        {
+            if (value > int.MaxValue)
+            {
+                throw new RocketPackDecoderException($"length overflow (position: {position})");
+            }
+            return (int)value;
+        }
+
+        private static RocketPackDecoderException Mismatch(int position, string name) =>
+            new RocketPackDecoderException($"mismatch field type (position: {position}, expected: {name})");
+
+        private static RocketPackDecoderException OutOfRange(int position, string name) =>
+            new RocketPackDecoderException($"{name} out of range (position: {position})");
+    }
+
+    // Comparers matching the order of Rust `BTreeMap` keys, so maps are written in the same order.
+    public static class RocketPackComparers
+    {
+        public static IComparer<string> String { get; } = new CodePointComparer();
+
+        public static IComparer<byte[]> Bytes { get; } = new ByteComparer();
+
+        // Code point order is UTF-8 byte order, unlike the UTF-16 order of `StringComparer.Ordinal`.
+        private sealed class CodePointComparer : IComparer<string>
+        {
+            public int Compare(string? x, string? y)
+            {
+                if (ReferenceEquals(x, y))
+                {
+                    return 0;
+                }
+                if (x is null)
+                {
+                    return -1;
+                }
+                if (y is null)
+                {
+                    return 1;
+                }
+
+                var len = Math.Min(x.Length, y.Length);
+                for (var i = 0; i < len; i++)
+                {
+                    if (x[i] != y[i])
+                    {
+                        return CodePointOrder(x[i]).CompareTo(CodePointOrder(y[i]));
+                    }
+                }
+                return x.Length.CompareTo(y.Length);
+            }
+
+            // Surrogates stand for code points above U+FFFF, so they move after the rest of the BMP.
+            private static int CodePointOrder(char c) => c >= 0xe000 ? c - 0x800 : c >= 0xd800 ? c + 0x2000 : c;
+        }
+
+        private sealed class ByteComparer : IComparer<byte[]>
+        {
+            public int Compare(byte[]? x, byte[]? y)
+            {
+                if (ReferenceEquals(x, y))
+                {
+                    return 0;
+                }
+                if (x is null)
+                {
+                    return -1;
+                }
+                if (y is null)
+                {
+                    return 1;
+                }
+
+                var len = Math.Min(x.Length, y.Length);
+                for (var i = 0; i < len; i++)
+                {
+                    if (x[i] != y[i])
+                    {
+                        return x[i].CompareTo(y[i]);
+                    }
+                }
+                return x.Length.CompareTo(y.Length);
+            }
+        }
+    }
+}
@@ -0,0 +1,1239 @@
+// <auto-generated>
+//     @generated by rocketpack-compiler
+// </auto-generated>
+#nullable enable
+
+using System;
+using System.Collections.Generic;
+using Omnius.RocketPack.Runtime;
+
+namespace Company.Project.Omnius.Demo.V1
+{
+    using ByteList = List<byte[]>;
+
+    public sealed partial record PrimitiveShowcase1
+    {
+        public bool BoolField { get; set; }
+        public byte U8Field { get; set; }
+        public short I16Field { get; set; }
+        public int I32Field { get; set; }
+        public long I64Field { get; set; }
+        public ushort U16Field { get; set; }
+        public uint U32Field { get; set; }
+        public ulong U64Field { get; set; }
+        public Half F16Field { get; set; }
+        public float F32Field { get; set; }
+        public double F64Field { get; set; }
+        public string StringField { get; set; } = string.Empty;
+        public byte[] BytesField { get; set; } = Array.Empty<byte>();
+        public List<byte> VecField1 { get; set; } = new List<byte>();
+        public List<string> VecField2 { get; set; } = new List<string>();
+        public List<byte[]> VecField3 { get; set; } = new List<byte[]>();
+        public SortedDictionary<byte, string> MapField1 { get; set; } = new SortedDictionary<byte, string>();
+        public SortedDictionary<string, byte> MapField2 { get; set; } = new SortedDictionary<string, byte>(RocketPackComparers.String);
+        public SortedDictionary<string, List<uint>> MapVecField1 { get; set; } = new SortedDictionary<string, List<uint>>(RocketPackComparers.String);
+        public SortedDictionary<string, List<byte[]>> MapVecField2 { get; set; } = new SortedDictionary<string, List<byte[]>>(RocketPackComparers.String);
+        public long[] SliceField { get; set; } = new long[4];
+        public SimpleMessage StructField { get; set; } = null!;
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static PrimitiveShowcase1 Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, PrimitiveShowcase1 value)
+        {
+            encoder.WriteMap(22);
+            encoder.WriteU64(1);
+            encoder.WriteBool(value.BoolField);
+            encoder.WriteU64(2);
+            encoder.WriteU8(value.U8Field);
+            encoder.WriteU64(3);
+            encoder.WriteI16(value.I16Field);
+            encoder.WriteU64(4);
+            encoder.WriteI32(value.I32Field);
+            encoder.WriteU64(5);
+            encoder.WriteI64(value.I64Field);
+            encoder.WriteU64(6);
+            encoder.WriteU16(value.U16Field);
+            encoder.WriteU64(7);
+            encoder.WriteU32(value.U32Field);
+            encoder.WriteU64(8);
+            encoder.WriteU64(value.U64Field);
+            encoder.WriteU64(9);
+            encoder.WriteF16(value.F16Field);
+            encoder.WriteU64(10);
+            encoder.WriteF32(value.F32Field);
+            encoder.WriteU64(11);
+            encoder.WriteF64(value.F64Field);
+            encoder.WriteU64(12);
+            encoder.WriteString(value.StringField);
+            encoder.WriteU64(13);
+            encoder.WriteBytes(value.BytesField);
+            encoder.WriteU64(14);
+            encoder.WriteArray(value.VecField1.Count);
+            foreach (var __item_0 in value.VecField1)
+            {
+                encoder.WriteU8(__item_0);
+            }
+            encoder.WriteU64(15);
+            encoder.WriteArray(value.VecField2.Count);
+            foreach (var __item_1 in value.VecField2)
+            {
+                encoder.WriteString(__item_1);
+            }
+            encoder.WriteU64(16);
+            encoder.WriteArray(value.VecField3.Count);
+            foreach (var __item_2 in value.VecField3)
+            {
+                encoder.WriteBytes(__item_2);
+            }
+            encoder.WriteU64(17);
+            encoder.WriteMap(value.MapField1.Count);
+            foreach (var __entry_3 in value.MapField1)
+            {
+                encoder.WriteU8(__entry_3.Key);
+                encoder.WriteString(__entry_3.Value);
+            }
+            encoder.WriteU64(18);
+            encoder.WriteMap(value.MapField2.Count);
+            foreach (var __entry_4 in value.MapField2)
+            {
+                encoder.WriteString(__entry_4.Key);
+                encoder.WriteU8(__entry_4.Value);
+            }
+            encoder.WriteU64(19);
+            encoder.WriteMap(value.MapVecField1.Count);
+            foreach (var __entry_5 in value.MapVecField1)
+            {
+                encoder.WriteString(__entry_5.Key);
+                encoder.WriteArray(__entry_5.Value.Count);
+                foreach (var __item_6 in __entry_5.Value)
+                {
+                    encoder.WriteU32(__item_6);
+                }
+            }
+            encoder.WriteU64(20);
+            encoder.WriteMap(value.MapVecField2.Count);
+            foreach (var __entry_7 in value.MapVecField2)
+            {
+                encoder.WriteString(__entry_7.Key);
+                encoder.WriteArray(__entry_7.Value.Count);
+                foreach (var __item_8 in __entry_7.Value)
+                {
+                    encoder.WriteBytes(__item_8);
+                }
+            }
+            encoder.WriteU64(21);
+            if (value.SliceField.Length != 4) throw new RocketPackEncoderException("array length mismatch: slice_field");
+            encoder.WriteArray(value.SliceField.Length);
+            foreach (var __item_9 in value.SliceField)
+            {
+                encoder.WriteI64(__item_9);
+            }
+            encoder.WriteU64(22);
+            encoder.WriteStruct(value.StructField, SimpleMessage.Pack);
+        }
+
+        public static PrimitiveShowcase1 Unpack(RocketPackDecoder decoder)
+        {
+            bool? boolField = null;
+            byte? u8Field = null;
+            short? i16Field = null;
+            int? i32Field = null;
+            long? i64Field = null;
+            ushort? u16Field = null;
+            uint? u32Field = null;
+            ulong? u64Field = null;
+            Half? f16Field = null;
+            float? f32Field = null;
+            double? f64Field = null;
+            string? stringField = null;
+            byte[]? bytesField = null;
+            List<byte>? vecField1 = null;
+            List<string>? vecField2 = null;
+            List<byte[]>? vecField3 = null;
+            SortedDictionary<byte, string>? mapField1 = null;
+            SortedDictionary<string, byte>? mapField2 = null;
+            SortedDictionary<string, List<uint>>? mapVecField1 = null;
+            SortedDictionary<string, List<byte[]>>? mapVecField2 = null;
+            long[]? sliceField = null;
+            SimpleMessage? structField = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        boolField = decoder.ReadBool();
+                        break;
+                    }
+                    case 2:
+                    {
+                        u8Field = decoder.ReadU8();
+                        break;
+                    }
+                    case 3:
+                    {
+                        i16Field = decoder.ReadI16();
+                        break;
+                    }
+                    case 4:
+                    {
+                        i32Field = decoder.ReadI32();
+                        break;
+                    }
+                    case 5:
+                    {
+                        i64Field = decoder.ReadI64();
+                        break;
+                    }
+                    case 6:
+                    {
+                        u16Field = decoder.ReadU16();
+                        break;
+                    }
+                    case 7:
+                    {
+                        u32Field = decoder.ReadU32();
+                        break;
+                    }
+                    case 8:
+                    {
+                        u64Field = decoder.ReadU64();
+                        break;
+                    }
+                    case 9:
+                    {
+                        f16Field = decoder.ReadF16();
+                        break;
+                    }
+                    case 10:
+                    {
+                        f32Field = decoder.ReadF32();
+                        break;
+                    }
+                    case 11:
+                    {
+                        f64Field = decoder.ReadF64();
+                        break;
+                    }
+                    case 12:
+                    {
+                        stringField = decoder.ReadString();
+                        break;
+                    }
+                    case 13:
+                    {
+                        bytesField = decoder.ReadBytes();
+                        break;
+                    }
+                    case 14:
+                    {
+                        var __count_2 = decoder.ReadArray();
+                        var __values_3 = new List<byte>();
+                        for (var __i_4 = 0; __i_4 < __count_2; __i_4++)
+                        {
+                            __values_3.Add(decoder.ReadU8());
+                        }
+                        vecField1 = __values_3;
+                        break;
+                    }
+                    case 15:
+                    {
+                        var __count_5 = decoder.ReadArray();
+                        var __values_6 = new List<string>();
+                        for (var __i_7 = 0; __i_7 < __count_5; __i_7++)
+                        {
+                            __values_6.Add(decoder.ReadString());
+                        }
+                        vecField2 = __values_6;
+                        break;
+                    }
+                    case 16:
+                    {
+                        var __count_8 = decoder.ReadArray();
+                        var __values_9 = new List<byte[]>();
+                        for (var __i_10 = 0; __i_10 < __count_8; __i_10++)
+                        {
+                            __values_9.Add(decoder.ReadBytes());
+                        }
+                        vecField3 = __values_9;
+                        break;
+                    }
+                    case 17:
+                    {
+                        var __count_11 = decoder.ReadMap();
+                        var __map_12 = new SortedDictionary<byte, string>();
+                        for (var __i_13 = 0; __i_13 < __count_11; __i_13++)
+                        {
+                            var __key_14 = decoder.ReadU8();
+                            __map_12[__key_14] = decoder.ReadString();
+                        }
+                        mapField1 = __map_12;
+                        break;
+                    }
+                    case 18:
+                    {
+                        var __count_15 = decoder.ReadMap();
+                        var __map_16 = new SortedDictionary<string, byte>(RocketPackComparers.String);
+                        for (var __i_17 = 0; __i_17 < __count_15; __i_17++)
+                        {
+                            var __key_18 = decoder.ReadString();
+                            __map_16[__key_18] = decoder.ReadU8();
+                        }
+                        mapField2 = __map_16;
+                        break;
+                    }
+                    case 19:
+                    {
+                        var __count_19 = decoder.ReadMap();
+                        var __map_20 = new SortedDictionary<string, List<uint>>(RocketPackComparers.String);
+                        for (var __i_21 = 0; __i_21 < __count_19; __i_21++)
+                        {
+                            var __key_22 = decoder.ReadString();
+                            var __count_23 = decoder.ReadArray();
+                            var __values_24 = new List<uint>();
+                            for (var __i_25 = 0; __i_25 < __count_23; __i_25++)
+                            {
+                                __values_24.Add(decoder.ReadU32());
+                            }
+                            __map_20[__key_22] = __values_24;
+                        }
+                        mapVecField1 = __map_20;
+                        break;
+                    }
+                    case 20:
+                    {
+                        var __count_26 = decoder.ReadMap();
+                        var __map_27 = new SortedDictionary<string, List<byte[]>>(RocketPackComparers.String);
+                        for (var __i_28 = 0; __i_28 < __count_26; __i_28++)
+                        {
+                            var __key_29 = decoder.ReadString();
+                            var __count_30 = decoder.ReadArray();
+                            var __values_31 = new List<byte[]>();
+                            for (var __i_32 = 0; __i_32 < __count_30; __i_32++)
+                            {
+                                __values_31.Add(decoder.ReadBytes());
+                            }
+                            __map_27[__key_29] = __values_31;
+                        }
+                        mapVecField2 = __map_27;
+                        break;
+                    }
+                    case 21:
+                    {
+                        var __count_33 = decoder.ReadArray();
+                        if (__count_33 != 4) throw new RocketPackDecoderException("array length mismatch: slice_field");
+                        var __array_34 = new long[4];
+                        for (var __i_35 = 0; __i_35 < __count_33; __i_35++)
+                        {
+                            __array_34[__i_35] = decoder.ReadI64();
+                        }
+                        sliceField = __array_34;
+                        break;
+                    }
+                    case 22:
+                    {
+                        structField = decoder.ReadStruct(SimpleMessage.Unpack);
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return new PrimitiveShowcase1
+            {
+                BoolField = boolField ?? throw new RocketPackDecoderException("missing field: bool_field"),
+                U8Field = u8Field ?? throw new RocketPackDecoderException("missing field: u8_field"),
+                I16Field = i16Field ?? throw new RocketPackDecoderException("missing field: i16_field"),
+                I32Field = i32Field ?? throw new RocketPackDecoderException("missing field: i32_field"),
+                I64Field = i64Field ?? throw new RocketPackDecoderException("missing field: i64_field"),
+                U16Field = u16Field ?? throw new RocketPackDecoderException("missing field: u16_field"),
+                U32Field = u32Field ?? throw new RocketPackDecoderException("missing field: u32_field"),
+                U64Field = u64Field ?? throw new RocketPackDecoderException("missing field: u64_field"),
+                F16Field = f16Field ?? throw new RocketPackDecoderException("missing field: f16_field"),
+                F32Field = f32Field ?? throw new RocketPackDecoderException("missing field: f32_field"),
+                F64Field = f64Field ?? throw new RocketPackDecoderException("missing field: f64_field"),
+                StringField = stringField ?? throw new RocketPackDecoderException("missing field: string_field"),
+                BytesField = bytesField ?? throw new RocketPackDecoderException("missing field: bytes_field"),
+                VecField1 = vecField1 ?? throw new RocketPackDecoderException("missing field: vec_field_1"),
+                VecField2 = vecField2 ?? throw new RocketPackDecoderException("missing field: vec_field_2"),
+                VecField3 = vecField3 ?? throw new RocketPackDecoderException("missing field: vec_field_3"),
+                MapField1 = mapField1 ?? throw new RocketPackDecoderException("missing field: map_field_1"),
+                MapField2 = mapField2 ?? throw new RocketPackDecoderException("missing field: map_field_2"),
+                MapVecField1 = mapVecField1 ?? throw new RocketPackDecoderException("missing field: map_vec_field_1"),
+                MapVecField2 = mapVecField2 ?? throw new RocketPackDecoderException("missing field: map_vec_field_2"),
+                SliceField = sliceField ?? throw new RocketPackDecoderException("missing field: slice_field"),
+                StructField = structField ?? throw new RocketPackDecoderException("missing field: struct_field"),
+            };
+        }
+    }
+
+    public sealed partial record PrimitiveShowcase2
+    {
+        public bool? BoolField { get; set; }
+        public byte? U8Field { get; set; }
+        public short? I16Field { get; set; }
+        public int? I32Field { get; set; }
+        public long? I64Field { get; set; }
+        public ushort? U16Field { get; set; }
+        public uint? U32Field { get; set; }
+        public ulong? U64Field { get; set; }
+        public Half? F16Field { get; set; }
+        public float? F32Field { get; set; }
+        public double? F64Field { get; set; }
+        public string? StringField { get; set; }
+        public byte[]? BytesField { get; set; }
+        public List<byte>? VecField1 { get; set; }
+        public List<string>? VecField2 { get; set; }
+        public List<byte[]>? VecField3 { get; set; }
+        public SortedDictionary<byte, string>? MapField1 { get; set; }
+        public SortedDictionary<string, byte>? MapField2 { get; set; }
+        public SortedDictionary<string, List<uint>>? MapVecField1 { get; set; }
+        public SortedDictionary<string, List<byte[]>>? MapVecField2 { get; set; }
+        public SimpleMessage? StructField { get; set; }
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static PrimitiveShowcase2 Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, PrimitiveShowcase2 value)
+        {
+            var __count_0 = 0;
+            if (value.BoolField is not null) __count_0++;
+            if (value.U8Field is not null) __count_0++;
+            if (value.I16Field is not null) __count_0++;
+            if (value.I32Field is not null) __count_0++;
+            if (value.I64Field is not null) __count_0++;
+            if (value.U16Field is not null) __count_0++;
+            if (value.U32Field is not null) __count_0++;
+            if (value.U64Field is not null) __count_0++;
+            if (value.F16Field is not null) __count_0++;
+            if (value.F32Field is not null) __count_0++;
+            if (value.F64Field is not null) __count_0++;
+            if (value.StringField is not null) __count_0++;
+            if (value.BytesField is not null) __count_0++;
+            if (value.VecField1 is not null) __count_0++;
+            if (value.VecField2 is not null) __count_0++;
+            if (value.VecField3 is not null) __count_0++;
+            if (value.MapField1 is not null) __count_0++;
+            if (value.MapField2 is not null) __count_0++;
+            if (value.MapVecField1 is not null) __count_0++;
+            if (value.MapVecField2 is not null) __count_0++;
+            if (value.StructField is not null) __count_0++;
+            encoder.WriteMap(__count_0);
+            if (value.BoolField is { } __value_1)
+            {
+                encoder.WriteU64(1);
+                encoder.WriteBool(__value_1);
+            }
+            if (value.U8Field is { } __value_2)
+            {
+                encoder.WriteU64(2);
+                encoder.WriteU8(__value_2);
+            }
+            if (value.I16Field is { } __value_3)
+            {
+                encoder.WriteU64(3);
+                encoder.WriteI16(__value_3);
+            }
+            if (value.I32Field is { } __value_4)
+            {
+                encoder.WriteU64(4);
+                encoder.WriteI32(__value_4);
+            }
+            if (value.I64Field is { } __value_5)
+            {
+                encoder.WriteU64(5);
+                encoder.WriteI64(__value_5);
+            }
+            if (value.U16Field is { } __value_6)
+            {
+                encoder.WriteU64(6);
+                encoder.WriteU16(__value_6);
+            }
+            if (value.U32Field is { } __value_7)
+            {
+                encoder.WriteU64(7);
+                encoder.WriteU32(__value_7);
+            }
+            if (value.U64Field is { } __value_8)
+            {
+                encoder.WriteU64(8);
+                encoder.WriteU64(__value_8);
+            }
+            if (value.F16Field is { } __value_9)
+            {
+                encoder.WriteU64(9);
+                encoder.WriteF16(__value_9);
+            }
+            if (value.F32Field is { } __value_10)
+            {
+                encoder.WriteU64(10);
+                encoder.WriteF32(__value_10);
+            }
+            if (value.F64Field is { } __value_11)
+            {
+                encoder.WriteU64(11);
+                encoder.WriteF64(__value_11);
+            }
+            if (value.StringField is { } __value_12)
+            {
+                encoder.WriteU64(12);
+                encoder.WriteString(__value_12);
+            }
+            if (value.BytesField is { } __value_13)
+            {
+                encoder.WriteU64(13);
+                encoder.WriteBytes(__value_13);
+            }
+            if (value.VecField1 is { } __value_14)
+            {
+                encoder.WriteU64(14);
+                encoder.WriteArray(__value_14.Count);
+                foreach (var __item_15 in __value_14)
+                {
+                    encoder.WriteU8(__item_15);
+                }
+            }
+            if (value.VecField2 is { } __value_16)
+            {
+                encoder.WriteU64(15);
+                encoder.WriteArray(__value_16.Count);
+                foreach (var __item_17 in __value_16)
+                {
+                    encoder.WriteString(__item_17);
+                }
+            }
+            if (value.VecField3 is { } __value_18)
+            {
+                encoder.WriteU64(16);
+                encoder.WriteArray(__value_18.Count);
+                foreach (var __item_19 in __value_18)
+                {
+                    encoder.WriteBytes(__item_19);
+                }
+            }
+            if (value.MapField1 is { } __value_20)
+            {
+                encoder.WriteU64(17);
+                encoder.WriteMap(__value_20.Count);
+                foreach (var __entry_21 in __value_20)
+                {
+                    encoder.WriteU8(__entry_21.Key);
+                    encoder.WriteString(__entry_21.Value);
+                }
+            }
+            if (value.MapField2 is { } __value_22)
+            {
+                encoder.WriteU64(18);
+                encoder.WriteMap(__value_22.Count);
+                foreach (var __entry_23 in __value_22)
+                {
+                    encoder.WriteString(__entry_23.Key);
+                    encoder.WriteU8(__entry_23.Value);
+                }
+            }
+            if (value.MapVecField1 is { } __value_24)
+            {
+                encoder.WriteU64(19);
+                encoder.WriteMap(__value_24.Count);
+                foreach (var __entry_25 in __value_24)
+                {
+                    encoder.WriteString(__entry_25.Key);
+                    encoder.WriteArray(__entry_25.Value.Count);
+                    foreach (var __item_26 in __entry_25.Value)
+                    {
+                        encoder.WriteU32(__item_26);
+                    }
+                }
+            }
+            if (value.MapVecField2 is { } __value_27)
+            {
+                encoder.WriteU64(20);
+                encoder.WriteMap(__value_27.Count);
+                foreach (var __entry_28 in __value_27)
+                {
+                    encoder.WriteString(__entry_28.Key);
+                    encoder.WriteArray(__entry_28.Value.Count);
+                    foreach (var __item_29 in __entry_28.Value)
+                    {
+                        encoder.WriteBytes(__item_29);
+                    }
+                }
+            }
+            if (value.StructField is { } __value_30)
+            {
+                encoder.WriteU64(21);
+                encoder.WriteStruct(__value_30, SimpleMessage.Pack);
+            }
+        }
+
+        public static PrimitiveShowcase2 Unpack(RocketPackDecoder decoder)
+        {
+            bool? boolField = null;
+            byte? u8Field = null;
+            short? i16Field = null;
+            int? i32Field = null;
+            long? i64Field = null;
+            ushort? u16Field = null;
+            uint? u32Field = null;
+            ulong? u64Field = null;
+            Half? f16Field = null;
+            float? f32Field = null;
+            double? f64Field = null;
+            string? stringField = null;
+            byte[]? bytesField = null;
+            List<byte>? vecField1 = null;
+            List<string>? vecField2 = null;
+            List<byte[]>? vecField3 = null;
+            SortedDictionary<byte, string>? mapField1 = null;
+            SortedDictionary<string, byte>? mapField2 = null;
+            SortedDictionary<string, List<uint>>? mapVecField1 = null;
+            SortedDictionary<string, List<byte[]>>? mapVecField2 = null;
+            SimpleMessage? structField = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        boolField = decoder.ReadBool();
+                        break;
+                    }
+                    case 2:
+                    {
+                        u8Field = decoder.ReadU8();
+                        break;
+                    }
+                    case 3:
+                    {
+                        i16Field = decoder.ReadI16();
+                        break;
+                    }
+                    case 4:
+                    {
+                        i32Field = decoder.ReadI32();
+                        break;
+                    }
+                    case 5:
+                    {
+                        i64Field = decoder.ReadI64();
+                        break;
+                    }
+                    case 6:
+                    {
+                        u16Field = decoder.ReadU16();
+                        break;
+                    }
+                    case 7:
+                    {
+                        u32Field = decoder.ReadU32();
+                        break;
+                    }
+                    case 8:
+                    {
+                        u64Field = decoder.ReadU64();
+                        break;
+                    }
+                    case 9:
+                    {
+                        f16Field = decoder.ReadF16();
+                        break;
+                    }
+                    case 10:
+                    {
+                        f32Field = decoder.ReadF32();
+                        break;
+                    }
+                    case 11:
+                    {
+                        f64Field = decoder.ReadF64();
+                        break;
+                    }
+                    case 12:
+                    {
+                        stringField = decoder.ReadString();
+                        break;
+                    }
+                    case 13:
+                    {
+                        bytesField = decoder.ReadBytes();
+                        break;
+                    }
+                    case 14:
+                    {
+                        var __count_2 = decoder.ReadArray();
+                        var __values_3 = new List<byte>();
+                        for (var __i_4 = 0; __i_4 < __count_2; __i_4++)
+                        {
+                            __values_3.Add(decoder.ReadU8());
+                        }
+                        vecField1 = __values_3;
+                        break;
+                    }
+                    case 15:
+                    {
+                        var __count_5 = decoder.ReadArray();
+                        var __values_6 = new List<string>();
+                        for (var __i_7 = 0; __i_7 < __count_5; __i_7++)
+                        {
+                            __values_6.Add(decoder.ReadString());
+                        }
+                        vecField2 = __values_6;
+                        break;
+                    }
+                    case 16:
+                    {
+                        var __count_8 = decoder.ReadArray();
+                        var __values_9 = new List<byte[]>();
+                        for (var __i_10 = 0; __i_10 < __count_8; __i_10++)
+                        {
+                            __values_9.Add(decoder.ReadBytes());
+                        }
+                        vecField3 = __values_9;
+                        break;
+                    }
+                    case 17:
+                    {
+                        var __count_11 = decoder.ReadMap();
+                        var __map_12 = new SortedDictionary<byte, string>();
+                        for (var __i_13 = 0; __i_13 < __count_11; __i_13++)
+                        {
+                            var __key_14 = decoder.ReadU8();
+                            __map_12[__key_14] = decoder.ReadString();
+                        }
+                        mapField1 = __map_12;
+                        break;
+                    }
+                    case 18:
+                    {
+                        var __count_15 = decoder.ReadMap();
+                        var __map_16 = new SortedDictionary<string, byte>(RocketPackComparers.String);
+                        for (var __i_17 = 0; __i_17 < __count_15; __i_17++)
+                        {
+                            var __key_18 = decoder.ReadString();
+                            __map_16[__key_18] = decoder.ReadU8();
+                        }
+                        mapField2 = __map_16;
+                        break;
+                    }
+                    case 19:
+                    {
+                        var __count_19 = decoder.ReadMap();
+                        var __map_20 = new SortedDictionary<string, List<uint>>(RocketPackComparers.String);
+                        for (var __i_21 = 0; __i_21 < __count_19; __i_21++)
+                        {
+                            var __key_22 = decoder.ReadString();
+                            var __count_23 = decoder.ReadArray();
+                            var __values_24 = new List<uint>();
+                            for (var __i_25 = 0; __i_25 < __count_23; __i_25++)
+                            {
+                                __values_24.Add(decoder.ReadU32());
+                            }
+                            __map_20[__key_22] = __values_24;
+                        }
+                        mapVecField1 = __map_20;
+                        break;
+                    }
+                    case 20:
+                    {
+                        var __count_26 = decoder.ReadMap();
+                        var __map_27 = new SortedDictionary<string, List<byte[]>>(RocketPackComparers.String);
+                        for (var __i_28 = 0; __i_28 < __count_26; __i_28++)
+                        {
+                            var __key_29 = decoder.ReadString();
+                            var __count_30 = decoder.ReadArray();
+                            var __values_31 = new List<byte[]>();
+                            for (var __i_32 = 0; __i_32 < __count_30; __i_32++)
+                            {
+                                __values_31.Add(decoder.ReadBytes());
+                            }
+                            __map_27[__key_29] = __values_31;
+                        }
+                        mapVecField2 = __map_27;
+                        break;
+                    }
+                    case 21:
+                    {
+                        structField = decoder.ReadStruct(SimpleMessage.Unpack);
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return new PrimitiveShowcase2
+            {
+                BoolField = boolField,
+                U8Field = u8Field,
+                I16Field = i16Field,
+                I32Field = i32Field,
+                I64Field = i64Field,
+                U16Field = u16Field,
+                U32Field = u32Field,
+                U64Field = u64Field,
+                F16Field = f16Field,
+                F32Field = f32Field,
+                F64Field = f64Field,
+                StringField = stringField,
+                BytesField = bytesField,
+                VecField1 = vecField1,
+                VecField2 = vecField2,
+                VecField3 = vecField3,
+                MapField1 = mapField1,
+                MapField2 = mapField2,
+                MapVecField1 = mapVecField1,
+                MapVecField2 = mapVecField2,
+                StructField = structField,
+            };
+        }
+    }
+
+    public abstract partial record PrimitiveShowcase3
+    {
+        private PrimitiveShowcase3()
+        {
+        }
+
+        public sealed partial record First : PrimitiveShowcase3
+        {
+        }
+
+        public sealed partial record Second : PrimitiveShowcase3
+        {
+            public string Entity { get; set; } = string.Empty;
+            public List<byte[]> Payload { get; set; } = new List<byte[]>();
+        }
+
+        public sealed partial record Third : PrimitiveShowcase3
+        {
+            public string Entity { get; set; } = string.Empty;
+            public Status Status { get; set; } = null!;
+            public uint Retries { get; set; } = 0;
+            public SimpleMessage? StructField { get; set; }
+        }
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static PrimitiveShowcase3 Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, PrimitiveShowcase3 value)
+        {
+            encoder.WriteMap(1);
+
+            switch (value)
+            {
+                case First _:
+                {
+                    encoder.WriteU64(1);
+                    encoder.WriteMap(0);
+                    break;
+                }
+                case Second __variant_1:
+                {
+                    encoder.WriteU64(2);
+                    encoder.WriteMap(2);
+                    encoder.WriteU64(0);
+                    encoder.WriteString(__variant_1.Entity);
+                    encoder.WriteU64(1);
+                    encoder.WriteArray(__variant_1.Payload.Count);
+                    foreach (var __item_2 in __variant_1.Payload)
+                    {
+                        encoder.WriteBytes(__item_2);
+                    }
+                    break;
+                }
+                case Third __variant_3:
+                {
+                    encoder.WriteU64(3);
+                    var __count_4 = 3;
+                    if (__variant_3.StructField is not null) __count_4++;
+                    encoder.WriteMap(__count_4);
+                    encoder.WriteU64(1);
+                    encoder.WriteString(__variant_3.Entity);
+                    encoder.WriteU64(2);
+                    encoder.WriteStruct(__variant_3.Status, Status.Pack);
+                    encoder.WriteU64(4);
+                    encoder.WriteU32(__variant_3.Retries);
+                    if (__variant_3.StructField is { } __value_5)
+                    {
+                        encoder.WriteU64(5);
+                        encoder.WriteStruct(__value_5, SimpleMessage.Pack);
+                    }
+                    break;
+                }
+                default:
+                    throw new RocketPackEncoderException("unknown enum variant: PrimitiveShowcase3");
+            }
+        }
+
+        public static PrimitiveShowcase3 Unpack(RocketPackDecoder decoder)
+        {
+            PrimitiveShowcase3? __result = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        var __count_2 = decoder.ReadMap();
+
+                        for (var __i_3 = 0; __i_3 < __count_2; __i_3++)
+                        {
+                            switch (decoder.ReadU64())
+                            {
+                                default:
+                                {
+                                    decoder.SkipField();
+                                    break;
+                                }
+                            }
+                        }
+
+                        __result = new First();
+                        break;
+                    }
+                    case 2:
+                    {
+                        string? entity = null;
+                        List<byte[]>? payload = null;
+                        var __count_4 = decoder.ReadMap();
+
+                        for (var __i_5 = 0; __i_5 < __count_4; __i_5++)
+                        {
+                            switch (decoder.ReadU64())
+                            {
+                                case 0:
+                                {
+                                    entity = decoder.ReadString();
+                                    break;
+                                }
+                                case 1:
+                                {
+                                    var __count_6 = decoder.ReadArray();
+                                    var __values_7 = new List<byte[]>();
+                                    for (var __i_8 = 0; __i_8 < __count_6; __i_8++)
+                                    {
+                                        __values_7.Add(decoder.ReadBytes());
+                                    }
+                                    payload = __values_7;
+                                    break;
+                                }
+                                default:
+                                {
+                                    decoder.SkipField();
+                                    break;
+                                }
+                            }
+                        }
+
+                        __result = new Second
+                        {
+                            Entity = entity ?? throw new RocketPackDecoderException("missing field: entity"),
+                            Payload = payload ?? throw new RocketPackDecoderException("missing field: payload"),
+                        };
+                        break;
+                    }
+                    case 3:
+                    {
+                        string? entity = null;
+                        Status? status = null;
+                        uint? retries = null;
+                        SimpleMessage? structField = null;
+                        var __count_9 = decoder.ReadMap();
+
+                        for (var __i_10 = 0; __i_10 < __count_9; __i_10++)
+                        {
+                            switch (decoder.ReadU64())
+                            {
+                                case 1:
+                                {
+                                    entity = decoder.ReadString();
+                                    break;
+                                }
+                                case 2:
+                                {
+                                    status = decoder.ReadStruct(Status.Unpack);
+                                    break;
+                                }
+                                case 4:
+                                {
+                                    retries = decoder.ReadU32();
+                                    break;
+                                }
+                                case 5:
+                                {
+                                    structField = decoder.ReadStruct(SimpleMessage.Unpack);
+                                    break;
+                                }
+                                default:
+                                {
+                                    decoder.SkipField();
+                                    break;
+                                }
+                            }
+                        }
+
+                        __result = new Third
+                        {
+                            Entity = entity ?? throw new RocketPackDecoderException("missing field: entity"),
+                            Status = status ?? throw new RocketPackDecoderException("missing field: status"),
+                            Retries = retries ?? 0,
+                            StructField = structField,
+                        };
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return __result ?? throw new RocketPackDecoderException("missing enum variant");
+        }
+    }
+
+    public abstract partial record Status
+    {
+        private Status()
+        {
+        }
+
+        public sealed partial record Success : Status
+        {
+        }
+
+        public sealed partial record Failed : Status
+        {
+        }
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static Status Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, Status value)
+        {
+            encoder.WriteMap(1);
+
+            switch (value)
+            {
+                case Success _:
+                {
+                    encoder.WriteU64(1);
+                    encoder.WriteMap(0);
+                    break;
+                }
+                case Failed _:
+                {
+                    encoder.WriteU64(2);
+                    encoder.WriteMap(0);
+                    break;
+                }
+                default:
+                    throw new RocketPackEncoderException("unknown enum variant: Status");
+            }
+        }
+
+        public static Status Unpack(RocketPackDecoder decoder)
+        {
+            Status? __result = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        var __count_2 = decoder.ReadMap();
+
+                        for (var __i_3 = 0; __i_3 < __count_2; __i_3++)
+                        {
+                            switch (decoder.ReadU64())
+                            {
+                                default:
+                                {
+                                    decoder.SkipField();
+                                    break;
+                                }
+                            }
+                        }
+
+                        __result = new Success();
+                        break;
+                    }
+                    case 2:
+                    {
+                        var __count_4 = decoder.ReadMap();
+
+                        for (var __i_5 = 0; __i_5 < __count_4; __i_5++)
+                        {
+                            switch (decoder.ReadU64())
+                            {
+                                default:
+                                {
+                                    decoder.SkipField();
+                                    break;
+                                }
+                            }
+                        }
+
+                        __result = new Failed();
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return __result ?? throw new RocketPackDecoderException("missing enum variant");
+        }
+    }
+
+    public sealed partial record Envelope
+    {
+        public global::Company.Project.Omnius.Demo.Common.Timestamp CreatedAt { get; set; } = null!;
+        public List<string> Labels { get; set; } = new List<string>();
+        public SimpleMessage? Message { get; set; }
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static Envelope Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, Envelope value)
+        {
+            var __count_0 = 2;
+            if (value.Message is not null) __count_0++;
+            encoder.WriteMap(__count_0);
+            encoder.WriteU64(1);
+            encoder.WriteStruct(value.CreatedAt, global::Company.Project.Omnius.Demo.Common.Timestamp.Pack);
+            encoder.WriteU64(2);
+            encoder.WriteArray(value.Labels.Count);
+            foreach (var __item_1 in value.Labels)
+            {
+                encoder.WriteString(__item_1);
+            }
+            if (value.Message is { } __value_2)
+            {
+                encoder.WriteU64(3);
+                encoder.WriteStruct(__value_2, SimpleMessage.Pack);
+            }
+        }
+
+        public static Envelope Unpack(RocketPackDecoder decoder)
+        {
+            global::Company.Project.Omnius.Demo.Common.Timestamp? createdAt = null;
+            List<string>? labels = null;
+            SimpleMessage? message = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        createdAt = decoder.ReadStruct(global::Company.Project.Omnius.Demo.Common.Timestamp.Unpack);
+                        break;
+                    }
+                    case 2:
+                    {
+                        var __count_2 = decoder.ReadArray();
+                        var __values_3 = new List<string>();
+                        for (var __i_4 = 0; __i_4 < __count_2; __i_4++)
+                        {
+                            __values_3.Add(decoder.ReadString());
+                        }
+                        labels = __values_3;
+                        break;
+                    }
+                    case 3:
+                    {
+                        message = decoder.ReadStruct(SimpleMessage.Unpack);
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return new Envelope
+            {
+                CreatedAt = createdAt ?? throw new RocketPackDecoderException("missing field: created_at"),
+                Labels = labels ?? throw new RocketPackDecoderException("missing field: labels"),
+                Message = message,
+            };
+        }
+    }
+
+    public sealed partial record SimpleMessage
+    {
+        public bool? BoolField { get; set; }
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static SimpleMessage Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, SimpleMessage value)
+        {
+            var __count_0 = 0;
+            if (value.BoolField is not null) __count_0++;
+            encoder.WriteMap(__count_0);
+            if (value.BoolField is { } __value_1)
+            {
+                encoder.WriteU64(1);
+                encoder.WriteBool(__value_1);
+            }
+        }
+
+        public static SimpleMessage Unpack(RocketPackDecoder decoder)
+        {
+            bool? boolField = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        boolField = decoder.ReadBool();
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return new SimpleMessage
+            {
+                BoolField = boolField,
+            };
+        }
+    }
+
+    public static partial class FirstConstants
+    {
+        public const uint MaxSampleSize = 1048576;
+    }
+}
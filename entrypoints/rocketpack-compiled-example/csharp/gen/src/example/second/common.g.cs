@@ -0,0 +1,66 @@
+// <auto-generated>
+//     @generated by rocketpack-compiler
+// </auto-generated>
+#nullable enable
+
+using System;
+using System.Collections.Generic;
+using Omnius.RocketPack.Runtime;
+
+namespace Company.Project.Omnius.Demo.Common
+{
+    using Labels = List<string>;
+
+    internal sealed partial record Timestamp
+    {
+        public long Seconds { get; set; }
+        public uint Nanos { get; set; }
+
+        public byte[] Export() => RocketPackEncoder.Encode(this, Pack);
+        public static Timestamp Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);
+
+        public static void Pack(RocketPackEncoder encoder, Timestamp value)
+        {
+            encoder.WriteMap(2);
+            encoder.WriteU64(1);
+            encoder.WriteI64(value.Seconds);
+            encoder.WriteU64(2);
+            encoder.WriteU32(value.Nanos);
+        }
+
+        public static Timestamp Unpack(RocketPackDecoder decoder)
+        {
+            long? seconds = null;
+            uint? nanos = null;
+            var __count_0 = decoder.ReadMap();
+
+            for (var __i_1 = 0; __i_1 < __count_0; __i_1++)
+            {
+                switch (decoder.ReadU64())
+                {
+                    case 1:
+                    {
+                        seconds = decoder.ReadI64();
+                        break;
+                    }
+                    case 2:
+                    {
+                        nanos = decoder.ReadU32();
+                        break;
+                    }
+                    default:
+                    {
+                        decoder.SkipField();
+                        break;
+                    }
+                }
+            }
+
+            return new Timestamp
+            {
+                Seconds = seconds ?? throw new RocketPackDecoderException("missing field: seconds"),
+                Nanos = nanos ?? throw new RocketPackDecoderException("missing field: nanos"),
+            };
+        }
+    }
+}
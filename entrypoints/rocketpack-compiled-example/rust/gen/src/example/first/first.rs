@@ -1,9 +1,12 @@
 // @generated by rocketpack-compiler
 #[allow(dead_code)]
+#[allow(deprecated)]
 #[allow(clippy::all)]
 pub mod omnius {
     pub mod demo {
         pub mod v1 {
+            use super::super::super::super::common::omnius::demo::common::Timestamp;
+
             #[derive(Debug, Clone, PartialEq)]
             pub struct PrimitiveShowcase1 {
                 pub bool_field: bool,
@@ -14,6 +17,7 @@ pub mod omnius {
                 pub u16_field: u16,
                 pub u32_field: u32,
                 pub u64_field: u64,
+                pub f16_field: omnius_core_rocketpack::f16,
                 pub f32_field: f32,
                 pub f64_field: f64,
                 pub string_field: String,
@@ -34,7 +38,7 @@ pub mod omnius {
                     encoder: &mut impl omnius_core_rocketpack::RocketPackEncoder,
                     value: &Self,
                 ) -> std::result::Result<(), omnius_core_rocketpack::RocketPackEncoderError> {
-                    encoder.write_map(21)?;
+                    encoder.write_map(22)?;
                     encoder.write_u64(1)?;
                     encoder.write_bool(*(&value.bool_field))?;
                     encoder.write_u64(2)?;
@@ -51,6 +55,8 @@ pub mod omnius {
                     encoder.write_u32(*(&value.u32_field))?;
                     encoder.write_u64(8)?;
                     encoder.write_u64(*(&value.u64_field))?;
+                    encoder.write_u64(9)?;
+                    encoder.write_f16(*(&value.f16_field))?;
                     encoder.write_u64(10)?;
                     encoder.write_f32(*(&value.f32_field))?;
                     encoder.write_u64(11)?;
@@ -128,6 +134,7 @@ pub mod omnius {
                     let mut u16_field: Option<u16> = None;
                     let mut u32_field: Option<u32> = None;
                     let mut u64_field: Option<u64> = None;
+                    let mut f16_field: Option<omnius_core_rocketpack::f16> = None;
                     let mut f32_field: Option<f32> = None;
                     let mut f64_field: Option<f64> = None;
                     let mut string_field: Option<String> = None;
@@ -169,6 +176,9 @@ pub mod omnius {
                             8 => {
                                 u64_field = Some(decoder.read_u64()?);
                             }
+                            9 => {
+                                f16_field = Some(decoder.read_f16()?);
+                            }
                             10 => {
                                 f32_field = Some(decoder.read_f32()?);
                             }
@@ -183,7 +193,7 @@ pub mod omnius {
                             }
                             14 => {
                                 let __count_0 = decoder.read_array()?;
-                                let mut __values_1: Vec<u8> = Vec::with_capacity(__count_0 as usize);
+                                let mut __values_1: Vec<u8> = decoder.alloc_vec(__count_0)?;
                                 for _ in 0..__count_0 {
                                     __values_1.push(decoder.read_u8()?);
                                 }
@@ -191,7 +201,7 @@ pub mod omnius {
                             }
                             15 => {
                                 let __count_2 = decoder.read_array()?;
-                                let mut __values_3: Vec<String> = Vec::with_capacity(__count_2 as usize);
+                                let mut __values_3: Vec<String> = decoder.alloc_vec(__count_2)?;
                                 for _ in 0..__count_2 {
                                     __values_3.push(decoder.read_string()?);
                                 }
@@ -199,7 +209,7 @@ pub mod omnius {
                             }
                             16 => {
                                 let __count_4 = decoder.read_array()?;
-                                let mut __values_5: Vec<Vec<u8>> = Vec::with_capacity(__count_4 as usize);
+                                let mut __values_5: Vec<Vec<u8>> = decoder.alloc_vec(__count_4)?;
                                 for _ in 0..__count_4 {
                                     __values_5.push(decoder.read_bytes_vec()?);
                                 }
@@ -209,55 +219,59 @@ pub mod omnius {
                                 let __count_6 = decoder.read_map()?;
                                 let mut __map_7: std::collections::BTreeMap<u8, String> = std::collections::BTreeMap::new();
                                 for _ in 0..__count_6 {
-                                    __map_7.insert(decoder.read_u8()?, decoder.read_string()?);
+                                    let __key_8 = decoder.read_u8()?;
+                                    __map_7.insert(__key_8, decoder.read_string()?);
                                 }
                                 map_field_1 = Some(__map_7);
                             }
                             18 => {
-                                let __count_8 = decoder.read_map()?;
-                                let mut __map_9: std::collections::BTreeMap<String, u8> = std::collections::BTreeMap::new();
-                                for _ in 0..__count_8 {
-                                    __map_9.insert(decoder.read_string()?, decoder.read_u8()?);
+                                let __count_9 = decoder.read_map()?;
+                                let mut __map_10: std::collections::BTreeMap<String, u8> = std::collections::BTreeMap::new();
+                                for _ in 0..__count_9 {
+                                    let __key_11 = decoder.read_string()?;
+                                    __map_10.insert(__key_11, decoder.read_u8()?);
                                 }
-                                map_field_2 = Some(__map_9);
+                                map_field_2 = Some(__map_10);
                             }
                             19 => {
-                                let __count_10 = decoder.read_map()?;
-                                let mut __map_11: std::collections::BTreeMap<String, Vec<u32>> = std::collections::BTreeMap::new();
-                                for _ in 0..__count_10 {
-                                    let __count_12 = decoder.read_array()?;
-                                    let mut __values_13: Vec<u32> = Vec::with_capacity(__count_12 as usize);
-                                    for _ in 0..__count_12 {
-                                        __values_13.push(decoder.read_u32()?);
+                                let __count_12 = decoder.read_map()?;
+                                let mut __map_13: std::collections::BTreeMap<String, Vec<u32>> = std::collections::BTreeMap::new();
+                                for _ in 0..__count_12 {
+                                    let __key_14 = decoder.read_string()?;
+                                    let __count_15 = decoder.read_array()?;
+                                    let mut __values_16: Vec<u32> = decoder.alloc_vec(__count_15)?;
+                                    for _ in 0..__count_15 {
+                                        __values_16.push(decoder.read_u32()?);
                                     }
-                                    __map_11.insert(decoder.read_string()?, __values_13);
+                                    __map_13.insert(__key_14, __values_16);
                                 }
-                                map_vec_field_1 = Some(__map_11);
+                                map_vec_field_1 = Some(__map_13);
                             }
                             20 => {
-                                let __count_14 = decoder.read_map()?;
-                                let mut __map_15: std::collections::BTreeMap<String, Vec<Vec<u8>>> = std::collections::BTreeMap::new();
-                                for _ in 0..__count_14 {
-                                    let __count_16 = decoder.read_array()?;
-                                    let mut __values_17: Vec<Vec<u8>> = Vec::with_capacity(__count_16 as usize);
-                                    for _ in 0..__count_16 {
-                                        __values_17.push(decoder.read_bytes_vec()?);
+                                let __count_17 = decoder.read_map()?;
+                                let mut __map_18: std::collections::BTreeMap<String, Vec<Vec<u8>>> = std::collections::BTreeMap::new();
+                                for _ in 0..__count_17 {
+                                    let __key_19 = decoder.read_string()?;
+                                    let __count_20 = decoder.read_array()?;
+                                    let mut __values_21: Vec<Vec<u8>> = decoder.alloc_vec(__count_20)?;
+                                    for _ in 0..__count_20 {
+                                        __values_21.push(decoder.read_bytes_vec()?);
                                     }
-                                    __map_15.insert(decoder.read_string()?, __values_17);
+                                    __map_18.insert(__key_19, __values_21);
                                 }
-                                map_vec_field_2 = Some(__map_15);
+                                map_vec_field_2 = Some(__map_18);
                             }
                             21 => {
-                                let __count_18 = decoder.read_array()?;
-                                if __count_18 != 4 {
+                                let __count_22 = decoder.read_array()?;
+                                if __count_22 != 4 {
                                     return Err(omnius_core_rocketpack::RocketPackDecoderError::Other("array length mismatch: slice_field"));
                                 }
-                                let mut __values_19: Vec<i64> = Vec::with_capacity(__count_18 as usize);
-                                for _ in 0..__count_18 {
-                                    __values_19.push(decoder.read_i64()?);
+                                let mut __values_23: Vec<i64> = decoder.alloc_vec(__count_22)?;
+                                for _ in 0..__count_22 {
+                                    __values_23.push(decoder.read_i64()?);
                                 }
-                                let __array_20: [i64; 4] = __values_19.try_into().map_err(|_| omnius_core_rocketpack::RocketPackDecoderError::Other("array length mismatch: slice_field"))?;
-                                slice_field = Some(__array_20);
+                                let __array_24: [i64; 4] = __values_23.try_into().map_err(|_| omnius_core_rocketpack::RocketPackDecoderError::Other("array length mismatch: slice_field"))?;
+                                slice_field = Some(__array_24);
                             }
                             22 => {
                                 struct_field = Some(decoder.read_struct::<SimpleMessage>()?);
@@ -275,6 +289,7 @@ pub mod omnius {
                         u16_field: u16_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: u16_field"))?,
                         u32_field: u32_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: u32_field"))?,
                         u64_field: u64_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: u64_field"))?,
+                        f16_field: f16_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: f16_field"))?,
                         f32_field: f32_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: f32_field"))?,
                         f64_field: f64_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: f64_field"))?,
                         string_field: string_field.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: string_field"))?,
@@ -292,6 +307,150 @@ pub mod omnius {
                 }
             }
 
+            impl omnius_core_rocketpack::RocketPackSchema for PrimitiveShowcase1 {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::v1",
+                    name: "PrimitiveShowcase1",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Struct {
+                        fields: &[
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 1,
+                                name: "bool_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Bool,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 2,
+                                name: "u8_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::U8,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 3,
+                                name: "i16_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::I16,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 4,
+                                name: "i32_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::I32,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 5,
+                                name: "i64_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::I64,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 6,
+                                name: "u16_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::U16,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 7,
+                                name: "u32_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::U32,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 8,
+                                name: "u64_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::U64,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 9,
+                                name: "f16_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::F16,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 10,
+                                name: "f32_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::F32,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 11,
+                                name: "f64_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::F64,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 12,
+                                name: "string_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::String,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 13,
+                                name: "bytes_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Bytes,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 14,
+                                name: "vec_field_1",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::U8),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 15,
+                                name: "vec_field_2",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::String),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 16,
+                                name: "vec_field_3",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::Bytes),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 17,
+                                name: "map_field_1",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::U8, &omnius_core_rocketpack::TypeDescriptor::String),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 18,
+                                name: "map_field_2",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::U8),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 19,
+                                name: "map_vec_field_1",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::U32)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 20,
+                                name: "map_vec_field_2",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::Bytes)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 21,
+                                name: "slice_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Array(&omnius_core_rocketpack::TypeDescriptor::I64, 4),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 22,
+                                name: "struct_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Named { name: "omnius::demo::v1::SimpleMessage", args: &[] },
+                                default: None,
+                            },
+                        ],
+                    },
+                };
+            }
+
             #[derive(Debug, Clone, PartialEq)]
             pub struct PrimitiveShowcase2 {
                 pub bool_field: Option<bool>,
@@ -302,6 +461,7 @@ pub mod omnius {
                 pub u16_field: Option<u16>,
                 pub u32_field: Option<u32>,
                 pub u64_field: Option<u64>,
+                pub f16_field: Option<omnius_core_rocketpack::f16>,
                 pub f32_field: Option<f32>,
                 pub f64_field: Option<f64>,
                 pub string_field: Option<String>,
@@ -346,6 +506,9 @@ pub mod omnius {
                     if value.u64_field.is_some() {
                         count += 1;
                     }
+                    if value.f16_field.is_some() {
+                        count += 1;
+                    }
                     if value.f32_field.is_some() {
                         count += 1;
                     }
@@ -415,6 +578,10 @@ pub mod omnius {
                         encoder.write_u64(8)?;
                         encoder.write_u64(*(u64_field))?;
                     }
+                    if let Some(f16_field) = &value.f16_field {
+                        encoder.write_u64(9)?;
+                        encoder.write_f16(*(f16_field))?;
+                    }
                     if let Some(f32_field) = &value.f32_field {
                         encoder.write_u64(10)?;
                         encoder.write_f32(*(f32_field))?;
@@ -511,6 +678,7 @@ pub mod omnius {
                     let mut u16_field: Option<u16> = None;
                     let mut u32_field: Option<u32> = None;
                     let mut u64_field: Option<u64> = None;
+                    let mut f16_field: Option<omnius_core_rocketpack::f16> = None;
                     let mut f32_field: Option<f32> = None;
                     let mut f64_field: Option<f64> = None;
                     let mut string_field: Option<String> = None;
@@ -551,6 +719,9 @@ pub mod omnius {
                             8 => {
                                 u64_field = Some(decoder.read_u64()?);
                             }
+                            9 => {
+                                f16_field = Some(decoder.read_f16()?);
+                            }
                             10 => {
                                 f32_field = Some(decoder.read_f32()?);
                             }
@@ -565,7 +736,7 @@ pub mod omnius {
                             }
                             14 => {
                                 let __count_0 = decoder.read_array()?;
-                                let mut __values_1: Vec<u8> = Vec::with_capacity(__count_0 as usize);
+                                let mut __values_1: Vec<u8> = decoder.alloc_vec(__count_0)?;
                                 for _ in 0..__count_0 {
                                     __values_1.push(decoder.read_u8()?);
                                 }
@@ -573,7 +744,7 @@ pub mod omnius {
                             }
                             15 => {
                                 let __count_2 = decoder.read_array()?;
-                                let mut __values_3: Vec<String> = Vec::with_capacity(__count_2 as usize);
+                                let mut __values_3: Vec<String> = decoder.alloc_vec(__count_2)?;
                                 for _ in 0..__count_2 {
                                     __values_3.push(decoder.read_string()?);
                                 }
@@ -581,7 +752,7 @@ pub mod omnius {
                             }
                             16 => {
                                 let __count_4 = decoder.read_array()?;
-                                let mut __values_5: Vec<Vec<u8>> = Vec::with_capacity(__count_4 as usize);
+                                let mut __values_5: Vec<Vec<u8>> = decoder.alloc_vec(__count_4)?;
                                 for _ in 0..__count_4 {
                                     __values_5.push(decoder.read_bytes_vec()?);
                                 }
@@ -591,43 +762,47 @@ pub mod omnius {
                                 let __count_6 = decoder.read_map()?;
                                 let mut __map_7: std::collections::BTreeMap<u8, String> = std::collections::BTreeMap::new();
                                 for _ in 0..__count_6 {
-                                    __map_7.insert(decoder.read_u8()?, decoder.read_string()?);
+                                    let __key_8 = decoder.read_u8()?;
+                                    __map_7.insert(__key_8, decoder.read_string()?);
                                 }
                                 map_field_1 = Some(__map_7);
                             }
                             18 => {
-                                let __count_8 = decoder.read_map()?;
-                                let mut __map_9: std::collections::BTreeMap<String, u8> = std::collections::BTreeMap::new();
-                                for _ in 0..__count_8 {
-                                    __map_9.insert(decoder.read_string()?, decoder.read_u8()?);
+                                let __count_9 = decoder.read_map()?;
+                                let mut __map_10: std::collections::BTreeMap<String, u8> = std::collections::BTreeMap::new();
+                                for _ in 0..__count_9 {
+                                    let __key_11 = decoder.read_string()?;
+                                    __map_10.insert(__key_11, decoder.read_u8()?);
                                 }
-                                map_field_2 = Some(__map_9);
+                                map_field_2 = Some(__map_10);
                             }
                             19 => {
-                                let __count_10 = decoder.read_map()?;
-                                let mut __map_11: std::collections::BTreeMap<String, Vec<u32>> = std::collections::BTreeMap::new();
-                                for _ in 0..__count_10 {
-                                    let __count_12 = decoder.read_array()?;
-                                    let mut __values_13: Vec<u32> = Vec::with_capacity(__count_12 as usize);
-                                    for _ in 0..__count_12 {
-                                        __values_13.push(decoder.read_u32()?);
+                                let __count_12 = decoder.read_map()?;
+                                let mut __map_13: std::collections::BTreeMap<String, Vec<u32>> = std::collections::BTreeMap::new();
+                                for _ in 0..__count_12 {
+                                    let __key_14 = decoder.read_string()?;
+                                    let __count_15 = decoder.read_array()?;
+                                    let mut __values_16: Vec<u32> = decoder.alloc_vec(__count_15)?;
+                                    for _ in 0..__count_15 {
+                                        __values_16.push(decoder.read_u32()?);
                                     }
-                                    __map_11.insert(decoder.read_string()?, __values_13);
+                                    __map_13.insert(__key_14, __values_16);
                                 }
-                                map_vec_field_1 = Some(__map_11);
+                                map_vec_field_1 = Some(__map_13);
                             }
                             20 => {
-                                let __count_14 = decoder.read_map()?;
-                                let mut __map_15: std::collections::BTreeMap<String, Vec<Vec<u8>>> = std::collections::BTreeMap::new();
-                                for _ in 0..__count_14 {
-                                    let __count_16 = decoder.read_array()?;
-                                    let mut __values_17: Vec<Vec<u8>> = Vec::with_capacity(__count_16 as usize);
-                                    for _ in 0..__count_16 {
-                                        __values_17.push(decoder.read_bytes_vec()?);
+                                let __count_17 = decoder.read_map()?;
+                                let mut __map_18: std::collections::BTreeMap<String, Vec<Vec<u8>>> = std::collections::BTreeMap::new();
+                                for _ in 0..__count_17 {
+                                    let __key_19 = decoder.read_string()?;
+                                    let __count_20 = decoder.read_array()?;
+                                    let mut __values_21: Vec<Vec<u8>> = decoder.alloc_vec(__count_20)?;
+                                    for _ in 0..__count_20 {
+                                        __values_21.push(decoder.read_bytes_vec()?);
                                     }
-                                    __map_15.insert(decoder.read_string()?, __values_17);
+                                    __map_18.insert(__key_19, __values_21);
                                 }
-                                map_vec_field_2 = Some(__map_15);
+                                map_vec_field_2 = Some(__map_18);
                             }
                             21 => {
                                 struct_field = Some(decoder.read_struct::<SimpleMessage>()?);
@@ -645,6 +820,7 @@ pub mod omnius {
                         u16_field: u16_field,
                         u32_field: u32_field,
                         u64_field: u64_field,
+                        f16_field: f16_field,
                         f32_field: f32_field,
                         f64_field: f64_field,
                         string_field: string_field,
@@ -661,6 +837,144 @@ pub mod omnius {
                 }
             }
 
+            impl omnius_core_rocketpack::RocketPackSchema for PrimitiveShowcase2 {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::v1",
+                    name: "PrimitiveShowcase2",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Struct {
+                        fields: &[
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 1,
+                                name: "bool_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Bool),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 2,
+                                name: "u8_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::U8),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 3,
+                                name: "i16_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::I16),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 4,
+                                name: "i32_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::I32),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 5,
+                                name: "i64_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::I64),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 6,
+                                name: "u16_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::U16),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 7,
+                                name: "u32_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::U32),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 8,
+                                name: "u64_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::U64),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 9,
+                                name: "f16_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::F16),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 10,
+                                name: "f32_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::F32),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 11,
+                                name: "f64_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::F64),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 12,
+                                name: "string_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::String),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 13,
+                                name: "bytes_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Bytes),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 14,
+                                name: "vec_field_1",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::U8)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 15,
+                                name: "vec_field_2",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::String)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 16,
+                                name: "vec_field_3",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::Bytes)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 17,
+                                name: "map_field_1",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::U8, &omnius_core_rocketpack::TypeDescriptor::String)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 18,
+                                name: "map_field_2",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::U8)),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 19,
+                                name: "map_vec_field_1",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::U32))),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 20,
+                                name: "map_vec_field_2",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::Bytes))),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 21,
+                                name: "struct_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Named { name: "omnius::demo::v1::SimpleMessage", args: &[] }),
+                                default: None,
+                            },
+                        ],
+                    },
+                };
+            }
+
             #[derive(Debug, Clone, PartialEq)]
             pub enum PrimitiveShowcase3 {
                 First,
@@ -752,7 +1066,7 @@ pub mod omnius {
                                         }
                                         1 => {
                                             let __count_2 = decoder.read_array()?;
-                                            let mut __values_3: Vec<Vec<u8>> = Vec::with_capacity(__count_2 as usize);
+                                            let mut __values_3: Vec<Vec<u8>> = decoder.alloc_vec(__count_2)?;
                                             for _ in 0..__count_2 {
                                                 __values_3.push(decoder.read_bytes_vec()?);
                                             }
@@ -796,6 +1110,71 @@ pub mod omnius {
                 }
             }
 
+            impl omnius_core_rocketpack::RocketPackSchema for PrimitiveShowcase3 {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::v1",
+                    name: "PrimitiveShowcase3",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Enum {
+                        variants: &[
+                            omnius_core_rocketpack::VariantDescriptor {
+                                tag: 1,
+                                name: "First",
+                                fields: &[],
+                            },
+                            omnius_core_rocketpack::VariantDescriptor {
+                                tag: 2,
+                                name: "Second",
+                                fields: &[
+                                    omnius_core_rocketpack::FieldDescriptor {
+                                        tag: 0,
+                                        name: "entity",
+                                        ty: omnius_core_rocketpack::TypeDescriptor::String,
+                                        default: None,
+                                    },
+                                    omnius_core_rocketpack::FieldDescriptor {
+                                        tag: 1,
+                                        name: "payload",
+                                        ty: omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::Bytes),
+                                        default: None,
+                                    },
+                                ],
+                            },
+                            omnius_core_rocketpack::VariantDescriptor {
+                                tag: 3,
+                                name: "Third",
+                                fields: &[
+                                    omnius_core_rocketpack::FieldDescriptor {
+                                        tag: 1,
+                                        name: "entity",
+                                        ty: omnius_core_rocketpack::TypeDescriptor::String,
+                                        default: None,
+                                    },
+                                    omnius_core_rocketpack::FieldDescriptor {
+                                        tag: 2,
+                                        name: "status",
+                                        ty: omnius_core_rocketpack::TypeDescriptor::Named { name: "omnius::demo::v1::Status", args: &[] },
+                                        default: None,
+                                    },
+                                    omnius_core_rocketpack::FieldDescriptor {
+                                        tag: 4,
+                                        name: "retries",
+                                        ty: omnius_core_rocketpack::TypeDescriptor::U32,
+                                        default: Some("0"),
+                                    },
+                                    omnius_core_rocketpack::FieldDescriptor {
+                                        tag: 5,
+                                        name: "struct_field",
+                                        ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Named { name: "omnius::demo::v1::SimpleMessage", args: &[] }),
+                                        default: None,
+                                    },
+                                ],
+                            },
+                        ],
+                    },
+                };
+            }
+
             #[derive(Debug, Clone, PartialEq)]
             pub enum Status {
                 Success,
@@ -858,6 +1237,128 @@ pub mod omnius {
                 }
             }
 
+            impl omnius_core_rocketpack::RocketPackSchema for Status {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::v1",
+                    name: "Status",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Enum {
+                        variants: &[
+                            omnius_core_rocketpack::VariantDescriptor {
+                                tag: 1,
+                                name: "Success",
+                                fields: &[],
+                            },
+                            omnius_core_rocketpack::VariantDescriptor {
+                                tag: 2,
+                                name: "Failed",
+                                fields: &[],
+                            },
+                        ],
+                    },
+                };
+            }
+
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct Envelope {
+                pub created_at: Timestamp,
+                pub labels: super::super::super::super::common::omnius::demo::common::Labels,
+                pub message: Option<SimpleMessage>,
+            }
+
+            impl omnius_core_rocketpack::RocketPackStruct for Envelope {
+                fn pack(
+                    encoder: &mut impl omnius_core_rocketpack::RocketPackEncoder,
+                    value: &Self,
+                ) -> std::result::Result<(), omnius_core_rocketpack::RocketPackEncoderError> {
+                    let mut count = 2;
+                    if value.message.is_some() {
+                        count += 1;
+                    }
+                    encoder.write_map(count)?;
+                    encoder.write_u64(1)?;
+                    encoder.write_struct(&value.created_at)?;
+                    encoder.write_u64(2)?;
+                    encoder.write_array((&value.labels).len())?;
+                    for item in (&value.labels).iter() {
+                        encoder.write_string((item).as_str())?;
+                    }
+                    if let Some(message) = &value.message {
+                        encoder.write_u64(3)?;
+                        encoder.write_struct(message)?;
+                    }
+                    Ok(())
+                }
+
+                fn unpack(
+                    decoder: &mut impl omnius_core_rocketpack::RocketPackDecoder,
+                ) -> std::result::Result<Self, omnius_core_rocketpack::RocketPackDecoderError>
+                where
+                    Self: Sized,
+                {
+                    let mut created_at: Option<Timestamp> = None;
+                    let mut labels: Option<super::super::super::super::common::omnius::demo::common::Labels> = None;
+                    let mut message: Option<SimpleMessage> = None;
+                    let count = decoder.read_map()?;
+
+                    for _ in 0..count {
+                        match decoder.read_u64()? {
+                            1 => {
+                                created_at = Some(decoder.read_struct::<super::super::super::super::common::omnius::demo::common::Timestamp>()?);
+                            }
+                            2 => {
+                                let __count_0 = decoder.read_array()?;
+                                let mut __values_1: Vec<String> = decoder.alloc_vec(__count_0)?;
+                                for _ in 0..__count_0 {
+                                    __values_1.push(decoder.read_string()?);
+                                }
+                                labels = Some(__values_1);
+                            }
+                            3 => {
+                                message = Some(decoder.read_struct::<SimpleMessage>()?);
+                            }
+                            _ => decoder.skip_field()?,
+                        }
+                    }
+
+                    Ok(Self {
+                        created_at: created_at.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: created_at"))?,
+                        labels: labels.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: labels"))?,
+                        message: message,
+                    })
+                }
+            }
+
+            impl omnius_core_rocketpack::RocketPackSchema for Envelope {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::v1",
+                    name: "Envelope",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Struct {
+                        fields: &[
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 1,
+                                name: "created_at",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Named { name: "omnius::demo::common::Timestamp", args: &[] },
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 2,
+                                name: "labels",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::String),
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 3,
+                                name: "message",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Named { name: "omnius::demo::v1::SimpleMessage", args: &[] }),
+                                default: None,
+                            },
+                        ],
+                    },
+                };
+            }
+
             #[derive(Debug, Clone, PartialEq)]
             pub struct SimpleMessage {
                 pub bool_field: Option<bool>,
@@ -904,10 +1405,114 @@ pub mod omnius {
                 }
             }
 
+            impl omnius_core_rocketpack::RocketPackSchema for SimpleMessage {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::v1",
+                    name: "SimpleMessage",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Struct {
+                        fields: &[
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 1,
+                                name: "bool_field",
+                                ty: omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Bool),
+                                default: None,
+                            },
+                        ],
+                    },
+                };
+            }
+
             pub type ByteList = Vec<Vec<u8>>;
 
             pub const MAX_SAMPLE_SIZE: u32 = 1048576;
 
+            pub struct SampleServiceClient {
+                max_frame_length: usize,
+            }
+
+            impl SampleServiceClient {
+                pub fn new(max_frame_length: usize) -> Self {
+                    Self { max_frame_length }
+                }
+
+                pub async fn echo<T>(&self, stream: T, request: Envelope) -> omnius_core_omnikit::Result<Envelope>
+                where
+                    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + 'static,
+                {
+                    let caller = omnius_core_omnikit::service::remoting::OmniRemotingCaller::new(stream, self.max_frame_length, 0x0001_0001).await?;
+                    let stream = caller.call_stream();
+                    stream.send(request).await?;
+                    stream.recv::<Envelope>().await
+                }
+
+                pub async fn watch<T>(&self, stream: T, request: SimpleMessage) -> omnius_core_omnikit::Result<omnius_core_omnikit::service::remoting::OmniRemotingChannel<SimpleMessage, Envelope>>
+                where
+                    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + 'static,
+                {
+                    let caller = omnius_core_omnikit::service::remoting::OmniRemotingCaller::new(stream, self.max_frame_length, 0x0001_0002).await?;
+                    let stream = caller.call_stream();
+                    stream.send(request).await?;
+                    Ok(omnius_core_omnikit::service::remoting::OmniRemotingChannel::new(stream))
+                }
+            }
+
+            pub trait SampleServiceServer: Send + Sync {
+                fn echo(&self, request: Envelope) -> impl std::future::Future<Output = omnius_core_omnikit::Result<Envelope>> + Send;
+
+                fn watch(&self, request: SimpleMessage, channel: &omnius_core_omnikit::service::remoting::OmniRemotingChannel<Envelope, SimpleMessage>) -> impl std::future::Future<Output = omnius_core_omnikit::Result<()>> + Send;
+            }
+
+            pub async fn dispatch_sample_service<S>(server: &S, listener: &omnius_core_omnikit::service::remoting::OmniRemotingListener) -> omnius_core_omnikit::Result<()>
+            where
+                S: SampleServiceServer,
+            {
+                use omnius_core_base::error::OmniError as _;
+
+                let mut result: omnius_core_omnikit::Result<()> = Ok(());
+                match listener.function_id() {
+                    0x0001_0001 => {
+                        listener
+                            .listen_stream(async |stream| {
+                                result = async {
+                                    let request = stream.recv::<Envelope>().await?;
+                                    let response = server.echo(request).await?;
+                                    stream.send(response).await
+                                }
+                                .await;
+                            })
+                            .await?;
+                    }
+                    0x0001_0002 => {
+                        listener
+                            .listen_stream(async |stream| {
+                                result = async {
+                                    let request = stream.recv::<SimpleMessage>().await?;
+                                    let channel = omnius_core_omnikit::service::remoting::OmniRemotingChannel::new(stream);
+                                    server.watch(request, &channel).await?;
+                                    channel.finish().await
+                                }
+                                .await;
+                            })
+                            .await?;
+                    }
+                    function_id => {
+                        return Err(omnius_core_omnikit::Error::new(omnius_core_omnikit::ErrorKind::UnsupportedType).with_message(format!("unknown function id: {function_id}")));
+                    }
+                }
+
+                result
+            }
+
+            pub static SCHEMA_REGISTRY: omnius_core_rocketpack::SchemaRegistry = omnius_core_rocketpack::SchemaRegistry::new(&[
+                &<PrimitiveShowcase1 as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+                &<PrimitiveShowcase2 as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+                &<PrimitiveShowcase3 as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+                &<Status as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+                &<Envelope as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+                &<SimpleMessage as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+            ]);
+
         }
     }
 }
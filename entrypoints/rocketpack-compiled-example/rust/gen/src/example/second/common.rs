@@ -0,0 +1,88 @@
+// @generated by rocketpack-compiler
+#[allow(dead_code)]
+#[allow(deprecated)]
+#[allow(clippy::all)]
+pub mod omnius {
+    pub mod demo {
+        pub mod common {
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct Timestamp {
+                pub seconds: i64,
+                pub nanos: u32,
+            }
+
+            impl omnius_core_rocketpack::RocketPackStruct for Timestamp {
+                fn pack(
+                    encoder: &mut impl omnius_core_rocketpack::RocketPackEncoder,
+                    value: &Self,
+                ) -> std::result::Result<(), omnius_core_rocketpack::RocketPackEncoderError> {
+                    encoder.write_map(2)?;
+                    encoder.write_u64(1)?;
+                    encoder.write_i64(*(&value.seconds))?;
+                    encoder.write_u64(2)?;
+                    encoder.write_u32(*(&value.nanos))?;
+                    Ok(())
+                }
+
+                fn unpack(
+                    decoder: &mut impl omnius_core_rocketpack::RocketPackDecoder,
+                ) -> std::result::Result<Self, omnius_core_rocketpack::RocketPackDecoderError>
+                where
+                    Self: Sized,
+                {
+                    let mut seconds: Option<i64> = None;
+                    let mut nanos: Option<u32> = None;
+                    let count = decoder.read_map()?;
+
+                    for _ in 0..count {
+                        match decoder.read_u64()? {
+                            1 => {
+                                seconds = Some(decoder.read_i64()?);
+                            }
+                            2 => {
+                                nanos = Some(decoder.read_u32()?);
+                            }
+                            _ => decoder.skip_field()?,
+                        }
+                    }
+
+                    Ok(Self {
+                        seconds: seconds.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: seconds"))?,
+                        nanos: nanos.ok_or(omnius_core_rocketpack::RocketPackDecoderError::Other("missing field: nanos"))?,
+                    })
+                }
+            }
+
+            impl omnius_core_rocketpack::RocketPackSchema for Timestamp {
+                const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {
+                    package: "omnius::demo::common",
+                    name: "Timestamp",
+                    type_params: &[],
+                    kind: omnius_core_rocketpack::ItemKind::Struct {
+                        fields: &[
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 1,
+                                name: "seconds",
+                                ty: omnius_core_rocketpack::TypeDescriptor::I64,
+                                default: None,
+                            },
+                            omnius_core_rocketpack::FieldDescriptor {
+                                tag: 2,
+                                name: "nanos",
+                                ty: omnius_core_rocketpack::TypeDescriptor::U32,
+                                default: None,
+                            },
+                        ],
+                    },
+                };
+            }
+
+            pub type Labels = Vec<String>;
+
+            pub static SCHEMA_REGISTRY: omnius_core_rocketpack::SchemaRegistry = omnius_core_rocketpack::SchemaRegistry::new(&[
+                &<Timestamp as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,
+            ]);
+
+        }
+    }
+}
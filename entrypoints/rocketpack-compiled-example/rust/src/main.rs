@@ -1,14 +1,25 @@
+// Generated files reference each other as sibling modules named after their source file.
+#[path = "../gen/src/example/second/common.rs"]
+mod common;
 #[path = "../gen/src/example/first/first.rs"]
-mod generated;
+mod first;
 
 use std::collections::BTreeMap;
 
-use generated::omnius::demo::v1::*;
-use omnius_core_rocketpack::RocketPackStruct;
+use common::omnius::demo::common::Timestamp;
+use first::omnius::demo::v1::*;
+use omnius_core_rocketpack::{ItemKind, RocketPackSchema, RocketPackStruct, TypeDescriptor, f16};
 
-fn main() {
+#[tokio::main]
+async fn main() {
     run_generated_roundtrip_checks();
     println!("generated round-trip checks passed");
+
+    run_generated_service_checks().await;
+    println!("generated service checks passed");
+
+    run_generated_schema_checks();
+    println!("generated schema checks passed");
 }
 
 fn run_generated_roundtrip_checks() {
@@ -19,6 +30,7 @@ fn run_generated_roundtrip_checks() {
     assert_roundtrip(&sample_primitive_showcase_3_third());
     assert_roundtrip(&Status::Success);
     assert_roundtrip(&Status::Failed);
+    assert_roundtrip(&sample_envelope());
     assert_eq!(MAX_SAMPLE_SIZE, 1_048_576);
 }
 
@@ -41,6 +53,7 @@ fn sample_primitive_showcase_1() -> PrimitiveShowcase1 {
         u16_field: 650,
         u32_field: 99_999,
         u64_field: 123_456_789,
+        f16_field: f16::from_f32(0.75),
         f32_field: 1.5,
         f64_field: 9.25,
         string_field: "roundtrip".to_string(),
@@ -67,6 +80,7 @@ fn sample_primitive_showcase_2() -> PrimitiveShowcase2 {
         u16_field: Some(512),
         u32_field: None,
         u64_field: Some(7_777),
+        f16_field: Some(f16::from_f32(-2.5)),
         f32_field: Some(3.25),
         f64_field: None,
         string_field: Some("optional".to_string()),
@@ -107,3 +121,86 @@ mod tests {
         run_generated_roundtrip_checks();
     }
 }
+
+fn sample_envelope() -> Envelope {
+    Envelope {
+        created_at: Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 123_456_789,
+        },
+        labels: vec!["alpha".to_string(), "beta".to_string()],
+        message: Some(SimpleMessage { bool_field: None }),
+    }
+}
+
+struct SampleServer;
+
+impl SampleServiceServer for SampleServer {
+    async fn echo(&self, request: Envelope) -> omnius_core_omnikit::Result<Envelope> {
+        Ok(request)
+    }
+
+    async fn watch(
+        &self,
+        request: SimpleMessage,
+        channel: &omnius_core_omnikit::service::remoting::OmniRemotingChannel<Envelope, SimpleMessage>,
+    ) -> omnius_core_omnikit::Result<()> {
+        for _ in 0..2 {
+            let mut envelope = sample_envelope();
+            envelope.message = Some(request.clone());
+            channel.send(envelope).await?;
+        }
+        Ok(())
+    }
+}
+
+// Each call runs over its own in-memory connection, the way every call gets its own connection in practice.
+async fn run_generated_service_checks() {
+    const MAX_FRAME_LENGTH: usize = 1024 * 1024;
+    let client = SampleServiceClient::new(MAX_FRAME_LENGTH);
+
+    let (client_side, server_side) = tokio::io::duplex(4096);
+    let server = tokio::spawn(serve_sample(server_side));
+    let response = client.echo(client_side, sample_envelope()).await.expect("failed to call echo");
+    assert_eq!(response, sample_envelope());
+    server.await.expect("server task panicked");
+
+    let (client_side, server_side) = tokio::io::duplex(4096);
+    let server = tokio::spawn(serve_sample(server_side));
+    let request = SimpleMessage { bool_field: Some(false) };
+    let channel = client.watch(client_side, request.clone()).await.expect("failed to call watch");
+    for _ in 0..2 {
+        let envelope = channel.recv().await.expect("failed to receive watched envelope");
+        assert_eq!(envelope.message, Some(request.clone()));
+    }
+    assert!(channel.recv_next().await.expect("failed to receive the end of the watch").is_none());
+    server.await.expect("server task panicked");
+}
+
+fn run_generated_schema_checks() {
+    let schema = PrimitiveShowcase3::SCHEMA;
+    assert_eq!(schema.package, "omnius::demo::v1");
+    assert_eq!(schema.name, "PrimitiveShowcase3");
+    assert!(matches!(schema.kind, ItemKind::Enum { .. }));
+
+    let third = &schema.variants()[2];
+    assert_eq!((third.tag, third.name), (3, "Third"));
+    let retries = third.fields.iter().find(|field| field.name == "retries").expect("missing retries field");
+    assert_eq!((retries.tag, retries.ty, retries.default), (4, TypeDescriptor::U32, Some("0")));
+
+    let envelope = first::omnius::demo::v1::SCHEMA_REGISTRY.get("omnius::demo::v1::Envelope").expect("missing Envelope schema");
+    let created_at = &envelope.fields()[0];
+    assert_eq!((created_at.tag, created_at.name), (1, "created_at"));
+    assert_eq!(created_at.ty.to_string(), "omnius::demo::common::Timestamp");
+    let TypeDescriptor::Named { name, args: [] } = created_at.ty else {
+        panic!("created_at is not a plain named type");
+    };
+    assert!(common::omnius::demo::common::SCHEMA_REGISTRY.get(name).is_some());
+}
+
+async fn serve_sample(stream: tokio::io::DuplexStream) {
+    let listener = omnius_core_omnikit::service::remoting::OmniRemotingListener::new(stream, 1024 * 1024)
+        .await
+        .expect("failed to accept call");
+    dispatch_sample_service(&SampleServer, &listener).await.expect("failed to dispatch call");
+}
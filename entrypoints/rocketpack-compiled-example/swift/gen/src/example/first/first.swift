@@ -0,0 +1,941 @@
+// @generated by rocketpack-compiler
+// package: omnius::demo::v1
+
+import Foundation
+
+public struct PrimitiveShowcase1: Hashable, Sendable {
+    public var boolField: Bool
+    public var u8Field: UInt8
+    public var i16Field: Int16
+    public var i32Field: Int32
+    public var i64Field: Int64
+    public var u16Field: UInt16
+    public var u32Field: UInt32
+    public var u64Field: UInt64
+    public var f16Field: Float16
+    public var f32Field: Float
+    public var f64Field: Double
+    public var stringField: String
+    public var bytesField: Data
+    public var vecField1: [UInt8]
+    public var vecField2: [String]
+    public var vecField3: [Data]
+    public var mapField1: [UInt8: String]
+    public var mapField2: [String: UInt8]
+    public var mapVecField1: [String: [UInt32]]
+    public var mapVecField2: [String: [Data]]
+    public var sliceField: [Int64]
+    public var structField: SimpleMessage
+
+    public init(boolField: Bool, u8Field: UInt8, i16Field: Int16, i32Field: Int32, i64Field: Int64, u16Field: UInt16, u32Field: UInt32, u64Field: UInt64, f16Field: Float16, f32Field: Float, f64Field: Double, stringField: String, bytesField: Data, vecField1: [UInt8], vecField2: [String], vecField3: [Data], mapField1: [UInt8: String], mapField2: [String: UInt8], mapVecField1: [String: [UInt32]], mapVecField2: [String: [Data]], sliceField: [Int64], structField: SimpleMessage) {
+        self.boolField = boolField
+        self.u8Field = u8Field
+        self.i16Field = i16Field
+        self.i32Field = i32Field
+        self.i64Field = i64Field
+        self.u16Field = u16Field
+        self.u32Field = u32Field
+        self.u64Field = u64Field
+        self.f16Field = f16Field
+        self.f32Field = f32Field
+        self.f64Field = f64Field
+        self.stringField = stringField
+        self.bytesField = bytesField
+        self.vecField1 = vecField1
+        self.vecField2 = vecField2
+        self.vecField3 = vecField3
+        self.mapField1 = mapField1
+        self.mapField2 = mapField2
+        self.mapVecField1 = mapVecField1
+        self.mapVecField2 = mapVecField2
+        self.sliceField = sliceField
+        self.structField = structField
+    }
+}
+
+extension PrimitiveShowcase1: RocketPackStruct {
+    public static func pack(encoder: RocketPackEncoder, value: PrimitiveShowcase1) throws {
+        try encoder.writeMap(22)
+        try encoder.writeU64(1)
+        try encoder.writeBool(value.boolField)
+        try encoder.writeU64(2)
+        try encoder.writeU8(value.u8Field)
+        try encoder.writeU64(3)
+        try encoder.writeI16(value.i16Field)
+        try encoder.writeU64(4)
+        try encoder.writeI32(value.i32Field)
+        try encoder.writeU64(5)
+        try encoder.writeI64(value.i64Field)
+        try encoder.writeU64(6)
+        try encoder.writeU16(value.u16Field)
+        try encoder.writeU64(7)
+        try encoder.writeU32(value.u32Field)
+        try encoder.writeU64(8)
+        try encoder.writeU64(value.u64Field)
+        try encoder.writeU64(9)
+        try encoder.writeF16(value.f16Field)
+        try encoder.writeU64(10)
+        try encoder.writeF32(value.f32Field)
+        try encoder.writeU64(11)
+        try encoder.writeF64(value.f64Field)
+        try encoder.writeU64(12)
+        try encoder.writeString(value.stringField)
+        try encoder.writeU64(13)
+        try encoder.writeBytes(value.bytesField)
+        try encoder.writeU64(14)
+        try encoder.writeArray(value.vecField1.count)
+        for __item_0 in value.vecField1 {
+            try encoder.writeU8(__item_0)
+        }
+        try encoder.writeU64(15)
+        try encoder.writeArray(value.vecField2.count)
+        for __item_1 in value.vecField2 {
+            try encoder.writeString(__item_1)
+        }
+        try encoder.writeU64(16)
+        try encoder.writeArray(value.vecField3.count)
+        for __item_2 in value.vecField3 {
+            try encoder.writeBytes(__item_2)
+        }
+        try encoder.writeU64(17)
+        try encoder.writeMap(value.mapField1.count)
+        for (__key_3, __value_4) in value.mapField1.sorted(by: { $0.key < $1.key }) {
+            try encoder.writeU8(__key_3)
+            try encoder.writeString(__value_4)
+        }
+        try encoder.writeU64(18)
+        try encoder.writeMap(value.mapField2.count)
+        for (__key_5, __value_6) in value.mapField2.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {
+            try encoder.writeString(__key_5)
+            try encoder.writeU8(__value_6)
+        }
+        try encoder.writeU64(19)
+        try encoder.writeMap(value.mapVecField1.count)
+        for (__key_7, __value_8) in value.mapVecField1.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {
+            try encoder.writeString(__key_7)
+            try encoder.writeArray(__value_8.count)
+            for __item_9 in __value_8 {
+                try encoder.writeU32(__item_9)
+            }
+        }
+        try encoder.writeU64(20)
+        try encoder.writeMap(value.mapVecField2.count)
+        for (__key_10, __value_11) in value.mapVecField2.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {
+            try encoder.writeString(__key_10)
+            try encoder.writeArray(__value_11.count)
+            for __item_12 in __value_11 {
+                try encoder.writeBytes(__item_12)
+            }
+        }
+        try encoder.writeU64(21)
+        guard value.sliceField.count == 4 else { throw RocketPackEncoderError.other("array length mismatch: slice_field") }
+        try encoder.writeArray(value.sliceField.count)
+        for __item_13 in value.sliceField {
+            try encoder.writeI64(__item_13)
+        }
+        try encoder.writeU64(22)
+        try encoder.writeStruct(value.structField)
+    }
+
+    public static func unpack(decoder: RocketPackDecoder) throws -> PrimitiveShowcase1 {
+        var _boolField: Bool? = nil
+        var _u8Field: UInt8? = nil
+        var _i16Field: Int16? = nil
+        var _i32Field: Int32? = nil
+        var _i64Field: Int64? = nil
+        var _u16Field: UInt16? = nil
+        var _u32Field: UInt32? = nil
+        var _u64Field: UInt64? = nil
+        var _f16Field: Float16? = nil
+        var _f32Field: Float? = nil
+        var _f64Field: Double? = nil
+        var _stringField: String? = nil
+        var _bytesField: Data? = nil
+        var _vecField1: [UInt8]? = nil
+        var _vecField2: [String]? = nil
+        var _vecField3: [Data]? = nil
+        var _mapField1: [UInt8: String]? = nil
+        var _mapField2: [String: UInt8]? = nil
+        var _mapVecField1: [String: [UInt32]]? = nil
+        var _mapVecField2: [String: [Data]]? = nil
+        var _sliceField: [Int64]? = nil
+        var _structField: SimpleMessage? = nil
+        let __count_14 = try decoder.readMap()
+
+        for _ in 0..<__count_14 {
+            switch try decoder.readU64() {
+            case 1:
+                _boolField = try decoder.readBool()
+            case 2:
+                _u8Field = try decoder.readU8()
+            case 3:
+                _i16Field = try decoder.readI16()
+            case 4:
+                _i32Field = try decoder.readI32()
+            case 5:
+                _i64Field = try decoder.readI64()
+            case 6:
+                _u16Field = try decoder.readU16()
+            case 7:
+                _u32Field = try decoder.readU32()
+            case 8:
+                _u64Field = try decoder.readU64()
+            case 9:
+                _f16Field = try decoder.readF16()
+            case 10:
+                _f32Field = try decoder.readF32()
+            case 11:
+                _f64Field = try decoder.readF64()
+            case 12:
+                _stringField = try decoder.readString()
+            case 13:
+                _bytesField = try decoder.readBytes()
+            case 14:
+                let __count_15 = try decoder.readArray()
+                var __values_16: [UInt8] = []
+                for _ in 0..<__count_15 {
+                    __values_16.append(try decoder.readU8())
+                }
+                _vecField1 = __values_16
+            case 15:
+                let __count_17 = try decoder.readArray()
+                var __values_18: [String] = []
+                for _ in 0..<__count_17 {
+                    __values_18.append(try decoder.readString())
+                }
+                _vecField2 = __values_18
+            case 16:
+                let __count_19 = try decoder.readArray()
+                var __values_20: [Data] = []
+                for _ in 0..<__count_19 {
+                    __values_20.append(try decoder.readBytes())
+                }
+                _vecField3 = __values_20
+            case 17:
+                let __count_21 = try decoder.readMap()
+                var __map_22: [UInt8: String] = [:]
+                for _ in 0..<__count_21 {
+                    let __key_23 = try decoder.readU8()
+                    __map_22[__key_23] = try decoder.readString()
+                }
+                _mapField1 = __map_22
+            case 18:
+                let __count_24 = try decoder.readMap()
+                var __map_25: [String: UInt8] = [:]
+                for _ in 0..<__count_24 {
+                    let __key_26 = try decoder.readString()
+                    __map_25[__key_26] = try decoder.readU8()
+                }
+                _mapField2 = __map_25
+            case 19:
+                let __count_27 = try decoder.readMap()
+                var __map_28: [String: [UInt32]] = [:]
+                for _ in 0..<__count_27 {
+                    let __key_29 = try decoder.readString()
+                    let __count_30 = try decoder.readArray()
+                    var __values_31: [UInt32] = []
+                    for _ in 0..<__count_30 {
+                        __values_31.append(try decoder.readU32())
+                    }
+                    __map_28[__key_29] = __values_31
+                }
+                _mapVecField1 = __map_28
+            case 20:
+                let __count_32 = try decoder.readMap()
+                var __map_33: [String: [Data]] = [:]
+                for _ in 0..<__count_32 {
+                    let __key_34 = try decoder.readString()
+                    let __count_35 = try decoder.readArray()
+                    var __values_36: [Data] = []
+                    for _ in 0..<__count_35 {
+                        __values_36.append(try decoder.readBytes())
+                    }
+                    __map_33[__key_34] = __values_36
+                }
+                _mapVecField2 = __map_33
+            case 21:
+                let __count_37 = try decoder.readArray()
+                guard __count_37 == 4 else { throw RocketPackDecoderError.other("array length mismatch: slice_field") }
+                var __values_38: [Int64] = []
+                for _ in 0..<__count_37 {
+                    __values_38.append(try decoder.readI64())
+                }
+                _sliceField = __values_38
+            case 22:
+                _structField = try decoder.readStruct(SimpleMessage.self)
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        guard let boolField = _boolField else { throw RocketPackDecoderError.other("missing field: bool_field") }
+        guard let u8Field = _u8Field else { throw RocketPackDecoderError.other("missing field: u8_field") }
+        guard let i16Field = _i16Field else { throw RocketPackDecoderError.other("missing field: i16_field") }
+        guard let i32Field = _i32Field else { throw RocketPackDecoderError.other("missing field: i32_field") }
+        guard let i64Field = _i64Field else { throw RocketPackDecoderError.other("missing field: i64_field") }
+        guard let u16Field = _u16Field else { throw RocketPackDecoderError.other("missing field: u16_field") }
+        guard let u32Field = _u32Field else { throw RocketPackDecoderError.other("missing field: u32_field") }
+        guard let u64Field = _u64Field else { throw RocketPackDecoderError.other("missing field: u64_field") }
+        guard let f16Field = _f16Field else { throw RocketPackDecoderError.other("missing field: f16_field") }
+        guard let f32Field = _f32Field else { throw RocketPackDecoderError.other("missing field: f32_field") }
+        guard let f64Field = _f64Field else { throw RocketPackDecoderError.other("missing field: f64_field") }
+        guard let stringField = _stringField else { throw RocketPackDecoderError.other("missing field: string_field") }
+        guard let bytesField = _bytesField else { throw RocketPackDecoderError.other("missing field: bytes_field") }
+        guard let vecField1 = _vecField1 else { throw RocketPackDecoderError.other("missing field: vec_field_1") }
+        guard let vecField2 = _vecField2 else { throw RocketPackDecoderError.other("missing field: vec_field_2") }
+        guard let vecField3 = _vecField3 else { throw RocketPackDecoderError.other("missing field: vec_field_3") }
+        guard let mapField1 = _mapField1 else { throw RocketPackDecoderError.other("missing field: map_field_1") }
+        guard let mapField2 = _mapField2 else { throw RocketPackDecoderError.other("missing field: map_field_2") }
+        guard let mapVecField1 = _mapVecField1 else { throw RocketPackDecoderError.other("missing field: map_vec_field_1") }
+        guard let mapVecField2 = _mapVecField2 else { throw RocketPackDecoderError.other("missing field: map_vec_field_2") }
+        guard let sliceField = _sliceField else { throw RocketPackDecoderError.other("missing field: slice_field") }
+        guard let structField = _structField else { throw RocketPackDecoderError.other("missing field: struct_field") }
+
+        return PrimitiveShowcase1(
+            boolField: boolField,
+            u8Field: u8Field,
+            i16Field: i16Field,
+            i32Field: i32Field,
+            i64Field: i64Field,
+            u16Field: u16Field,
+            u32Field: u32Field,
+            u64Field: u64Field,
+            f16Field: f16Field,
+            f32Field: f32Field,
+            f64Field: f64Field,
+            stringField: stringField,
+            bytesField: bytesField,
+            vecField1: vecField1,
+            vecField2: vecField2,
+            vecField3: vecField3,
+            mapField1: mapField1,
+            mapField2: mapField2,
+            mapVecField1: mapVecField1,
+            mapVecField2: mapVecField2,
+            sliceField: sliceField,
+            structField: structField
+        )
+    }
+}
+
+public struct PrimitiveShowcase2: Hashable, Sendable {
+    public var boolField: Bool?
+    public var u8Field: UInt8?
+    public var i16Field: Int16?
+    public var i32Field: Int32?
+    public var i64Field: Int64?
+    public var u16Field: UInt16?
+    public var u32Field: UInt32?
+    public var u64Field: UInt64?
+    public var f16Field: Float16?
+    public var f32Field: Float?
+    public var f64Field: Double?
+    public var stringField: String?
+    public var bytesField: Data?
+    public var vecField1: [UInt8]?
+    public var vecField2: [String]?
+    public var vecField3: [Data]?
+    public var mapField1: [UInt8: String]?
+    public var mapField2: [String: UInt8]?
+    public var mapVecField1: [String: [UInt32]]?
+    public var mapVecField2: [String: [Data]]?
+    public var structField: SimpleMessage?
+
+    public init(boolField: Bool? = nil, u8Field: UInt8? = nil, i16Field: Int16? = nil, i32Field: Int32? = nil, i64Field: Int64? = nil, u16Field: UInt16? = nil, u32Field: UInt32? = nil, u64Field: UInt64? = nil, f16Field: Float16? = nil, f32Field: Float? = nil, f64Field: Double? = nil, stringField: String? = nil, bytesField: Data? = nil, vecField1: [UInt8]? = nil, vecField2: [String]? = nil, vecField3: [Data]? = nil, mapField1: [UInt8: String]? = nil, mapField2: [String: UInt8]? = nil, mapVecField1: [String: [UInt32]]? = nil, mapVecField2: [String: [Data]]? = nil, structField: SimpleMessage? = nil) {
+        self.boolField = boolField
+        self.u8Field = u8Field
+        self.i16Field = i16Field
+        self.i32Field = i32Field
+        self.i64Field = i64Field
+        self.u16Field = u16Field
+        self.u32Field = u32Field
+        self.u64Field = u64Field
+        self.f16Field = f16Field
+        self.f32Field = f32Field
+        self.f64Field = f64Field
+        self.stringField = stringField
+        self.bytesField = bytesField
+        self.vecField1 = vecField1
+        self.vecField2 = vecField2
+        self.vecField3 = vecField3
+        self.mapField1 = mapField1
+        self.mapField2 = mapField2
+        self.mapVecField1 = mapVecField1
+        self.mapVecField2 = mapVecField2
+        self.structField = structField
+    }
+}
+
+extension PrimitiveShowcase2: RocketPackStruct {
+    public static func pack(encoder: RocketPackEncoder, value: PrimitiveShowcase2) throws {
+        var __count_0 = 0
+        if value.boolField != nil { __count_0 += 1 }
+        if value.u8Field != nil { __count_0 += 1 }
+        if value.i16Field != nil { __count_0 += 1 }
+        if value.i32Field != nil { __count_0 += 1 }
+        if value.i64Field != nil { __count_0 += 1 }
+        if value.u16Field != nil { __count_0 += 1 }
+        if value.u32Field != nil { __count_0 += 1 }
+        if value.u64Field != nil { __count_0 += 1 }
+        if value.f16Field != nil { __count_0 += 1 }
+        if value.f32Field != nil { __count_0 += 1 }
+        if value.f64Field != nil { __count_0 += 1 }
+        if value.stringField != nil { __count_0 += 1 }
+        if value.bytesField != nil { __count_0 += 1 }
+        if value.vecField1 != nil { __count_0 += 1 }
+        if value.vecField2 != nil { __count_0 += 1 }
+        if value.vecField3 != nil { __count_0 += 1 }
+        if value.mapField1 != nil { __count_0 += 1 }
+        if value.mapField2 != nil { __count_0 += 1 }
+        if value.mapVecField1 != nil { __count_0 += 1 }
+        if value.mapVecField2 != nil { __count_0 += 1 }
+        if value.structField != nil { __count_0 += 1 }
+        try encoder.writeMap(__count_0)
+        if let __value_1 = value.boolField {
+            try encoder.writeU64(1)
+            try encoder.writeBool(__value_1)
+        }
+        if let __value_2 = value.u8Field {
+            try encoder.writeU64(2)
+            try encoder.writeU8(__value_2)
+        }
+        if let __value_3 = value.i16Field {
+            try encoder.writeU64(3)
+            try encoder.writeI16(__value_3)
+        }
+        if let __value_4 = value.i32Field {
+            try encoder.writeU64(4)
+            try encoder.writeI32(__value_4)
+        }
+        if let __value_5 = value.i64Field {
+            try encoder.writeU64(5)
+            try encoder.writeI64(__value_5)
+        }
+        if let __value_6 = value.u16Field {
+            try encoder.writeU64(6)
+            try encoder.writeU16(__value_6)
+        }
+        if let __value_7 = value.u32Field {
+            try encoder.writeU64(7)
+            try encoder.writeU32(__value_7)
+        }
+        if let __value_8 = value.u64Field {
+            try encoder.writeU64(8)
+            try encoder.writeU64(__value_8)
+        }
+        if let __value_9 = value.f16Field {
+            try encoder.writeU64(9)
+            try encoder.writeF16(__value_9)
+        }
+        if let __value_10 = value.f32Field {
+            try encoder.writeU64(10)
+            try encoder.writeF32(__value_10)
+        }
+        if let __value_11 = value.f64Field {
+            try encoder.writeU64(11)
+            try encoder.writeF64(__value_11)
+        }
+        if let __value_12 = value.stringField {
+            try encoder.writeU64(12)
+            try encoder.writeString(__value_12)
+        }
+        if let __value_13 = value.bytesField {
+            try encoder.writeU64(13)
+            try encoder.writeBytes(__value_13)
+        }
+        if let __value_14 = value.vecField1 {
+            try encoder.writeU64(14)
+            try encoder.writeArray(__value_14.count)
+            for __item_15 in __value_14 {
+                try encoder.writeU8(__item_15)
+            }
+        }
+        if let __value_16 = value.vecField2 {
+            try encoder.writeU64(15)
+            try encoder.writeArray(__value_16.count)
+            for __item_17 in __value_16 {
+                try encoder.writeString(__item_17)
+            }
+        }
+        if let __value_18 = value.vecField3 {
+            try encoder.writeU64(16)
+            try encoder.writeArray(__value_18.count)
+            for __item_19 in __value_18 {
+                try encoder.writeBytes(__item_19)
+            }
+        }
+        if let __value_20 = value.mapField1 {
+            try encoder.writeU64(17)
+            try encoder.writeMap(__value_20.count)
+            for (__key_21, __value_22) in __value_20.sorted(by: { $0.key < $1.key }) {
+                try encoder.writeU8(__key_21)
+                try encoder.writeString(__value_22)
+            }
+        }
+        if let __value_23 = value.mapField2 {
+            try encoder.writeU64(18)
+            try encoder.writeMap(__value_23.count)
+            for (__key_24, __value_25) in __value_23.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {
+                try encoder.writeString(__key_24)
+                try encoder.writeU8(__value_25)
+            }
+        }
+        if let __value_26 = value.mapVecField1 {
+            try encoder.writeU64(19)
+            try encoder.writeMap(__value_26.count)
+            for (__key_27, __value_28) in __value_26.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {
+                try encoder.writeString(__key_27)
+                try encoder.writeArray(__value_28.count)
+                for __item_29 in __value_28 {
+                    try encoder.writeU32(__item_29)
+                }
+            }
+        }
+        if let __value_30 = value.mapVecField2 {
+            try encoder.writeU64(20)
+            try encoder.writeMap(__value_30.count)
+            for (__key_31, __value_32) in __value_30.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {
+                try encoder.writeString(__key_31)
+                try encoder.writeArray(__value_32.count)
+                for __item_33 in __value_32 {
+                    try encoder.writeBytes(__item_33)
+                }
+            }
+        }
+        if let __value_34 = value.structField {
+            try encoder.writeU64(21)
+            try encoder.writeStruct(__value_34)
+        }
+    }
+
+    public static func unpack(decoder: RocketPackDecoder) throws -> PrimitiveShowcase2 {
+        var _boolField: Bool? = nil
+        var _u8Field: UInt8? = nil
+        var _i16Field: Int16? = nil
+        var _i32Field: Int32? = nil
+        var _i64Field: Int64? = nil
+        var _u16Field: UInt16? = nil
+        var _u32Field: UInt32? = nil
+        var _u64Field: UInt64? = nil
+        var _f16Field: Float16? = nil
+        var _f32Field: Float? = nil
+        var _f64Field: Double? = nil
+        var _stringField: String? = nil
+        var _bytesField: Data? = nil
+        var _vecField1: [UInt8]? = nil
+        var _vecField2: [String]? = nil
+        var _vecField3: [Data]? = nil
+        var _mapField1: [UInt8: String]? = nil
+        var _mapField2: [String: UInt8]? = nil
+        var _mapVecField1: [String: [UInt32]]? = nil
+        var _mapVecField2: [String: [Data]]? = nil
+        var _structField: SimpleMessage? = nil
+        let __count_35 = try decoder.readMap()
+
+        for _ in 0..<__count_35 {
+            switch try decoder.readU64() {
+            case 1:
+                _boolField = try decoder.readBool()
+            case 2:
+                _u8Field = try decoder.readU8()
+            case 3:
+                _i16Field = try decoder.readI16()
+            case 4:
+                _i32Field = try decoder.readI32()
+            case 5:
+                _i64Field = try decoder.readI64()
+            case 6:
+                _u16Field = try decoder.readU16()
+            case 7:
+                _u32Field = try decoder.readU32()
+            case 8:
+                _u64Field = try decoder.readU64()
+            case 9:
+                _f16Field = try decoder.readF16()
+            case 10:
+                _f32Field = try decoder.readF32()
+            case 11:
+                _f64Field = try decoder.readF64()
+            case 12:
+                _stringField = try decoder.readString()
+            case 13:
+                _bytesField = try decoder.readBytes()
+            case 14:
+                let __count_36 = try decoder.readArray()
+                var __values_37: [UInt8] = []
+                for _ in 0..<__count_36 {
+                    __values_37.append(try decoder.readU8())
+                }
+                _vecField1 = __values_37
+            case 15:
+                let __count_38 = try decoder.readArray()
+                var __values_39: [String] = []
+                for _ in 0..<__count_38 {
+                    __values_39.append(try decoder.readString())
+                }
+                _vecField2 = __values_39
+            case 16:
+                let __count_40 = try decoder.readArray()
+                var __values_41: [Data] = []
+                for _ in 0..<__count_40 {
+                    __values_41.append(try decoder.readBytes())
+                }
+                _vecField3 = __values_41
+            case 17:
+                let __count_42 = try decoder.readMap()
+                var __map_43: [UInt8: String] = [:]
+                for _ in 0..<__count_42 {
+                    let __key_44 = try decoder.readU8()
+                    __map_43[__key_44] = try decoder.readString()
+                }
+                _mapField1 = __map_43
+            case 18:
+                let __count_45 = try decoder.readMap()
+                var __map_46: [String: UInt8] = [:]
+                for _ in 0..<__count_45 {
+                    let __key_47 = try decoder.readString()
+                    __map_46[__key_47] = try decoder.readU8()
+                }
+                _mapField2 = __map_46
+            case 19:
+                let __count_48 = try decoder.readMap()
+                var __map_49: [String: [UInt32]] = [:]
+                for _ in 0..<__count_48 {
+                    let __key_50 = try decoder.readString()
+                    let __count_51 = try decoder.readArray()
+                    var __values_52: [UInt32] = []
+                    for _ in 0..<__count_51 {
+                        __values_52.append(try decoder.readU32())
+                    }
+                    __map_49[__key_50] = __values_52
+                }
+                _mapVecField1 = __map_49
+            case 20:
+                let __count_53 = try decoder.readMap()
+                var __map_54: [String: [Data]] = [:]
+                for _ in 0..<__count_53 {
+                    let __key_55 = try decoder.readString()
+                    let __count_56 = try decoder.readArray()
+                    var __values_57: [Data] = []
+                    for _ in 0..<__count_56 {
+                        __values_57.append(try decoder.readBytes())
+                    }
+                    __map_54[__key_55] = __values_57
+                }
+                _mapVecField2 = __map_54
+            case 21:
+                _structField = try decoder.readStruct(SimpleMessage.self)
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        return PrimitiveShowcase2(
+            boolField: _boolField,
+            u8Field: _u8Field,
+            i16Field: _i16Field,
+            i32Field: _i32Field,
+            i64Field: _i64Field,
+            u16Field: _u16Field,
+            u32Field: _u32Field,
+            u64Field: _u64Field,
+            f16Field: _f16Field,
+            f32Field: _f32Field,
+            f64Field: _f64Field,
+            stringField: _stringField,
+            bytesField: _bytesField,
+            vecField1: _vecField1,
+            vecField2: _vecField2,
+            vecField3: _vecField3,
+            mapField1: _mapField1,
+            mapField2: _mapField2,
+            mapVecField1: _mapVecField1,
+            mapVecField2: _mapVecField2,
+            structField: _structField
+        )
+    }
+}
+
+public enum PrimitiveShowcase3: Hashable, Sendable {
+    case first
+    case second(entity: String, payload: ByteList)
+    case third(entity: String, status: Status, retries: UInt32 = 0, structField: SimpleMessage? = nil)
+}
+
+extension PrimitiveShowcase3: RocketPackStruct {
+    public static func pack(encoder: RocketPackEncoder, value: PrimitiveShowcase3) throws {
+        try encoder.writeMap(1)
+
+        switch value {
+        case .first:
+            try encoder.writeU64(1)
+            try encoder.writeMap(0)
+        case let .second(entity, payload):
+            try encoder.writeU64(2)
+            try encoder.writeMap(2)
+            try encoder.writeU64(0)
+            try encoder.writeString(entity)
+            try encoder.writeU64(1)
+            try encoder.writeArray(payload.count)
+            for __item_0 in payload {
+                try encoder.writeBytes(__item_0)
+            }
+        case let .third(entity, status, retries, structField):
+            try encoder.writeU64(3)
+            var __count_1 = 3
+            if structField != nil { __count_1 += 1 }
+            try encoder.writeMap(__count_1)
+            try encoder.writeU64(1)
+            try encoder.writeString(entity)
+            try encoder.writeU64(2)
+            try encoder.writeStruct(status)
+            try encoder.writeU64(4)
+            try encoder.writeU32(retries)
+            if let __value_2 = structField {
+                try encoder.writeU64(5)
+                try encoder.writeStruct(__value_2)
+            }
+        }
+    }
+
+    public static func unpack(decoder: RocketPackDecoder) throws -> PrimitiveShowcase3 {
+        var __result: PrimitiveShowcase3? = nil
+        let __count_3 = try decoder.readMap()
+
+        for _ in 0..<__count_3 {
+            switch try decoder.readU64() {
+            case 1:
+                let __count_4 = try decoder.readMap()
+
+                for _ in 0..<__count_4 {
+                    _ = try decoder.readU64()
+                    try decoder.skipField()
+                }
+                __result = .first
+            case 2:
+                var _entity: String? = nil
+                var _payload: [Data]? = nil
+                let __count_5 = try decoder.readMap()
+
+                for _ in 0..<__count_5 {
+                    switch try decoder.readU64() {
+                    case 0:
+                        _entity = try decoder.readString()
+                    case 1:
+                        let __count_6 = try decoder.readArray()
+                        var __values_7: [Data] = []
+                        for _ in 0..<__count_6 {
+                            __values_7.append(try decoder.readBytes())
+                        }
+                        _payload = __values_7
+                    default:
+                        try decoder.skipField()
+                    }
+                }
+
+                guard let entity = _entity else { throw RocketPackDecoderError.other("missing field: entity") }
+                guard let payload = _payload else { throw RocketPackDecoderError.other("missing field: payload") }
+
+                __result = .second(
+                    entity: entity,
+                    payload: payload
+                )
+            case 3:
+                var _entity: String? = nil
+                var _status: Status? = nil
+                var _retries: UInt32? = nil
+                var _structField: SimpleMessage? = nil
+                let __count_8 = try decoder.readMap()
+
+                for _ in 0..<__count_8 {
+                    switch try decoder.readU64() {
+                    case 1:
+                        _entity = try decoder.readString()
+                    case 2:
+                        _status = try decoder.readStruct(Status.self)
+                    case 4:
+                        _retries = try decoder.readU32()
+                    case 5:
+                        _structField = try decoder.readStruct(SimpleMessage.self)
+                    default:
+                        try decoder.skipField()
+                    }
+                }
+
+                guard let entity = _entity else { throw RocketPackDecoderError.other("missing field: entity") }
+                guard let status = _status else { throw RocketPackDecoderError.other("missing field: status") }
+                let retries = _retries ?? 0
+
+                __result = .third(
+                    entity: entity,
+                    status: status,
+                    retries: retries,
+                    structField: _structField
+                )
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        guard let __result = __result else { throw RocketPackDecoderError.other("missing enum variant") }
+        return __result
+    }
+}
+
+public enum Status: Hashable, Sendable {
+    case success
+    case failed
+}
+
+extension Status: RocketPackStruct {
+    public static func pack(encoder: RocketPackEncoder, value: Status) throws {
+        try encoder.writeMap(1)
+
+        switch value {
+        case .success:
+            try encoder.writeU64(1)
+            try encoder.writeMap(0)
+        case .failed:
+            try encoder.writeU64(2)
+            try encoder.writeMap(0)
+        }
+    }
+
+    public static func unpack(decoder: RocketPackDecoder) throws -> Status {
+        var __result: Status? = nil
+        let __count_0 = try decoder.readMap()
+
+        for _ in 0..<__count_0 {
+            switch try decoder.readU64() {
+            case 1:
+                let __count_1 = try decoder.readMap()
+
+                for _ in 0..<__count_1 {
+                    _ = try decoder.readU64()
+                    try decoder.skipField()
+                }
+                __result = .success
+            case 2:
+                let __count_2 = try decoder.readMap()
+
+                for _ in 0..<__count_2 {
+                    _ = try decoder.readU64()
+                    try decoder.skipField()
+                }
+                __result = .failed
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        guard let __result = __result else { throw RocketPackDecoderError.other("missing enum variant") }
+        return __result
+    }
+}
+
+public struct Envelope: Hashable, Sendable {
+    public var createdAt: Timestamp
+    public var labels: Labels
+    public var message: SimpleMessage?
+
+    public init(createdAt: Timestamp, labels: Labels, message: SimpleMessage? = nil) {
+        self.createdAt = createdAt
+        self.labels = labels
+        self.message = message
+    }
+}
+
+extension Envelope: RocketPackStruct {
+    public static func pack(encoder: RocketPackEncoder, value: Envelope) throws {
+        var __count_0 = 2
+        if value.message != nil { __count_0 += 1 }
+        try encoder.writeMap(__count_0)
+        try encoder.writeU64(1)
+        try encoder.writeStruct(value.createdAt)
+        try encoder.writeU64(2)
+        try encoder.writeArray(value.labels.count)
+        for __item_1 in value.labels {
+            try encoder.writeString(__item_1)
+        }
+        if let __value_2 = value.message {
+            try encoder.writeU64(3)
+            try encoder.writeStruct(__value_2)
+        }
+    }
+
+    public static func unpack(decoder: RocketPackDecoder) throws -> Envelope {
+        var _createdAt: Timestamp? = nil
+        var _labels: [String]? = nil
+        var _message: SimpleMessage? = nil
+        let __count_3 = try decoder.readMap()
+
+        for _ in 0..<__count_3 {
+            switch try decoder.readU64() {
+            case 1:
+                _createdAt = try decoder.readStruct(Timestamp.self)
+            case 2:
+                let __count_4 = try decoder.readArray()
+                var __values_5: [String] = []
+                for _ in 0..<__count_4 {
+                    __values_5.append(try decoder.readString())
+                }
+                _labels = __values_5
+            case 3:
+                _message = try decoder.readStruct(SimpleMessage.self)
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        guard let createdAt = _createdAt else { throw RocketPackDecoderError.other("missing field: created_at") }
+        guard let labels = _labels else { throw RocketPackDecoderError.other("missing field: labels") }
+
+        return Envelope(
+            createdAt: createdAt,
+            labels: labels,
+            message: _message
+        )
+    }
+}
+
+public struct SimpleMessage: Hashable, Sendable {
+    public var boolField: Bool?
+
+    public init(boolField: Bool? = nil) {
+        self.boolField = boolField
+    }
+}
+
+extension SimpleMessage: RocketPackStruct {
+    public static func pack(encoder: RocketPackEncoder, value: SimpleMessage) throws {
+        var __count_0 = 0
+        if value.boolField != nil { __count_0 += 1 }
+        try encoder.writeMap(__count_0)
+        if let __value_1 = value.boolField {
+            try encoder.writeU64(1)
+            try encoder.writeBool(__value_1)
+        }
+    }
+
+    public static func unpack(decoder: RocketPackDecoder) throws -> SimpleMessage {
+        var _boolField: Bool? = nil
+        let __count_2 = try decoder.readMap()
+
+        for _ in 0..<__count_2 {
+            switch try decoder.readU64() {
+            case 1:
+                _boolField = try decoder.readBool()
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        return SimpleMessage(
+            boolField: _boolField
+        )
+    }
+}
+
+public typealias ByteList = [Data]
+
+public let maxSampleSize: UInt32 = 1048576
@@ -0,0 +1,51 @@
+// @generated by rocketpack-compiler
+// package: omnius::demo::common
+
+import Foundation
+
+internal struct Timestamp: Hashable, Sendable {
+    internal var seconds: Int64
+    internal var nanos: UInt32
+
+    internal init(seconds: Int64, nanos: UInt32) {
+        self.seconds = seconds
+        self.nanos = nanos
+    }
+}
+
+extension Timestamp: RocketPackStruct {
+    internal static func pack(encoder: RocketPackEncoder, value: Timestamp) throws {
+        try encoder.writeMap(2)
+        try encoder.writeU64(1)
+        try encoder.writeI64(value.seconds)
+        try encoder.writeU64(2)
+        try encoder.writeU32(value.nanos)
+    }
+
+    internal static func unpack(decoder: RocketPackDecoder) throws -> Timestamp {
+        var _seconds: Int64? = nil
+        var _nanos: UInt32? = nil
+        let __count_0 = try decoder.readMap()
+
+        for _ in 0..<__count_0 {
+            switch try decoder.readU64() {
+            case 1:
+                _seconds = try decoder.readI64()
+            case 2:
+                _nanos = try decoder.readU32()
+            default:
+                try decoder.skipField()
+            }
+        }
+
+        guard let seconds = _seconds else { throw RocketPackDecoderError.other("missing field: seconds") }
+        guard let nanos = _nanos else { throw RocketPackDecoderError.other("missing field: nanos") }
+
+        return Timestamp(
+            seconds: seconds,
+            nanos: nanos
+        )
+    }
+}
+
+internal typealias Labels = [String]
@@ -0,0 +1,1008 @@
+// @generated by rocketpack-compiler
+// package: omnius::demo::v1
+
+import { type RocketPackCodec, type RocketPackDecoder, RocketPackDecoderError, type RocketPackEncoder, RocketPackEncoderError, compareNumbers, compareStrings, sortedEntries } from "./rocketpack_runtime";
+import * as common from "../second/common";
+
+export interface PrimitiveShowcase1 {
+    boolField: boolean;
+    u8Field: number;
+    i16Field: number;
+    i32Field: number;
+    i64Field: bigint;
+    u16Field: number;
+    u32Field: number;
+    u64Field: bigint;
+    f16Field: number;
+    f32Field: number;
+    f64Field: number;
+    stringField: string;
+    bytesField: Uint8Array;
+    vecField1: number[];
+    vecField2: string[];
+    vecField3: Uint8Array[];
+    mapField1: Map<number, string>;
+    mapField2: Map<string, number>;
+    mapVecField1: Map<string, number[]>;
+    mapVecField2: Map<string, Uint8Array[]>;
+    sliceField: bigint[];
+    structField: SimpleMessage;
+}
+
+export const PrimitiveShowcase1: RocketPackCodec<PrimitiveShowcase1> = {
+    pack(encoder: RocketPackEncoder, value: PrimitiveShowcase1): void {
+        encoder.writeMap(22);
+        encoder.writeU32(1);
+        encoder.writeBool(value.boolField);
+        encoder.writeU32(2);
+        encoder.writeU8(value.u8Field);
+        encoder.writeU32(3);
+        encoder.writeI16(value.i16Field);
+        encoder.writeU32(4);
+        encoder.writeI32(value.i32Field);
+        encoder.writeU32(5);
+        encoder.writeI64(value.i64Field);
+        encoder.writeU32(6);
+        encoder.writeU16(value.u16Field);
+        encoder.writeU32(7);
+        encoder.writeU32(value.u32Field);
+        encoder.writeU32(8);
+        encoder.writeU64(value.u64Field);
+        encoder.writeU32(9);
+        encoder.writeF16(value.f16Field);
+        encoder.writeU32(10);
+        encoder.writeF32(value.f32Field);
+        encoder.writeU32(11);
+        encoder.writeF64(value.f64Field);
+        encoder.writeU32(12);
+        encoder.writeString(value.stringField);
+        encoder.writeU32(13);
+        encoder.writeBytes(value.bytesField);
+        encoder.writeU32(14);
+        encoder.writeArray(value.vecField1.length);
+        for (const __item_0 of value.vecField1) {
+            encoder.writeU8(__item_0);
+        }
+        encoder.writeU32(15);
+        encoder.writeArray(value.vecField2.length);
+        for (const __item_1 of value.vecField2) {
+            encoder.writeString(__item_1);
+        }
+        encoder.writeU32(16);
+        encoder.writeArray(value.vecField3.length);
+        for (const __item_2 of value.vecField3) {
+            encoder.writeBytes(__item_2);
+        }
+        encoder.writeU32(17);
+        encoder.writeMap(value.mapField1.size);
+        for (const [__key_3, __value_4] of sortedEntries(value.mapField1, compareNumbers)) {
+            encoder.writeU8(__key_3);
+            encoder.writeString(__value_4);
+        }
+        encoder.writeU32(18);
+        encoder.writeMap(value.mapField2.size);
+        for (const [__key_5, __value_6] of sortedEntries(value.mapField2, compareStrings)) {
+            encoder.writeString(__key_5);
+            encoder.writeU8(__value_6);
+        }
+        encoder.writeU32(19);
+        encoder.writeMap(value.mapVecField1.size);
+        for (const [__key_7, __value_8] of sortedEntries(value.mapVecField1, compareStrings)) {
+            encoder.writeString(__key_7);
+            encoder.writeArray(__value_8.length);
+            for (const __item_9 of __value_8) {
+                encoder.writeU32(__item_9);
+            }
+        }
+        encoder.writeU32(20);
+        encoder.writeMap(value.mapVecField2.size);
+        for (const [__key_10, __value_11] of sortedEntries(value.mapVecField2, compareStrings)) {
+            encoder.writeString(__key_10);
+            encoder.writeArray(__value_11.length);
+            for (const __item_12 of __value_11) {
+                encoder.writeBytes(__item_12);
+            }
+        }
+        encoder.writeU32(21);
+        if (value.sliceField.length !== 4) throw new RocketPackEncoderError("array length mismatch: slice_field");
+        encoder.writeArray(value.sliceField.length);
+        for (const __item_13 of value.sliceField) {
+            encoder.writeI64(__item_13);
+        }
+        encoder.writeU32(22);
+        encoder.writeStruct(SimpleMessage, value.structField);
+    },
+
+    unpack(decoder: RocketPackDecoder): PrimitiveShowcase1 {
+        let _boolField: boolean | undefined;
+        let _u8Field: number | undefined;
+        let _i16Field: number | undefined;
+        let _i32Field: number | undefined;
+        let _i64Field: bigint | undefined;
+        let _u16Field: number | undefined;
+        let _u32Field: number | undefined;
+        let _u64Field: bigint | undefined;
+        let _f16Field: number | undefined;
+        let _f32Field: number | undefined;
+        let _f64Field: number | undefined;
+        let _stringField: string | undefined;
+        let _bytesField: Uint8Array | undefined;
+        let _vecField1: number[] | undefined;
+        let _vecField2: string[] | undefined;
+        let _vecField3: Uint8Array[] | undefined;
+        let _mapField1: Map<number, string> | undefined;
+        let _mapField2: Map<string, number> | undefined;
+        let _mapVecField1: Map<string, number[]> | undefined;
+        let _mapVecField2: Map<string, Uint8Array[]> | undefined;
+        let _sliceField: bigint[] | undefined;
+        let _structField: SimpleMessage | undefined;
+        const __count_14 = decoder.readMap();
+
+        for (let __i_15 = 0; __i_15 < __count_14; __i_15++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    _boolField = decoder.readBool();
+                    break;
+                }
+                case 2: {
+                    _u8Field = decoder.readU8();
+                    break;
+                }
+                case 3: {
+                    _i16Field = decoder.readI16();
+                    break;
+                }
+                case 4: {
+                    _i32Field = decoder.readI32();
+                    break;
+                }
+                case 5: {
+                    _i64Field = decoder.readI64();
+                    break;
+                }
+                case 6: {
+                    _u16Field = decoder.readU16();
+                    break;
+                }
+                case 7: {
+                    _u32Field = decoder.readU32();
+                    break;
+                }
+                case 8: {
+                    _u64Field = decoder.readU64();
+                    break;
+                }
+                case 9: {
+                    _f16Field = decoder.readF16();
+                    break;
+                }
+                case 10: {
+                    _f32Field = decoder.readF32();
+                    break;
+                }
+                case 11: {
+                    _f64Field = decoder.readF64();
+                    break;
+                }
+                case 12: {
+                    _stringField = decoder.readString();
+                    break;
+                }
+                case 13: {
+                    _bytesField = decoder.readBytes();
+                    break;
+                }
+                case 14: {
+                    const __count_16 = decoder.readArray();
+                    const __values_17: number[] = [];
+                    for (let __i_18 = 0; __i_18 < __count_16; __i_18++) {
+                        __values_17.push(decoder.readU8());
+                    }
+                    _vecField1 = __values_17;
+                    break;
+                }
+                case 15: {
+                    const __count_19 = decoder.readArray();
+                    const __values_20: string[] = [];
+                    for (let __i_21 = 0; __i_21 < __count_19; __i_21++) {
+                        __values_20.push(decoder.readString());
+                    }
+                    _vecField2 = __values_20;
+                    break;
+                }
+                case 16: {
+                    const __count_22 = decoder.readArray();
+                    const __values_23: Uint8Array[] = [];
+                    for (let __i_24 = 0; __i_24 < __count_22; __i_24++) {
+                        __values_23.push(decoder.readBytes());
+                    }
+                    _vecField3 = __values_23;
+                    break;
+                }
+                case 17: {
+                    const __count_25 = decoder.readMap();
+                    const __map_26: Map<number, string> = new Map();
+                    for (let __i_27 = 0; __i_27 < __count_25; __i_27++) {
+                        const __key_28 = decoder.readU8();
+                        __map_26.set(__key_28, decoder.readString());
+                    }
+                    _mapField1 = __map_26;
+                    break;
+                }
+                case 18: {
+                    const __count_29 = decoder.readMap();
+                    const __map_30: Map<string, number> = new Map();
+                    for (let __i_31 = 0; __i_31 < __count_29; __i_31++) {
+                        const __key_32 = decoder.readString();
+                        __map_30.set(__key_32, decoder.readU8());
+                    }
+                    _mapField2 = __map_30;
+                    break;
+                }
+                case 19: {
+                    const __count_33 = decoder.readMap();
+                    const __map_34: Map<string, number[]> = new Map();
+                    for (let __i_35 = 0; __i_35 < __count_33; __i_35++) {
+                        const __key_36 = decoder.readString();
+                        const __count_37 = decoder.readArray();
+                        const __values_38: number[] = [];
+                        for (let __i_39 = 0; __i_39 < __count_37; __i_39++) {
+                            __values_38.push(decoder.readU32());
+                        }
+                        __map_34.set(__key_36, __values_38);
+                    }
+                    _mapVecField1 = __map_34;
+                    break;
+                }
+                case 20: {
+                    const __count_40 = decoder.readMap();
+                    const __map_41: Map<string, Uint8Array[]> = new Map();
+                    for (let __i_42 = 0; __i_42 < __count_40; __i_42++) {
+                        const __key_43 = decoder.readString();
+                        const __count_44 = decoder.readArray();
+                        const __values_45: Uint8Array[] = [];
+                        for (let __i_46 = 0; __i_46 < __count_44; __i_46++) {
+                            __values_45.push(decoder.readBytes());
+                        }
+                        __map_41.set(__key_43, __values_45);
+                    }
+                    _mapVecField2 = __map_41;
+                    break;
+                }
+                case 21: {
+                    const __count_47 = decoder.readArray();
+                    if (__count_47 !== 4) throw new RocketPackDecoderError("array length mismatch: slice_field");
+                    const __values_48: bigint[] = [];
+                    for (let __i_49 = 0; __i_49 < __count_47; __i_49++) {
+                        __values_48.push(decoder.readI64());
+                    }
+                    _sliceField = __values_48;
+                    break;
+                }
+                case 22: {
+                    _structField = decoder.readStruct(SimpleMessage);
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        if (_boolField === undefined) throw new RocketPackDecoderError("missing field: bool_field");
+        if (_u8Field === undefined) throw new RocketPackDecoderError("missing field: u8_field");
+        if (_i16Field === undefined) throw new RocketPackDecoderError("missing field: i16_field");
+        if (_i32Field === undefined) throw new RocketPackDecoderError("missing field: i32_field");
+        if (_i64Field === undefined) throw new RocketPackDecoderError("missing field: i64_field");
+        if (_u16Field === undefined) throw new RocketPackDecoderError("missing field: u16_field");
+        if (_u32Field === undefined) throw new RocketPackDecoderError("missing field: u32_field");
+        if (_u64Field === undefined) throw new RocketPackDecoderError("missing field: u64_field");
+        if (_f16Field === undefined) throw new RocketPackDecoderError("missing field: f16_field");
+        if (_f32Field === undefined) throw new RocketPackDecoderError("missing field: f32_field");
+        if (_f64Field === undefined) throw new RocketPackDecoderError("missing field: f64_field");
+        if (_stringField === undefined) throw new RocketPackDecoderError("missing field: string_field");
+        if (_bytesField === undefined) throw new RocketPackDecoderError("missing field: bytes_field");
+        if (_vecField1 === undefined) throw new RocketPackDecoderError("missing field: vec_field_1");
+        if (_vecField2 === undefined) throw new RocketPackDecoderError("missing field: vec_field_2");
+        if (_vecField3 === undefined) throw new RocketPackDecoderError("missing field: vec_field_3");
+        if (_mapField1 === undefined) throw new RocketPackDecoderError("missing field: map_field_1");
+        if (_mapField2 === undefined) throw new RocketPackDecoderError("missing field: map_field_2");
+        if (_mapVecField1 === undefined) throw new RocketPackDecoderError("missing field: map_vec_field_1");
+        if (_mapVecField2 === undefined) throw new RocketPackDecoderError("missing field: map_vec_field_2");
+        if (_sliceField === undefined) throw new RocketPackDecoderError("missing field: slice_field");
+        if (_structField === undefined) throw new RocketPackDecoderError("missing field: struct_field");
+
+        return {
+            boolField: _boolField,
+            u8Field: _u8Field,
+            i16Field: _i16Field,
+            i32Field: _i32Field,
+            i64Field: _i64Field,
+            u16Field: _u16Field,
+            u32Field: _u32Field,
+            u64Field: _u64Field,
+            f16Field: _f16Field,
+            f32Field: _f32Field,
+            f64Field: _f64Field,
+            stringField: _stringField,
+            bytesField: _bytesField,
+            vecField1: _vecField1,
+            vecField2: _vecField2,
+            vecField3: _vecField3,
+            mapField1: _mapField1,
+            mapField2: _mapField2,
+            mapVecField1: _mapVecField1,
+            mapVecField2: _mapVecField2,
+            sliceField: _sliceField,
+            structField: _structField,
+        };
+    },
+};
+
+export interface PrimitiveShowcase2 {
+    boolField?: boolean;
+    u8Field?: number;
+    i16Field?: number;
+    i32Field?: number;
+    i64Field?: bigint;
+    u16Field?: number;
+    u32Field?: number;
+    u64Field?: bigint;
+    f16Field?: number;
+    f32Field?: number;
+    f64Field?: number;
+    stringField?: string;
+    bytesField?: Uint8Array;
+    vecField1?: number[];
+    vecField2?: string[];
+    vecField3?: Uint8Array[];
+    mapField1?: Map<number, string>;
+    mapField2?: Map<string, number>;
+    mapVecField1?: Map<string, number[]>;
+    mapVecField2?: Map<string, Uint8Array[]>;
+    structField?: SimpleMessage;
+}
+
+export const PrimitiveShowcase2: RocketPackCodec<PrimitiveShowcase2> = {
+    pack(encoder: RocketPackEncoder, value: PrimitiveShowcase2): void {
+        let __count_0 = 0;
+        if (value.boolField !== undefined) __count_0++;
+        if (value.u8Field !== undefined) __count_0++;
+        if (value.i16Field !== undefined) __count_0++;
+        if (value.i32Field !== undefined) __count_0++;
+        if (value.i64Field !== undefined) __count_0++;
+        if (value.u16Field !== undefined) __count_0++;
+        if (value.u32Field !== undefined) __count_0++;
+        if (value.u64Field !== undefined) __count_0++;
+        if (value.f16Field !== undefined) __count_0++;
+        if (value.f32Field !== undefined) __count_0++;
+        if (value.f64Field !== undefined) __count_0++;
+        if (value.stringField !== undefined) __count_0++;
+        if (value.bytesField !== undefined) __count_0++;
+        if (value.vecField1 !== undefined) __count_0++;
+        if (value.vecField2 !== undefined) __count_0++;
+        if (value.vecField3 !== undefined) __count_0++;
+        if (value.mapField1 !== undefined) __count_0++;
+        if (value.mapField2 !== undefined) __count_0++;
+        if (value.mapVecField1 !== undefined) __count_0++;
+        if (value.mapVecField2 !== undefined) __count_0++;
+        if (value.structField !== undefined) __count_0++;
+        encoder.writeMap(__count_0);
+        if (value.boolField !== undefined) {
+            encoder.writeU32(1);
+            encoder.writeBool(value.boolField);
+        }
+        if (value.u8Field !== undefined) {
+            encoder.writeU32(2);
+            encoder.writeU8(value.u8Field);
+        }
+        if (value.i16Field !== undefined) {
+            encoder.writeU32(3);
+            encoder.writeI16(value.i16Field);
+        }
+        if (value.i32Field !== undefined) {
+            encoder.writeU32(4);
+            encoder.writeI32(value.i32Field);
+        }
+        if (value.i64Field !== undefined) {
+            encoder.writeU32(5);
+            encoder.writeI64(value.i64Field);
+        }
+        if (value.u16Field !== undefined) {
+            encoder.writeU32(6);
+            encoder.writeU16(value.u16Field);
+        }
+        if (value.u32Field !== undefined) {
+            encoder.writeU32(7);
+            encoder.writeU32(value.u32Field);
+        }
+        if (value.u64Field !== undefined) {
+            encoder.writeU32(8);
+            encoder.writeU64(value.u64Field);
+        }
+        if (value.f16Field !== undefined) {
+            encoder.writeU32(9);
+            encoder.writeF16(value.f16Field);
+        }
+        if (value.f32Field !== undefined) {
+            encoder.writeU32(10);
+            encoder.writeF32(value.f32Field);
+        }
+        if (value.f64Field !== undefined) {
+            encoder.writeU32(11);
+            encoder.writeF64(value.f64Field);
+        }
+        if (value.stringField !== undefined) {
+            encoder.writeU32(12);
+            encoder.writeString(value.stringField);
+        }
+        if (value.bytesField !== undefined) {
+            encoder.writeU32(13);
+            encoder.writeBytes(value.bytesField);
+        }
+        if (value.vecField1 !== undefined) {
+            encoder.writeU32(14);
+            encoder.writeArray(value.vecField1.length);
+            for (const __item_1 of value.vecField1) {
+                encoder.writeU8(__item_1);
+            }
+        }
+        if (value.vecField2 !== undefined) {
+            encoder.writeU32(15);
+            encoder.writeArray(value.vecField2.length);
+            for (const __item_2 of value.vecField2) {
+                encoder.writeString(__item_2);
+            }
+        }
+        if (value.vecField3 !== undefined) {
+            encoder.writeU32(16);
+            encoder.writeArray(value.vecField3.length);
+            for (const __item_3 of value.vecField3) {
+                encoder.writeBytes(__item_3);
+            }
+        }
+        if (value.mapField1 !== undefined) {
+            encoder.writeU32(17);
+            encoder.writeMap(value.mapField1.size);
+            for (const [__key_4, __value_5] of sortedEntries(value.mapField1, compareNumbers)) {
+                encoder.writeU8(__key_4);
+                encoder.writeString(__value_5);
+            }
+        }
+        if (value.mapField2 !== undefined) {
+            encoder.writeU32(18);
+            encoder.writeMap(value.mapField2.size);
+            for (const [__key_6, __value_7] of sortedEntries(value.mapField2, compareStrings)) {
+                encoder.writeString(__key_6);
+                encoder.writeU8(__value_7);
+            }
+        }
+        if (value.mapVecField1 !== undefined) {
+            encoder.writeU32(19);
+            encoder.writeMap(value.mapVecField1.size);
+            for (const [__key_8, __value_9] of sortedEntries(value.mapVecField1, compareStrings)) {
+                encoder.writeString(__key_8);
+                encoder.writeArray(__value_9.length);
+                for (const __item_10 of __value_9) {
+                    encoder.writeU32(__item_10);
+                }
+            }
+        }
+        if (value.mapVecField2 !== undefined) {
+            encoder.writeU32(20);
+            encoder.writeMap(value.mapVecField2.size);
+            for (const [__key_11, __value_12] of sortedEntries(value.mapVecField2, compareStrings)) {
+                encoder.writeString(__key_11);
+                encoder.writeArray(__value_12.length);
+                for (const __item_13 of __value_12) {
+                    encoder.writeBytes(__item_13);
+                }
+            }
+        }
+        if (value.structField !== undefined) {
+            encoder.writeU32(21);
+            encoder.writeStruct(SimpleMessage, value.structField);
+        }
+    },
+
+    unpack(decoder: RocketPackDecoder): PrimitiveShowcase2 {
+        let _boolField: boolean | undefined;
+        let _u8Field: number | undefined;
+        let _i16Field: number | undefined;
+        let _i32Field: number | undefined;
+        let _i64Field: bigint | undefined;
+        let _u16Field: number | undefined;
+        let _u32Field: number | undefined;
+        let _u64Field: bigint | undefined;
+        let _f16Field: number | undefined;
+        let _f32Field: number | undefined;
+        let _f64Field: number | undefined;
+        let _stringField: string | undefined;
+        let _bytesField: Uint8Array | undefined;
+        let _vecField1: number[] | undefined;
+        let _vecField2: string[] | undefined;
+        let _vecField3: Uint8Array[] | undefined;
+        let _mapField1: Map<number, string> | undefined;
+        let _mapField2: Map<string, number> | undefined;
+        let _mapVecField1: Map<string, number[]> | undefined;
+        let _mapVecField2: Map<string, Uint8Array[]> | undefined;
+        let _structField: SimpleMessage | undefined;
+        const __count_14 = decoder.readMap();
+
+        for (let __i_15 = 0; __i_15 < __count_14; __i_15++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    _boolField = decoder.readBool();
+                    break;
+                }
+                case 2: {
+                    _u8Field = decoder.readU8();
+                    break;
+                }
+                case 3: {
+                    _i16Field = decoder.readI16();
+                    break;
+                }
+                case 4: {
+                    _i32Field = decoder.readI32();
+                    break;
+                }
+                case 5: {
+                    _i64Field = decoder.readI64();
+                    break;
+                }
+                case 6: {
+                    _u16Field = decoder.readU16();
+                    break;
+                }
+                case 7: {
+                    _u32Field = decoder.readU32();
+                    break;
+                }
+                case 8: {
+                    _u64Field = decoder.readU64();
+                    break;
+                }
+                case 9: {
+                    _f16Field = decoder.readF16();
+                    break;
+                }
+                case 10: {
+                    _f32Field = decoder.readF32();
+                    break;
+                }
+                case 11: {
+                    _f64Field = decoder.readF64();
+                    break;
+                }
+                case 12: {
+                    _stringField = decoder.readString();
+                    break;
+                }
+                case 13: {
+                    _bytesField = decoder.readBytes();
+                    break;
+                }
+                case 14: {
+                    const __count_16 = decoder.readArray();
+                    const __values_17: number[] = [];
+                    for (let __i_18 = 0; __i_18 < __count_16; __i_18++) {
+                        __values_17.push(decoder.readU8());
+                    }
+                    _vecField1 = __values_17;
+                    break;
+                }
+                case 15: {
+                    const __count_19 = decoder.readArray();
+                    const __values_20: string[] = [];
+                    for (let __i_21 = 0; __i_21 < __count_19; __i_21++) {
+                        __values_20.push(decoder.readString());
+                    }
+                    _vecField2 = __values_20;
+                    break;
+                }
+                case 16: {
+                    const __count_22 = decoder.readArray();
+                    const __values_23: Uint8Array[] = [];
+                    for (let __i_24 = 0; __i_24 < __count_22; __i_24++) {
+                        __values_23.push(decoder.readBytes());
+                    }
+                    _vecField3 = __values_23;
+                    break;
+                }
+                case 17: {
+                    const __count_25 = decoder.readMap();
+                    const __map_26: Map<number, string> = new Map();
+                    for (let __i_27 = 0; __i_27 < __count_25; __i_27++) {
+                        const __key_28 = decoder.readU8();
+                        __map_26.set(__key_28, decoder.readString());
+                    }
+                    _mapField1 = __map_26;
+                    break;
+                }
+                case 18: {
+                    const __count_29 = decoder.readMap();
+                    const __map_30: Map<string, number> = new Map();
+                    for (let __i_31 = 0; __i_31 < __count_29; __i_31++) {
+                        const __key_32 = decoder.readString();
+                        __map_30.set(__key_32, decoder.readU8());
+                    }
+                    _mapField2 = __map_30;
+                    break;
+                }
+                case 19: {
+                    const __count_33 = decoder.readMap();
+                    const __map_34: Map<string, number[]> = new Map();
+                    for (let __i_35 = 0; __i_35 < __count_33; __i_35++) {
+                        const __key_36 = decoder.readString();
+                        const __count_37 = decoder.readArray();
+                        const __values_38: number[] = [];
+                        for (let __i_39 = 0; __i_39 < __count_37; __i_39++) {
+                            __values_38.push(decoder.readU32());
+                        }
+                        __map_34.set(__key_36, __values_38);
+                    }
+                    _mapVecField1 = __map_34;
+                    break;
+                }
+                case 20: {
+                    const __count_40 = decoder.readMap();
+                    const __map_41: Map<string, Uint8Array[]> = new Map();
+                    for (let __i_42 = 0; __i_42 < __count_40; __i_42++) {
+                        const __key_43 = decoder.readString();
+                        const __count_44 = decoder.readArray();
+                        const __values_45: Uint8Array[] = [];
+                        for (let __i_46 = 0; __i_46 < __count_44; __i_46++) {
+                            __values_45.push(decoder.readBytes());
+                        }
+                        __map_41.set(__key_43, __values_45);
+                    }
+                    _mapVecField2 = __map_41;
+                    break;
+                }
+                case 21: {
+                    _structField = decoder.readStruct(SimpleMessage);
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        return {
+            boolField: _boolField,
+            u8Field: _u8Field,
+            i16Field: _i16Field,
+            i32Field: _i32Field,
+            i64Field: _i64Field,
+            u16Field: _u16Field,
+            u32Field: _u32Field,
+            u64Field: _u64Field,
+            f16Field: _f16Field,
+            f32Field: _f32Field,
+            f64Field: _f64Field,
+            stringField: _stringField,
+            bytesField: _bytesField,
+            vecField1: _vecField1,
+            vecField2: _vecField2,
+            vecField3: _vecField3,
+            mapField1: _mapField1,
+            mapField2: _mapField2,
+            mapVecField1: _mapVecField1,
+            mapVecField2: _mapVecField2,
+            structField: _structField,
+        };
+    },
+};
+
+export type PrimitiveShowcase3 =
+    | { kind: "first" }
+    | { kind: "second"; entity: string; payload: ByteList }
+    | { kind: "third"; entity: string; status: Status; retries: number; structField?: SimpleMessage };
+
+export const PrimitiveShowcase3: RocketPackCodec<PrimitiveShowcase3> = {
+    pack(encoder: RocketPackEncoder, value: PrimitiveShowcase3): void {
+        encoder.writeMap(1);
+
+        switch (value.kind) {
+            case "first": {
+                encoder.writeU32(1);
+                encoder.writeMap(0);
+                break;
+            }
+            case "second": {
+                encoder.writeU32(2);
+                encoder.writeMap(2);
+                encoder.writeU32(0);
+                encoder.writeString(value.entity);
+                encoder.writeU32(1);
+                encoder.writeArray(value.payload.length);
+                for (const __item_0 of value.payload) {
+                    encoder.writeBytes(__item_0);
+                }
+                break;
+            }
+            case "third": {
+                encoder.writeU32(3);
+                let __count_1 = 3;
+                if (value.structField !== undefined) __count_1++;
+                encoder.writeMap(__count_1);
+                encoder.writeU32(1);
+                encoder.writeString(value.entity);
+                encoder.writeU32(2);
+                encoder.writeStruct(Status, value.status);
+                encoder.writeU32(4);
+                encoder.writeU32(value.retries);
+                if (value.structField !== undefined) {
+                    encoder.writeU32(5);
+                    encoder.writeStruct(SimpleMessage, value.structField);
+                }
+                break;
+            }
+        }
+    },
+
+    unpack(decoder: RocketPackDecoder): PrimitiveShowcase3 {
+        let __result: PrimitiveShowcase3 | undefined;
+        const __count_2 = decoder.readMap();
+
+        for (let __i_3 = 0; __i_3 < __count_2; __i_3++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    const __count_4 = decoder.readMap();
+
+                    for (let __i_5 = 0; __i_5 < __count_4; __i_5++) {
+                        decoder.skipField();
+                        decoder.skipField();
+                    }
+                    __result = { kind: "first" };
+                    break;
+                }
+                case 2: {
+                    let _entity: string | undefined;
+                    let _payload: Uint8Array[] | undefined;
+                    const __count_6 = decoder.readMap();
+
+                    for (let __i_7 = 0; __i_7 < __count_6; __i_7++) {
+                        switch (decoder.readU32()) {
+                            case 0: {
+                                _entity = decoder.readString();
+                                break;
+                            }
+                            case 1: {
+                                const __count_8 = decoder.readArray();
+                                const __values_9: Uint8Array[] = [];
+                                for (let __i_10 = 0; __i_10 < __count_8; __i_10++) {
+                                    __values_9.push(decoder.readBytes());
+                                }
+                                _payload = __values_9;
+                                break;
+                            }
+                            default:
+                                decoder.skipField();
+                        }
+                    }
+
+                    if (_entity === undefined) throw new RocketPackDecoderError("missing field: entity");
+                    if (_payload === undefined) throw new RocketPackDecoderError("missing field: payload");
+
+                    __result = {
+                        kind: "second",
+                        entity: _entity,
+                        payload: _payload,
+                    };
+                    break;
+                }
+                case 3: {
+                    let _entity: string | undefined;
+                    let _status: Status | undefined;
+                    let _retries: number | undefined;
+                    let _structField: SimpleMessage | undefined;
+                    const __count_11 = decoder.readMap();
+
+                    for (let __i_12 = 0; __i_12 < __count_11; __i_12++) {
+                        switch (decoder.readU32()) {
+                            case 1: {
+                                _entity = decoder.readString();
+                                break;
+                            }
+                            case 2: {
+                                _status = decoder.readStruct(Status);
+                                break;
+                            }
+                            case 4: {
+                                _retries = decoder.readU32();
+                                break;
+                            }
+                            case 5: {
+                                _structField = decoder.readStruct(SimpleMessage);
+                                break;
+                            }
+                            default:
+                                decoder.skipField();
+                        }
+                    }
+
+                    if (_entity === undefined) throw new RocketPackDecoderError("missing field: entity");
+                    if (_status === undefined) throw new RocketPackDecoderError("missing field: status");
+
+                    __result = {
+                        kind: "third",
+                        entity: _entity,
+                        status: _status,
+                        retries: _retries ?? 0,
+                        structField: _structField,
+                    };
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        if (__result === undefined) throw new RocketPackDecoderError("missing enum variant");
+        return __result;
+    },
+};
+
+export type Status =
+    | { kind: "success" }
+    | { kind: "failed" };
+
+export const Status: RocketPackCodec<Status> = {
+    pack(encoder: RocketPackEncoder, value: Status): void {
+        encoder.writeMap(1);
+
+        switch (value.kind) {
+            case "success": {
+                encoder.writeU32(1);
+                encoder.writeMap(0);
+                break;
+            }
+            case "failed": {
+                encoder.writeU32(2);
+                encoder.writeMap(0);
+                break;
+            }
+        }
+    },
+
+    unpack(decoder: RocketPackDecoder): Status {
+        let __result: Status | undefined;
+        const __count_0 = decoder.readMap();
+
+        for (let __i_1 = 0; __i_1 < __count_0; __i_1++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    const __count_2 = decoder.readMap();
+
+                    for (let __i_3 = 0; __i_3 < __count_2; __i_3++) {
+                        decoder.skipField();
+                        decoder.skipField();
+                    }
+                    __result = { kind: "success" };
+                    break;
+                }
+                case 2: {
+                    const __count_4 = decoder.readMap();
+
+                    for (let __i_5 = 0; __i_5 < __count_4; __i_5++) {
+                        decoder.skipField();
+                        decoder.skipField();
+                    }
+                    __result = { kind: "failed" };
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        if (__result === undefined) throw new RocketPackDecoderError("missing enum variant");
+        return __result;
+    },
+};
+
+export interface Envelope {
+    createdAt: common.Timestamp;
+    labels: common.Labels;
+    message?: SimpleMessage;
+}
+
+export const Envelope: RocketPackCodec<Envelope> = {
+    pack(encoder: RocketPackEncoder, value: Envelope): void {
+        let __count_0 = 2;
+        if (value.message !== undefined) __count_0++;
+        encoder.writeMap(__count_0);
+        encoder.writeU32(1);
+        encoder.writeStruct(common.Timestamp, value.createdAt);
+        encoder.writeU32(2);
+        encoder.writeArray(value.labels.length);
+        for (const __item_1 of value.labels) {
+            encoder.writeString(__item_1);
+        }
+        if (value.message !== undefined) {
+            encoder.writeU32(3);
+            encoder.writeStruct(SimpleMessage, value.message);
+        }
+    },
+
+    unpack(decoder: RocketPackDecoder): Envelope {
+        let _createdAt: common.Timestamp | undefined;
+        let _labels: string[] | undefined;
+        let _message: SimpleMessage | undefined;
+        const __count_2 = decoder.readMap();
+
+        for (let __i_3 = 0; __i_3 < __count_2; __i_3++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    _createdAt = decoder.readStruct(common.Timestamp);
+                    break;
+                }
+                case 2: {
+                    const __count_4 = decoder.readArray();
+                    const __values_5: string[] = [];
+                    for (let __i_6 = 0; __i_6 < __count_4; __i_6++) {
+                        __values_5.push(decoder.readString());
+                    }
+                    _labels = __values_5;
+                    break;
+                }
+                case 3: {
+                    _message = decoder.readStruct(SimpleMessage);
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        if (_createdAt === undefined) throw new RocketPackDecoderError("missing field: created_at");
+        if (_labels === undefined) throw new RocketPackDecoderError("missing field: labels");
+
+        return {
+            createdAt: _createdAt,
+            labels: _labels,
+            message: _message,
+        };
+    },
+};
+
+export interface SimpleMessage {
+    boolField?: boolean;
+}
+
+export const SimpleMessage: RocketPackCodec<SimpleMessage> = {
+    pack(encoder: RocketPackEncoder, value: SimpleMessage): void {
+        let __count_0 = 0;
+        if (value.boolField !== undefined) __count_0++;
+        encoder.writeMap(__count_0);
+        if (value.boolField !== undefined) {
+            encoder.writeU32(1);
+            encoder.writeBool(value.boolField);
+        }
+    },
+
+    unpack(decoder: RocketPackDecoder): SimpleMessage {
+        let _boolField: boolean | undefined;
+        const __count_1 = decoder.readMap();
+
+        for (let __i_2 = 0; __i_2 < __count_1; __i_2++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    _boolField = decoder.readBool();
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        return {
+            boolField: _boolField,
+        };
+    },
+};
+
+export type ByteList = Uint8Array[];
+
+export const MAX_SAMPLE_SIZE: number = 1048576;
@@ -0,0 +1,734 @@
+// @generated by rocketpack-compiler
+
+// Encoder and decoder for the rocketpack wire format, byte-compatible with `RocketPackBytesEncoder` /
+// `RocketPackBytesDecoder`. 8/16/32-bit integers and floats are `number`; 64/128-bit integers are `bigint`.
+
+const TAG_POSITIVE_BIGNUM = 2n;
+const TAG_NEGATIVE_BIGNUM = 3n;
+
+const U64_MAX = 0xffff_ffff_ffff_ffffn;
+const I64_MIN = -0x8000_0000_0000_0000n;
+const I64_MAX = 0x7fff_ffff_ffff_ffffn;
+const U128_MAX = (1n << 128n) - 1n;
+const I128_MIN = -(1n << 127n);
+const I128_MAX = (1n << 127n) - 1n;
+
+const MAX_DEPTH = 128;
+
+const textEncoder = new TextEncoder();
+const textDecoder = new TextDecoder("utf-8", { fatal: true });
+
+export class RocketPackEncoderError extends Error {
+    constructor(message: string) {
+        super(message);
+        this.name = "RocketPackEncoderError";
+    }
+}
+
+export class RocketPackDecoderError extends Error {
+    constructor(message: string) {
+        super(message);
+        this.name = "RocketPackDecoderError";
+    }
+}
+
+export interface RocketPackCodec<T> {
+    pack(encoder: RocketPackEncoder, value: T): void;
+    unpack(decoder: RocketPackDecoder): T;
+}
+
+export function encode<T>(codec: RocketPackCodec<T>, value: T): Uint8Array {
+    const encoder = new RocketPackEncoder();
+    codec.pack(encoder, value);
+    return encoder.toBytes();
+}
+
+export function decode<T>(codec: RocketPackCodec<T>, bytes: Uint8Array): T {
+    return codec.unpack(new RocketPackDecoder(bytes));
+}
+
+export class RocketPackEncoder {
+    private buf = new Uint8Array(64);
+    private view = new DataView(this.buf.buffer);
+    private len = 0;
+
+    toBytes(): Uint8Array {
+        return this.buf.slice(0, this.len);
+    }
+
+    writeBool(value: boolean): void {
+        this.writeByte(value ? 0xf5 : 0xf4);
+    }
+
+    writeU8(value: number): void {
+        this.writeUnsigned(value, 0xff, "u8");
+    }
+
+    writeU16(value: number): void {
+        this.writeUnsigned(value, 0xffff, "u16");
+    }
+
+    writeU32(value: number): void {
+        this.writeUnsigned(value, 0xffff_ffff, "u32");
+    }
+
+    writeU64(value: bigint): void {
+        checkRange(value, 0n, U64_MAX, "u64");
+        this.writeHead(0, value);
+    }
+
+    // Values that fit in 64 bits are plain integers, larger ones a positive bignum (tag 2).
+    writeU128(value: bigint): void {
+        checkRange(value, 0n, U128_MAX, "u128");
+        if (value <= U64_MAX) {
+            this.writeHead(0, value);
+            return;
+        }
+
+        this.writeTag(TAG_POSITIVE_BIGNUM);
+        this.writeBytes(bignumBytes(value));
+    }
+
+    writeI8(value: number): void {
+        this.writeSigned(value, -0x80, 0x7f, "i8");
+    }
+
+    writeI16(value: number): void {
+        this.writeSigned(value, -0x8000, 0x7fff, "i16");
+    }
+
+    writeI32(value: number): void {
+        this.writeSigned(value, -0x8000_0000, 0x7fff_ffff, "i32");
+    }
+
+    writeI64(value: bigint): void {
+        checkRange(value, I64_MIN, I64_MAX, "i64");
+        if (value >= 0n) {
+            this.writeHead(0, value);
+        } else {
+            this.writeHead(1, -1n - value);
+        }
+    }
+
+    // Values in the `i64` range are plain integers, others a positive or negative bignum (tag 2 or 3).
+    writeI128(value: bigint): void {
+        checkRange(value, I128_MIN, I128_MAX, "i128");
+        if (value >= I64_MIN && value <= I64_MAX) {
+            this.writeI64(value);
+            return;
+        }
+        if (value >= 0n) {
+            this.writeU128(value);
+            return;
+        }
+
+        this.writeTag(TAG_NEGATIVE_BIGNUM);
+        this.writeBytes(bignumBytes(-1n - value));
+    }
+
+    writeF16(value: number): void {
+        this.reserve(3);
+        this.buf[this.len++] = 0xf9;
+        this.view.setUint16(this.len, toHalfBits(value));
+        this.len += 2;
+    }
+
+    writeF32(value: number): void {
+        this.reserve(5);
+        this.buf[this.len++] = 0xfa;
+        this.view.setFloat32(this.len, value);
+        this.len += 4;
+    }
+
+    writeF64(value: number): void {
+        this.reserve(9);
+        this.buf[this.len++] = 0xfb;
+        this.view.setFloat64(this.len, value);
+        this.len += 8;
+    }
+
+    writeBytes(value: Uint8Array): void {
+        this.writeHead(2, value.length);
+        this.writeRaw(value);
+    }
+
+    writeString(value: string): void {
+        const bytes = textEncoder.encode(value);
+        this.writeHead(3, bytes.length);
+        this.writeRaw(bytes);
+    }
+
+    writeArray(len: number): void {
+        this.writeHead(4, len);
+    }
+
+    writeMap(len: number): void {
+        this.writeHead(5, len);
+    }
+
+    writeNull(): void {
+        this.writeByte(0xf6);
+    }
+
+    writeTag(tag: bigint): void {
+        this.writeHead(6, tag);
+    }
+
+    writeStruct<T>(codec: RocketPackCodec<T>, value: T): void {
+        codec.pack(this, value);
+    }
+
+    private writeUnsigned(value: number, max: number, name: string): void {
+        if (!Number.isInteger(value) || value < 0 || value > max) {
+            throw new RocketPackEncoderError(`${name} out of range: ${value}`);
+        }
+        this.writeHead(0, value);
+    }
+
+    private writeSigned(value: number, min: number, max: number, name: string): void {
+        if (!Number.isInteger(value) || value < min || value > max) {
+            throw new RocketPackEncoderError(`${name} out of range: ${value}`);
+        }
+        if (value >= 0) {
+            this.writeHead(0, value);
+        } else {
+            this.writeHead(1, -1 - value);
+        }
+    }
+
+    // Writes the major type with the argument in its shortest form.
+    private writeHead(major: number, value: number | bigint): void {
+        const prefix = major << 5;
+        if (typeof value === "bigint" ? value > 0xffff_ffffn : value > 0xffff_ffff) {
+            this.reserve(9);
+            this.buf[this.len++] = prefix | 27;
+            this.view.setBigUint64(this.len, BigInt(value));
+            this.len += 8;
+            return;
+        }
+
+        const n = Number(value);
+        if (n <= 23) {
+            this.writeByte(prefix | n);
+        } else if (n <= 0xff) {
+            this.reserve(2);
+            this.buf[this.len++] = prefix | 24;
+            this.buf[this.len++] = n;
+        } else if (n <= 0xffff) {
+            this.reserve(3);
+            this.buf[this.len++] = prefix | 25;
+            this.view.setUint16(this.len, n);
+            this.len += 2;
+        } else {
+            this.reserve(5);
+            this.buf[this.len++] = prefix | 26;
+            this.view.setUint32(this.len, n);
+            this.len += 4;
+        }
+    }
+
+    private writeByte(value: number): void {
+        this.reserve(1);
+        this.buf[this.len++] = value;
+    }
+
+    private writeRaw(value: Uint8Array): void {
+        this.reserve(value.length);
+        this.buf.set(value, this.len);
+        this.len += value.length;
+    }
+
+    private reserve(additional: number): void {
+        const required = this.len + additional;
+        if (required <= this.buf.length) {
+            return;
+        }
+
+        let capacity = this.buf.length * 2;
+        while (capacity < required) {
+            capacity *= 2;
+        }
+        const buf = new Uint8Array(capacity);
+        buf.set(this.buf.subarray(0, this.len));
+        this.buf = buf;
+        this.view = new DataView(buf.buffer);
+    }
+}
+
+export class RocketPackDecoder {
+    private readonly buf: Uint8Array;
+    private readonly view: DataView;
+    private pos = 0;
+    private depth = 0;
+
+    constructor(buf: Uint8Array) {
+        this.buf = buf;
+        this.view = new DataView(buf.buffer, buf.byteOffset, buf.byteLength);
+    }
+
+    position(): number {
+        return this.pos;
+    }
+
+    remaining(): number {
+        return this.buf.length - this.pos;
+    }
+
+    // Returns the declared length of the byte string, text string, array or map at the current position without
+    // reading it, so an oversized item can be rejected before it is decoded.
+    currentLen(): number {
+        const position = this.pos;
+        const [major, value] = this.readHead();
+        this.pos = position;
+        if (major < 2 || major > 5) {
+            throw mismatch(position, "length");
+        }
+        return toLength(position, value);
+    }
+
+    readBool(): boolean {
+        const position = this.pos;
+        switch (this.readByte()) {
+            case 0xf4:
+                return false;
+            case 0xf5:
+                return true;
+            default:
+                throw mismatch(position, "bool");
+        }
+    }
+
+    readU8(): number {
+        return Number(this.readInteger(0n, 0xffn, "u8"));
+    }
+
+    readU16(): number {
+        return Number(this.readInteger(0n, 0xffffn, "u16"));
+    }
+
+    readU32(): number {
+        return Number(this.readInteger(0n, 0xffff_ffffn, "u32"));
+    }
+
+    readU64(): bigint {
+        return this.readInteger(0n, U64_MAX, "u64");
+    }
+
+    // Reads a plain unsigned integer or a positive bignum (tag 2).
+    readU128(): bigint {
+        const position = this.pos;
+        if (this.peekMajor() !== 6) {
+            return this.readU64();
+        }
+
+        if (this.readTag() !== TAG_POSITIVE_BIGNUM) {
+            throw mismatch(position, "u128");
+        }
+        const value = bignumFromBytes(this.readBytes());
+        if (value > U128_MAX) {
+            throw outOfRange(position, "u128");
+        }
+        return value;
+    }
+
+    readI8(): number {
+        return Number(this.readInteger(-0x80n, 0x7fn, "i8"));
+    }
+
+    readI16(): number {
+        return Number(this.readInteger(-0x8000n, 0x7fffn, "i16"));
+    }
+
+    readI32(): number {
+        return Number(this.readInteger(-0x8000_0000n, 0x7fff_ffffn, "i32"));
+    }
+
+    readI64(): bigint {
+        return this.readInteger(I64_MIN, I64_MAX, "i64");
+    }
+
+    // Reads a plain integer or a positive or negative bignum (tag 2 or 3).
+    readI128(): bigint {
+        const position = this.pos;
+        if (this.peekMajor() !== 6) {
+            return this.readInteger(I64_MIN, U64_MAX, "i128");
+        }
+
+        const tag = this.readTag();
+        if (tag !== TAG_POSITIVE_BIGNUM && tag !== TAG_NEGATIVE_BIGNUM) {
+            throw mismatch(position, "i128");
+        }
+        const magnitude = bignumFromBytes(this.readBytes());
+        const value = tag === TAG_POSITIVE_BIGNUM ? magnitude : -1n - magnitude;
+        if (value < I128_MIN || value > I128_MAX) {
+            throw outOfRange(position, "i128");
+        }
+        return value;
+    }
+
+    readF16(): number {
+        const position = this.pos;
+        if (this.readByte() !== 0xf9) {
+            throw mismatch(position, "f16");
+        }
+        return fromHalfBits(this.readFixed(2).getUint16(0));
+    }
+
+    // Narrower floats are widened, as the Rust decoder does.
+    readF32(): number {
+        const position = this.pos;
+        switch (this.readByte()) {
+            case 0xf9:
+                return fromHalfBits(this.readFixed(2).getUint16(0));
+            case 0xfa:
+                return this.readFixed(4).getFloat32(0);
+            default:
+                throw mismatch(position, "f32");
+        }
+    }
+
+    readF64(): number {
+        const position = this.pos;
+        switch (this.readByte()) {
+            case 0xf9:
+                return fromHalfBits(this.readFixed(2).getUint16(0));
+            case 0xfa:
+                return this.readFixed(4).getFloat32(0);
+            case 0xfb:
+                return this.readFixed(8).getFloat64(0);
+            default:
+                throw mismatch(position, "f64");
+        }
+    }
+
+    readBytes(): Uint8Array {
+        const len = this.readLength(2, "bytes", 1);
+        return new Uint8Array(this.readRaw(len));
+    }
+
+    readString(): string {
+        const position = this.pos;
+        const len = this.readLength(3, "string", 1);
+        try {
+            return textDecoder.decode(this.readRaw(len));
+        } catch {
+            throw new RocketPackDecoderError(`invalid utf-8 (position: ${position})`);
+        }
+    }
+
+    readArray(): number {
+        return this.readLength(4, "array", 1);
+    }
+
+    readMap(): number {
+        return this.readLength(5, "map", 2);
+    }
+
+    readNull(): void {
+        const position = this.pos;
+        if (this.readByte() !== 0xf6) {
+            throw mismatch(position, "null");
+        }
+    }
+
+    readTag(): bigint {
+        const position = this.pos;
+        const [major, value] = this.readHead();
+        if (major !== 6) {
+            throw mismatch(position, "tag");
+        }
+        return value;
+    }
+
+    readStruct<T>(codec: RocketPackCodec<T>): T {
+        if (this.depth >= MAX_DEPTH) {
+            throw new RocketPackDecoderError(`depth limit exceeded (position: ${this.pos}, limit: ${MAX_DEPTH})`);
+        }
+
+        this.depth++;
+        const value = codec.unpack(this);
+        this.depth--;
+        return value;
+    }
+
+    skipField(): void {
+        let remain = 1;
+
+        while (remain > 0) {
+            const position = this.pos;
+            const major = this.peekMajor();
+            const info = this.buf[this.pos] & 0x1f;
+
+            if (major === 7) {
+                this.pos++;
+                switch (info) {
+                    case 20:
+                    case 21:
+                    case 22:
+                        break;
+                    case 25:
+                        this.readRaw(2);
+                        break;
+                    case 26:
+                        this.readRaw(4);
+                        break;
+                    case 27:
+                        this.readRaw(8);
+                        break;
+                    default:
+                        throw mismatch(position, "field");
+                }
+            } else {
+                const [, value] = this.readHead();
+                switch (major) {
+                    case 2:
+                    case 3:
+                        this.readRaw(toLength(position, value));
+                        break;
+                    case 4:
+                        remain += toLength(position, value);
+                        break;
+                    case 5:
+                        remain += toLength(position, value) * 2;
+                        break;
+                    // The tagged value follows the tag and still has to be skipped.
+                    case 6:
+                        remain += 1;
+                        break;
+                }
+            }
+
+            remain--;
+        }
+    }
+
+    // Reads a collection or string header, rejecting lengths the remaining input cannot hold.
+    private readLength(expectedMajor: number, name: string, minItemSize: number): number {
+        const position = this.pos;
+        const [major, value] = this.readHead();
+        if (major !== expectedMajor) {
+            throw mismatch(position, name);
+        }
+
+        const len = toLength(position, value);
+        if (len * minItemSize > this.remaining()) {
+            throw new RocketPackDecoderError(`unexpected eof (position: ${position})`);
+        }
+        return len;
+    }
+
+    private readInteger(min: bigint, max: bigint, name: string): bigint {
+        const position = this.pos;
+        const [major, value] = this.readHead();
+
+        let result: bigint;
+        if (major === 0) {
+            result = value;
+        } else if (major === 1) {
+            result = -1n - value;
+        } else {
+            throw mismatch(position, name);
+        }
+
+        if (result < min || result > max) {
+            throw outOfRange(position, name);
+        }
+        return result;
+    }
+
+    // Reads an initial byte of major type 0-6 and its argument.
+    private readHead(): [number, bigint] {
+        const position = this.pos;
+        const initial = this.readByte();
+        const major = initial >> 5;
+        const info = initial & 0x1f;
+        if (major === 7) {
+            throw mismatch(position, "integer");
+        }
+
+        if (info <= 23) {
+            return [major, BigInt(info)];
+        }
+        switch (info) {
+            case 24:
+                return [major, BigInt(this.readByte())];
+            case 25:
+                return [major, BigInt(this.readFixed(2).getUint16(0))];
+            case 26:
+                return [major, BigInt(this.readFixed(4).getUint32(0))];
+            case 27:
+                return [major, this.readFixed(8).getBigUint64(0)];
+            default:
+                throw mismatch(position, "integer");
+        }
+    }
+
+    private peekMajor(): number {
+        if (this.pos >= this.buf.length) {
+            throw new RocketPackDecoderError("unexpected eof");
+        }
+        return this.buf[this.pos] >> 5;
+    }
+
+    private readByte(): number {
+        if (this.pos >= this.buf.length) {
+            throw new RocketPackDecoderError("unexpected eof");
+        }
+        return this.buf[this.pos++];
+    }
+
+    private readFixed(len: number): DataView {
+        const position = this.pos;
+        this.readRaw(len);
+        return new DataView(this.view.buffer, this.view.byteOffset + position, len);
+    }
+
+    private readRaw(len: number): Uint8Array {
+        if (len > this.remaining()) {
+            throw new RocketPackDecoderError(`unexpected eof (position: ${this.pos})`);
+        }
+        const value = this.buf.subarray(this.pos, this.pos + len);
+        this.pos += len;
+        return value;
+    }
+}
+
+// Comparators matching the order of Rust `BTreeMap` keys, so maps are written in the same order.
+export function compareNumbers<T extends number | bigint>(a: T, b: T): number {
+    return a < b ? -1 : a > b ? 1 : 0;
+}
+
+export function compareBooleans(a: boolean, b: boolean): number {
+    return Number(a) - Number(b);
+}
+
+// Code point order is UTF-8 byte order, unlike the UTF-16 order of `<` on strings.
+export function compareStrings(a: string, b: string): number {
+    const left = a[Symbol.iterator]();
+    const right = b[Symbol.iterator]();
+    for (;;) {
+        const l = left.next();
+        const r = right.next();
+        if (l.done || r.done) {
+            return Number(!l.done) - Number(!r.done);
+        }
+
+        const diff = (l.value.codePointAt(0) ?? 0) - (r.value.codePointAt(0) ?? 0);
+        if (diff !== 0) {
+            return diff;
+        }
+    }
+}
+
+export function compareBytes(a: Uint8Array, b: Uint8Array): number {
+    const len = Math.min(a.length, b.length);
+    for (let i = 0; i < len; i++) {
+        if (a[i] !== b[i]) {
+            return a[i] - b[i];
+        }
+    }
+    return a.length - b.length;
+}
+
+export function sortedEntries<K, V>(map: ReadonlyMap<K, V>, compare?: (a: K, b: K) => number): [K, V][] {
+    const entries = [...map.entries()];
+    if (compare !== undefined) {
+        entries.sort(([a], [b]) => compare(a, b));
+    }
+    return entries;
+}
+
+function checkRange(value: bigint, min: bigint, max: bigint, name: string): void {
+    if (value < min || value > max) {
+        throw new RocketPackEncoderError(`${name} out of range: ${value}`);
+    }
+}
+
+function mismatch(position: number, name: string): RocketPackDecoderError {
+    return new RocketPackDecoderError(`mismatch field type (position: ${position}, expected: ${name})`);
+}
+
+function outOfRange(position: number, name: string): RocketPackDecoderError {
+    return new RocketPackDecoderError(`${name} out of range (position: ${position})`);
+}
+
+function toLength(position: number, value: bigint): number {
+    if (value > BigInt(Number.MAX_SAFE_INTEGER)) {
+        throw new RocketPackDecoderError(`length overflow (position: ${position})`);
+    }
+    return Number(value);
+}
+
+// Big-endian bytes without leading zeros, as bignums are written in preferred serialization.
+function bignumBytes(value: bigint): Uint8Array {
+    const bytes: number[] = [];
+    while (value > 0n) {
+        bytes.unshift(Number(value & 0xffn));
+        value >>= 8n;
+    }
+    return Uint8Array.from(bytes);
+}
+
+function bignumFromBytes(bytes: Uint8Array): bigint {
+    let value = 0n;
+    for (const byte of bytes) {
+        value = (value << 8n) | BigInt(byte);
+    }
+    return value;
+}
+
+// Rounds to the nearest half-precision value, ties to even, like `half::f16::from_f64`.
+function toHalfBits(value: number): number {
+    if (Number.isNaN(value)) {
+        return 0x7e00;
+    }
+
+    const sign = value < 0 || Object.is(value, -0) ? 0x8000 : 0;
+    const abs = Math.abs(value);
+    if (abs >= 65520) {
+        return sign | 0x7c00;
+    }
+    if (abs < 2 ** -14) {
+        return sign | roundHalfEven(abs * 2 ** 24);
+    }
+
+    let exponent = Math.floor(Math.log2(abs));
+    if (2 ** exponent > abs) {
+        exponent--;
+    } else if (2 ** (exponent + 1) <= abs) {
+        exponent++;
+    }
+
+    let mantissa = roundHalfEven((abs / 2 ** exponent - 1) * 1024);
+    if (mantissa === 1024) {
+        mantissa = 0;
+        exponent++;
+    }
+    return sign | ((exponent + 15) << 10) | mantissa;
+}
+
+function fromHalfBits(bits: number): number {
+    const sign = bits & 0x8000 ? -1 : 1;
+    const exponent = (bits >> 10) & 0x1f;
+    const mantissa = bits & 0x3ff;
+
+    if (exponent === 0) {
+        return sign * mantissa * 2 ** -24;
+    }
+    if (exponent === 0x1f) {
+        return mantissa === 0 ? sign * Infinity : NaN;
+    }
+    return sign * (1 + mantissa / 1024) * 2 ** (exponent - 15);
+}
+
+function roundHalfEven(value: number): number {
+    const floor = Math.floor(value);
+    const diff = value - floor;
+    if (diff > 0.5) {
+        return floor + 1;
+    }
+    if (diff < 0.5) {
+        return floor;
+    }
+    return floor % 2 === 0 ? floor : floor + 1;
+}
@@ -0,0 +1,50 @@
+// @generated by rocketpack-compiler
+// package: omnius::demo::common
+
+import { type RocketPackCodec, type RocketPackDecoder, RocketPackDecoderError, type RocketPackEncoder } from "../first/rocketpack_runtime";
+
+export interface Timestamp {
+    seconds: bigint;
+    nanos: number;
+}
+
+export const Timestamp: RocketPackCodec<Timestamp> = {
+    pack(encoder: RocketPackEncoder, value: Timestamp): void {
+        encoder.writeMap(2);
+        encoder.writeU32(1);
+        encoder.writeI64(value.seconds);
+        encoder.writeU32(2);
+        encoder.writeU32(value.nanos);
+    },
+
+    unpack(decoder: RocketPackDecoder): Timestamp {
+        let _seconds: bigint | undefined;
+        let _nanos: number | undefined;
+        const __count_0 = decoder.readMap();
+
+        for (let __i_1 = 0; __i_1 < __count_0; __i_1++) {
+            switch (decoder.readU32()) {
+                case 1: {
+                    _seconds = decoder.readI64();
+                    break;
+                }
+                case 2: {
+                    _nanos = decoder.readU32();
+                    break;
+                }
+                default:
+                    decoder.skipField();
+            }
+        }
+
+        if (_seconds === undefined) throw new RocketPackDecoderError("missing field: seconds");
+        if (_nanos === undefined) throw new RocketPackDecoderError("missing field: nanos");
+
+        return {
+            seconds: _seconds,
+            nanos: _nanos,
+        };
+    },
+};
+
+export type Labels = string[];
@@ -1,25 +1,68 @@
-use tracing::{info, warn};
+use std::path::PathBuf;
+
+use tracing::warn;
 
 use crate::{config::AppConfig, error::CodegenError};
 
+use self::{
+    common::{discover_source_files, parse_sources},
+    output::OutputWriter,
+    resolver::resolve_sources,
+};
+
+mod analysis;
+mod builder;
+mod common;
+mod compat;
+mod csharp;
+mod formatter;
+#[cfg(feature = "cli")]
+mod lsp;
+mod output;
+mod resolver;
 mod rust;
+mod swift;
+mod typescript;
+mod validator;
+#[cfg(feature = "cli")]
+mod watch;
+
+pub use builder::Builder;
+pub use common::SourceCache;
+pub use compat::check_compat;
+pub use formatter::format;
+#[cfg(feature = "cli")]
+pub use lsp::serve as serve_lsp;
+pub use output::OutputMode;
+#[cfg(feature = "cli")]
+pub use watch::watch;
+
+// Parses and validates every source without generating code, returning the number of checked sources.
+pub async fn check(conf: &AppConfig) -> Result<usize, CodegenError> {
+    let sources = discover_source_files(&conf.root_dir, &conf.sources)?;
+    let parsed_sources = parse_sources(&sources)?;
+    resolve_sources(&parsed_sources)?;
+    Ok(parsed_sources.len())
+}
+
+// Returns the generated files that differed from the disk: the rewritten files in write mode, the out-of-date files in
+// check mode.
+pub async fn generate(conf: &AppConfig, cache: &mut SourceCache, mode: OutputMode) -> Result<Vec<PathBuf>, CodegenError> {
+    let sources = discover_source_files(&conf.root_dir, &conf.sources)?;
+    let parsed_sources = cache.parse(&sources)?;
+    let mut writer = OutputWriter::new(mode);
 
-pub async fn generate(conf: AppConfig) -> Result<(), CodegenError> {
     for generator_conf in &conf.generators {
         match generator_conf.plugin.as_str() {
-            "rocketpack-rust" => rust::generate(&conf.root_dir, &conf.sources, generator_conf).await?,
-            "rocketpack-csharp" | "rocketpack-swift" => {
-                info!(
-                    generator_id = %generator_conf.id,
-                    plugin = %generator_conf.plugin,
-                    "skip non-rust generator for current rust-only implementation"
-                );
-            }
+            "rocketpack-rust" => rust::generate(&conf.root_dir, &parsed_sources, generator_conf, &mut writer).await?,
+            "rocketpack-csharp" => csharp::generate(&conf.root_dir, &parsed_sources, generator_conf, &mut writer).await?,
+            "rocketpack-swift" => swift::generate(&conf.root_dir, &parsed_sources, generator_conf, &mut writer).await?,
+            "rocketpack-typescript" => typescript::generate(&conf.root_dir, &parsed_sources, generator_conf, &mut writer).await?,
             _ => {
                 warn!(generator_id = %generator_conf.id, plugin = %generator_conf.plugin, "skip unknown generator plugin");
             }
         }
     }
 
-    Ok(())
+    Ok(writer.into_changed())
 }
@@ -0,0 +1,513 @@
+use std::path::Path as FsPath;
+
+use super::{
+    common::{
+        DiscoveredSource, ParsedSource, SchemaIndex, builtin_type, canonical_path, format_literal, format_resolved_type, format_type, format_type_params, path_segments,
+        resolve_type,
+    },
+    resolver::{SourceAnalysis, analyze_sources},
+};
+use crate::{
+    error::{CodegenError, ParseError},
+    parser::{
+        Parser,
+        ast::{Field, File, Item, Path as AstPath, Span, Type, VariantKind},
+        lexer::{self, Token},
+    },
+};
+
+const BUILTIN_TYPE_NAMES: [&str; 16] = [
+    "bool", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f16", "f32", "f64", "string", "bytes",
+];
+const GENERIC_TYPE_NAMES: [&str; 3] = ["Option", "Vec", "Map"];
+
+#[derive(Debug, Clone)]
+pub(super) struct Document {
+    pub(super) source: DiscoveredSource,
+    pub(super) text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CompletionKind {
+    Builtin,
+    Struct,
+    Enum,
+    Alias,
+    Tag,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Completion {
+    pub(super) label: String,
+    pub(super) kind: CompletionKind,
+    pub(super) detail: Option<String>,
+}
+
+enum Reference<'a> {
+    Use(&'a AstPath),
+    Type(&'a AstPath),
+    Item(&'a Item),
+}
+
+// Schemas of an editor workspace, analyzed as a whole so imports between files resolve. Files are parsed with error
+// recovery, so definitions, hovers and completions keep working while a file is being edited.
+pub(super) struct Analysis {
+    sources: Vec<ParsedSource>,
+    indexes: Option<Vec<SchemaIndex>>,
+    errors: Vec<Vec<ParseError>>,
+}
+
+impl Analysis {
+    pub(super) fn new(documents: Vec<Document>) -> Result<Self, CodegenError> {
+        let mut sources = Vec::with_capacity(documents.len());
+        let mut syntax_errors = Vec::with_capacity(documents.len());
+
+        for document in documents {
+            let (tokens, docs, mut errors) = lexer::lex_with_docs(&document.text);
+            let mut parser = Parser::new(tokens).with_docs(docs);
+            let file = parser.parse_file();
+            errors.append(&mut parser.errors);
+
+            sources.push(ParsedSource {
+                source: document.source,
+                text: document.text,
+                file,
+            });
+            syntax_errors.push(errors);
+        }
+
+        let SourceAnalysis { indexes, errors } = analyze_sources(&sources)?;
+
+        // The validation of a file with syntax errors mostly reports the gaps left by the recovery, so only the syntax
+        // errors are kept for it.
+        let errors = syntax_errors
+            .into_iter()
+            .zip(errors)
+            .map(|(syntax_errors, errors)| {
+                let mut errors = if syntax_errors.is_empty() { errors } else { syntax_errors };
+                errors.sort_by_key(|error| (error.span.start, error.span.end));
+                errors
+            })
+            .collect();
+
+        Ok(Self { sources, indexes, errors })
+    }
+
+    pub(super) fn diagnostics(&self) -> impl Iterator<Item = (&ParsedSource, &[ParseError])> {
+        self.sources.iter().zip(&self.errors).map(|(source, errors)| (source, errors.as_slice()))
+    }
+
+    pub(super) fn source(&self, path: &FsPath) -> Option<&ParsedSource> {
+        self.sources.iter().find(|source| source.source.absolute_path == path)
+    }
+
+    // Returns the defining source and the span of the name of the item referenced at `offset`.
+    pub(super) fn definition(&self, path: &FsPath, offset: usize) -> Option<(&DiscoveredSource, Span)> {
+        let (source_index, item) = self.resolve_reference(path, offset)?;
+        Some((&self.sources[source_index].source, item_name(item).1.clone()))
+    }
+
+    // Returns the declaration of the item or builtin type at `offset`, with aliases resolved to their final type.
+    pub(super) fn hover(&self, path: &FsPath, offset: usize) -> Option<String> {
+        let source_index = self.source_index(path)?;
+        if let Some(Reference::Type(type_path)) = find_reference(&self.sources[source_index].file, offset)
+            && builtin_type(type_path).is_some()
+        {
+            return Some(path_segments(type_path).join("::"));
+        }
+
+        let (source_index, item) = self.resolve_reference(path, offset)?;
+        let file = &self.sources[source_index].file;
+        let name = qualified_name(file, item_name(item).0);
+
+        let declaration = match item {
+            Item::Struct(item) => format!("struct {name}{}", format_type_params(&item.type_params)),
+            Item::Enum(item) => format!("enum {name}{}", format_type_params(&item.type_params)),
+            Item::TypeAlias(item) => {
+                let index = self.indexes.as_ref().map(|indexes| &indexes[source_index]);
+                let resolved = index.and_then(|index| resolve_type(index, &item.ty.value).ok().map(|resolved| format_resolved_type(index, &resolved)));
+                format!("type {name} = {}", resolved.unwrap_or_else(|| format_type(&item.ty.value)))
+            }
+            Item::Const(item) => format!("const {name}: {} = {}", format_type(&item.ty.value), format_literal(&item.value.value)),
+            Item::Service(item) => format!("@{} service {name}", item.tag.value),
+        };
+
+        Some(declaration)
+    }
+
+    pub(super) fn completions(&self, path: &FsPath, offset: usize) -> Vec<Completion> {
+        let Some(source_index) = self.source_index(path) else {
+            return Vec::new();
+        };
+        let parsed_source = &self.sources[source_index];
+
+        if parsed_source.text.get(..offset).is_some_and(|prefix| prefix.trim_end().ends_with('@')) {
+            return next_free_tag(&parsed_source.text, offset)
+                .map(|tag| Completion {
+                    label: tag.to_string(),
+                    kind: CompletionKind::Tag,
+                    detail: Some("next free tag".to_string()),
+                })
+                .into_iter()
+                .collect();
+        }
+
+        let mut completions = BUILTIN_TYPE_NAMES
+            .iter()
+            .chain(&GENERIC_TYPE_NAMES)
+            .map(|name| Completion {
+                label: name.to_string(),
+                kind: CompletionKind::Builtin,
+                detail: None,
+            })
+            .collect::<Vec<_>>();
+
+        let file = &parsed_source.file;
+        for item in &file.items {
+            if let Some(kind) = completion_kind(item) {
+                completions.push(Completion {
+                    label: item_name(item).0.clone(),
+                    kind,
+                    detail: Some(qualified_name(file, item_name(item).0)),
+                });
+            }
+        }
+
+        for use_decl in &file.uses {
+            let path = path_segments(&use_decl.path.value);
+            let Some((_, item)) = self.find_item(&path) else {
+                continue;
+            };
+            let Some(kind) = completion_kind(item) else {
+                continue;
+            };
+
+            let label = match &use_decl.alias {
+                Some(alias) => alias.value.clone(),
+                None => path.last().cloned().unwrap_or_default(),
+            };
+            completions.push(Completion {
+                label,
+                kind,
+                detail: Some(path.join("::")),
+            });
+        }
+
+        completions
+    }
+
+    fn source_index(&self, path: &FsPath) -> Option<usize> {
+        self.sources.iter().position(|source| source.source.absolute_path == path)
+    }
+
+    fn resolve_reference(&self, path: &FsPath, offset: usize) -> Option<(usize, &Item)> {
+        let source_index = self.source_index(path)?;
+        let file = &self.sources[source_index].file;
+
+        match find_reference(file, offset)? {
+            Reference::Item(item) => Some((source_index, item)),
+            Reference::Use(use_path) => self.find_item(&path_segments(use_path)),
+            Reference::Type(type_path) => {
+                let index = &self.indexes.as_ref()?[source_index];
+                let canonical = canonical_path(index, type_path).ok()?;
+                let qualified = match canonical.as_slice() {
+                    [_] => index.package.iter().chain(&canonical).cloned().collect(),
+                    _ => canonical,
+                };
+                self.find_item(&qualified)
+            }
+        }
+    }
+
+    // Finds a struct, enum or alias by its package path plus name.
+    fn find_item(&self, qualified: &[String]) -> Option<(usize, &Item)> {
+        let (name, package) = qualified.split_last()?;
+
+        self.sources.iter().enumerate().find_map(|(source_index, parsed_source)| {
+            let file = &parsed_source.file;
+            if file_package(file) != package {
+                return None;
+            }
+
+            file.items
+                .iter()
+                .find(|item| !matches!(item, Item::Const(_) | Item::Service(_)) && item_name(item).0 == name)
+                .map(|item| (source_index, item))
+        })
+    }
+}
+
+fn file_package(file: &File) -> Vec<String> {
+    file.package.as_ref().map(|package| path_segments(&package.value)).unwrap_or_default()
+}
+
+fn qualified_name(file: &File, name: &str) -> String {
+    file_package(file).into_iter().chain([name.to_string()]).collect::<Vec<_>>().join("::")
+}
+
+fn item_name(item: &Item) -> (&String, &Span) {
+    let name = match item {
+        Item::Struct(item) => &item.name,
+        Item::Enum(item) => &item.name,
+        Item::TypeAlias(item) => &item.name,
+        Item::Const(item) => &item.name,
+        Item::Service(item) => &item.name,
+    };
+    (&name.value, &name.span)
+}
+
+fn completion_kind(item: &Item) -> Option<CompletionKind> {
+    match item {
+        Item::Struct(_) => Some(CompletionKind::Struct),
+        Item::Enum(_) => Some(CompletionKind::Enum),
+        Item::TypeAlias(_) => Some(CompletionKind::Alias),
+        Item::Const(_) | Item::Service(_) => None,
+    }
+}
+
+fn find_reference(file: &File, offset: usize) -> Option<Reference<'_>> {
+    for use_decl in &file.uses {
+        if path_contains(&use_decl.path.value, offset) {
+            return Some(Reference::Use(&use_decl.path.value));
+        }
+    }
+
+    for item in &file.items {
+        if span_contains(item_name(item).1, offset) {
+            return Some(Reference::Item(item));
+        }
+
+        let found = match item {
+            Item::Struct(item) => find_in_fields(&item.fields, offset),
+            Item::Enum(item) => item.variants.iter().find_map(|variant| match &variant.kind {
+                VariantKind::Unit => None,
+                VariantKind::Tuple(fields) => fields.iter().find_map(|(_, ty)| find_in_type(&ty.value, offset)),
+                VariantKind::Record(fields) => find_in_fields(fields, offset),
+            }),
+            Item::TypeAlias(item) => find_in_type(&item.ty.value, offset),
+            Item::Const(item) => find_in_type(&item.ty.value, offset),
+            Item::Service(item) => item
+                .rpcs
+                .iter()
+                .find_map(|rpc| find_in_type(&rpc.request.ty.value, offset).or_else(|| find_in_type(&rpc.response.ty.value, offset))),
+        };
+        if let Some(path) = found {
+            return Some(Reference::Type(path));
+        }
+    }
+
+    None
+}
+
+fn find_in_fields(fields: &[Field], offset: usize) -> Option<&AstPath> {
+    fields.iter().find_map(|field| find_in_type(&field.ty.value, offset))
+}
+
+fn find_in_type(ty: &Type, offset: usize) -> Option<&AstPath> {
+    match ty {
+        Type::Path(path) => path_contains(path, offset).then_some(path),
+        Type::Generic(path, args) => path_contains(path, offset)
+            .then_some(path)
+            .or_else(|| args.iter().find_map(|arg| find_in_type(arg, offset))),
+        Type::Option(inner) | Type::Vec(inner) | Type::Array(inner, _) => find_in_type(inner, offset),
+        Type::Map(key, value) => find_in_type(key, offset).or_else(|| find_in_type(value, offset)),
+    }
+}
+
+// The end is inclusive, so a cursor placed right after an identifier still refers to it.
+fn span_contains(span: &Span, offset: usize) -> bool {
+    span.start <= offset && offset <= span.end
+}
+
+fn path_contains(path: &AstPath, offset: usize) -> bool {
+    match (path.segments.first(), path.segments.last()) {
+        (Some(first), Some(last)) => first.span.start <= offset && offset <= last.span.end,
+        _ => false,
+    }
+}
+
+// Suggests the tag after the largest one of the block enclosing `offset`. Works on tokens rather than the syntax tree,
+// since the file does not parse while a tag is being typed.
+fn next_free_tag(text: &str, offset: usize) -> Option<u128> {
+    let (tokens, _) = lexer::lex(text);
+
+    let mut open_braces = Vec::new();
+    for (position, token) in tokens.iter().enumerate().take_while(|(_, token)| token.span.end <= offset) {
+        match token.token {
+            Token::LBrace => open_braces.push(position),
+            Token::RBrace => {
+                open_braces.pop();
+            }
+            _ => {}
+        }
+    }
+    let block_start = *open_braces.last()?;
+
+    let block = &tokens[block_start + 1..];
+    let mut tags = Vec::new();
+    let mut depth = 0usize;
+    for (position, token) in block.iter().enumerate() {
+        match &token.token {
+            Token::LBrace => depth += 1,
+            Token::RBrace if depth == 0 => break,
+            Token::RBrace => depth -= 1,
+            Token::At if depth == 0 => {
+                if let Some(Token::Int(tag)) = block.get(position + 1).map(|next| &next.token) {
+                    tags.push(*tag);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(tags.into_iter().max().map_or(1, |tag| tag + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use super::*;
+
+    fn document(relative_path: &str, text: &str) -> Document {
+        Document {
+            source: DiscoveredSource {
+                base_dir: PathBuf::from("/rpfs"),
+                absolute_path: PathBuf::from("/rpfs").join(relative_path),
+                relative_path: PathBuf::from(relative_path),
+            },
+            text: text.to_string(),
+        }
+    }
+
+    fn offset_of(text: &str, needle: &str) -> usize {
+        text.find(needle).unwrap_or_default()
+    }
+
+    const COMMON: &str = r#"package omnius::common;
+
+struct Id {
+  @1 value: u64;
+}
+
+type Ids = Vec<Id>;
+"#;
+
+    const USER: &str = r#"package omnius::user;
+use omnius::common::Id as UserId;
+
+struct User {
+  @1 id: UserId;
+  @2 friends: omnius::common::Ids;
+  @5 name: string;
+}
+"#;
+
+    #[test]
+    fn definition_test() -> TestResult {
+        let analysis = Analysis::new(vec![document("common.rpf", COMMON), document("user.rpf", USER)])?;
+        let user_path = PathBuf::from("/rpfs/user.rpf");
+
+        let Some((source, span)) = analysis.definition(&user_path, offset_of(USER, "id: UserId") + 4) else {
+            return Err("expected definition".into());
+        };
+        assert_eq!(source.absolute_path, PathBuf::from("/rpfs/common.rpf"));
+        assert_eq!(&COMMON[span.start..span.end], "Id");
+        assert_eq!(span.start, offset_of(COMMON, "Id {"));
+
+        let Some((_, span)) = analysis.definition(&user_path, offset_of(USER, "Ids;") + 3) else {
+            return Err("expected definition".into());
+        };
+        assert_eq!(span.start, offset_of(COMMON, "Ids ="));
+
+        assert!(analysis.definition(&user_path, offset_of(USER, "string")).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hover_test() -> TestResult {
+        let analysis = Analysis::new(vec![document("common.rpf", COMMON), document("user.rpf", USER)])?;
+        let user_path = PathBuf::from("/rpfs/user.rpf");
+
+        assert_eq!(analysis.hover(&user_path, offset_of(USER, "id: UserId") + 4).as_deref(), Some("struct omnius::common::Id"));
+        assert_eq!(
+            analysis.hover(&user_path, offset_of(USER, "Ids;")).as_deref(),
+            Some("type omnius::common::Ids = Vec<omnius::common::Id>")
+        );
+        assert_eq!(analysis.hover(&user_path, offset_of(USER, "string")).as_deref(), Some("string"));
+        assert_eq!(analysis.hover(&user_path, offset_of(USER, "User {")).as_deref(), Some("struct omnius::user::User"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn completions_test() -> TestResult {
+        let text = USER.replace("  @5 name: string;\n", "  @5 name: string;\n  @\n");
+        let analysis = Analysis::new(vec![document("common.rpf", COMMON), document("user.rpf", &text)])?;
+        let user_path = PathBuf::from("/rpfs/user.rpf");
+
+        let tag_offset = offset_of(&text, "  @\n") + 3;
+        assert_eq!(
+            analysis.completions(&user_path, tag_offset),
+            vec![Completion {
+                label: "6".to_string(),
+                kind: CompletionKind::Tag,
+                detail: Some("next free tag".to_string()),
+            }]
+        );
+
+        let completions = analysis.completions(&user_path, offset_of(&text, "string"));
+        let labels = completions.iter().map(|completion| completion.label.as_str()).collect::<Vec<_>>();
+        assert!(labels.contains(&"u32"));
+        assert!(labels.contains(&"Option"));
+        assert!(completions.contains(&Completion {
+            label: "User".to_string(),
+            kind: CompletionKind::Struct,
+            detail: Some("omnius::user::User".to_string()),
+        }));
+        assert!(completions.contains(&Completion {
+            label: "UserId".to_string(),
+            kind: CompletionKind::Struct,
+            detail: Some("omnius::common::Id".to_string()),
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_test() -> TestResult {
+        let broken = "struct Broken {\n  @1 id: u64\n}\n";
+        let invalid = "struct Invalid {\n  @1 id: u64;\n  @1 name: Missing;\n}\n";
+        let analysis = Analysis::new(vec![document("broken.rpf", broken), document("invalid.rpf", invalid)])?;
+
+        let diagnostics = analysis
+            .diagnostics()
+            .map(|(source, errors)| {
+                (
+                    source.source.relative_path.display().to_string(),
+                    errors.iter().map(|error| error.kind.to_string()).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics[0].0, "broken.rpf");
+        assert_eq!(diagnostics[0].1.len(), 1);
+        assert_eq!(
+            diagnostics[1],
+            ("invalid.rpf".to_string(), vec!["duplicate tag: @1".to_string(), "unknown type: Missing".to_string()])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn next_free_tag_test() {
+        let text = "enum E {\n  @1 A;\n  @4 B { @7 x: u8; };\n  @\n}\nstruct S {\n  \n}\n";
+        assert_eq!(next_free_tag(text, offset_of(text, "  @\n") + 3), Some(5));
+        assert_eq!(next_free_tag(text, offset_of(text, "x: u8")), Some(8));
+        assert_eq!(next_free_tag(text, offset_of(text, "  \n}") + 2), Some(1));
+        assert_eq!(next_free_tag(text, 0), None);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    fmt::Write as _,
+    fs,
+    path::{Path as FsPath, PathBuf},
+};
+
+use crate::{
+    config::{AppConfig, SourceConfig},
+    error::CodegenError,
+};
+
+use super::{
+    common::{DEFAULT_SOURCE_PATTERN, DiscoveredSource, discover_source_files, parse_sources, source_file_stem},
+    output::{OutputMode, OutputWriter},
+    rust::{self, RustOptions},
+};
+
+const MODULE_INDEX_FILE_NAME: &str = "rocketpack.rs";
+
+/// Generates Rust code for the schemas from a build script.
+///
+/// Every source is written to `OUT_DIR` as `<file stem>.rs`, next to `rocketpack.rs`, which declares one module per
+/// source. The generated modules refer to each other as siblings, so `rocketpack.rs` is included as a whole:
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     if let Err(err) = omnius_core_rocketpack_compiler::Builder::new().config("rocketpack.yaml").compile() {
+///         panic!("{err}");
+///     }
+/// }
+///
+/// // lib.rs
+/// include!(concat!(env!("OUT_DIR"), "/rocketpack.rs"));
+/// ```
+///
+/// Only the `sources` of a `rocketpack.yaml` are used, its generators are ignored. The builder does not need the command
+/// line tool, so build dependencies can leave out its dependencies with `default-features = false`.
+#[derive(Debug, Default)]
+pub struct Builder {
+    configs: Vec<PathBuf>,
+    source_dirs: Vec<PathBuf>,
+    out_dir: Option<PathBuf>,
+    options: RustOptions,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the sources of a `rocketpack.yaml`, whose `base_dir`s are relative to the file.
+    pub fn config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.configs.push(path.into());
+        self
+    }
+
+    /// Adds every `.rpf` file below `dir`.
+    pub fn source_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.source_dirs.push(dir.into());
+        self
+    }
+
+    /// Writes the generated files to `dir` instead of `OUT_DIR`.
+    pub fn out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(dir.into());
+        self
+    }
+
+    /// Implements `RocketPackSchema` for every struct and enum and adds a `SCHEMA_REGISTRY` to every package, like the
+    /// `emit_schema` option of the Rust generator.
+    pub fn emit_schema(mut self, enabled: bool) -> Self {
+        self.options.emit_schema = enabled;
+        self
+    }
+
+    /// Generates the Rust code, printing `cargo:rerun-if-changed` for the configs, the source directories and every
+    /// source. Files whose contents did not change are left untouched.
+    pub fn compile(self) -> Result<(), CodegenError> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| CodegenError::Other("OUT_DIR is not set, run from a build script or set out_dir".to_string()))?,
+        };
+
+        let sources = self.discover_sources()?;
+        for source in &sources {
+            println!("cargo:rerun-if-changed={}", source.absolute_path.display());
+        }
+
+        let parsed_sources = parse_sources(&sources)?;
+        let generated_files = rust::render_sources(&parsed_sources, |_| Ok(self.options.clone()))?;
+
+        let mut writer = OutputWriter::new(OutputMode::Write);
+        let mut modules = BTreeMap::<String, PathBuf>::new();
+        for generated_file in &generated_files {
+            let module_name = rust::sanitize_ident(source_file_stem(&generated_file.source)?);
+            let output_path = out_dir.join(format!("{module_name}.rs"));
+
+            // Sibling modules are named after the file stem, so two sources with the same stem would collide.
+            match modules.entry(module_name) {
+                Entry::Vacant(entry) => {
+                    entry.insert(output_path.clone());
+                }
+                Entry::Occupied(entry) => {
+                    return Err(CodegenError::Other(format!(
+                        "sources share the module name {}: {}",
+                        entry.key(),
+                        generated_file.source.absolute_path.display()
+                    )));
+                }
+            }
+
+            writer.write(&output_path, &generated_file.contents)?;
+        }
+
+        writer.write(&out_dir.join(MODULE_INDEX_FILE_NAME), &render_module_index(&modules))?;
+
+        Ok(())
+    }
+
+    fn discover_sources(&self) -> Result<Vec<DiscoveredSource>, CodegenError> {
+        let mut discovered = BTreeMap::<PathBuf, DiscoveredSource>::new();
+
+        for config_path in &self.configs {
+            println!("cargo:rerun-if-changed={}", config_path.display());
+
+            let conf = AppConfig::from_yaml(&fs::read_to_string(config_path)?)?;
+            let root_dir = config_path.parent().unwrap_or_else(|| FsPath::new("."));
+            for source in &conf.sources {
+                println!("cargo:rerun-if-changed={}", root_dir.join(&source.base_dir).display());
+            }
+
+            for source in discover_source_files(root_dir, &conf.sources)? {
+                discovered.entry(source.absolute_path.clone()).or_insert(source);
+            }
+        }
+
+        for source_dir in &self.source_dirs {
+            println!("cargo:rerun-if-changed={}", source_dir.display());
+
+            let source = SourceConfig {
+                base_dir: ".".to_string(),
+                includes: vec![DEFAULT_SOURCE_PATTERN.to_string()],
+                excludes: Vec::new(),
+            };
+            for source in discover_source_files(source_dir, &[source])? {
+                discovered.entry(source.absolute_path.clone()).or_insert(source);
+            }
+        }
+
+        if discovered.is_empty() {
+            return Err(CodegenError::Other("no rocketpack sources found".to_string()));
+        }
+
+        Ok(discovered.into_values().collect())
+    }
+}
+
+fn render_module_index(modules: &BTreeMap<String, PathBuf>) -> String {
+    let mut out = String::new();
+    writeln!(out, "// @generated by rocketpack-compiler").ok();
+    for (module_name, path) in modules {
+        writeln!(out, "pub mod {module_name} {{").ok();
+        writeln!(out, "    include!({:?});", path.display().to_string()).ok();
+        writeln!(out, "}}").ok();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+
+    use super::*;
+
+    #[test]
+    fn compile_config_test() -> TestResult {
+        let out_dir = tempfile::tempdir()?;
+
+        Builder::new().config("./data/rocketpack.yaml").out_dir(out_dir.path()).compile()?;
+
+        let generated = fs::read_to_string(out_dir.path().join("example.rs"))?;
+        assert!(generated.starts_with("// @generated by rocketpack-compiler\n"));
+
+        let index = fs::read_to_string(out_dir.path().join(MODULE_INDEX_FILE_NAME))?;
+        let expected = format!(
+            "// @generated by rocketpack-compiler\npub mod example {{\n    include!({:?});\n}}\n",
+            out_dir.path().join("example.rs").display().to_string()
+        );
+        assert_eq!(index, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn compile_emit_schema_test() -> TestResult {
+        let out_dir = tempfile::tempdir()?;
+
+        Builder::new().config("./data/rocketpack.yaml").out_dir(out_dir.path()).compile()?;
+        assert!(!fs::read_to_string(out_dir.path().join("example.rs"))?.contains("SCHEMA_REGISTRY"));
+
+        Builder::new().config("./data/rocketpack.yaml").out_dir(out_dir.path()).emit_schema(true).compile()?;
+        let generated = fs::read_to_string(out_dir.path().join("example.rs"))?;
+        assert!(generated.contains("impl omnius_core_rocketpack::RocketPackSchema for "));
+        assert!(generated.contains("pub static SCHEMA_REGISTRY: omnius_core_rocketpack::SchemaRegistry"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn compile_parse_error_test() -> TestResult {
+        let source_dir = tempfile::tempdir()?;
+        let out_dir = tempfile::tempdir()?;
+        fs::write(source_dir.path().join("broken.rpf"), "version 1;\npackage broken;\n\nstruct User {\n  @1 id u64;\n}\n")?;
+
+        let result = Builder::new().source_dir(source_dir.path()).out_dir(out_dir.path()).compile();
+
+        assert!(matches!(result, Err(CodegenError::Parse(_))));
+        assert!(!out_dir.path().join(MODULE_INDEX_FILE_NAME).exists());
+
+        Ok(())
+    }
+}
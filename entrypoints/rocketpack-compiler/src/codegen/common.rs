@@ -0,0 +1,716 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path as FsPath, PathBuf},
+};
+
+use serde_yaml_ng::{Mapping, Value};
+
+use crate::{
+    config::{AppConfig, GeneratorConfig, GeneratorTargetConfig, SourceConfig},
+    error::CodegenError,
+    parser::{
+        self,
+        ast::{Attribute, File, Item, Literal, Path as AstPath, Span, Spanned, Type},
+    },
+};
+
+const CONFIG_FILE_NAME: &str = "rocketpack.yaml";
+pub(super) const DEFAULT_SOURCE_PATTERN: &str = "**/*.rpf";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DiscoveredSource {
+    pub(super) base_dir: PathBuf,
+    pub(super) absolute_path: PathBuf,
+    pub(super) relative_path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ParsedSource {
+    pub(super) source: DiscoveredSource,
+    pub(super) text: String,
+    pub(super) file: File,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct SchemaIndex {
+    pub(super) package: Vec<String>,
+    pub(super) uses: Vec<UseBinding>,
+    pub(super) imported_paths: BTreeMap<String, Vec<String>>,
+    pub(super) type_aliases: BTreeMap<String, Type>,
+    pub(super) user_types: BTreeMap<String, NamedTypeKind>,
+    // Type parameters of the generic structs and enums of this file.
+    pub(super) type_params: BTreeMap<String, Vec<String>>,
+    // Types defined in imported source files, keyed by package path plus name.
+    pub(super) external_types: BTreeMap<Vec<String>, ExternalType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ExternalType {
+    pub(super) origin: DiscoveredSource,
+    // Aliases are stored already resolved against their own source file.
+    pub(super) resolved: ResolvedType,
+    pub(super) type_params: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct UseBinding {
+    pub(super) path: Vec<String>,
+    pub(super) alias: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum NamedTypeKind {
+    Struct,
+    Enum,
+    Alias,
+    // A type parameter of the enclosing generic struct or enum.
+    Param,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BuiltinType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F16,
+    F32,
+    F64,
+    String,
+    Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct NamedType {
+    // The bare name for types of the current file, package path plus name for types of other files.
+    pub(super) path: Vec<String>,
+    pub(super) kind: NamedTypeKind,
+    // The defining source for types of other files.
+    pub(super) origin: Option<DiscoveredSource>,
+    // Type arguments of a generic struct or enum, empty otherwise.
+    pub(super) args: Vec<ResolvedType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ResolvedType {
+    Builtin(BuiltinType),
+    Named(NamedType),
+    Option(Box<ResolvedType>),
+    Vec(Box<ResolvedType>),
+    Map(Box<ResolvedType>, Box<ResolvedType>),
+    Array(Box<ResolvedType>, u64),
+}
+
+pub(super) fn parse_sources(sources: &[DiscoveredSource]) -> Result<Vec<ParsedSource>, CodegenError> {
+    SourceCache::default().parse(sources)
+}
+
+// Keeps the sources of the previous compilation, so a recompilation only parses the files whose text changed.
+#[derive(Debug, Default)]
+pub struct SourceCache {
+    entries: BTreeMap<PathBuf, ParsedSource>,
+}
+
+impl SourceCache {
+    pub(super) fn parse(&mut self, sources: &[DiscoveredSource]) -> Result<Vec<ParsedSource>, CodegenError> {
+        let mut entries = BTreeMap::new();
+        let mut parsed_sources = Vec::with_capacity(sources.len());
+
+        for source in sources {
+            let text =
+                fs::read_to_string(&source.absolute_path).map_err(|err| CodegenError::Other(format!("failed to read source: {}: {}", source.absolute_path.display(), err)))?;
+            let parsed_source = match self.entries.remove(&source.absolute_path) {
+                Some(cached) if cached.source == *source && cached.text == text => cached,
+                _ => {
+                    let file = parser::parse_source(&source.absolute_path, &text)?;
+                    ParsedSource {
+                        source: source.clone(),
+                        text,
+                        file,
+                    }
+                }
+            };
+
+            entries.insert(source.absolute_path.clone(), parsed_source.clone());
+            parsed_sources.push(parsed_source);
+        }
+
+        // Sources that were removed since the previous compilation are dropped with the old entries.
+        self.entries = entries;
+        Ok(parsed_sources)
+    }
+}
+
+pub(super) fn resolve_output_path(root_dir: &FsPath, conf: &GeneratorConfig, source: &DiscoveredSource, extension: &str) -> Result<Option<PathBuf>, CodegenError> {
+    let Some(target) = find_target(conf, source) else {
+        return Ok(None);
+    };
+
+    let dir = target_option_dir(target)?.ok_or_else(|| CodegenError::Other(format!("missing dir option for {} target pattern: {}", conf.plugin, target.pattern)))?;
+    let file_stem = source_file_stem(source)?;
+
+    Ok(Some(root_dir.join(dir).join(format!("{file_stem}{extension}"))))
+}
+
+pub(super) fn find_target<'a>(conf: &'a GeneratorConfig, source: &DiscoveredSource) -> Option<&'a GeneratorTargetConfig> {
+    let relative_path = normalize_path(&source.relative_path);
+    conf.targets.iter().find(|target| glob_matches(&target.pattern, &relative_path))
+}
+
+pub(super) fn source_file_stem(source: &DiscoveredSource) -> Result<&str, CodegenError> {
+    source
+        .relative_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| CodegenError::Other(format!("invalid source file name: {}", source.relative_path.display())))
+}
+
+fn target_option_dir(target: &GeneratorTargetConfig) -> Result<Option<&str>, CodegenError> {
+    match &target.options {
+        Some(options) => mapping_string(options, "dir"),
+        None => Ok(None),
+    }
+}
+
+fn mapping_string<'a>(mapping: &'a Mapping, key: &str) -> Result<Option<&'a str>, CodegenError> {
+    let Some(value) = mapping.get(Value::String(key.to_string())) else {
+        return Ok(None);
+    };
+
+    match value {
+        Value::String(value) => Ok(Some(value.as_str())),
+        _ => Err(CodegenError::Other(format!("target option `{key}` must be a string"))),
+    }
+}
+
+// Target options override the generator-wide options of the same name.
+fn option_value<'a>(conf: &'a GeneratorConfig, target: Option<&'a GeneratorTargetConfig>, key: &str) -> Option<&'a Value> {
+    let key = Value::String(key.to_string());
+    target
+        .and_then(|target| target.options.as_ref())
+        .and_then(|options| options.get(&key))
+        .or_else(|| conf.options.as_ref().and_then(|options| options.get(&key)))
+}
+
+pub(super) fn option_string(conf: &GeneratorConfig, target: Option<&GeneratorTargetConfig>, key: &str) -> Result<Option<String>, CodegenError> {
+    match option_value(conf, target, key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(Value::Number(value)) => Ok(Some(value.to_string())),
+        Some(_) => Err(CodegenError::Other(format!("{} option `{key}` must be a string", conf.plugin))),
+    }
+}
+
+pub(super) fn option_bool(conf: &GeneratorConfig, target: Option<&GeneratorTargetConfig>, key: &str) -> Result<Option<bool>, CodegenError> {
+    match option_value(conf, target, key) {
+        None => Ok(None),
+        Some(Value::Bool(value)) => Ok(Some(*value)),
+        Some(_) => Err(CodegenError::Other(format!("{} option `{key}` must be a boolean", conf.plugin))),
+    }
+}
+
+pub(super) fn build_schema_index(file: &File) -> SchemaIndex {
+    let mut index = SchemaIndex {
+        package: file.package.as_ref().map(|package| path_segments(&package.value)).unwrap_or_default(),
+        ..SchemaIndex::default()
+    };
+
+    for use_decl in &file.uses {
+        let path = path_segments(&use_decl.path.value);
+        let alias = use_decl.alias.as_ref().map(|alias| alias.value.clone());
+        let import_name = alias.clone().or_else(|| path.last().cloned()).unwrap_or_default();
+
+        index.uses.push(UseBinding { path: path.clone(), alias });
+        index.imported_paths.insert(import_name, path);
+    }
+
+    for item in &file.items {
+        match item {
+            Item::Struct(item) => {
+                index.user_types.insert(item.name.value.clone(), NamedTypeKind::Struct);
+                if !item.type_params.is_empty() {
+                    index.type_params.insert(item.name.value.clone(), type_param_names(&item.type_params));
+                }
+            }
+            Item::Enum(item) => {
+                index.user_types.insert(item.name.value.clone(), NamedTypeKind::Enum);
+                if !item.type_params.is_empty() {
+                    index.type_params.insert(item.name.value.clone(), type_param_names(&item.type_params));
+                }
+            }
+            Item::TypeAlias(item) => {
+                index.user_types.insert(item.name.value.clone(), NamedTypeKind::Alias);
+                index.type_aliases.insert(item.name.value.clone(), item.ty.value.clone());
+            }
+            Item::Const(_) | Item::Service(_) => {}
+        }
+    }
+
+    index
+}
+
+pub(super) fn path_span(path: &AstPath) -> Span {
+    match (path.segments.first(), path.segments.last()) {
+        (Some(first), Some(last)) => Span {
+            start: first.span.start,
+            end: last.span.end,
+        },
+        _ => Span { start: 0, end: 0 },
+    }
+}
+
+pub(super) fn find_attribute<'a>(attributes: &'a [Spanned<Attribute>], name: &str) -> Option<&'a Spanned<Attribute>> {
+    attributes.iter().find(|attribute| attribute.value.name.value == name)
+}
+
+pub(super) fn type_param_names(type_params: &[Spanned<String>]) -> Vec<String> {
+    type_params.iter().map(|param| param.value.clone()).collect()
+}
+
+// Generic structs and enums are only emitted by the Rust generator so far.
+pub(super) fn ensure_no_generics(parsed_sources: &[ParsedSource], generator: &str) -> Result<(), CodegenError> {
+    for parsed in parsed_sources {
+        for item in parsed.file.items.iter() {
+            let (name, type_params) = match item {
+                Item::Struct(item) => (&item.name, &item.type_params),
+                Item::Enum(item) => (&item.name, &item.type_params),
+                _ => continue,
+            };
+            if !type_params.is_empty() {
+                return Err(CodegenError::Other(format!(
+                    "generic type {} in {} is not supported by the {generator} generator",
+                    name.value,
+                    normalize_path(&parsed.source.relative_path)
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn resolve_type(index: &SchemaIndex, ty: &Type) -> Result<ResolvedType, CodegenError> {
+    resolve_type_with_params(index, ty, &[])
+}
+
+// Resolves a type written inside a generic struct or enum, where the names in `params` refer to its type parameters.
+pub(super) fn resolve_type_with_params(index: &SchemaIndex, ty: &Type, params: &[String]) -> Result<ResolvedType, CodegenError> {
+    let mut resolving_aliases = Vec::<String>::new();
+    resolve_type_inner(index, ty, params, &mut resolving_aliases)
+}
+
+fn resolve_type_inner(index: &SchemaIndex, ty: &Type, params: &[String], resolving_aliases: &mut Vec<String>) -> Result<ResolvedType, CodegenError> {
+    match ty {
+        Type::Path(path) => resolve_path_type(index, path, &[], params, resolving_aliases),
+        Type::Generic(path, args) => {
+            let args = args
+                .iter()
+                .map(|arg| resolve_type_inner(index, arg, params, resolving_aliases))
+                .collect::<Result<Vec<_>, _>>()?;
+            resolve_path_type(index, path, &args, params, resolving_aliases)
+        }
+        Type::Option(inner) => Ok(ResolvedType::Option(Box::new(resolve_type_inner(index, inner, params, resolving_aliases)?))),
+        Type::Vec(inner) => Ok(ResolvedType::Vec(Box::new(resolve_type_inner(index, inner, params, resolving_aliases)?))),
+        Type::Map(key, value) => Ok(ResolvedType::Map(
+            Box::new(resolve_type_inner(index, key, params, resolving_aliases)?),
+            Box::new(resolve_type_inner(index, value, params, resolving_aliases)?),
+        )),
+        Type::Array(inner, len) => Ok(ResolvedType::Array(Box::new(resolve_type_inner(index, inner, params, resolving_aliases)?), *len)),
+    }
+}
+
+fn resolve_path_type(index: &SchemaIndex, path: &AstPath, args: &[ResolvedType], params: &[String], resolving_aliases: &mut Vec<String>) -> Result<ResolvedType, CodegenError> {
+    let segments = path_segments(path);
+    if let [name] = segments.as_slice()
+        && params.contains(name)
+    {
+        if !args.is_empty() {
+            return Err(CodegenError::Other(format!("type parameter `{name}` cannot take type arguments")));
+        }
+        return Ok(ResolvedType::Named(NamedType {
+            path: vec![name.clone()],
+            kind: NamedTypeKind::Param,
+            origin: None,
+            args: Vec::new(),
+        }));
+    }
+
+    if let Some(builtin) = builtin_type(path) {
+        if !args.is_empty() {
+            return Err(CodegenError::Other(format!("type `{}` cannot take type arguments", segments.join("::"))));
+        }
+        return Ok(ResolvedType::Builtin(builtin));
+    }
+
+    let expected = type_param_count(index, path);
+    if expected != args.len() {
+        return Err(CodegenError::Other(format!(
+            "type `{}` expects {expected} type argument(s), found {}",
+            segments.join("::"),
+            args.len()
+        )));
+    }
+    resolve_named_path(index, path, resolving_aliases).map(|resolved| match resolved {
+        ResolvedType::Named(named) if !args.is_empty() => ResolvedType::Named(NamedType { args: args.to_vec(), ..named }),
+        resolved => resolved,
+    })
+}
+
+// The number of type parameters of the struct or enum at `path`; aliases and unknown types take none.
+pub(super) fn type_param_count(index: &SchemaIndex, path: &AstPath) -> usize {
+    match canonical_path(index, path) {
+        Ok(canonical) => match canonical.as_slice() {
+            [name] => index.type_params.get(name).map_or(0, Vec::len),
+            _ => index.external_types.get(&canonical).map_or(0, |external| external.type_params.len()),
+        },
+        Err(_) => 0,
+    }
+}
+
+fn resolve_named_path(index: &SchemaIndex, path: &AstPath, resolving_aliases: &mut Vec<String>) -> Result<ResolvedType, CodegenError> {
+    let canonical = canonical_path(index, path)?;
+    let [name] = canonical.as_slice() else {
+        return index
+            .external_types
+            .get(&canonical)
+            .map(|external| external.resolved.clone())
+            .ok_or_else(|| CodegenError::Other(format!("unknown type: {}", canonical.join("::"))));
+    };
+
+    if let Some(alias_ty) = index.type_aliases.get(name) {
+        if resolving_aliases.iter().any(|current| current == name) {
+            return Err(CodegenError::Other(format!("cyclic type alias: {name}")));
+        }
+
+        resolving_aliases.push(name.clone());
+        let resolved = resolve_type_inner(index, alias_ty, &[], resolving_aliases)?;
+        resolving_aliases.pop();
+        return Ok(resolved);
+    }
+
+    let kind = index.user_types.get(name).ok_or_else(|| CodegenError::Other(format!("unknown type: {name}")))?;
+    Ok(ResolvedType::Named(NamedType {
+        path: vec![name.clone()],
+        kind: kind.clone(),
+        origin: None,
+        args: Vec::new(),
+    }))
+}
+
+// Maps a type path as written in the source to the bare name of a local type, or to the package path plus name of an imported one.
+pub(super) fn canonical_path(index: &SchemaIndex, path: &AstPath) -> Result<Vec<String>, CodegenError> {
+    let segments = path_segments(path);
+    let canonical = match segments.as_slice() {
+        [name] if index.user_types.contains_key(name) => Some(vec![name.clone()]),
+        [name] => index.imported_paths.get(name).cloned(),
+        [package @ .., name] if package == index.package.as_slice() && index.user_types.contains_key(name) => Some(vec![name.clone()]),
+        _ => index.external_types.contains_key(&segments).then(|| segments.clone()),
+    };
+
+    canonical.ok_or_else(|| CodegenError::Other(format!("unknown type: {}", segments.join("::"))))
+}
+
+// The smallest and largest value of an integer type; the lower bound is kept signed and the upper unsigned to fit both i128 and u128.
+pub(super) fn integer_range(builtin: &BuiltinType) -> Option<(i128, u128)> {
+    Some(match builtin {
+        BuiltinType::U8 => (0, u8::MAX as u128),
+        BuiltinType::U16 => (0, u16::MAX as u128),
+        BuiltinType::U32 => (0, u32::MAX as u128),
+        BuiltinType::U64 => (0, u64::MAX as u128),
+        BuiltinType::U128 => (0, u128::MAX),
+        BuiltinType::I8 => (i8::MIN as i128, i8::MAX as u128),
+        BuiltinType::I16 => (i16::MIN as i128, i16::MAX as u128),
+        BuiltinType::I32 => (i32::MIN as i128, i32::MAX as u128),
+        BuiltinType::I64 => (i64::MIN as i128, i64::MAX as u128),
+        BuiltinType::I128 => (i128::MIN, i128::MAX as u128),
+        _ => return None,
+    })
+}
+
+// Renders literals and types the way they are written in `.rpf` sources, for diagnostics.
+pub(super) fn format_literal(literal: &Literal) -> String {
+    match literal {
+        Literal::Bool(value) => value.to_string(),
+        Literal::Int(value) => value.to_string(),
+        Literal::Float(value) => format!("{value:?}"),
+        Literal::String(value) => format!("{value:?}"),
+        Literal::Bytes(bytes) => format!("b\"{}\"", bytes.iter().map(|byte| format!("\\x{byte:02x}")).collect::<String>()),
+    }
+}
+
+pub(super) fn format_type(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) => path_segments(path).join("::"),
+        Type::Option(inner) => format!("Option<{}>", format_type(inner)),
+        Type::Vec(inner) => format!("Vec<{}>", format_type(inner)),
+        Type::Map(key, value) => format!("Map<{}, {}>", format_type(key), format_type(value)),
+        Type::Array(inner, len) => format!("[{}; {}]", format_type(inner), len),
+        Type::Generic(path, args) => format!("{}<{}>", path_segments(path).join("::"), args.iter().map(format_type).collect::<Vec<_>>().join(", ")),
+    }
+}
+
+// The package path plus name of a named type, whichever file it is defined in.
+pub(super) fn format_type_params(type_params: &[Spanned<String>]) -> String {
+    if type_params.is_empty() {
+        return String::new();
+    }
+    format!("<{}>", type_param_names(type_params).join(", "))
+}
+
+pub(super) fn qualified_path(index: &SchemaIndex, resolved: &ResolvedType) -> Vec<String> {
+    match resolved {
+        ResolvedType::Named(named) if named.kind == NamedTypeKind::Param => named.path.clone(),
+        ResolvedType::Named(named) if named.origin.is_none() => index.package.iter().chain(&named.path).cloned().collect(),
+        ResolvedType::Named(named) => named.path.clone(),
+        _ => Vec::new(),
+    }
+}
+
+pub(super) fn format_resolved_type(index: &SchemaIndex, resolved: &ResolvedType) -> String {
+    match resolved {
+        ResolvedType::Builtin(builtin) => match builtin {
+            BuiltinType::Bool => "bool",
+            BuiltinType::U8 => "u8",
+            BuiltinType::U16 => "u16",
+            BuiltinType::U32 => "u32",
+            BuiltinType::U64 => "u64",
+            BuiltinType::U128 => "u128",
+            BuiltinType::I8 => "i8",
+            BuiltinType::I16 => "i16",
+            BuiltinType::I32 => "i32",
+            BuiltinType::I64 => "i64",
+            BuiltinType::I128 => "i128",
+            BuiltinType::F16 => "f16",
+            BuiltinType::F32 => "f32",
+            BuiltinType::F64 => "f64",
+            BuiltinType::String => "string",
+            BuiltinType::Bytes => "bytes",
+        }
+        .to_string(),
+        ResolvedType::Named(named) if named.args.is_empty() => qualified_path(index, resolved).join("::"),
+        ResolvedType::Named(named) => format!(
+            "{}<{}>",
+            qualified_path(index, resolved).join("::"),
+            named.args.iter().map(|arg| format_resolved_type(index, arg)).collect::<Vec<_>>().join(", ")
+        ),
+        ResolvedType::Option(inner) => format!("Option<{}>", format_resolved_type(index, inner)),
+        ResolvedType::Vec(inner) => format!("Vec<{}>", format_resolved_type(index, inner)),
+        ResolvedType::Map(key, value) => format!("Map<{}, {}>", format_resolved_type(index, key), format_resolved_type(index, value)),
+        ResolvedType::Array(inner, len) => format!("[{}; {}]", format_resolved_type(index, inner), len),
+    }
+}
+
+// The sources of a project directory: those of its `rocketpack.yaml`, or every `.rpf` file when it has none.
+pub(super) fn discover_project_sources(dir: &FsPath) -> Result<Vec<DiscoveredSource>, CodegenError> {
+    let config_path = dir.join(CONFIG_FILE_NAME);
+    if config_path.is_file() {
+        let conf = AppConfig::from_yaml(&fs::read_to_string(&config_path)?)?;
+        return discover_source_files(dir, &conf.sources);
+    }
+
+    let source = SourceConfig {
+        base_dir: ".".to_string(),
+        includes: vec![DEFAULT_SOURCE_PATTERN.to_string()],
+        excludes: Vec::new(),
+    };
+    discover_source_files(dir, &[source])
+}
+
+pub(super) fn discover_source_files(root_dir: &FsPath, sources: &[SourceConfig]) -> Result<Vec<DiscoveredSource>, CodegenError> {
+    let mut discovered = BTreeMap::<PathBuf, DiscoveredSource>::new();
+
+    for source in sources {
+        let base_dir = root_dir.join(&source.base_dir);
+        if !base_dir.exists() {
+            return Err(CodegenError::Other(format!("source base_dir not found: {}", base_dir.display())));
+        }
+
+        if !base_dir.is_dir() {
+            return Err(CodegenError::Other(format!("source base_dir is not a directory: {}", base_dir.display())));
+        }
+
+        let mut relative_paths = Vec::new();
+        collect_relative_files(&base_dir, &base_dir, &mut relative_paths)?;
+
+        for relative_path in relative_paths {
+            if !source_includes(source, &relative_path) {
+                continue;
+            }
+
+            let absolute_path = base_dir.join(&relative_path);
+            discovered.entry(absolute_path.clone()).or_insert_with(|| DiscoveredSource {
+                base_dir: base_dir.clone(),
+                absolute_path,
+                relative_path,
+            });
+        }
+    }
+
+    Ok(discovered.into_values().collect())
+}
+
+// Whether a path relative to the source's `base_dir` is selected by its `includes` / `excludes` patterns.
+pub(super) fn source_includes(source: &SourceConfig, relative_path: &FsPath) -> bool {
+    let normalized = normalize_path(relative_path);
+    let included = source.includes.is_empty() || source.includes.iter().any(|pattern| glob_matches(pattern, &normalized));
+    let excluded = source.excludes.iter().any(|pattern| glob_matches(pattern, &normalized));
+    included && !excluded
+}
+
+fn collect_relative_files(base_dir: &FsPath, current_dir: &FsPath, out: &mut Vec<PathBuf>) -> Result<(), CodegenError> {
+    for entry in fs::read_dir(current_dir).map_err(|err| CodegenError::Other(format!("failed to read directory: {}: {}", current_dir.display(), err)))? {
+        let entry = entry.map_err(|err| CodegenError::Other(format!("failed to read directory entry: {}: {}", current_dir.display(), err)))?;
+
+        let path = entry.path();
+        if path.is_dir() {
+            collect_relative_files(base_dir, &path, out)?;
+        } else if path.is_file() {
+            let relative_path = path
+                .strip_prefix(base_dir)
+                .map_err(|err| CodegenError::Other(format!("failed to build relative path: {}: {}", path.display(), err)))?
+                .to_path_buf();
+            out.push(relative_path);
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn normalize_path(path: &FsPath) -> String {
+    path.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+pub(super) fn path_segments(path: &AstPath) -> Vec<String> {
+    path.segments.iter().map(|segment| segment.value.clone()).collect()
+}
+
+pub(super) fn builtin_type(path: &AstPath) -> Option<BuiltinType> {
+    let segments = path_segments(path);
+    if segments.len() != 1 {
+        return None;
+    }
+
+    match segments[0].as_str() {
+        "bool" => Some(BuiltinType::Bool),
+        "u8" => Some(BuiltinType::U8),
+        "u16" => Some(BuiltinType::U16),
+        "u32" => Some(BuiltinType::U32),
+        "u64" => Some(BuiltinType::U64),
+        "u128" => Some(BuiltinType::U128),
+        "i8" => Some(BuiltinType::I8),
+        "i16" => Some(BuiltinType::I16),
+        "i32" => Some(BuiltinType::I32),
+        "i64" => Some(BuiltinType::I64),
+        "i128" => Some(BuiltinType::I128),
+        "f16" => Some(BuiltinType::F16),
+        "f32" => Some(BuiltinType::F32),
+        "f64" => Some(BuiltinType::F64),
+        "string" => Some(BuiltinType::String),
+        "bytes" => Some(BuiltinType::Bytes),
+        _ => None,
+    }
+}
+
+pub(super) fn glob_matches(pattern: &str, candidate: &str) -> bool {
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let mut memo = BTreeMap::<(usize, usize), bool>::new();
+    glob_matches_inner(&pattern_chars, &candidate_chars, 0, 0, &mut memo)
+}
+
+fn glob_matches_inner(pattern: &[char], candidate: &[char], pattern_index: usize, candidate_index: usize, memo: &mut BTreeMap<(usize, usize), bool>) -> bool {
+    if let Some(value) = memo.get(&(pattern_index, candidate_index)) {
+        return *value;
+    }
+
+    let result = if pattern_index == pattern.len() {
+        candidate_index == candidate.len()
+    } else if pattern[pattern_index] == '*' {
+        let mut next_index = pattern_index;
+        while next_index < pattern.len() && pattern[next_index] == '*' {
+            next_index += 1;
+        }
+
+        let is_double_star = next_index - pattern_index >= 2;
+        if is_double_star {
+            let mut matched = glob_matches_inner(pattern, candidate, next_index, candidate_index, memo);
+
+            if !matched && next_index < pattern.len() && pattern[next_index] == '/' {
+                matched = glob_matches_inner(pattern, candidate, next_index + 1, candidate_index, memo);
+            }
+
+            if !matched && candidate_index < candidate.len() {
+                matched = glob_matches_inner(pattern, candidate, pattern_index, candidate_index + 1, memo);
+            }
+
+            matched
+        } else {
+            glob_matches_inner(pattern, candidate, pattern_index + 1, candidate_index, memo)
+                || (candidate_index < candidate.len() && candidate[candidate_index] != '/' && glob_matches_inner(pattern, candidate, pattern_index, candidate_index + 1, memo))
+        }
+    } else if pattern[pattern_index] == '?' {
+        candidate_index < candidate.len() && candidate[candidate_index] != '/' && glob_matches_inner(pattern, candidate, pattern_index + 1, candidate_index + 1, memo)
+    } else {
+        candidate_index < candidate.len()
+            && pattern[pattern_index] == candidate[candidate_index]
+            && glob_matches_inner(pattern, candidate, pattern_index + 1, candidate_index + 1, memo)
+    };
+
+    memo.insert((pattern_index, candidate_index), result);
+    result
+}
+
+pub(super) fn to_pascal_case(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for part in value.split('_').filter(|part| !part.is_empty()) {
+        // SCREAMING_CASE segments are normalized so `MAX_SIZE` becomes `MaxSize`.
+        let normalized = if part.chars().any(|c| c.is_ascii_lowercase()) {
+            part.to_string()
+        } else {
+            part.to_ascii_lowercase()
+        };
+        let mut chars = normalized.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+    result
+}
+
+pub(super) fn to_camel_case(value: &str) -> String {
+    let pascal = to_pascal_case(value);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => pascal,
+    }
+}
+
+pub(super) fn to_snake_case(value: &str) -> String {
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(value.len() + 4);
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_ascii_lowercase());
+            // Acronyms stay together, so `HTTPServer` becomes `http_server`.
+            if previous.is_ascii_lowercase() || previous.is_ascii_digit() || (previous.is_ascii_uppercase() && next_is_lower) {
+                result.push('_');
+            }
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+pub(super) fn indent(level: usize) -> String {
+    "    ".repeat(level)
+}
@@ -0,0 +1,694 @@
+use std::{collections::BTreeMap, fmt, path::Path as FsPath};
+
+use thiserror::Error;
+
+use super::{
+    common::{
+        BuiltinType, ParsedSource, ResolvedType, SchemaIndex, discover_project_sources, format_literal, format_resolved_type, format_type, integer_range, parse_sources,
+        qualified_path, resolve_type, resolve_type_with_params, type_param_names,
+    },
+    resolver::resolve_sources,
+};
+use crate::{
+    error::CodegenError,
+    parser::ast::{Const, Enum, Field, Item, Rpc, RpcType, Service, Spanned, Type, Variant, VariantKind},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatIssue {
+    // Package path plus name of the affected item; enum variants are appended to their enum.
+    pub item: String,
+    pub kind: CompatIssueKind,
+}
+
+impl fmt::Display for CompatIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.item, self.kind)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CompatIssueKind {
+    #[error("type was removed")]
+    RemovedType,
+
+    #[error("type changed from {old} to {new}")]
+    ChangedTypeKind { old: &'static str, new: &'static str },
+
+    #[error("field @{tag} `{name}` changed type from {old} to {new}")]
+    RetypedField { tag: u32, name: String, old: String, new: String },
+
+    #[error("tag @{tag} of field `{old_name}` is reused by `{new_name}` with type {new} instead of {old}")]
+    ReusedTag {
+        tag: u32,
+        old_name: String,
+        new_name: String,
+        old: String,
+        new: String,
+    },
+
+    #[error("field @{tag} `{name}` narrowed from {old} to {new}")]
+    NarrowedField { tag: u32, name: String, old: String, new: String },
+
+    #[error("non-optional field @{tag} `{name}` was removed")]
+    RemovedField { tag: u32, name: String },
+
+    #[error("non-optional field @{tag} `{name}` was added")]
+    AddedField { tag: u32, name: String },
+
+    #[error("variant `{name}` changed tag from @{old} to @{new}")]
+    ChangedVariantTag { name: String, old: u32, new: u32 },
+
+    #[error("variant @{tag} `{name}` was removed")]
+    RemovedVariant { tag: u32, name: String },
+
+    #[error("variant @{tag} `{name}` changed from {old} to {new}")]
+    ChangedVariantShape { tag: u32, name: String, old: &'static str, new: &'static str },
+
+    #[error("const value changed from {old} to {new}")]
+    ChangedConst { old: String, new: String },
+
+    #[error("service was removed")]
+    RemovedService,
+
+    #[error("service id changed from @{old} to @{new}")]
+    ChangedServiceId { old: u32, new: u32 },
+
+    #[error("rpc @{tag} `{name}` was removed")]
+    RemovedRpc { tag: u32, name: String },
+
+    #[error("rpc @{tag} `{name}` changed from {old} to {new}")]
+    ChangedRpc { tag: u32, name: String, old: String, new: String },
+}
+
+// A directory with a `rocketpack.yaml` is loaded through its `sources`; any other directory contributes every `.rpf` below it.
+pub async fn check_compat(old_dir: &FsPath, new_dir: &FsPath) -> Result<Vec<CompatIssue>, CodegenError> {
+    let old_sources = load_sources(old_dir).await?;
+    let new_sources = load_sources(new_dir).await?;
+    compare_sources(&old_sources, &new_sources)
+}
+
+async fn load_sources(dir: &FsPath) -> Result<Vec<ParsedSource>, CodegenError> {
+    parse_sources(&discover_project_sources(dir)?)
+}
+
+struct Definition<'a> {
+    item: &'a Item,
+    index: &'a SchemaIndex,
+}
+
+// Peers only exchange tags, so fields and variants are matched by tag; names matter only for diagnostics.
+fn compare_sources(old_sources: &[ParsedSource], new_sources: &[ParsedSource]) -> Result<Vec<CompatIssue>, CodegenError> {
+    let old_indexes = resolve_sources(old_sources)?;
+    let new_indexes = resolve_sources(new_sources)?;
+    let old_definitions = collect_definitions(old_sources, &old_indexes);
+    let new_definitions = collect_definitions(new_sources, &new_indexes);
+
+    let mut issues = Vec::new();
+    for (path, old) in &old_definitions {
+        let item_path = path.join("::");
+        let Some(new) = new_definitions.get(path) else {
+            match old.item {
+                Item::Struct(_) | Item::Enum(_) => issues.push(CompatIssue {
+                    item: item_path,
+                    kind: CompatIssueKind::RemovedType,
+                }),
+                Item::Service(_) => issues.push(CompatIssue {
+                    item: item_path,
+                    kind: CompatIssueKind::RemovedService,
+                }),
+                Item::TypeAlias(_) | Item::Const(_) => {}
+            }
+            continue;
+        };
+
+        match (old.item, new.item) {
+            (Item::Struct(old_item), Item::Struct(new_item)) => {
+                let old_fields = record_fields(old.index, &type_param_names(&old_item.type_params), &old_item.fields)?;
+                let new_fields = record_fields(new.index, &type_param_names(&new_item.type_params), &new_item.fields)?;
+                compare_fields(&item_path, old.index, &old_fields, new.index, &new_fields, &mut issues);
+            }
+            (Item::Enum(old_item), Item::Enum(new_item)) => compare_enums(&item_path, old.index, old_item, new.index, new_item, &mut issues)?,
+            (Item::Const(old_item), Item::Const(new_item)) => compare_consts(&item_path, old_item, new_item, &mut issues),
+            (Item::Service(old_item), Item::Service(new_item)) => compare_services(&item_path, old.index, old_item, new.index, new_item, &mut issues),
+            (Item::TypeAlias(_), Item::TypeAlias(_)) => {}
+            (old_item, new_item) => {
+                if matches!(old_item, Item::Struct(_) | Item::Enum(_) | Item::Service(_)) {
+                    issues.push(CompatIssue {
+                        item: item_path,
+                        kind: CompatIssueKind::ChangedTypeKind {
+                            old: item_kind_name(old_item),
+                            new: item_kind_name(new_item),
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(issues)
+}
+
+fn collect_definitions<'a>(parsed_sources: &'a [ParsedSource], indexes: &'a [SchemaIndex]) -> BTreeMap<Vec<String>, Definition<'a>> {
+    let mut definitions = BTreeMap::new();
+
+    for (parsed_source, index) in parsed_sources.iter().zip(indexes) {
+        for item in &parsed_source.file.items {
+            let name = match item {
+                Item::Struct(item) => &item.name.value,
+                Item::Enum(item) => &item.name.value,
+                Item::TypeAlias(item) => &item.name.value,
+                Item::Const(item) => &item.name.value,
+                Item::Service(item) => &item.name.value,
+            };
+            let path = index.package.iter().cloned().chain([name.clone()]).collect::<Vec<_>>();
+            definitions.insert(path, Definition { item, index });
+        }
+    }
+
+    definitions
+}
+
+fn item_kind_name(item: &Item) -> &'static str {
+    match item {
+        Item::Struct(_) => "struct",
+        Item::Enum(_) => "enum",
+        Item::TypeAlias(_) => "type alias",
+        Item::Const(_) => "const",
+        Item::Service(_) => "service",
+    }
+}
+
+// Struct fields, tuple variant elements (tagged by position) and record variant fields share one shape.
+struct CompatField<'a> {
+    tag: u32,
+    name: &'a str,
+    resolved: ResolvedType,
+    // Decoders reject input without the field unless it is optional or has a default.
+    required: bool,
+}
+
+fn record_fields<'a>(index: &SchemaIndex, params: &[String], fields: &'a [Field]) -> Result<Vec<CompatField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for field in fields {
+        let ty = resolve_type_with_params(index, &field.ty.value, params)?;
+        resolved.push(CompatField {
+            tag: field.tag.value,
+            name: &field.name.value,
+            required: !matches!(ty, ResolvedType::Option(_)) && field.default.is_none(),
+            resolved: ty,
+        });
+    }
+    Ok(resolved)
+}
+
+fn tuple_fields<'a>(index: &SchemaIndex, params: &[String], fields: &'a [(Spanned<String>, Spanned<Type>)]) -> Result<Vec<CompatField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for (tag, (name, ty)) in fields.iter().enumerate() {
+        resolved.push(CompatField {
+            tag: tag as u32,
+            name: &name.value,
+            resolved: resolve_type_with_params(index, &ty.value, params)?,
+            required: true,
+        });
+    }
+    Ok(resolved)
+}
+
+fn compare_fields(
+    item_path: &str,
+    old_index: &SchemaIndex,
+    old_fields: &[CompatField<'_>],
+    new_index: &SchemaIndex,
+    new_fields: &[CompatField<'_>],
+    issues: &mut Vec<CompatIssue>,
+) {
+    let mut push = |kind| {
+        issues.push(CompatIssue {
+            item: item_path.to_string(),
+            kind,
+        })
+    };
+
+    for old_field in old_fields {
+        let Some(new_field) = new_fields.iter().find(|field| field.tag == old_field.tag) else {
+            if old_field.required {
+                push(CompatIssueKind::RemovedField {
+                    tag: old_field.tag,
+                    name: old_field.name.to_string(),
+                });
+            }
+            continue;
+        };
+
+        let old = format_resolved_type(old_index, &old_field.resolved);
+        let new = format_resolved_type(new_index, &new_field.resolved);
+        match compare_types(old_index, &old_field.resolved, new_index, &new_field.resolved) {
+            TypeChange::Same | TypeChange::Widened => {}
+            TypeChange::Narrowed => push(CompatIssueKind::NarrowedField {
+                tag: new_field.tag,
+                name: new_field.name.to_string(),
+                old,
+                new,
+            }),
+            TypeChange::Changed if old_field.name == new_field.name => push(CompatIssueKind::RetypedField {
+                tag: new_field.tag,
+                name: new_field.name.to_string(),
+                old,
+                new,
+            }),
+            TypeChange::Changed => push(CompatIssueKind::ReusedTag {
+                tag: new_field.tag,
+                old_name: old_field.name.to_string(),
+                new_name: new_field.name.to_string(),
+                old,
+                new,
+            }),
+        }
+    }
+
+    for new_field in new_fields {
+        if new_field.required && !old_fields.iter().any(|field| field.tag == new_field.tag) {
+            push(CompatIssueKind::AddedField {
+                tag: new_field.tag,
+                name: new_field.name.to_string(),
+            });
+        }
+    }
+}
+
+fn compare_enums(item_path: &str, old_index: &SchemaIndex, old_item: &Enum, new_index: &SchemaIndex, new_item: &Enum, issues: &mut Vec<CompatIssue>) -> Result<(), CodegenError> {
+    let old_params = type_param_names(&old_item.type_params);
+    let new_params = type_param_names(&new_item.type_params);
+    for old_variant in &old_item.variants {
+        let renamed = new_item.variants.iter().find(|variant| variant.name.value == old_variant.name.value);
+        if let Some(new_variant) = renamed
+            && new_variant.tag.value != old_variant.tag.value
+        {
+            issues.push(CompatIssue {
+                item: item_path.to_string(),
+                kind: CompatIssueKind::ChangedVariantTag {
+                    name: old_variant.name.value.clone(),
+                    old: old_variant.tag.value,
+                    new: new_variant.tag.value,
+                },
+            });
+        }
+
+        let Some(new_variant) = new_item.variants.iter().find(|variant| variant.tag.value == old_variant.tag.value) else {
+            if renamed.is_none() {
+                issues.push(CompatIssue {
+                    item: item_path.to_string(),
+                    kind: CompatIssueKind::RemovedVariant {
+                        tag: old_variant.tag.value,
+                        name: old_variant.name.value.clone(),
+                    },
+                });
+            }
+            continue;
+        };
+
+        let variant_path = format!("{item_path}::{}", new_variant.name.value);
+        let old_fields = variant_fields(old_index, &old_params, old_variant)?;
+        let new_fields = variant_fields(new_index, &new_params, new_variant)?;
+        if variant_shape(old_variant) != variant_shape(new_variant) {
+            issues.push(CompatIssue {
+                item: item_path.to_string(),
+                kind: CompatIssueKind::ChangedVariantShape {
+                    tag: new_variant.tag.value,
+                    name: new_variant.name.value.clone(),
+                    old: variant_shape(old_variant),
+                    new: variant_shape(new_variant),
+                },
+            });
+            continue;
+        }
+        compare_fields(&variant_path, old_index, &old_fields, new_index, &new_fields, issues);
+    }
+
+    Ok(())
+}
+
+fn variant_fields<'a>(index: &SchemaIndex, params: &[String], variant: &'a Variant) -> Result<Vec<CompatField<'a>>, CodegenError> {
+    match &variant.kind {
+        VariantKind::Unit => Ok(Vec::new()),
+        VariantKind::Tuple(fields) => tuple_fields(index, params, fields),
+        VariantKind::Record(fields) => record_fields(index, params, fields),
+    }
+}
+
+fn variant_shape(variant: &Variant) -> &'static str {
+    match variant.kind {
+        VariantKind::Unit => "unit",
+        VariantKind::Tuple(_) => "tuple",
+        VariantKind::Record(_) => "record",
+    }
+}
+
+fn compare_consts(item_path: &str, old_item: &Const, new_item: &Const, issues: &mut Vec<CompatIssue>) {
+    let old = format_literal(&old_item.value.value);
+    let new = format_literal(&new_item.value.value);
+    if old != new {
+        issues.push(CompatIssue {
+            item: item_path.to_string(),
+            kind: CompatIssueKind::ChangedConst { old, new },
+        });
+    }
+}
+
+// Callers select an rpc by the service id and its tag, so renaming is safe while any change of the messages breaks
+// existing peers.
+fn compare_services(item_path: &str, old_index: &SchemaIndex, old_item: &Service, new_index: &SchemaIndex, new_item: &Service, issues: &mut Vec<CompatIssue>) {
+    if old_item.tag.value != new_item.tag.value {
+        issues.push(CompatIssue {
+            item: item_path.to_string(),
+            kind: CompatIssueKind::ChangedServiceId {
+                old: old_item.tag.value,
+                new: new_item.tag.value,
+            },
+        });
+    }
+
+    for old_rpc in &old_item.rpcs {
+        let Some(new_rpc) = new_item.rpcs.iter().find(|rpc| rpc.tag.value == old_rpc.tag.value) else {
+            issues.push(CompatIssue {
+                item: item_path.to_string(),
+                kind: CompatIssueKind::RemovedRpc {
+                    tag: old_rpc.tag.value,
+                    name: old_rpc.name.value.clone(),
+                },
+            });
+            continue;
+        };
+
+        let old = rpc_signature(old_index, old_rpc);
+        let new = rpc_signature(new_index, new_rpc);
+        if old != new {
+            issues.push(CompatIssue {
+                item: item_path.to_string(),
+                kind: CompatIssueKind::ChangedRpc {
+                    tag: new_rpc.tag.value,
+                    name: new_rpc.name.value.clone(),
+                    old,
+                    new,
+                },
+            });
+        }
+    }
+}
+
+fn rpc_signature(index: &SchemaIndex, rpc: &Rpc) -> String {
+    format!("({}) returns ({})", rpc_type_signature(index, &rpc.request), rpc_type_signature(index, &rpc.response))
+}
+
+fn rpc_type_signature(index: &SchemaIndex, rpc_type: &RpcType) -> String {
+    let ty = match resolve_type(index, &rpc_type.ty.value) {
+        Ok(resolved @ ResolvedType::Named(_)) => qualified_path(index, &resolved).join("::"),
+        Ok(resolved) => format_resolved_type(index, &resolved),
+        Err(_) => format_type(&rpc_type.ty.value),
+    };
+    if rpc_type.stream { format!("stream {ty}") } else { ty }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TypeChange {
+    Same,
+    Widened,
+    Narrowed,
+    Changed,
+}
+
+fn compare_types(old_index: &SchemaIndex, old: &ResolvedType, new_index: &SchemaIndex, new: &ResolvedType) -> TypeChange {
+    match (old, new) {
+        (ResolvedType::Builtin(old), ResolvedType::Builtin(new)) => compare_builtin_types(old, new),
+        (ResolvedType::Named(old_named), ResolvedType::Named(new_named)) => {
+            if qualified_path(old_index, old) != qualified_path(new_index, new) || old_named.args.len() != new_named.args.len() {
+                return TypeChange::Changed;
+            }
+            let args_changed = old_named
+                .args
+                .iter()
+                .zip(new_named.args.iter())
+                .any(|(old, new)| compare_types(old_index, old, new_index, new) != TypeChange::Same);
+            if args_changed { TypeChange::Changed } else { TypeChange::Same }
+        }
+        (ResolvedType::Option(old), ResolvedType::Option(new)) | (ResolvedType::Vec(old), ResolvedType::Vec(new)) => compare_types(old_index, old, new_index, new),
+        (ResolvedType::Map(old_key, old_value), ResolvedType::Map(new_key, new_value)) => {
+            compare_types(old_index, old_key, new_index, new_key).max(compare_types(old_index, old_value, new_index, new_value))
+        }
+        (ResolvedType::Array(old, old_len), ResolvedType::Array(new, new_len)) if old_len == new_len => compare_types(old_index, old, new_index, new),
+        _ => TypeChange::Changed,
+    }
+}
+
+// A new integer type is a widening when it can hold every value of the old one.
+fn compare_builtin_types(old: &BuiltinType, new: &BuiltinType) -> TypeChange {
+    if old == new {
+        return TypeChange::Same;
+    }
+
+    match (integer_range(old), integer_range(new)) {
+        (Some(old), Some(new)) if new.0 <= old.0 && old.1 <= new.1 => TypeChange::Widened,
+        (Some(_), Some(_)) => TypeChange::Narrowed,
+        _ => TypeChange::Changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use super::*;
+    use crate::{codegen::common::DiscoveredSource, parser};
+
+    fn parse(text: &str) -> Result<Vec<ParsedSource>, CodegenError> {
+        let source = DiscoveredSource {
+            base_dir: PathBuf::from("."),
+            absolute_path: PathBuf::from("./sample.rpf"),
+            relative_path: PathBuf::from("sample.rpf"),
+        };
+        let file = parser::parse_source(&source.absolute_path, text)?;
+        Ok(vec![ParsedSource {
+            source,
+            text: text.to_string(),
+            file,
+        }])
+    }
+
+    fn compare(old: &str, new: &str) -> Result<Vec<String>, CodegenError> {
+        let issues = compare_sources(&parse(old)?, &parse(new)?)?;
+        Ok(issues.iter().map(|issue| issue.to_string()).collect())
+    }
+
+    #[test]
+    fn compare_struct_fields_test() -> TestResult {
+        let issues = compare(
+            r#"
+package demo;
+
+struct Message {
+  @1 id: u32;
+  @2 name: string;
+  @3 size: u64;
+  @4 note: Option<string>;
+  @5 count: u16;
+  @6 score: i32;
+}
+"#,
+            r#"
+package demo;
+
+struct Message {
+  @1 id: u64;
+  @2 name: bytes;
+  @3 size: u32;
+  @5 flags: Vec<u8>;
+  @6 score: u32;
+  @7 kind: string;
+  @8 label: Option<string>;
+  @9 retries: u32 = 3;
+}
+"#,
+        )?;
+
+        assert_eq!(
+            issues,
+            vec![
+                "demo::Message: field @2 `name` changed type from string to bytes",
+                "demo::Message: field @3 `size` narrowed from u64 to u32",
+                "demo::Message: tag @5 of field `count` is reused by `flags` with type Vec<u8> instead of u16",
+                "demo::Message: field @6 `score` narrowed from i32 to u32",
+                "demo::Message: non-optional field @7 `kind` was added",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_removed_items_test() -> TestResult {
+        let issues = compare(
+            r#"
+package demo;
+
+struct Message {
+  @1 id: u32;
+  @2 note: Option<string>;
+  @3 retries: u32 = 3;
+}
+
+struct Legacy {
+  @1 id: u32;
+}
+"#,
+            r#"
+package demo;
+
+struct Message {
+}
+
+enum Legacy {
+  @1 Empty;
+}
+"#,
+        )?;
+
+        assert_eq!(
+            issues,
+            vec!["demo::Legacy: type changed from struct to enum", "demo::Message: non-optional field @1 `id` was removed",]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_enums_and_consts_test() -> TestResult {
+        let issues = compare(
+            r#"
+package demo;
+
+enum Status {
+  @1 Success;
+  @2 Failed(reason: string);
+  @3 Retry { @1 after: u32; };
+  @4 Unknown;
+}
+
+type Reason = string;
+const MAX_SIZE: u32 = 1024;
+const NAME: string = "demo";
+"#,
+            r#"
+package demo;
+
+enum Status {
+  @1 Success;
+  @2 Failed(reason: Reason);
+  @3 Retry;
+  @5 Unknown;
+}
+
+type Reason = string;
+const MAX_SIZE: u32 = 2048;
+const NAME: string = "demo";
+"#,
+        )?;
+
+        assert_eq!(
+            issues,
+            vec![
+                "demo::MAX_SIZE: const value changed from 1024 to 2048",
+                "demo::Status: variant @3 `Retry` changed from record to unit",
+                "demo::Status: variant `Unknown` changed tag from @4 to @5",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_services_test() -> TestResult {
+        let issues = compare(
+            r#"
+package demo;
+
+struct Request {}
+struct Response {}
+
+@1 service Users {
+  @1 rpc Get(Request) returns (Response);
+  @2 rpc Watch(Request) returns (stream Response);
+  @3 rpc Delete(Request) returns (Response);
+}
+
+@2 service Legacy {
+  @1 rpc Ping(Request) returns (Response);
+}
+"#,
+            r#"
+package demo;
+
+struct Request {}
+struct Response {}
+
+@3 service Users {
+  @1 rpc Fetch(Request) returns (Response);
+  @2 rpc Watch(stream Request) returns (stream Response);
+}
+"#,
+        )?;
+
+        assert_eq!(
+            issues,
+            vec![
+                "demo::Legacy: service was removed",
+                "demo::Users: service id changed from @1 to @3",
+                "demo::Users: rpc @2 `Watch` changed from (demo::Request) returns (stream demo::Response) to (stream demo::Request) returns (stream demo::Response)",
+                "demo::Users: rpc @3 `Delete` was removed",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_generics_test() -> TestResult {
+        let issues = compare(
+            r#"
+package demo;
+
+struct User {}
+struct Group {}
+struct Page<T> {
+  @1 items: Vec<T>;
+}
+struct Listing {
+  @1 users: Page<User>;
+  @2 groups: Page<Group>;
+}
+"#,
+            r#"
+package demo;
+
+struct User {}
+struct Group {}
+struct Page<T> {
+  @1 items: Vec<T>;
+}
+struct Listing {
+  @1 users: Page<User>;
+  @2 groups: Page<User>;
+}
+"#,
+        )?;
+
+        assert_eq!(
+            issues,
+            vec!["demo::Listing: field @2 `groups` changed type from demo::Page<demo::Group> to demo::Page<demo::User>"]
+        );
+
+        Ok(())
+    }
+}
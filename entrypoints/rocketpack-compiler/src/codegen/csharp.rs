@@ -0,0 +1,1227 @@
+use std::{fmt::Write as _, path::Path as FsPath};
+
+use tracing::info;
+
+use super::{
+    common::{
+        BuiltinType, DiscoveredSource, NamedType, NamedTypeKind, ParsedSource, ResolvedType, SchemaIndex, ensure_no_generics, find_target, indent, normalize_path, option_bool,
+        option_string, resolve_output_path, resolve_type, source_file_stem, to_camel_case, to_pascal_case,
+    },
+    output::OutputWriter,
+    resolver::resolve_sources,
+};
+use crate::{
+    config::{GeneratorConfig, GeneratorTargetConfig},
+    error::CodegenError,
+    parser::ast::{Const, Enum, Field, Item, Literal, Spanned, Struct, Type, TypeAlias, Variant, VariantKind},
+};
+
+// The generated code depends on a small runtime that mirrors the Rust `RocketPackEncoder` / `RocketPackDecoder` traits
+// method for method (`WriteU32`, `ReadMap`, `SkipField`, ...). It is written next to the generated files unless
+// `emit_runtime: false` is set, which a project with several output directories needs for all but one of them.
+const RUNTIME_SOURCE: &str = include_str!("csharp_runtime.cs");
+const RUNTIME_FILE_STEM: &str = "RocketPackRuntime";
+const DEFAULT_RUNTIME_NAMESPACE: &str = "Omnius.RocketPack.Runtime";
+const DEFAULT_TARGET_FRAMEWORK: &str = "net8.0";
+const DEFAULT_FILE_EXTENSION: &str = ".cs";
+
+#[derive(Debug, Clone)]
+struct GeneratedCSharpFile {
+    source: DiscoveredSource,
+    extension: String,
+    runtime: Option<String>,
+    contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CSharpOptions {
+    visibility: String,
+    partial_types: bool,
+    base_namespace: Vec<String>,
+    file_extension: String,
+    dotnet_version: Option<u32>,
+    runtime_namespace: String,
+    emit_runtime: bool,
+}
+
+impl Default for CSharpOptions {
+    fn default() -> Self {
+        Self {
+            visibility: "public".to_string(),
+            partial_types: false,
+            base_namespace: Vec::new(),
+            file_extension: DEFAULT_FILE_EXTENSION.to_string(),
+            dotnet_version: parse_dotnet_version(DEFAULT_TARGET_FRAMEWORK),
+            runtime_namespace: DEFAULT_RUNTIME_NAMESPACE.to_string(),
+            emit_runtime: true,
+        }
+    }
+}
+
+impl CSharpOptions {
+    fn load(conf: &GeneratorConfig, target: Option<&GeneratorTargetConfig>) -> Result<Self, CodegenError> {
+        let visibility = option_string(conf, target, "visibility")?.unwrap_or_else(|| "public".to_string());
+        if visibility != "public" && visibility != "internal" {
+            return Err(CodegenError::Other(format!("unsupported csharp visibility: {visibility}")));
+        }
+
+        let target_framework = option_string(conf, target, "target_framework")?.unwrap_or_else(|| DEFAULT_TARGET_FRAMEWORK.to_string());
+        let base_namespace = option_string(conf, target, "base_namespace")?
+            .map(|value| value.split('.').filter(|segment| !segment.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            visibility,
+            partial_types: option_bool(conf, target, "partial_types")?.unwrap_or(false),
+            base_namespace,
+            file_extension: option_string(conf, target, "file_extension")?.unwrap_or_else(|| DEFAULT_FILE_EXTENSION.to_string()),
+            dotnet_version: parse_dotnet_version(&target_framework),
+            runtime_namespace: option_string(conf, target, "runtime_namespace")?.unwrap_or_else(|| DEFAULT_RUNTIME_NAMESPACE.to_string()),
+            emit_runtime: option_bool(conf, target, "emit_runtime")?.unwrap_or(true),
+        })
+    }
+
+    // `record` and `System.Half` arrived with .NET 5, `Int128` / `UInt128` with .NET 7 and alias-any-type with C# 12 (.NET 8).
+    fn supports_records(&self) -> bool {
+        self.dotnet_version.is_some_and(|version| version >= 5)
+    }
+
+    fn supports_half(&self) -> bool {
+        self.dotnet_version.is_some_and(|version| version >= 5)
+    }
+
+    fn supports_int128(&self) -> bool {
+        self.dotnet_version.is_some_and(|version| version >= 7)
+    }
+
+    fn supports_any_type_alias(&self) -> bool {
+        self.dotnet_version.is_some_and(|version| version >= 8)
+    }
+
+    fn type_keyword(&self) -> &'static str {
+        if self.supports_records() { "record" } else { "class" }
+    }
+
+    fn partial_keyword(&self) -> &'static str {
+        if self.partial_types { "partial " } else { "" }
+    }
+}
+
+// `net8.0` / `net8.0-windows` map to 8. .NET Framework (`net48`), `netstandard*` and `netcoreapp*` have no version here and get
+// the conservative feature set.
+fn parse_dotnet_version(target_framework: &str) -> Option<u32> {
+    let rest = target_framework.strip_prefix("net")?;
+    let (major, _) = rest.split_once('.')?;
+    major.parse().ok()
+}
+
+struct CSharpContext<'a> {
+    index: &'a SchemaIndex,
+    options: &'a CSharpOptions,
+}
+
+pub(super) async fn generate(root_dir: &FsPath, parsed_sources: &[ParsedSource], conf: &GeneratorConfig, writer: &mut OutputWriter) -> Result<(), CodegenError> {
+    let generated_files = render_sources(conf, parsed_sources)?;
+    let changed_count = write_generated_files(root_dir, conf, &generated_files, writer)?;
+
+    info!(
+        generator_id = %conf.id,
+        plugin = %conf.plugin,
+        source_count = parsed_sources.len(),
+        rendered_count = generated_files.len(),
+        changed_count,
+        "generated rocketpack csharp files"
+    );
+
+    Ok(())
+}
+
+fn render_sources(conf: &GeneratorConfig, parsed_sources: &[ParsedSource]) -> Result<Vec<GeneratedCSharpFile>, CodegenError> {
+    ensure_no_generics(parsed_sources, "C#")?;
+    let indexes = resolve_sources(parsed_sources)?;
+    let mut generated_files = Vec::with_capacity(parsed_sources.len());
+
+    for (parsed_source, index) in parsed_sources.iter().zip(&indexes) {
+        let Some(target) = find_target(conf, &parsed_source.source) else {
+            info!(
+                generator_id = %conf.id,
+                source = normalize_path(&parsed_source.source.relative_path),
+                "skip csharp source without matching target"
+            );
+            continue;
+        };
+
+        let options = CSharpOptions::load(conf, Some(target))?;
+        generated_files.push(GeneratedCSharpFile {
+            source: parsed_source.source.clone(),
+            extension: options.file_extension.clone(),
+            runtime: options.emit_runtime.then(|| render_runtime(&options)),
+            contents: render_csharp_file(parsed_source, index, &options)?,
+        });
+    }
+
+    Ok(generated_files)
+}
+
+fn write_generated_files(root_dir: &FsPath, conf: &GeneratorConfig, generated_files: &[GeneratedCSharpFile], writer: &mut OutputWriter) -> Result<usize, CodegenError> {
+    let mut changed_count = 0usize;
+
+    for generated_file in generated_files {
+        let Some(output_path) = resolve_output_path(root_dir, conf, &generated_file.source, &generated_file.extension)? else {
+            continue;
+        };
+
+        if let Some(runtime) = &generated_file.runtime
+            && let Some(parent_dir) = output_path.parent()
+            && writer.write(&parent_dir.join(format!("{RUNTIME_FILE_STEM}{}", generated_file.extension)), runtime)?
+        {
+            changed_count += 1;
+        }
+
+        if writer.write(&output_path, &generated_file.contents)? {
+            changed_count += 1;
+        }
+    }
+
+    Ok(changed_count)
+}
+
+fn render_runtime(options: &CSharpOptions) -> String {
+    RUNTIME_SOURCE.replacen(&format!("namespace {DEFAULT_RUNTIME_NAMESPACE}"), &format!("namespace {}", options.runtime_namespace), 1)
+}
+
+fn render_csharp_file(parsed_source: &ParsedSource, index: &SchemaIndex, options: &CSharpOptions) -> Result<String, CodegenError> {
+    let ctx = CSharpContext { index, options };
+    let mut out = String::new();
+
+    writeln!(&mut out, "// <auto-generated>").ok();
+    writeln!(&mut out, "//     @generated by rocketpack-compiler").ok();
+    writeln!(&mut out, "// </auto-generated>").ok();
+    writeln!(&mut out, "#nullable enable").ok();
+    writeln!(&mut out).ok();
+    writeln!(&mut out, "using System;").ok();
+    writeln!(&mut out, "using System.Collections.Generic;").ok();
+    writeln!(&mut out, "using {};", options.runtime_namespace).ok();
+    writeln!(&mut out).ok();
+
+    let namespace = file_namespace(options, index);
+    let depth = usize::from(!namespace.is_empty());
+    if !namespace.is_empty() {
+        writeln!(&mut out, "namespace {}", namespace.join(".")).ok();
+        writeln!(&mut out, "{{").ok();
+    }
+
+    let mut has_alias = false;
+    for item in &parsed_source.file.items {
+        if let Item::TypeAlias(item) = item {
+            has_alias |= write_type_alias_declaration(&mut out, &ctx, item, depth)?;
+        }
+    }
+    if has_alias {
+        writeln!(&mut out).ok();
+    }
+
+    for item in &parsed_source.file.items {
+        match item {
+            Item::Struct(item) => {
+                write_struct_declaration(&mut out, &ctx, item, depth)?;
+                writeln!(&mut out).ok();
+            }
+            Item::Enum(item) => {
+                write_enum_declaration(&mut out, &ctx, item, depth)?;
+                writeln!(&mut out).ok();
+            }
+            // Remoting stubs are only generated for Rust.
+            Item::TypeAlias(_) | Item::Const(_) | Item::Service(_) => {}
+        }
+    }
+
+    let consts = parsed_source
+        .file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(item) => Some(item),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !consts.is_empty() {
+        let class_name = format!("{}Constants", to_pascal_case(source_file_stem(&parsed_source.source)?));
+        write_constants_class(&mut out, &ctx, &class_name, &consts, depth)?;
+    }
+
+    trim_trailing_blank_line(&mut out);
+
+    if !namespace.is_empty() {
+        writeln!(&mut out, "}}").ok();
+    }
+
+    Ok(out)
+}
+
+fn file_namespace(options: &CSharpOptions, index: &SchemaIndex) -> Vec<String> {
+    options
+        .base_namespace
+        .iter()
+        .cloned()
+        .chain(index.package.iter().map(|segment| sanitize_ident(&to_pascal_case(segment))))
+        .collect()
+}
+
+fn trim_trailing_blank_line(out: &mut String) {
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+}
+
+// Declarations always spell out the resolved type, so an alias that the target language version cannot express is simply omitted.
+fn write_type_alias_declaration(out: &mut String, ctx: &CSharpContext<'_>, item: &TypeAlias, depth: usize) -> Result<bool, CodegenError> {
+    let resolved = resolve_type(ctx.index, &item.ty.value)?;
+    let expressible = ctx.options.supports_any_type_alias() || matches!(resolved, ResolvedType::Vec(_) | ResolvedType::Map(_, _) | ResolvedType::Named(_));
+    if !expressible {
+        return Ok(false);
+    }
+
+    writeln!(
+        out,
+        "{}using {} = {};",
+        indent(depth),
+        sanitize_ident(&to_pascal_case(&item.name.value)),
+        render_type(ctx, &resolved)?
+    )
+    .ok();
+
+    Ok(true)
+}
+
+fn write_struct_declaration(out: &mut String, ctx: &CSharpContext<'_>, item: &Struct, depth: usize) -> Result<(), CodegenError> {
+    let type_name = sanitize_ident(&to_pascal_case(&item.name.value));
+    let fields = resolve_sorted_fields(ctx.index, &item.fields)?;
+
+    writeln!(
+        out,
+        "{}{} sealed {}{} {}",
+        indent(depth),
+        ctx.options.visibility,
+        ctx.options.partial_keyword(),
+        ctx.options.type_keyword(),
+        type_name
+    )
+    .ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    write_property_declarations(out, ctx, &type_name, &fields, depth + 1)?;
+    writeln!(out).ok();
+    write_export_import_fns(out, &type_name, depth + 1);
+    writeln!(out).ok();
+    write_struct_pack_fn(out, ctx, &type_name, &fields, depth + 1)?;
+    writeln!(out).ok();
+    write_struct_unpack_fn(out, ctx, &type_name, &fields, depth + 1)?;
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn resolve_sorted_fields<'a>(index: &SchemaIndex, fields: &'a [Field]) -> Result<Vec<CSharpField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for field in fields {
+        resolved.push(CSharpField {
+            tag: field.tag.value as usize,
+            name: &field.name.value,
+            default: field.default.as_ref().map(|default| &default.value),
+            resolved: resolve_type(index, &field.ty.value)?,
+        });
+    }
+    resolved.sort_by_key(|field| field.tag);
+    Ok(resolved)
+}
+
+fn resolve_tuple_fields<'a>(index: &SchemaIndex, fields: &'a [(Spanned<String>, Spanned<Type>)]) -> Result<Vec<CSharpField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for (tag, (name, ty)) in fields.iter().enumerate() {
+        resolved.push(CSharpField {
+            tag,
+            name: &name.value,
+            default: None,
+            resolved: resolve_type(index, &ty.value)?,
+        });
+    }
+    Ok(resolved)
+}
+
+// Struct fields, tuple variant elements (tagged by position) and record variant fields share one shape.
+#[derive(Debug, Clone)]
+struct CSharpField<'a> {
+    tag: usize,
+    name: &'a str,
+    default: Option<&'a Literal>,
+    resolved: ResolvedType,
+}
+
+fn property_name(field_name: &str, owner_name: &str) -> String {
+    let name = sanitize_ident(&to_pascal_case(field_name));
+    // A member cannot share its enclosing type's name.
+    if name == owner_name { format!("{name}Value") } else { name }
+}
+
+fn local_name(field_name: &str) -> String {
+    let name = to_camel_case(field_name);
+    match name.as_str() {
+        "encoder" | "decoder" | "value" => format!("{name}_"),
+        _ => sanitize_ident(&name),
+    }
+}
+
+fn write_property_declarations(out: &mut String, ctx: &CSharpContext<'_>, owner_name: &str, fields: &[CSharpField<'_>], depth: usize) -> Result<(), CodegenError> {
+    for field in fields {
+        let initializer = match (field.default, &field.resolved) {
+            (_, ResolvedType::Option(_)) => None,
+            (Some(default), resolved) => Some(render_typed_literal(ctx, default, resolved)?),
+            (None, resolved) => render_initializer(ctx, resolved)?,
+        };
+
+        write!(
+            out,
+            "{}public {} {} {{ get; set; }}",
+            indent(depth),
+            render_type(ctx, &field.resolved)?,
+            property_name(field.name, owner_name)
+        )
+        .ok();
+        match initializer {
+            Some(initializer) => writeln!(out, " = {initializer};").ok(),
+            None => writeln!(out).ok(),
+        };
+    }
+
+    Ok(())
+}
+
+fn render_initializer(ctx: &CSharpContext<'_>, resolved: &ResolvedType) -> Result<Option<String>, CodegenError> {
+    Ok(match resolved {
+        ResolvedType::Builtin(BuiltinType::String) => Some("string.Empty".to_string()),
+        ResolvedType::Builtin(BuiltinType::Bytes) => Some("Array.Empty<byte>()".to_string()),
+        ResolvedType::Builtin(_) | ResolvedType::Option(_) => None,
+        ResolvedType::Named(_) => Some("null!".to_string()),
+        ResolvedType::Vec(_) | ResolvedType::Map(_, _) => Some(render_new_collection(ctx, resolved)?),
+        ResolvedType::Array(inner, len) => Some(render_new_array(ctx, inner, &len.to_string())?),
+    })
+}
+
+fn render_new_collection(ctx: &CSharpContext<'_>, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    Ok(match resolved {
+        // String and byte string keys are kept in the order of the Rust `BTreeMap`, i.e. by their UTF-8 / raw bytes.
+        ResolvedType::Map(key, _) if matches!(key.as_ref(), ResolvedType::Builtin(BuiltinType::String)) => {
+            format!("new {}(RocketPackComparers.String)", render_type(ctx, resolved)?)
+        }
+        ResolvedType::Map(key, _) if matches!(key.as_ref(), ResolvedType::Builtin(BuiltinType::Bytes)) => {
+            format!("new {}(RocketPackComparers.Bytes)", render_type(ctx, resolved)?)
+        }
+        _ => format!("new {}()", render_type(ctx, resolved)?),
+    })
+}
+
+// `new T[n]` must put the rank after the element type, even when the element is itself an array (`new byte[4][]`).
+fn render_new_array(ctx: &CSharpContext<'_>, inner: &ResolvedType, len: &str) -> Result<String, CodegenError> {
+    let inner_type = render_type(ctx, inner)?;
+    Ok(match inner_type.find('[') {
+        Some(position) => format!("new {}[{}]{}", &inner_type[..position], len, &inner_type[position..]),
+        None => format!("new {inner_type}[{len}]"),
+    })
+}
+
+fn write_export_import_fns(out: &mut String, type_name: &str, depth: usize) {
+    writeln!(out, "{}public byte[] Export() => RocketPackEncoder.Encode(this, Pack);", indent(depth)).ok();
+    writeln!(
+        out,
+        "{}public static {} Import(ReadOnlySpan<byte> bytes) => RocketPackDecoder.Decode(bytes, Unpack);",
+        indent(depth),
+        type_name
+    )
+    .ok();
+}
+
+fn write_struct_pack_fn(out: &mut String, ctx: &CSharpContext<'_>, type_name: &str, fields: &[CSharpField<'_>], depth: usize) -> Result<(), CodegenError> {
+    writeln!(out, "{}public static void Pack(RocketPackEncoder encoder, {} value)", indent(depth), type_name).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    let mut temp_counter = 0usize;
+    write_fields_pack_body(out, ctx, type_name, "value", fields, depth + 1, &mut temp_counter)?;
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn write_fields_pack_body(
+    out: &mut String,
+    ctx: &CSharpContext<'_>,
+    owner_name: &str,
+    value_expr: &str,
+    fields: &[CSharpField<'_>],
+    depth: usize,
+    temp_counter: &mut usize,
+) -> Result<(), CodegenError> {
+    let required_count = fields.iter().filter(|field| !matches!(field.resolved, ResolvedType::Option(_))).count();
+    let has_optional = fields.iter().any(|field| matches!(field.resolved, ResolvedType::Option(_)));
+
+    if has_optional {
+        let count_name = next_temp_name(temp_counter, "count");
+        writeln!(out, "{}var {} = {};", indent(depth), count_name, required_count).ok();
+        for field in fields {
+            if matches!(field.resolved, ResolvedType::Option(_)) {
+                writeln!(
+                    out,
+                    "{}if ({}.{} is not null) {}++;",
+                    indent(depth),
+                    value_expr,
+                    property_name(field.name, owner_name),
+                    count_name
+                )
+                .ok();
+            }
+        }
+        writeln!(out, "{}encoder.WriteMap({});", indent(depth), count_name).ok();
+    } else {
+        writeln!(out, "{}encoder.WriteMap({});", indent(depth), required_count).ok();
+    }
+
+    for field in fields {
+        let property_expr = format!("{}.{}", value_expr, property_name(field.name, owner_name));
+        match &field.resolved {
+            ResolvedType::Option(inner) => {
+                let binding = next_temp_name(temp_counter, "value");
+                writeln!(out, "{}if ({} is {{ }} {})", indent(depth), property_expr, binding).ok();
+                writeln!(out, "{}{{", indent(depth)).ok();
+                writeln!(out, "{}encoder.WriteU64({});", indent(depth + 1), field.tag).ok();
+                write_encode_value(out, ctx, inner, &binding, field.name, depth + 1, temp_counter)?;
+                writeln!(out, "{}}}", indent(depth)).ok();
+            }
+            resolved => {
+                writeln!(out, "{}encoder.WriteU64({});", indent(depth), field.tag).ok();
+                write_encode_value(out, ctx, resolved, &property_expr, field.name, depth, temp_counter)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_encode_value(
+    out: &mut String,
+    ctx: &CSharpContext<'_>,
+    resolved: &ResolvedType,
+    expr: &str,
+    context_name: &str,
+    depth: usize,
+    temp_counter: &mut usize,
+) -> Result<(), CodegenError> {
+    match resolved {
+        ResolvedType::Builtin(builtin) => {
+            writeln!(out, "{}encoder.{}({});", indent(depth), encoder_method(builtin), expr).ok();
+        }
+        ResolvedType::Named(named) => {
+            writeln!(out, "{}encoder.WriteStruct({}, {}.Pack);", indent(depth), expr, render_named_type(ctx, named)).ok();
+        }
+        ResolvedType::Option(inner) => {
+            write_encode_value(out, ctx, inner, expr, context_name, depth, temp_counter)?;
+        }
+        ResolvedType::Vec(inner) => {
+            let item_name = next_temp_name(temp_counter, "item");
+            writeln!(out, "{}encoder.WriteArray({}.Count);", indent(depth), expr).ok();
+            writeln!(out, "{}foreach (var {} in {})", indent(depth), item_name, expr).ok();
+            writeln!(out, "{}{{", indent(depth)).ok();
+            write_encode_value(out, ctx, inner, &item_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+        ResolvedType::Map(key, value) => {
+            let entry_name = next_temp_name(temp_counter, "entry");
+            writeln!(out, "{}encoder.WriteMap({}.Count);", indent(depth), expr).ok();
+            writeln!(out, "{}foreach (var {} in {})", indent(depth), entry_name, expr).ok();
+            writeln!(out, "{}{{", indent(depth)).ok();
+            write_encode_value(out, ctx, key, &format!("{entry_name}.Key"), context_name, depth + 1, temp_counter)?;
+            write_encode_value(out, ctx, value, &format!("{entry_name}.Value"), context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+        ResolvedType::Array(inner, len) => {
+            let item_name = next_temp_name(temp_counter, "item");
+            writeln!(
+                out,
+                "{}if ({}.Length != {}) throw new RocketPackEncoderException(\"array length mismatch: {}\");",
+                indent(depth),
+                expr,
+                len,
+                context_name
+            )
+            .ok();
+            writeln!(out, "{}encoder.WriteArray({}.Length);", indent(depth), expr).ok();
+            writeln!(out, "{}foreach (var {} in {})", indent(depth), item_name, expr).ok();
+            writeln!(out, "{}{{", indent(depth)).ok();
+            write_encode_value(out, ctx, inner, &item_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+    }
+
+    Ok(())
+}
+
+fn encoder_method(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "WriteBool",
+        BuiltinType::U8 => "WriteU8",
+        BuiltinType::U16 => "WriteU16",
+        BuiltinType::U32 => "WriteU32",
+        BuiltinType::U64 => "WriteU64",
+        BuiltinType::U128 => "WriteU128",
+        BuiltinType::I8 => "WriteI8",
+        BuiltinType::I16 => "WriteI16",
+        BuiltinType::I32 => "WriteI32",
+        BuiltinType::I64 => "WriteI64",
+        BuiltinType::I128 => "WriteI128",
+        BuiltinType::F16 => "WriteF16",
+        BuiltinType::F32 => "WriteF32",
+        BuiltinType::F64 => "WriteF64",
+        BuiltinType::String => "WriteString",
+        BuiltinType::Bytes => "WriteBytes",
+    }
+}
+
+fn decoder_method(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "ReadBool",
+        BuiltinType::U8 => "ReadU8",
+        BuiltinType::U16 => "ReadU16",
+        BuiltinType::U32 => "ReadU32",
+        BuiltinType::U64 => "ReadU64",
+        BuiltinType::U128 => "ReadU128",
+        BuiltinType::I8 => "ReadI8",
+        BuiltinType::I16 => "ReadI16",
+        BuiltinType::I32 => "ReadI32",
+        BuiltinType::I64 => "ReadI64",
+        BuiltinType::I128 => "ReadI128",
+        BuiltinType::F16 => "ReadF16",
+        BuiltinType::F32 => "ReadF32",
+        BuiltinType::F64 => "ReadF64",
+        BuiltinType::String => "ReadString",
+        BuiltinType::Bytes => "ReadBytes",
+    }
+}
+
+fn write_struct_unpack_fn(out: &mut String, ctx: &CSharpContext<'_>, type_name: &str, fields: &[CSharpField<'_>], depth: usize) -> Result<(), CodegenError> {
+    writeln!(out, "{}public static {} Unpack(RocketPackDecoder decoder)", indent(depth), type_name).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    let mut temp_counter = 0usize;
+    write_fields_unpack_body(out, ctx, fields, depth + 1, &mut temp_counter)?;
+    writeln!(out).ok();
+    write_object_initializer(out, ctx, &format!("return new {type_name}"), type_name, fields, depth + 1)?;
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn write_fields_unpack_body(out: &mut String, ctx: &CSharpContext<'_>, fields: &[CSharpField<'_>], depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+    for field in fields {
+        writeln!(out, "{}{} {} = null;", indent(depth), render_storage_type(ctx, &field.resolved)?, local_name(field.name)).ok();
+    }
+
+    let count_name = next_temp_name(temp_counter, "count");
+    let index_name = next_temp_name(temp_counter, "i");
+    writeln!(out, "{}var {} = decoder.ReadMap();", indent(depth), count_name).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}for (var {} = 0; {} < {}; {}++)", indent(depth), index_name, index_name, count_name, index_name).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    writeln!(out, "{}switch (decoder.ReadU64())", indent(depth + 1)).ok();
+    writeln!(out, "{}{{", indent(depth + 1)).ok();
+
+    for field in fields {
+        let decode_target = match &field.resolved {
+            ResolvedType::Option(inner) => inner.as_ref(),
+            resolved => resolved,
+        };
+
+        writeln!(out, "{}case {}:", indent(depth + 2), field.tag).ok();
+        writeln!(out, "{}{{", indent(depth + 2)).ok();
+        let value_expr = write_decode_value(out, ctx, decode_target, depth + 3, field.name, temp_counter)?;
+        writeln!(out, "{}{} = {};", indent(depth + 3), local_name(field.name), value_expr).ok();
+        writeln!(out, "{}break;", indent(depth + 3)).ok();
+        writeln!(out, "{}}}", indent(depth + 2)).ok();
+    }
+
+    writeln!(out, "{}default:", indent(depth + 2)).ok();
+    writeln!(out, "{}{{", indent(depth + 2)).ok();
+    writeln!(out, "{}decoder.SkipField();", indent(depth + 3)).ok();
+    writeln!(out, "{}break;", indent(depth + 3)).ok();
+    writeln!(out, "{}}}", indent(depth + 2)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn write_object_initializer(out: &mut String, ctx: &CSharpContext<'_>, prefix: &str, owner_name: &str, fields: &[CSharpField<'_>], depth: usize) -> Result<(), CodegenError> {
+    if fields.is_empty() {
+        writeln!(out, "{}{}();", indent(depth), prefix).ok();
+        return Ok(());
+    }
+
+    writeln!(out, "{}{}", indent(depth), prefix).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    for field in fields {
+        writeln!(out, "{}{} = {},", indent(depth + 1), property_name(field.name, owner_name), render_value_init(ctx, field)?).ok();
+    }
+    writeln!(out, "{}}};", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn render_storage_type(ctx: &CSharpContext<'_>, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    match resolved {
+        ResolvedType::Option(_) => render_type(ctx, resolved),
+        _ => Ok(format!("{}?", render_type(ctx, resolved)?)),
+    }
+}
+
+fn render_value_init(ctx: &CSharpContext<'_>, field: &CSharpField<'_>) -> Result<String, CodegenError> {
+    let local = local_name(field.name);
+
+    if matches!(field.resolved, ResolvedType::Option(_)) {
+        return Ok(local);
+    }
+
+    if let Some(default) = field.default {
+        return Ok(format!("{local} ?? {}", render_typed_literal(ctx, default, &field.resolved)?));
+    }
+
+    Ok(format!("{local} ?? throw new RocketPackDecoderException(\"missing field: {}\")", field.name))
+}
+
+fn write_decode_value(
+    out: &mut String,
+    ctx: &CSharpContext<'_>,
+    resolved: &ResolvedType,
+    depth: usize,
+    context_name: &str,
+    temp_counter: &mut usize,
+) -> Result<String, CodegenError> {
+    match resolved {
+        ResolvedType::Builtin(builtin) => Ok(format!("decoder.{}()", decoder_method(builtin))),
+        ResolvedType::Named(named) => Ok(format!("decoder.ReadStruct({}.Unpack)", render_named_type(ctx, named))),
+        ResolvedType::Option(inner) => write_decode_value(out, ctx, inner, depth, context_name, temp_counter),
+        ResolvedType::Vec(inner) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let values_name = next_temp_name(temp_counter, "values");
+            let index_name = next_temp_name(temp_counter, "i");
+            writeln!(out, "{}var {} = decoder.ReadArray();", indent(depth), count_name).ok();
+            writeln!(out, "{}var {} = {};", indent(depth), values_name, render_new_collection(ctx, resolved)?).ok();
+            writeln!(out, "{}for (var {} = 0; {} < {}; {}++)", indent(depth), index_name, index_name, count_name, index_name).ok();
+            writeln!(out, "{}{{", indent(depth)).ok();
+            let inner_expr = write_decode_value(out, ctx, inner, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}.Add({});", indent(depth + 1), values_name, inner_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(values_name)
+        }
+        ResolvedType::Map(key, value) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let map_name = next_temp_name(temp_counter, "map");
+            let index_name = next_temp_name(temp_counter, "i");
+            writeln!(out, "{}var {} = decoder.ReadMap();", indent(depth), count_name).ok();
+            writeln!(out, "{}var {} = {};", indent(depth), map_name, render_new_collection(ctx, resolved)?).ok();
+            writeln!(out, "{}for (var {} = 0; {} < {}; {}++)", indent(depth), index_name, index_name, count_name, index_name).ok();
+            writeln!(out, "{}{{", indent(depth)).ok();
+            let key_expr = write_decode_value(out, ctx, key, depth + 1, context_name, temp_counter)?;
+            let key_name = next_temp_name(temp_counter, "key");
+            writeln!(out, "{}var {} = {};", indent(depth + 1), key_name, key_expr).ok();
+            let value_expr = write_decode_value(out, ctx, value, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}[{}] = {};", indent(depth + 1), map_name, key_name, value_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(map_name)
+        }
+        ResolvedType::Array(inner, len) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let array_name = next_temp_name(temp_counter, "array");
+            let index_name = next_temp_name(temp_counter, "i");
+            writeln!(out, "{}var {} = decoder.ReadArray();", indent(depth), count_name).ok();
+            writeln!(
+                out,
+                "{}if ({} != {}) throw new RocketPackDecoderException(\"array length mismatch: {}\");",
+                indent(depth),
+                count_name,
+                len,
+                context_name
+            )
+            .ok();
+            writeln!(out, "{}var {} = {};", indent(depth), array_name, render_new_array(ctx, inner, &len.to_string())?).ok();
+            writeln!(out, "{}for (var {} = 0; {} < {}; {}++)", indent(depth), index_name, index_name, count_name, index_name).ok();
+            writeln!(out, "{}{{", indent(depth)).ok();
+            let inner_expr = write_decode_value(out, ctx, inner, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}[{}] = {};", indent(depth + 1), array_name, index_name, inner_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(array_name)
+        }
+    }
+}
+
+fn next_temp_name(counter: &mut usize, prefix: &str) -> String {
+    let current = *counter;
+    *counter += 1;
+    format!("__{}_{}", prefix, current)
+}
+
+fn write_enum_declaration(out: &mut String, ctx: &CSharpContext<'_>, item: &Enum, depth: usize) -> Result<(), CodegenError> {
+    let type_name = sanitize_ident(&to_pascal_case(&item.name.value));
+    let type_keyword = ctx.options.type_keyword();
+    let partial_keyword = ctx.options.partial_keyword();
+
+    writeln!(
+        out,
+        "{}{} abstract {}{} {}",
+        indent(depth),
+        ctx.options.visibility,
+        partial_keyword,
+        type_keyword,
+        type_name
+    )
+    .ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    writeln!(out, "{}private {}()", indent(depth + 1), type_name).ok();
+    writeln!(out, "{}{{", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out).ok();
+
+    for variant in &item.variants {
+        let variant_name = sanitize_ident(&to_pascal_case(&variant.name.value));
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+
+        writeln!(
+            out,
+            "{}public sealed {}{} {} : {}",
+            indent(depth + 1),
+            partial_keyword,
+            type_keyword,
+            variant_name,
+            type_name
+        )
+        .ok();
+        writeln!(out, "{}{{", indent(depth + 1)).ok();
+        write_property_declarations(out, ctx, &variant_name, &fields, depth + 2)?;
+        writeln!(out, "{}}}", indent(depth + 1)).ok();
+        writeln!(out).ok();
+    }
+
+    write_export_import_fns(out, &type_name, depth + 1);
+    writeln!(out).ok();
+    write_enum_pack_fn(out, ctx, &type_name, item, depth + 1)?;
+    writeln!(out).ok();
+    write_enum_unpack_fn(out, ctx, &type_name, item, depth + 1)?;
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn resolve_variant_fields<'a>(index: &SchemaIndex, variant: &'a Variant) -> Result<Vec<CSharpField<'a>>, CodegenError> {
+    match &variant.kind {
+        VariantKind::Unit => Ok(Vec::new()),
+        VariantKind::Tuple(fields) => resolve_tuple_fields(index, fields),
+        VariantKind::Record(fields) => resolve_sorted_fields(index, fields),
+    }
+}
+
+fn write_enum_pack_fn(out: &mut String, ctx: &CSharpContext<'_>, type_name: &str, item: &Enum, depth: usize) -> Result<(), CodegenError> {
+    writeln!(out, "{}public static void Pack(RocketPackEncoder encoder, {} value)", indent(depth), type_name).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    writeln!(out, "{}encoder.WriteMap(1);", indent(depth + 1)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}switch (value)", indent(depth + 1)).ok();
+    writeln!(out, "{}{{", indent(depth + 1)).ok();
+
+    let mut temp_counter = 0usize;
+    for variant in &item.variants {
+        let variant_name = sanitize_ident(&to_pascal_case(&variant.name.value));
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        let binding = next_temp_name(&mut temp_counter, "variant");
+
+        if fields.is_empty() {
+            writeln!(out, "{}case {} _:", indent(depth + 2), variant_name).ok();
+        } else {
+            writeln!(out, "{}case {} {}:", indent(depth + 2), variant_name, binding).ok();
+        }
+        writeln!(out, "{}{{", indent(depth + 2)).ok();
+        writeln!(out, "{}encoder.WriteU64({});", indent(depth + 3), variant.tag.value).ok();
+        write_fields_pack_body(out, ctx, &variant_name, &binding, &fields, depth + 3, &mut temp_counter)?;
+        writeln!(out, "{}break;", indent(depth + 3)).ok();
+        writeln!(out, "{}}}", indent(depth + 2)).ok();
+    }
+
+    writeln!(out, "{}default:", indent(depth + 2)).ok();
+    writeln!(
+        out,
+        "{}throw new RocketPackEncoderException(\"unknown enum variant: {}\");",
+        indent(depth + 3),
+        item.name.value
+    )
+    .ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn write_enum_unpack_fn(out: &mut String, ctx: &CSharpContext<'_>, type_name: &str, item: &Enum, depth: usize) -> Result<(), CodegenError> {
+    let mut temp_counter = 0usize;
+    let count_name = next_temp_name(&mut temp_counter, "count");
+    let index_name = next_temp_name(&mut temp_counter, "i");
+
+    writeln!(out, "{}public static {} Unpack(RocketPackDecoder decoder)", indent(depth), type_name).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    writeln!(out, "{}{}? __result = null;", indent(depth + 1), type_name).ok();
+    writeln!(out, "{}var {} = decoder.ReadMap();", indent(depth + 1), count_name).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}for (var {} = 0; {} < {}; {}++)", indent(depth + 1), index_name, index_name, count_name, index_name).ok();
+    writeln!(out, "{}{{", indent(depth + 1)).ok();
+    writeln!(out, "{}switch (decoder.ReadU64())", indent(depth + 2)).ok();
+    writeln!(out, "{}{{", indent(depth + 2)).ok();
+
+    for variant in &item.variants {
+        let variant_name = sanitize_ident(&to_pascal_case(&variant.name.value));
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+
+        writeln!(out, "{}case {}:", indent(depth + 3), variant.tag.value).ok();
+        writeln!(out, "{}{{", indent(depth + 3)).ok();
+        write_fields_unpack_body(out, ctx, &fields, depth + 4, &mut temp_counter)?;
+        writeln!(out).ok();
+        write_object_initializer(out, ctx, &format!("__result = new {variant_name}"), &variant_name, &fields, depth + 4)?;
+        writeln!(out, "{}break;", indent(depth + 4)).ok();
+        writeln!(out, "{}}}", indent(depth + 3)).ok();
+    }
+
+    writeln!(out, "{}default:", indent(depth + 3)).ok();
+    writeln!(out, "{}{{", indent(depth + 3)).ok();
+    writeln!(out, "{}decoder.SkipField();", indent(depth + 4)).ok();
+    writeln!(out, "{}break;", indent(depth + 4)).ok();
+    writeln!(out, "{}}}", indent(depth + 3)).ok();
+    writeln!(out, "{}}}", indent(depth + 2)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out).ok();
+    writeln!(
+        out,
+        "{}return __result ?? throw new RocketPackDecoderException(\"missing enum variant\");",
+        indent(depth + 1)
+    )
+    .ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn write_constants_class(out: &mut String, ctx: &CSharpContext<'_>, class_name: &str, consts: &[&Const], depth: usize) -> Result<(), CodegenError> {
+    writeln!(
+        out,
+        "{}{} static {}class {}",
+        indent(depth),
+        ctx.options.visibility,
+        ctx.options.partial_keyword(),
+        class_name
+    )
+    .ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+
+    for item in consts {
+        let resolved = resolve_type(ctx.index, &item.ty.value)?;
+        // Only primitives and strings are compile-time constants in C#; everything else becomes `static readonly`.
+        let modifier = match &resolved {
+            ResolvedType::Builtin(BuiltinType::Bytes | BuiltinType::F16 | BuiltinType::U128 | BuiltinType::I128) => "static readonly",
+            ResolvedType::Builtin(_) => "const",
+            _ => "static readonly",
+        };
+
+        writeln!(
+            out,
+            "{}public {} {} {} = {};",
+            indent(depth + 1),
+            modifier,
+            render_type(ctx, &resolved)?,
+            sanitize_ident(&to_pascal_case(&item.name.value)),
+            render_typed_literal(ctx, &item.value.value, &resolved)?
+        )
+        .ok();
+    }
+
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn render_type(ctx: &CSharpContext<'_>, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    Ok(match resolved {
+        ResolvedType::Builtin(builtin) => render_builtin_type(ctx, builtin)?.to_string(),
+        ResolvedType::Named(named) => render_named_type(ctx, named),
+        ResolvedType::Option(inner) => format!("{}?", render_type(ctx, inner)?),
+        ResolvedType::Vec(inner) => format!("List<{}>", render_type(ctx, inner)?),
+        ResolvedType::Map(key, value) => format!("SortedDictionary<{}, {}>", render_type(ctx, key)?, render_type(ctx, value)?),
+        ResolvedType::Array(inner, _) => format!("{}[]", render_type(ctx, inner)?),
+    })
+}
+
+fn render_builtin_type(ctx: &CSharpContext<'_>, builtin: &BuiltinType) -> Result<&'static str, CodegenError> {
+    Ok(match builtin {
+        BuiltinType::Bool => "bool",
+        BuiltinType::U8 => "byte",
+        BuiltinType::U16 => "ushort",
+        BuiltinType::U32 => "uint",
+        BuiltinType::U64 => "ulong",
+        BuiltinType::I8 => "sbyte",
+        BuiltinType::I16 => "short",
+        BuiltinType::I32 => "int",
+        BuiltinType::I64 => "long",
+        BuiltinType::F32 => "float",
+        BuiltinType::F64 => "double",
+        BuiltinType::String => "string",
+        BuiltinType::Bytes => "byte[]",
+        BuiltinType::F16 if ctx.options.supports_half() => "Half",
+        BuiltinType::U128 if ctx.options.supports_int128() => "UInt128",
+        BuiltinType::I128 if ctx.options.supports_int128() => "Int128",
+        BuiltinType::F16 => return Err(CodegenError::Other("f16 requires target_framework net5.0 or later".to_string())),
+        BuiltinType::U128 | BuiltinType::I128 => return Err(CodegenError::Other("u128/i128 require target_framework net7.0 or later".to_string())),
+    })
+}
+
+fn render_named_type(ctx: &CSharpContext<'_>, named: &NamedType) -> String {
+    if named.origin.is_none() {
+        return sanitize_ident(&to_pascal_case(&named.path[0]));
+    }
+
+    // Imported types live under the same base namespace, one namespace segment per package segment.
+    let segments = ctx
+        .options
+        .base_namespace
+        .iter()
+        .cloned()
+        .chain(named.path.iter().map(|segment| sanitize_ident(&to_pascal_case(segment))))
+        .collect::<Vec<_>>();
+    format!("global::{}", segments.join("."))
+}
+
+fn render_typed_literal(ctx: &CSharpContext<'_>, literal: &Literal, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    let resolved = match resolved {
+        ResolvedType::Option(inner) => inner.as_ref(),
+        resolved => resolved,
+    };
+
+    Ok(match (literal, resolved) {
+        (Literal::Int(value), ResolvedType::Builtin(BuiltinType::F16)) => format!("(Half){value}f"),
+        (Literal::Float(_), ResolvedType::Builtin(BuiltinType::F16)) => format!("(Half){}f", render_float(literal)),
+        (Literal::Int(value), ResolvedType::Builtin(BuiltinType::F32)) => format!("{value}f"),
+        (Literal::Float(_), ResolvedType::Builtin(BuiltinType::F32)) => format!("{}f", render_float(literal)),
+        (Literal::Int(value), ResolvedType::Builtin(BuiltinType::F64)) => format!("{value}d"),
+        (Literal::Int(value), ResolvedType::Builtin(BuiltinType::U64)) => format!("{value}UL"),
+        (Literal::Int(value), ResolvedType::Builtin(BuiltinType::I64)) => format!("{value}L"),
+        (Literal::Int(value), ResolvedType::Builtin(builtin @ (BuiltinType::U128 | BuiltinType::I128))) => {
+            let type_name = render_builtin_type(ctx, builtin)?;
+            format!("new {type_name}({}UL, {}UL)", (*value >> 64) as u64, *value as u64)
+        }
+        (Literal::Bool(value), _) => value.to_string(),
+        (Literal::Int(value), _) => value.to_string(),
+        (Literal::Float(_), _) => render_float(literal),
+        (Literal::String(value), _) => render_string_literal(value),
+        (Literal::Bytes(bytes), _) => {
+            let rendered = bytes.iter().map(|byte| byte.to_string()).collect::<Vec<_>>().join(", ");
+            format!("new byte[] {{ {rendered} }}")
+        }
+    })
+}
+
+fn render_float(literal: &Literal) -> String {
+    let Literal::Float(value) = literal else {
+        return String::new();
+    };
+
+    let mut rendered = value.to_string();
+    if !rendered.contains('.') && !rendered.contains('e') && !rendered.contains('E') {
+        rendered.push_str(".0");
+    }
+    rendered
+}
+
+fn render_string_literal(value: &str) -> String {
+    let mut rendered = String::with_capacity(value.len() + 2);
+    rendered.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => rendered.push_str("\\\""),
+            '\\' => rendered.push_str("\\\\"),
+            '\n' => rendered.push_str("\\n"),
+            '\r' => rendered.push_str("\\r"),
+            '\t' => rendered.push_str("\\t"),
+            '\0' => rendered.push_str("\\0"),
+            c if c.is_control() => {
+                write!(&mut rendered, "\\u{:04x}", c as u32).ok();
+            }
+            c => rendered.push(c),
+        }
+    }
+    rendered.push('"');
+    rendered
+}
+
+fn sanitize_ident(value: &str) -> String {
+    match value {
+        "abstract" | "as" | "base" | "bool" | "break" | "byte" | "case" | "catch" | "char" | "checked" | "class" | "const" | "continue" | "decimal" | "default" | "delegate"
+        | "do" | "double" | "else" | "enum" | "event" | "explicit" | "extern" | "false" | "finally" | "fixed" | "float" | "for" | "foreach" | "goto" | "if" | "implicit" | "in"
+        | "int" | "interface" | "internal" | "is" | "lock" | "long" | "namespace" | "new" | "null" | "object" | "operator" | "out" | "override" | "params" | "private"
+        | "protected" | "public" | "readonly" | "ref" | "return" | "sbyte" | "sealed" | "short" | "sizeof" | "stackalloc" | "static" | "string" | "struct" | "switch" | "this"
+        | "throw" | "true" | "try" | "typeof" | "uint" | "ulong" | "unchecked" | "unsafe" | "ushort" | "using" | "virtual" | "void" | "volatile" | "while" => format!("@{value}"),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use super::*;
+    use crate::parser;
+
+    fn render(source: &str, options: &CSharpOptions) -> Result<String, CodegenError> {
+        let file = parser::parse_source("test.rpf", source)?;
+        let parsed_source = ParsedSource {
+            source: DiscoveredSource {
+                base_dir: PathBuf::from("."),
+                absolute_path: PathBuf::from("./sample.rpf"),
+                relative_path: PathBuf::from("sample.rpf"),
+            },
+            text: source.to_string(),
+            file,
+        };
+        let index = resolve_sources(std::slice::from_ref(&parsed_source))?.remove(0);
+        render_csharp_file(&parsed_source, &index, options)
+    }
+
+    #[test]
+    fn render_struct_test() -> TestResult {
+        let options = CSharpOptions {
+            partial_types: true,
+            base_namespace: vec!["Company".to_string(), "Project".to_string()],
+            ..CSharpOptions::default()
+        };
+        let rendered = render(
+            r#"
+version 1;
+package omnius::demo::v1;
+
+struct Message {
+  @2 names: Vec<string>;
+  @1 id: u32 = 7;
+  @3 note: Option<string>;
+}
+
+type Names = Vec<string>;
+const MAX_SIZE: u64 = 16;
+"#,
+            &options,
+        )?;
+        println!("{rendered}");
+
+        assert!(rendered.contains("namespace Company.Project.Omnius.Demo.V1"));
+        assert!(rendered.contains("    using Names = List<string>;"));
+        assert!(rendered.contains("    public sealed partial record Message"));
+        assert!(rendered.contains("        public uint Id { get; set; } = 7;"));
+        assert!(rendered.contains("        public List<string> Names { get; set; } = new List<string>();"));
+        assert!(rendered.contains("        public string? Note { get; set; }"));
+        assert!(rendered.contains("            if (value.Note is not null) __count_0++;"));
+        assert!(rendered.contains("                Id = id ?? 7,"));
+        assert!(rendered.contains("                Names = names ?? throw new RocketPackDecoderException(\"missing field: names\"),"));
+        assert!(rendered.contains("        public const ulong MaxSize = 16UL;"));
+
+        // Fields are written in tag order, like the Rust output.
+        let id_position = rendered.find("encoder.WriteU32(value.Id)").ok_or("missing id")?;
+        let names_position = rendered.find("encoder.WriteArray(value.Names.Count)").ok_or("missing names")?;
+        assert!(id_position < names_position);
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_enum_test() -> TestResult {
+        let options = CSharpOptions {
+            visibility: "internal".to_string(),
+            dotnet_version: None,
+            ..CSharpOptions::default()
+        };
+        let rendered = render(
+            r#"
+version 1;
+
+enum Shape {
+  @1 Empty;
+  @2 Line(length: f64, label: Option<string>);
+  @3 Rect {
+    @1 width: f64;
+    @2 height: f64;
+  };
+}
+"#,
+            &options,
+        )?;
+        println!("{rendered}");
+
+        assert!(!rendered.contains("namespace"));
+        assert!(rendered.contains("internal abstract class Shape"));
+        assert!(rendered.contains("    public sealed class Line : Shape"));
+        assert!(rendered.contains("            case Empty _:"));
+        assert!(rendered.contains("            case Line __variant_1:"));
+        assert!(rendered.contains("                if (__variant_1.Label is { } __value_3)"));
+        assert!(rendered.contains("                    __result = new Rect"));
+        assert!(rendered.contains("                    __result = new Empty();"));
+
+        let error = render("version 1;\nstruct Half { @1 value: f16; }", &options).err().ok_or("f16 must be rejected")?;
+        assert!(error.to_string().contains("net5.0"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_map_comparer_test() -> TestResult {
+        let rendered = render(
+            r#"
+version 1;
+
+struct Index {
+  @1 names: Map<string, u32>;
+  @2 blobs: Map<bytes, u32>;
+  @3 ids: Map<u32, string>;
+}
+"#,
+            &CSharpOptions::default(),
+        )?;
+        println!("{rendered}");
+
+        assert!(rendered.contains("using Omnius.RocketPack.Runtime;"));
+        assert!(rendered.contains("new SortedDictionary<string, uint>(RocketPackComparers.String)"));
+        assert!(rendered.contains("new SortedDictionary<byte[], uint>(RocketPackComparers.Bytes)"));
+        assert!(rendered.contains("new SortedDictionary<uint, string>()"));
+        assert!(!rendered.contains("StringComparer.Ordinal"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_runtime_test() {
+        let options = CSharpOptions {
+            runtime_namespace: "Company.Project.RocketPack".to_string(),
+            ..CSharpOptions::default()
+        };
+        let runtime = render_runtime(&options);
+
+        assert!(runtime.contains("namespace Company.Project.RocketPack\n{"));
+        assert!(!runtime.contains(DEFAULT_RUNTIME_NAMESPACE));
+        assert!(runtime.contains("public sealed class RocketPackEncoder"));
+        assert!(runtime.contains("public static class RocketPackComparers"));
+    }
+
+    #[test]
+    fn parse_dotnet_version_test() {
+        assert_eq!(parse_dotnet_version("net8.0"), Some(8));
+        assert_eq!(parse_dotnet_version("net10.0-windows"), Some(10));
+        assert_eq!(parse_dotnet_version("net48"), None);
+        assert_eq!(parse_dotnet_version("netstandard2.0"), None);
+    }
+}
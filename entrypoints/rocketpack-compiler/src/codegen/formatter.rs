@@ -0,0 +1,481 @@
+use std::path::{Path as FsPath, PathBuf};
+
+use super::{
+    common::{discover_project_sources, format_type, format_type_params, parse_sources, path_segments},
+    output::{OutputMode, OutputWriter},
+};
+use crate::{
+    error::CodegenError,
+    parser::{
+        ast::{Attribute, AttributeArgs, Field, File, Item, RpcType, Span, Spanned, Variant, VariantKind},
+        lexer::{self, SpannedToken, Token},
+    },
+};
+
+const INDENT: &str = "  ";
+
+// Formats every source of the project in `dir`, returning the files that were rewritten, or in check mode the files
+// that are not formatted.
+pub async fn format(dir: &FsPath, mode: OutputMode) -> Result<Vec<PathBuf>, CodegenError> {
+    let parsed_sources = parse_sources(&discover_project_sources(dir)?)?;
+    let mut writer = OutputWriter::new(mode);
+
+    for parsed_source in &parsed_sources {
+        let formatted = format_file(&parsed_source.text, &parsed_source.file);
+        writer.write(&parsed_source.source.absolute_path, &formatted)?;
+    }
+
+    Ok(writer.into_changed())
+}
+
+#[derive(Debug, Clone)]
+struct Comment {
+    span: Span,
+    // Whether the comment starts its own line, rather than trailing the code before it.
+    own_line: bool,
+    // Whether a blank line separates the comment from what follows it.
+    blank_after: bool,
+}
+
+// Prints the syntax tree in canonical layout. Comments are not part of the tree, so they are taken from the gaps
+// between tokens and placed before the element that follows them.
+pub(super) fn format_file(text: &str, file: &File) -> String {
+    let (tokens, _) = lexer::lex(text);
+    let mut printer = Printer {
+        text,
+        tokens: &tokens,
+        comments: collect_comments(text, &tokens),
+        next_comment: 0,
+        lines: Vec::new(),
+        blank_pending: false,
+    };
+
+    if let Some(version) = &file.version {
+        printer.comments_before(version.span.start, 0);
+        printer.line(0, format!("version {};", version.value));
+    }
+    if let Some(package) = &file.package {
+        printer.comments_before(package.span.start, 0);
+        printer.line(0, format!("package {};", path_segments(&package.value).join("::")));
+    }
+
+    printer.separate();
+    for use_decl in &file.uses {
+        printer.comments_before(use_decl.path.span.start, 0);
+        let path = path_segments(&use_decl.path.value).join("::");
+        match &use_decl.alias {
+            Some(alias) => printer.line(0, format!("use {path} as {};", alias.value)),
+            None => printer.line(0, format!("use {path};")),
+        }
+    }
+
+    for item in &file.items {
+        printer.separate();
+        printer.print_item(item);
+    }
+
+    printer.comments_before(text.len(), 0);
+
+    let mut formatted = printer.lines.join("\n");
+    formatted.push('\n');
+    formatted
+}
+
+struct Printer<'a> {
+    text: &'a str,
+    tokens: &'a [SpannedToken],
+    comments: Vec<Comment>,
+    next_comment: usize,
+    lines: Vec<String>,
+    blank_pending: bool,
+}
+
+impl Printer<'_> {
+    fn print_item(&mut self, item: &Item) {
+        match item {
+            Item::Struct(item) => {
+                self.print_attributes(&item.attributes, 0);
+                self.comments_before(item.name.span.start, 0);
+                self.line(0, format!("struct {}{} {{", item.name.value, format_type_params(&item.type_params)));
+                self.print_fields(&item.fields, 1);
+                self.comments_before(self.closing_brace(item.name.span.end), 1);
+                self.line(0, "}".to_string());
+            }
+            Item::Enum(item) => {
+                self.print_attributes(&item.attributes, 0);
+                self.comments_before(item.name.span.start, 0);
+                self.line(0, format!("enum {}{} {{", item.name.value, format_type_params(&item.type_params)));
+                let width = tag_width(item.variants.iter().map(|variant| variant.tag.value));
+                for variant in &item.variants {
+                    self.print_variant(variant, width);
+                }
+                self.comments_before(self.closing_brace(item.name.span.end), 1);
+                self.line(0, "}".to_string());
+            }
+            Item::TypeAlias(item) => {
+                self.print_attributes(&item.attributes, 0);
+                self.comments_before(item.name.span.start, 0);
+                self.line(0, format!("type {} = {};", item.name.value, format_type(&item.ty.value)));
+            }
+            Item::Const(item) => {
+                self.print_attributes(&item.attributes, 0);
+                self.comments_before(item.name.span.start, 0);
+                let value = self.source(&item.value);
+                self.line(0, format!("const {}: {} = {};", item.name.value, format_type(&item.ty.value), value));
+            }
+            Item::Service(item) => {
+                self.print_attributes(&item.attributes, 0);
+                self.comments_before(item.tag.span.start, 0);
+                self.line(0, format!("@{} service {} {{", item.tag.value, item.name.value));
+                let width = tag_width(item.rpcs.iter().map(|rpc| rpc.tag.value));
+                for rpc in &item.rpcs {
+                    self.print_attributes(&rpc.attributes, 1);
+                    self.comments_before(rpc.tag.span.start, 1);
+                    let line = format!(
+                        "@{:<width$} rpc {}({}) returns ({});",
+                        rpc.tag.value,
+                        rpc.name.value,
+                        format_rpc_type(&rpc.request),
+                        format_rpc_type(&rpc.response)
+                    );
+                    self.line(1, line);
+                }
+                self.comments_before(self.closing_brace(item.name.span.end), 1);
+                self.line(0, "}".to_string());
+            }
+        }
+    }
+
+    fn print_fields(&mut self, fields: &[Field], level: usize) {
+        let width = tag_width(fields.iter().map(|field| field.tag.value));
+        for field in fields {
+            self.print_attributes(&field.attributes, level);
+            self.comments_before(field.tag.span.start, level);
+            let mut line = format!("@{:<width$} {}: {}", field.tag.value, field.name.value, format_type(&field.ty.value));
+            if let Some(default) = &field.default {
+                line.push_str(" = ");
+                line.push_str(self.source(default));
+            }
+            line.push(';');
+            self.line(level, line);
+        }
+    }
+
+    fn print_variant(&mut self, variant: &Variant, width: usize) {
+        self.print_attributes(&variant.attributes, 1);
+        self.comments_before(variant.tag.span.start, 1);
+        let head = format!("@{:<width$} {}", variant.tag.value, variant.name.value);
+
+        match &variant.kind {
+            VariantKind::Unit => self.line(1, format!("{head};")),
+            VariantKind::Tuple(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, ty)| format!("{}: {}", name.value, format_type(&ty.value)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.line(1, format!("{head}({fields});"));
+            }
+            VariantKind::Record(fields) => {
+                self.line(1, format!("{head} {{"));
+                self.print_fields(fields, 2);
+                self.comments_before(self.closing_brace(variant.name.span.end), 2);
+                self.line(1, "};".to_string());
+            }
+        }
+    }
+
+    // Each attribute gets its own line above the element; doc comments are kept like any other comment.
+    fn print_attributes(&mut self, attributes: &[Spanned<Attribute>], level: usize) {
+        for attribute in attributes {
+            self.comments_before(attribute.span.start, level);
+            let name = &attribute.value.name.value;
+            let line = match &attribute.value.args {
+                AttributeArgs::Empty => format!("#[{name}]"),
+                AttributeArgs::Value(value) => format!("#[{name} = {}]", self.source(value)),
+                AttributeArgs::List(args) => {
+                    let args = args
+                        .iter()
+                        .map(|arg| format!("{} = {}", arg.name.value, self.source(&arg.value)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("#[{name}({args})]")
+                }
+            };
+            self.line(level, line);
+        }
+    }
+
+    fn line(&mut self, level: usize, content: String) {
+        if self.blank_pending && !self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.blank_pending = false;
+        self.lines.push(format!("{}{}", INDENT.repeat(level), content));
+    }
+
+    // Requests a blank line before whatever is printed next.
+    fn separate(&mut self) {
+        self.blank_pending = true;
+    }
+
+    fn comments_before(&mut self, offset: usize, level: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment).cloned() {
+            if comment.span.start >= offset {
+                break;
+            }
+            self.next_comment += 1;
+
+            let content = self.text[comment.span.start..comment.span.end].trim_end().to_string();
+            match self.lines.last_mut() {
+                Some(last) if !comment.own_line => {
+                    last.push(' ');
+                    last.push_str(&content);
+                }
+                _ => self.line(level, content),
+            }
+            if comment.blank_after {
+                self.separate();
+            }
+        }
+    }
+
+    // Returns the offset of the `}` closing the first block opened after `offset`.
+    fn closing_brace(&self, offset: usize) -> usize {
+        let mut depth = 0usize;
+        for token in self.tokens.iter().filter(|token| token.span.start >= offset) {
+            match token.token {
+                Token::LBrace => depth += 1,
+                Token::RBrace if depth <= 1 => return token.span.start,
+                Token::RBrace => depth -= 1,
+                _ => {}
+            }
+        }
+        self.text.len()
+    }
+
+    // Literals are copied as written, so hex numbers and escapes keep their spelling.
+    fn source<T>(&self, spanned: &Spanned<T>) -> &str {
+        &self.text[spanned.span.start..spanned.span.end]
+    }
+}
+
+fn format_rpc_type(rpc_type: &RpcType) -> String {
+    if rpc_type.stream {
+        format!("stream {}", format_type(&rpc_type.ty.value))
+    } else {
+        format_type(&rpc_type.ty.value)
+    }
+}
+
+fn tag_width(tags: impl Iterator<Item = u32>) -> usize {
+    tags.map(|tag| tag.to_string().len()).max().unwrap_or(1)
+}
+
+fn collect_comments(text: &str, tokens: &[SpannedToken]) -> Vec<Comment> {
+    let mut gaps = Vec::with_capacity(tokens.len() + 1);
+    let mut previous_end = 0usize;
+    for token in tokens {
+        gaps.push((previous_end, token.span.start));
+        previous_end = token.span.end;
+    }
+    gaps.push((previous_end, text.len()));
+
+    let mut comments = Vec::new();
+    for (start, end) in gaps {
+        let mut position = start;
+        while position < end {
+            let rest = &text[position..end];
+            let trimmed = rest.trim_start();
+            position += rest.len() - trimmed.len();
+
+            let length = if trimmed.starts_with("//") {
+                trimmed.find('\n').unwrap_or(trimmed.len())
+            } else if trimmed.starts_with("/*") {
+                trimmed.find("*/").map_or(trimmed.len(), |index| index + 2)
+            } else {
+                break;
+            };
+
+            let line_start = text[..position].rfind('\n').map_or(0, |index| index + 1);
+            let following = &text[position + length..end];
+            let next_line = following.trim_start_matches([' ', '\t', '\r']);
+            let blank_after = next_line.starts_with('\n') && next_line[1..].trim_start_matches([' ', '\t', '\r']).starts_with('\n');
+
+            comments.push(Comment {
+                span: Span {
+                    start: position,
+                    end: position + length,
+                },
+                own_line: text[line_start..position].trim().is_empty(),
+                blank_after,
+            });
+            position += length;
+        }
+    }
+
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+
+    use super::*;
+    use crate::parser;
+
+    fn format_text(text: &str) -> Result<String, CodegenError> {
+        let file = parser::parse_source("test.rpf", text)?;
+        Ok(format_file(text, &file))
+    }
+
+    #[test]
+    fn format_layout_test() -> TestResult {
+        let text = r#"version 1; package  omnius :: demo;
+use omnius::common::Id;   use omnius::common::Name as N;
+struct User{@1 id:Id;@10 name : Option< N > = "x";
+@2 tags: map<string,Vec<u8>>; @3 key: [u8;4];}
+type Ids=Vec<Id>;
+const LIMIT:u32=0x10;
+enum Event{@1 Created; @2 Renamed( from:string,to :string); @10 Moved{@1 x:i32; @2 y:i32;};}
+@3  service Users{@1 rpc Get(Id)returns(User); @10 rpc Watch( stream Id ) returns (stream Event);}
+"#;
+
+        assert_eq!(
+            format_text(text)?,
+            r#"version 1;
+package omnius::demo;
+
+use omnius::common::Id;
+use omnius::common::Name as N;
+
+struct User {
+  @1  id: Id;
+  @10 name: Option<N> = "x";
+  @2  tags: Map<string, Vec<u8>>;
+  @3  key: [u8; 4];
+}
+
+type Ids = Vec<Id>;
+
+const LIMIT: u32 = 0x10;
+
+enum Event {
+  @1  Created;
+  @2  Renamed(from: string, to: string);
+  @10 Moved {
+    @1 x: i32;
+    @2 y: i32;
+  };
+}
+
+@3 service Users {
+  @1  rpc Get(Id) returns (User);
+  @10 rpc Watch(stream Id) returns (stream Event);
+}
+"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_comments_test() -> TestResult {
+        let text = r#"// header
+
+package demo; // trailing package
+/* block
+   comment */
+struct User {
+    // leading field
+    @1 id: u64; // trailing field
+    // dangling
+}
+// footer
+"#;
+
+        let formatted = format_text(text)?;
+        assert_eq!(
+            formatted,
+            r#"// header
+
+package demo; // trailing package
+
+/* block
+   comment */
+struct User {
+  // leading field
+  @1 id: u64; // trailing field
+  // dangling
+}
+// footer
+"#
+        );
+        assert_eq!(format_text(&formatted)?, formatted);
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_generics_test() -> TestResult {
+        let text = r#"struct Page< T >{@1 items:Vec<T>; @2 next:Option<T>;}
+enum Either<L,R>{@1 Left(value:L); @2 Right(value:R);}
+struct Listing{@1 users:Page< Page<User> >; @2 choice:Either<User,Page<User>>;}
+"#;
+
+        assert_eq!(
+            format_text(text)?,
+            r#"struct Page<T> {
+  @1 items: Vec<T>;
+  @2 next: Option<T>;
+}
+
+enum Either<L, R> {
+  @1 Left(value: L);
+  @2 Right(value: R);
+}
+
+struct Listing {
+  @1 users: Page<Page<User>>;
+  @2 choice: Either<User, Page<User>>;
+}
+"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_attributes_test() -> TestResult {
+        let text = r#"/// A user.
+#[rust( derive="Eq, Hash" )] #[deprecated]
+struct User{
+/// The name.
+#[max_len=0x40] @1 name:string;}
+#[deprecated] @1 service Users{#[deprecated = "use Find"] @1 rpc Get(User)returns(User);}
+"#;
+
+        let formatted = format_text(text)?;
+        assert_eq!(
+            formatted,
+            r#"/// A user.
+#[rust(derive = "Eq, Hash")]
+#[deprecated]
+struct User {
+  /// The name.
+  #[max_len = 0x40]
+  @1 name: string;
+}
+
+#[deprecated]
+@1 service Users {
+  #[deprecated = "use Find"]
+  @1 rpc Get(User) returns (User);
+}
+"#
+        );
+        assert_eq!(format_text(&formatted)?, formatted);
+
+        Ok(())
+    }
+}
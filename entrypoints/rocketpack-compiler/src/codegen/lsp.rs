@@ -0,0 +1,351 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path as FsPath, PathBuf},
+};
+
+use tokio::sync::Mutex;
+use tower_lsp::{
+    Client, LanguageServer, LspService, Server,
+    jsonrpc::Result as RpcResult,
+    lsp_types::{
+        CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+        HoverProviderCapability, InitializeParams, InitializeResult, InitializedParams, LanguageString, Location, MarkedString, MessageType, OneOf, Position, Range,
+        ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    },
+};
+
+use super::{
+    analysis::{Analysis, CompletionKind, Document},
+    common::{DiscoveredSource, discover_project_sources},
+};
+use crate::{error::CodegenError, parser::ast::Span};
+
+const LANGUAGE_ID: &str = "rocketpack";
+
+// Serves the language server protocol over stdin and stdout until the client exits.
+pub async fn serve() -> Result<(), CodegenError> {
+    let (service, socket) = LspService::new(|client| Backend {
+        client,
+        state: Mutex::new(State::default()),
+    });
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket).serve(service).await;
+
+    Ok(())
+}
+
+struct Backend {
+    client: Client,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    root_dir: Option<PathBuf>,
+    // Texts of the documents open in the editor, which take precedence over the files on disk.
+    documents: BTreeMap<PathBuf, String>,
+    analysis: Option<Analysis>,
+    // Files that were sent diagnostics, so they can be cleared once their errors are fixed.
+    published: BTreeSet<PathBuf>,
+}
+
+impl Backend {
+    async fn refresh(&self) {
+        let mut state = self.state.lock().await;
+
+        let documents = collect_documents(state.root_dir.as_deref(), &state.documents);
+        let analysis = match Analysis::new(documents) {
+            Ok(analysis) => analysis,
+            Err(err) => {
+                drop(state);
+                self.client.log_message(MessageType::ERROR, err.to_string()).await;
+                return;
+            }
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut published = BTreeSet::new();
+        for (parsed_source, errors) in analysis.diagnostics() {
+            let path = &parsed_source.source.absolute_path;
+            if errors.is_empty() && !state.published.contains(path) {
+                continue;
+            }
+
+            let items = errors
+                .iter()
+                .map(|error| Diagnostic {
+                    range: span_to_range(&parsed_source.text, &error.span),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some(LANGUAGE_ID.to_string()),
+                    message: error.kind.to_string(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            if !items.is_empty() {
+                published.insert(path.clone());
+            }
+            diagnostics.push((path.clone(), items));
+        }
+        for path in state.published.difference(&published) {
+            if !diagnostics.iter().any(|(current, _)| current == path) {
+                diagnostics.push((path.clone(), Vec::new()));
+            }
+        }
+
+        state.published = published;
+        state.analysis = Some(analysis);
+        drop(state);
+
+        for (path, items) in diagnostics {
+            if let Ok(uri) = Url::from_file_path(&path) {
+                self.client.publish_diagnostics(uri, items, None).await;
+            }
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> RpcResult<InitializeResult> {
+        #[allow(deprecated)]
+        let root_uri = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| folder.uri.clone())
+            .or(params.root_uri);
+        self.state.lock().await.root_dir = root_uri.and_then(|uri| uri.to_file_path().ok());
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+                definition_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec!["@".to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: env!("CARGO_PKG_NAME").to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        self.refresh().await;
+    }
+
+    async fn shutdown(&self) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return;
+        };
+        self.state.lock().await.documents.insert(path, params.text_document.text);
+        self.refresh().await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return;
+        };
+        // Documents are synchronized in full, so the last change holds the whole text.
+        let Some(change) = params.content_changes.into_iter().last() else {
+            return;
+        };
+        self.state.lock().await.documents.insert(path, change.text);
+        self.refresh().await;
+    }
+
+    async fn did_save(&self, _: DidSaveTextDocumentParams) {
+        self.refresh().await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return;
+        };
+        self.state.lock().await.documents.remove(&path);
+        self.refresh().await;
+    }
+
+    async fn goto_definition(&self, params: GotoDefinitionParams) -> RpcResult<Option<GotoDefinitionResponse>> {
+        let position = params.text_document_position_params;
+        let Ok(path) = position.text_document.uri.to_file_path() else {
+            return Ok(None);
+        };
+
+        let state = self.state.lock().await;
+        let Some((analysis, offset)) = locate(&state, &path, position.position) else {
+            return Ok(None);
+        };
+        let Some((source, span)) = analysis.definition(&path, offset) else {
+            return Ok(None);
+        };
+        let (Some(target), Ok(uri)) = (analysis.source(&source.absolute_path), Url::from_file_path(&source.absolute_path)) else {
+            return Ok(None);
+        };
+
+        Ok(Some(GotoDefinitionResponse::Scalar(Location {
+            uri,
+            range: span_to_range(&target.text, &span),
+        })))
+    }
+
+    async fn hover(&self, params: HoverParams) -> RpcResult<Option<Hover>> {
+        let position = params.text_document_position_params;
+        let Ok(path) = position.text_document.uri.to_file_path() else {
+            return Ok(None);
+        };
+
+        let state = self.state.lock().await;
+        let Some((analysis, offset)) = locate(&state, &path, position.position) else {
+            return Ok(None);
+        };
+
+        Ok(analysis.hover(&path, offset).map(|declaration| Hover {
+            contents: HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: LANGUAGE_ID.to_string(),
+                value: declaration,
+            })),
+            range: None,
+        }))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> RpcResult<Option<CompletionResponse>> {
+        let position = params.text_document_position;
+        let Ok(path) = position.text_document.uri.to_file_path() else {
+            return Ok(None);
+        };
+
+        let state = self.state.lock().await;
+        let Some((analysis, offset)) = locate(&state, &path, position.position) else {
+            return Ok(None);
+        };
+
+        let items = analysis
+            .completions(&path, offset)
+            .into_iter()
+            .map(|completion| CompletionItem {
+                label: completion.label,
+                kind: Some(match completion.kind {
+                    CompletionKind::Builtin => CompletionItemKind::KEYWORD,
+                    CompletionKind::Struct => CompletionItemKind::STRUCT,
+                    CompletionKind::Enum => CompletionItemKind::ENUM,
+                    CompletionKind::Alias => CompletionItemKind::TYPE_PARAMETER,
+                    CompletionKind::Tag => CompletionItemKind::VALUE,
+                }),
+                detail: completion.detail,
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+}
+
+fn locate<'a>(state: &'a State, path: &FsPath, position: Position) -> Option<(&'a Analysis, usize)> {
+    let analysis = state.analysis.as_ref()?;
+    let parsed_source = analysis.source(path)?;
+    Some((analysis, position_to_offset(&parsed_source.text, position)))
+}
+
+// Sources of the workspace plus the open documents outside of it, each analyzed as its own file.
+fn collect_documents(root_dir: Option<&FsPath>, open_documents: &BTreeMap<PathBuf, String>) -> Vec<Document> {
+    let sources = root_dir.and_then(|root_dir| discover_project_sources(root_dir).ok()).unwrap_or_default();
+
+    let mut documents = Vec::with_capacity(sources.len());
+    for source in sources {
+        let text = match open_documents.get(&source.absolute_path) {
+            Some(text) => text.clone(),
+            None => match fs::read_to_string(&source.absolute_path) {
+                Ok(text) => text,
+                Err(_) => continue,
+            },
+        };
+        documents.push(Document { source, text });
+    }
+
+    for (path, text) in open_documents {
+        if documents.iter().any(|document| document.source.absolute_path == *path) {
+            continue;
+        }
+
+        let (Some(base_dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+            continue;
+        };
+        documents.push(Document {
+            source: DiscoveredSource {
+                base_dir: base_dir.to_path_buf(),
+                absolute_path: path.clone(),
+                relative_path: PathBuf::from(file_name),
+            },
+            text: text.clone(),
+        });
+    }
+
+    documents
+}
+
+// LSP positions count UTF-16 code units, while spans are byte offsets.
+fn offset_to_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let line_start = text[..offset].rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let line = text[..line_start].matches('\n').count();
+    let character = text[line_start..offset].chars().map(char::len_utf16).sum::<usize>();
+
+    Position::new(line as u32, character as u32)
+}
+
+fn position_to_offset(text: &str, position: Position) -> usize {
+    let line_start = if position.line == 0 {
+        0
+    } else {
+        match text.match_indices('\n').nth(position.line as usize - 1) {
+            Some((index, _)) => index + 1,
+            None => return text.len(),
+        }
+    };
+
+    let mut units = 0usize;
+    for (index, c) in text[line_start..].char_indices() {
+        if c == '\n' || units >= position.character as usize {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+
+    text.len()
+}
+
+fn span_to_range(text: &str, span: &Span) -> Range {
+    Range::new(offset_to_position(text, span.start), offset_to_position(text, span.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_conversion_test() {
+        let text = "struct A {\n  @1 name: string = \"日本\";\n}\n";
+
+        let offset = text.find("string").unwrap_or_default();
+        assert_eq!(offset_to_position(text, offset), Position::new(1, 11));
+        assert_eq!(position_to_offset(text, Position::new(1, 11)), offset);
+
+        // Each of the two characters is a single UTF-16 unit but three bytes.
+        let end = text.find("\";").unwrap_or_default();
+        assert_eq!(offset_to_position(text, end), Position::new(1, 23));
+        assert_eq!(position_to_offset(text, Position::new(1, 23)), end);
+
+        assert_eq!(position_to_offset(text, Position::new(1, 100)), text.find("\n}").unwrap_or_default());
+        assert_eq!(position_to_offset(text, Position::new(9, 0)), text.len());
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path as FsPath, PathBuf},
+};
+
+use crate::error::CodegenError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    // Writes every generated file whose contents differ from the file on disk.
+    Write,
+    // Leaves the disk untouched and only records the files that are out of date.
+    Check,
+}
+
+// Generated files are compared with the disk first, so unchanged outputs keep their timestamps and do not retrigger
+// downstream builds.
+#[derive(Debug)]
+pub(super) struct OutputWriter {
+    mode: OutputMode,
+    changed: BTreeSet<PathBuf>,
+}
+
+impl OutputWriter {
+    pub(super) fn new(mode: OutputMode) -> Self {
+        Self { mode, changed: BTreeSet::new() }
+    }
+
+    // Returns whether the file on disk differed from `contents`.
+    pub(super) fn write(&mut self, path: &FsPath, contents: &str) -> Result<bool, CodegenError> {
+        if fs::read(path).is_ok_and(|existing| existing == contents.as_bytes()) {
+            return Ok(false);
+        }
+
+        if self.mode == OutputMode::Write {
+            if let Some(parent_dir) = path.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            fs::write(path, contents)?;
+        }
+
+        self.changed.insert(path.to_path_buf());
+        Ok(true)
+    }
+
+    pub(super) fn into_changed(self) -> Vec<PathBuf> {
+        self.changed.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+
+    use super::*;
+
+    #[test]
+    fn write_changed_files_only_test() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("out").join("first.rs");
+
+        let mut writer = OutputWriter::new(OutputMode::Write);
+        assert!(writer.write(&path, "a")?);
+        assert!(!writer.write(&path, "a")?);
+        assert_eq!(fs::read_to_string(&path)?, "a");
+        assert_eq!(writer.into_changed(), vec![path.clone()]);
+
+        let mut writer = OutputWriter::new(OutputMode::Write);
+        assert!(!writer.write(&path, "a")?);
+        assert!(writer.into_changed().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_out_of_date_files_test() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let current = dir.path().join("current.rs");
+        let stale = dir.path().join("stale.rs");
+        let missing = dir.path().join("missing.rs");
+        fs::write(&current, "a")?;
+        fs::write(&stale, "a")?;
+
+        let mut writer = OutputWriter::new(OutputMode::Check);
+        assert!(!writer.write(&current, "a")?);
+        assert!(writer.write(&stale, "b")?);
+        assert!(writer.write(&missing, "b")?);
+
+        assert_eq!(fs::read_to_string(&stale)?, "a");
+        assert!(!missing.exists());
+        assert_eq!(writer.into_changed(), vec![missing, stale]);
+
+        Ok(())
+    }
+}
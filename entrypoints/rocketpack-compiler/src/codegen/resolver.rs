@@ -0,0 +1,548 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    common::{
+        ExternalType, NamedType, NamedTypeKind, ParsedSource, ResolvedType, SchemaIndex, build_schema_index, builtin_type, normalize_path, path_segments, path_span, resolve_type,
+        type_param_names,
+    },
+    validator::{validate_file, validate_types},
+};
+use crate::{
+    error::{CodegenError, ParseError, ParseErrorBundle, ParseErrorKind},
+    parser::ast::{Item, Path as AstPath, Span, Type, VariantKind},
+};
+
+// Each source depends on the sources it imports from, keyed by source index with the span of the first reference.
+type Dependencies = BTreeMap<usize, Span>;
+
+// Types of other files are only visible through a `use` declaration or a fully qualified path; sharing a package does not
+// make them visible. Imports must not form cycles, so every source can be indexed after the sources it depends on.
+// Every source is validated before anything is reported, so one run lists all errors of all files.
+pub(super) fn resolve_sources(parsed_sources: &[ParsedSource]) -> Result<Vec<SchemaIndex>, CodegenError> {
+    let SourceAnalysis { indexes, errors } = analyze_sources(parsed_sources)?;
+    if errors.iter().any(|errors| !errors.is_empty()) {
+        return Err(validation_error(parsed_sources, errors));
+    }
+
+    indexes.ok_or(CodegenError::Unexpected("source indexes are not built"))
+}
+
+pub(super) struct SourceAnalysis {
+    // Missing when imports form a cycle, since the sources cannot be indexed in dependency order.
+    pub(super) indexes: Option<Vec<SchemaIndex>>,
+    // The errors of every source, in the order of the given sources.
+    pub(super) errors: Vec<Vec<ParseError>>,
+}
+
+// Like `resolve_sources`, but keeps the indexes next to the errors for tools that work on schemas being edited.
+pub(super) fn analyze_sources(parsed_sources: &[ParsedSource]) -> Result<SourceAnalysis, CodegenError> {
+    let mut errors = parsed_sources.iter().map(|_| Vec::<ParseError>::new()).collect::<Vec<_>>();
+
+    let definitions = collect_definitions(parsed_sources, &mut errors);
+    let dependencies = parsed_sources
+        .iter()
+        .enumerate()
+        .map(|(source_index, parsed_source)| check_source(parsed_source, source_index, &definitions, &mut errors[source_index]))
+        .collect::<Vec<_>>();
+    for (parsed_source, errors) in parsed_sources.iter().zip(errors.iter_mut()) {
+        errors.extend(validate_file(&parsed_source.file));
+    }
+
+    let order = match sort_sources(parsed_sources, &dependencies) {
+        Ok(order) => order,
+        Err((source_index, error)) => {
+            errors[source_index].push(error);
+            return Ok(SourceAnalysis { indexes: None, errors });
+        }
+    };
+
+    let indexes = build_indexes(parsed_sources, &dependencies, &order)?;
+    for ((parsed_source, index), errors) in parsed_sources.iter().zip(&indexes).zip(errors.iter_mut()) {
+        errors.extend(validate_types(&parsed_source.file, index));
+    }
+
+    Ok(SourceAnalysis { indexes: Some(indexes), errors })
+}
+
+// Maps the package path plus name of every struct, enum and alias to the index of its source.
+fn collect_definitions(parsed_sources: &[ParsedSource], errors: &mut [Vec<ParseError>]) -> BTreeMap<Vec<String>, usize> {
+    let mut definitions = BTreeMap::<Vec<String>, usize>::new();
+
+    for (source_index, parsed_source) in parsed_sources.iter().enumerate() {
+        let package = parsed_source.file.package.as_ref().map(|package| path_segments(&package.value)).unwrap_or_default();
+
+        for item in &parsed_source.file.items {
+            let name = match item {
+                Item::Struct(item) => &item.name,
+                Item::Enum(item) => &item.name,
+                Item::TypeAlias(item) => &item.name,
+                Item::Const(_) | Item::Service(_) => continue,
+            };
+
+            let path = package.iter().cloned().chain([name.value.clone()]).collect::<Vec<_>>();
+            match definitions.get(&path) {
+                Some(existing) if *existing != source_index => {
+                    let message = format!(
+                        "type `{}` is already defined in {}",
+                        path.join("::"),
+                        normalize_path(&parsed_sources[*existing].source.relative_path)
+                    );
+                    errors[source_index].push(ParseError::new(ParseErrorKind::Other(message), name.span.start, name.span.end));
+                }
+                Some(_) => {}
+                None => {
+                    definitions.insert(path, source_index);
+                }
+            }
+        }
+    }
+
+    definitions
+}
+
+fn check_source(parsed_source: &ParsedSource, source_index: usize, definitions: &BTreeMap<Vec<String>, usize>, errors: &mut Vec<ParseError>) -> Dependencies {
+    let file = &parsed_source.file;
+    let package = file.package.as_ref().map(|package| path_segments(&package.value)).unwrap_or_default();
+    let local_names = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some(item.name.value.clone()),
+            Item::Enum(item) => Some(item.name.value.clone()),
+            Item::TypeAlias(item) => Some(item.name.value.clone()),
+            Item::Const(_) | Item::Service(_) => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    let mut dependencies = Dependencies::new();
+    let mut bindings = BTreeSet::<String>::new();
+
+    for use_decl in &file.uses {
+        let path = path_segments(&use_decl.path.value);
+        let span = path_span(&use_decl.path.value);
+
+        let Some(&defining_index) = definitions.get(&path) else {
+            errors.push(ParseError::new(ParseErrorKind::UnknownImport(path.join("::")), span.start, span.end));
+            continue;
+        };
+
+        if defining_index == source_index {
+            let message = format!("`{}` is defined in this file and cannot be imported", path.join("::"));
+            errors.push(ParseError::new(ParseErrorKind::Other(message), span.start, span.end));
+            continue;
+        }
+
+        let (binding, binding_span) = match &use_decl.alias {
+            Some(alias) => (alias.value.clone(), alias.span.clone()),
+            None => (path.last().cloned().unwrap_or_default(), span.clone()),
+        };
+        if local_names.contains(&binding) {
+            let message = format!("import `{binding}` conflicts with a type of the same name");
+            errors.push(ParseError::new(ParseErrorKind::Other(message), binding_span.start, binding_span.end));
+            continue;
+        }
+        if !bindings.insert(binding.clone()) {
+            let message = format!("import `{binding}` is defined multiple times");
+            errors.push(ParseError::new(ParseErrorKind::Other(message), binding_span.start, binding_span.end));
+            continue;
+        }
+
+        dependencies.entry(defining_index).or_insert(span);
+    }
+
+    let mut checker = ReferenceChecker {
+        source_index,
+        package: &package,
+        local_names: &local_names,
+        bindings: &bindings,
+        definitions,
+        dependencies: &mut dependencies,
+        errors,
+        params: Vec::new(),
+    };
+
+    for item in &file.items {
+        checker.params = match item {
+            Item::Struct(item) => type_param_names(&item.type_params),
+            Item::Enum(item) => type_param_names(&item.type_params),
+            _ => Vec::new(),
+        };
+
+        match item {
+            Item::Struct(item) => item.fields.iter().for_each(|field| checker.check_type(&field.ty.value)),
+            Item::Enum(item) => {
+                for variant in &item.variants {
+                    match &variant.kind {
+                        VariantKind::Unit => {}
+                        VariantKind::Tuple(fields) => fields.iter().for_each(|(_, ty)| checker.check_type(&ty.value)),
+                        VariantKind::Record(fields) => fields.iter().for_each(|field| checker.check_type(&field.ty.value)),
+                    }
+                }
+            }
+            Item::TypeAlias(item) => checker.check_type(&item.ty.value),
+            Item::Const(item) => checker.check_type(&item.ty.value),
+            Item::Service(item) => {
+                for rpc in &item.rpcs {
+                    checker.check_type(&rpc.request.ty.value);
+                    checker.check_type(&rpc.response.ty.value);
+                }
+            }
+        }
+    }
+
+    dependencies
+}
+
+struct ReferenceChecker<'a> {
+    source_index: usize,
+    package: &'a [String],
+    local_names: &'a BTreeSet<String>,
+    bindings: &'a BTreeSet<String>,
+    definitions: &'a BTreeMap<Vec<String>, usize>,
+    dependencies: &'a mut Dependencies,
+    errors: &'a mut Vec<ParseError>,
+    // Type parameters of the item being checked.
+    params: Vec<String>,
+}
+
+impl ReferenceChecker<'_> {
+    fn check_type(&mut self, ty: &Type) {
+        match ty {
+            Type::Path(path) => self.check_path(path),
+            Type::Generic(path, args) => {
+                self.check_path(path);
+                args.iter().for_each(|arg| self.check_type(arg));
+            }
+            Type::Option(inner) | Type::Vec(inner) | Type::Array(inner, _) => self.check_type(inner),
+            Type::Map(key, value) => {
+                self.check_type(key);
+                self.check_type(value);
+            }
+        }
+    }
+
+    fn check_path(&mut self, path: &AstPath) {
+        if builtin_type(path).is_some() {
+            return;
+        }
+
+        let segments = path_segments(path);
+        let found = match segments.as_slice() {
+            [name] => self.params.contains(name) || self.local_names.contains(name) || self.bindings.contains(name),
+            [package @ .., name] if package == self.package && self.local_names.contains(name) => true,
+            _ => match self.definitions.get(&segments) {
+                Some(&defining_index) => {
+                    if defining_index != self.source_index {
+                        self.dependencies.entry(defining_index).or_insert_with(|| path_span(path));
+                    }
+                    true
+                }
+                None => false,
+            },
+        };
+
+        if !found {
+            let span = path_span(path);
+            self.errors.push(ParseError::new(ParseErrorKind::UnknownType(segments.join("::")), span.start, span.end));
+        }
+    }
+}
+
+fn validation_error(parsed_sources: &[ParsedSource], errors: Vec<Vec<ParseError>>) -> CodegenError {
+    let bundles = parsed_sources
+        .iter()
+        .zip(errors)
+        .filter(|(_, errors)| !errors.is_empty())
+        .map(|(parsed_source, mut errors)| {
+            errors.sort_by_key(|error| (error.span.start, error.span.end));
+            ParseErrorBundle::new(&parsed_source.source.absolute_path, parsed_source.text.clone(), errors)
+        })
+        .collect();
+    CodegenError::Validation(bundles)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    Visiting,
+    Visited,
+}
+
+// Returns the source indexes with every source placed after the sources it imports from, or the first import cycle.
+fn sort_sources(parsed_sources: &[ParsedSource], dependencies: &[Dependencies]) -> Result<Vec<usize>, (usize, ParseError)> {
+    let mut states = BTreeMap::<usize, VisitState>::new();
+    let mut stack = Vec::<usize>::new();
+    let mut order = Vec::with_capacity(parsed_sources.len());
+
+    for source_index in 0..parsed_sources.len() {
+        visit_source(parsed_sources, dependencies, source_index, &mut states, &mut stack, &mut order)?;
+    }
+
+    Ok(order)
+}
+
+fn visit_source(
+    parsed_sources: &[ParsedSource],
+    dependencies: &[Dependencies],
+    source_index: usize,
+    states: &mut BTreeMap<usize, VisitState>,
+    stack: &mut Vec<usize>,
+    order: &mut Vec<usize>,
+) -> Result<(), (usize, ParseError)> {
+    match states.get(&source_index) {
+        Some(VisitState::Visited) => return Ok(()),
+        Some(VisitState::Visiting) => {
+            let position = stack.iter().position(|current| *current == source_index).unwrap_or_default();
+            let cycle = &stack[position..];
+            let rendered = cycle
+                .iter()
+                .chain([&source_index])
+                .map(|current| normalize_path(&parsed_sources[*current].source.relative_path))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            // Reported on the import that leaves the first source of the cycle.
+            let next_index = cycle.get(1).copied().unwrap_or(source_index);
+            let span = dependencies[source_index].get(&next_index).cloned().unwrap_or(Span { start: 0, end: 0 });
+            return Err((source_index, ParseError::new(ParseErrorKind::ImportCycle(rendered), span.start, span.end)));
+        }
+        None => {}
+    }
+
+    states.insert(source_index, VisitState::Visiting);
+    stack.push(source_index);
+    for dependency_index in dependencies[source_index].keys() {
+        visit_source(parsed_sources, dependencies, *dependency_index, states, stack, order)?;
+    }
+    stack.pop();
+    states.insert(source_index, VisitState::Visited);
+    order.push(source_index);
+
+    Ok(())
+}
+
+fn build_indexes(parsed_sources: &[ParsedSource], dependencies: &[Dependencies], order: &[usize]) -> Result<Vec<SchemaIndex>, CodegenError> {
+    let mut indexes = parsed_sources.iter().map(|_| None::<SchemaIndex>).collect::<Vec<_>>();
+
+    for source_index in order {
+        let mut index = build_schema_index(&parsed_sources[*source_index].file);
+
+        for dependency_index in dependencies[*source_index].keys() {
+            let dependency = &parsed_sources[*dependency_index];
+            let Some(dependency_schema) = indexes[*dependency_index].as_ref() else {
+                return Err(CodegenError::Unexpected("dependency index is not built"));
+            };
+
+            for item in &dependency.file.items {
+                let (name, resolved, type_params) = match item {
+                    Item::Struct(item) => (
+                        &item.name.value,
+                        local_named_type(&item.name.value, NamedTypeKind::Struct),
+                        type_param_names(&item.type_params),
+                    ),
+                    Item::Enum(item) => (
+                        &item.name.value,
+                        local_named_type(&item.name.value, NamedTypeKind::Enum),
+                        type_param_names(&item.type_params),
+                    ),
+                    // Aliases that fail to resolve are reported by the validation of their own file.
+                    Item::TypeAlias(item) => match resolve_type(dependency_schema, &item.ty.value) {
+                        Ok(resolved) => (&item.name.value, resolved, Vec::new()),
+                        Err(_) => continue,
+                    },
+                    Item::Const(_) | Item::Service(_) => continue,
+                };
+
+                let path = dependency_schema.package.iter().cloned().chain([name.clone()]).collect::<Vec<_>>();
+                let external = ExternalType {
+                    origin: dependency.source.clone(),
+                    resolved: rebase_local_types(resolved, dependency_schema, dependency),
+                    type_params,
+                };
+                index.external_types.insert(path, external);
+            }
+        }
+
+        indexes[*source_index] = Some(index);
+    }
+
+    indexes
+        .into_iter()
+        .map(|index| index.ok_or(CodegenError::Unexpected("source index is not built")))
+        .collect()
+}
+
+fn local_named_type(name: &str, kind: NamedTypeKind) -> ResolvedType {
+    ResolvedType::Named(NamedType {
+        path: vec![name.to_string()],
+        kind,
+        origin: None,
+        args: Vec::new(),
+    })
+}
+
+// Local types of the imported file become qualified types of another file once seen from the importing side.
+fn rebase_local_types(resolved: ResolvedType, index: &SchemaIndex, dependency: &ParsedSource) -> ResolvedType {
+    match resolved {
+        ResolvedType::Named(named) if named.origin.is_none() => ResolvedType::Named(NamedType {
+            path: index.package.iter().cloned().chain(named.path).collect(),
+            kind: named.kind,
+            origin: Some(dependency.source.clone()),
+            args: rebase_type_args(named.args, index, dependency),
+        }),
+        ResolvedType::Named(named) => ResolvedType::Named(NamedType {
+            args: rebase_type_args(named.args, index, dependency),
+            ..named
+        }),
+        ResolvedType::Option(inner) => ResolvedType::Option(Box::new(rebase_local_types(*inner, index, dependency))),
+        ResolvedType::Vec(inner) => ResolvedType::Vec(Box::new(rebase_local_types(*inner, index, dependency))),
+        ResolvedType::Map(key, value) => ResolvedType::Map(
+            Box::new(rebase_local_types(*key, index, dependency)),
+            Box::new(rebase_local_types(*value, index, dependency)),
+        ),
+        ResolvedType::Array(inner, len) => ResolvedType::Array(Box::new(rebase_local_types(*inner, index, dependency)), len),
+        resolved => resolved,
+    }
+}
+
+fn rebase_type_args(args: Vec<ResolvedType>, index: &SchemaIndex, dependency: &ParsedSource) -> Vec<ResolvedType> {
+    args.into_iter().map(|arg| rebase_local_types(arg, index, dependency)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use super::*;
+    use crate::{codegen::common::DiscoveredSource, parser};
+
+    fn parse(relative_path: &str, text: &str) -> Result<ParsedSource, CodegenError> {
+        let source = DiscoveredSource {
+            base_dir: PathBuf::from("/rpfs"),
+            absolute_path: PathBuf::from("/rpfs").join(relative_path),
+            relative_path: PathBuf::from(relative_path),
+        };
+        let file = parser::parse_source(&source.absolute_path, text)?;
+        Ok(ParsedSource {
+            source,
+            text: text.to_string(),
+            file,
+        })
+    }
+
+    #[test]
+    fn resolve_imported_types_test() -> TestResult {
+        let common = parse(
+            "common.rpf",
+            r#"
+package omnius::common;
+
+struct Id {
+  @1 value: u64;
+}
+
+type Ids = Vec<Id>;
+"#,
+        )?;
+        let user = parse(
+            "user.rpf",
+            r#"
+package omnius::user;
+use omnius::common::Id as UserId;
+
+struct User {
+  @1 id: UserId;
+  @2 friends: omnius::common::Ids;
+}
+"#,
+        )?;
+
+        let indexes = resolve_sources(&[user.clone(), common.clone()])?;
+        let id = ResolvedType::Named(NamedType {
+            path: vec!["omnius".to_string(), "common".to_string(), "Id".to_string()],
+            kind: NamedTypeKind::Struct,
+            origin: Some(common.source.clone()),
+            args: Vec::new(),
+        });
+
+        let Item::Struct(item) = &user.file.items[0] else {
+            return Err("expected struct".into());
+        };
+        assert_eq!(resolve_type(&indexes[0], &item.fields[0].ty.value)?, id);
+        assert_eq!(resolve_type(&indexes[0], &item.fields[1].ty.value)?, ResolvedType::Vec(Box::new(id)));
+        assert!(indexes[1].external_types.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_unknown_import_test() -> TestResult {
+        let user = parse(
+            "user.rpf",
+            r#"package omnius::user;
+use omnius::common::Id;
+"#,
+        )?;
+
+        let Err(CodegenError::Validation(bundles)) = resolve_sources(&[user]) else {
+            return Err("expected validation error".into());
+        };
+        let err = &bundles[0];
+        assert_eq!(
+            err.to_string(),
+            "/rpfs/user.rpf:2:5: unknown import: omnius::common::Id\n  use omnius::common::Id;\n      ^~~~~~~~~~~~~~~~~~\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_unknown_type_test() -> TestResult {
+        let user = parse(
+            "user.rpf",
+            r#"package omnius::user;
+struct User {
+  @1 id: Option<omnius::common::Id>;
+}
+"#,
+        )?;
+
+        let Err(CodegenError::Validation(bundles)) = resolve_sources(&[user]) else {
+            return Err("expected validation error".into());
+        };
+        let err = &bundles[0];
+        assert!(err.to_string().starts_with("/rpfs/user.rpf:3:17: unknown type: omnius::common::Id\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_import_cycle_test() -> TestResult {
+        let a = parse(
+            "a.rpf",
+            r#"package a;
+use b::B;
+struct A {
+  @1 b: B;
+}
+"#,
+        )?;
+        let b = parse(
+            "b.rpf",
+            r#"package b;
+struct B {
+  @1 a: a::A;
+}
+"#,
+        )?;
+
+        let Err(CodegenError::Validation(bundles)) = resolve_sources(&[a, b]) else {
+            return Err("expected validation error".into());
+        };
+        let err = &bundles[0];
+        assert!(err.to_string().starts_with("/rpfs/a.rpf:2:5: import cycle: a.rpf -> b.rpf -> a.rpf\n"));
+
+        Ok(())
+    }
+}
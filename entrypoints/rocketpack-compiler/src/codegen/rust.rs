@@ -1,147 +1,94 @@
-use std::{
-    collections::BTreeMap,
-    fmt::Write as _,
-    fs,
-    path::{Path as FsPath, PathBuf},
-};
+use std::{fmt::Write as _, path::Path as FsPath};
 
-use serde_yaml_ng::{Mapping, Value};
 use tracing::info;
 
+use super::{
+    common::{
+        BuiltinType, DiscoveredSource, NamedType, NamedTypeKind, ParsedSource, ResolvedType, SchemaIndex, builtin_type, canonical_path, find_attribute, find_target,
+        format_literal, indent, normalize_path, option_bool, path_segments, qualified_path, resolve_output_path, resolve_type, resolve_type_with_params, to_snake_case,
+        type_param_names,
+    },
+    output::OutputWriter,
+    resolver::resolve_sources,
+};
 use crate::{
-    config::{GeneratorConfig, GeneratorTargetConfig, SourceConfig},
+    config::{GeneratorConfig, GeneratorTargetConfig},
     error::CodegenError,
-    parser::{
-        self,
-        ast::{Const, Enum, Field, File, Item, Literal, Path as AstPath, Struct, Type, Use, VariantKind},
-    },
+    parser::ast::{Attribute, AttributeArgs, Const, Enum, Field, Item, Literal, Path as AstPath, Rpc, Service, Spanned, Struct, Type, VariantKind},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct DiscoveredSource {
-    base_dir: PathBuf,
-    absolute_path: PathBuf,
-    relative_path: PathBuf,
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub(super) struct GeneratedRustFile {
+    pub(super) source: DiscoveredSource,
+    pub(super) contents: String,
 }
 
-#[derive(Debug, Clone)]
-struct ParsedSource {
-    source: DiscoveredSource,
-    file: File,
+// `emit_schema: true` adds a `RocketPackSchema` impl to every struct and enum, plus a `SCHEMA_REGISTRY` per package.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct RustOptions {
+    pub(super) emit_schema: bool,
 }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct GeneratedRustFile {
-    source: DiscoveredSource,
-    contents: String,
-}
-
-#[derive(Debug, Clone, Default)]
-struct SchemaIndex {
-    package: Vec<String>,
-    uses: Vec<UseBinding>,
-    imported_paths: BTreeMap<String, Vec<String>>,
-    type_aliases: BTreeMap<String, Type>,
-    user_types: BTreeMap<String, NamedTypeKind>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct UseBinding {
-    path: Vec<String>,
-    alias: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum NamedTypeKind {
-    Struct,
-    Enum,
-    Alias,
-    External,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum BuiltinType {
-    Bool,
-    U8,
-    U16,
-    U32,
-    U64,
-    U128,
-    I8,
-    I16,
-    I32,
-    I64,
-    I128,
-    F32,
-    F64,
-    String,
-    Bytes,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct NamedType {
-    rust_path: Vec<String>,
-    kind: NamedTypeKind,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum ResolvedType {
-    Builtin(BuiltinType),
-    Named(NamedType),
-    Option(Box<ResolvedType>),
-    Vec(Box<ResolvedType>),
-    Map(Box<ResolvedType>, Box<ResolvedType>),
-    Array(Box<ResolvedType>, u64),
-}
-
-pub async fn generate(root_dir: &FsPath, sources: &[SourceConfig], conf: &GeneratorConfig) -> Result<(), CodegenError> {
-    let discovered_sources = discover_source_files(root_dir, sources)?;
-    let parsed_sources = parse_sources(&discovered_sources)?;
-    let generated_files = render_sources(&parsed_sources)?;
-    let written_count = write_generated_files(root_dir, conf, &generated_files)?;
+impl RustOptions {
+    fn load(conf: &GeneratorConfig, target: Option<&GeneratorTargetConfig>) -> Result<Self, CodegenError> {
+        Ok(Self {
+            emit_schema: option_bool(conf, target, "emit_schema")?.unwrap_or(false),
+        })
+    }
+}
+
+pub(super) async fn generate(root_dir: &FsPath, parsed_sources: &[ParsedSource], conf: &GeneratorConfig, writer: &mut OutputWriter) -> Result<(), CodegenError> {
+    let generated_files = render_sources(parsed_sources, |source| RustOptions::load(conf, find_target(conf, source)))?;
+    let changed_count = write_generated_files(root_dir, conf, &generated_files, writer)?;
 
     info!(
         generator_id = %conf.id,
         plugin = %conf.plugin,
         source_count = parsed_sources.len(),
         rendered_count = generated_files.len(),
-        written_count,
+        changed_count,
         "generated rocketpack rust files"
     );
 
     Ok(())
 }
 
-fn parse_sources(sources: &[DiscoveredSource]) -> Result<Vec<ParsedSource>, CodegenError> {
-    let mut parsed_sources = Vec::with_capacity(sources.len());
-
-    for source in sources {
-        let file = parser::parse(&source.absolute_path)?;
-        parsed_sources.push(ParsedSource { source: source.clone(), file });
-    }
-
-    Ok(parsed_sources)
-}
-
-fn render_sources(parsed_sources: &[ParsedSource]) -> Result<Vec<GeneratedRustFile>, CodegenError> {
+pub(super) fn render_sources(
+    parsed_sources: &[ParsedSource],
+    load_options: impl Fn(&DiscoveredSource) -> Result<RustOptions, CodegenError>,
+) -> Result<Vec<GeneratedRustFile>, CodegenError> {
+    let indexes = resolve_sources(parsed_sources)?;
+    let options = parsed_sources
+        .iter()
+        .map(|parsed_source| load_options(&parsed_source.source))
+        .collect::<Result<Vec<_>, _>>()?;
     let mut generated_files = Vec::with_capacity(parsed_sources.len());
 
-    for parsed_source in parsed_sources {
+    for ((parsed_source, index), file_options) in parsed_sources.iter().zip(&indexes).zip(&options) {
+        // The schema registry covers every file of the package that emits schemas.
+        let package_sources = parsed_sources
+            .iter()
+            .zip(&indexes)
+            .zip(&options)
+            .filter(|((_, other_index), other_options)| other_options.emit_schema && other_index.package == index.package)
+            .map(|((other_source, _), _)| other_source)
+            .collect::<Vec<_>>();
+
         generated_files.push(GeneratedRustFile {
             source: parsed_source.source.clone(),
-            contents: render_rust_file(parsed_source)?,
+            contents: render_rust_file(parsed_source, index, file_options, &package_sources)?,
         });
     }
 
     Ok(generated_files)
 }
 
-fn write_generated_files(root_dir: &FsPath, conf: &GeneratorConfig, generated_files: &[GeneratedRustFile]) -> Result<usize, CodegenError> {
-    let mut written_count = 0usize;
+fn write_generated_files(root_dir: &FsPath, conf: &GeneratorConfig, generated_files: &[GeneratedRustFile], writer: &mut OutputWriter) -> Result<usize, CodegenError> {
+    let mut changed_count = 0usize;
 
     for generated_file in generated_files {
-        let Some(output_path) = resolve_output_path(root_dir, conf, &generated_file.source)? else {
+        let Some(output_path) = resolve_output_path(root_dir, conf, &generated_file.source, ".rs")? else {
             info!(
                 generator_id = %conf.id,
                 source = normalize_path(&generated_file.source.relative_path),
@@ -150,91 +97,20 @@ fn write_generated_files(root_dir: &FsPath, conf: &GeneratorConfig, generated_fi
             continue;
         };
 
-        if let Some(parent_dir) = output_path.parent() {
-            fs::create_dir_all(parent_dir)?;
+        if writer.write(&output_path, &generated_file.contents)? {
+            changed_count += 1;
         }
-
-        fs::write(&output_path, &generated_file.contents)?;
-        written_count += 1;
     }
 
-    Ok(written_count)
+    Ok(changed_count)
 }
 
-fn resolve_output_path(root_dir: &FsPath, conf: &GeneratorConfig, source: &DiscoveredSource) -> Result<Option<PathBuf>, CodegenError> {
-    let relative_path = normalize_path(&source.relative_path);
-    let Some(target) = conf.targets.iter().find(|target| glob_matches(&target.pattern, &relative_path)) else {
-        return Ok(None);
-    };
-
-    let dir = target_option_dir(target)?.ok_or_else(|| CodegenError::Other(format!("missing dir option for rust target pattern: {}", target.pattern)))?;
-    let file_stem = source
-        .relative_path
-        .file_stem()
-        .and_then(|stem| stem.to_str())
-        .ok_or_else(|| CodegenError::Other(format!("invalid source file name: {}", source.relative_path.display())))?;
-
-    Ok(Some(root_dir.join(dir).join(format!("{file_stem}.rs"))))
-}
-
-fn target_option_dir(target: &GeneratorTargetConfig) -> Result<Option<&str>, CodegenError> {
-    match &target.options {
-        Some(options) => mapping_string(options, "dir"),
-        None => Ok(None),
-    }
-}
-
-fn mapping_string<'a>(mapping: &'a Mapping, key: &str) -> Result<Option<&'a str>, CodegenError> {
-    let Some(value) = mapping.get(Value::String(key.to_string())) else {
-        return Ok(None);
-    };
-
-    match value {
-        Value::String(value) => Ok(Some(value.as_str())),
-        _ => Err(CodegenError::Other(format!("target option `{key}` must be a string"))),
-    }
-}
-
-fn build_schema_index(file: &File) -> SchemaIndex {
-    let mut index = SchemaIndex {
-        package: file.package.as_ref().map(|package| path_segments(&package.value)).unwrap_or_default(),
-        ..SchemaIndex::default()
-    };
-
-    for use_decl in &file.uses {
-        let path = path_segments(&use_decl.path.value);
-        let alias = use_decl.alias.as_ref().map(|alias| alias.value.clone());
-        let import_name = alias.clone().or_else(|| path.last().cloned()).unwrap_or_default();
-
-        index.uses.push(UseBinding { path: path.clone(), alias });
-        index.imported_paths.insert(import_name, path);
-    }
-
-    for item in &file.items {
-        match item {
-            Item::Struct(item) => {
-                index.user_types.insert(item.name.value.clone(), NamedTypeKind::Struct);
-            }
-            Item::Enum(item) => {
-                index.user_types.insert(item.name.value.clone(), NamedTypeKind::Enum);
-            }
-            Item::TypeAlias(item) => {
-                index.user_types.insert(item.name.value.clone(), NamedTypeKind::Alias);
-                index.type_aliases.insert(item.name.value.clone(), item.ty.value.clone());
-            }
-            Item::Const(_) => {}
-        }
-    }
-
-    index
-}
-
-fn render_rust_file(parsed_source: &ParsedSource) -> Result<String, CodegenError> {
-    let index = build_schema_index(&parsed_source.file);
+fn render_rust_file(parsed_source: &ParsedSource, index: &SchemaIndex, options: &RustOptions, package_sources: &[&ParsedSource]) -> Result<String, CodegenError> {
     let mut out = String::new();
 
     writeln!(&mut out, "// @generated by rocketpack-compiler").ok();
     writeln!(&mut out, "#[allow(dead_code)]").ok();
+    writeln!(&mut out, "#[allow(deprecated)]").ok();
     writeln!(&mut out, "#[allow(clippy::all)]").ok();
 
     let mut depth = 0usize;
@@ -245,7 +121,7 @@ fn render_rust_file(parsed_source: &ParsedSource) -> Result<String, CodegenError
 
     if !index.uses.is_empty() {
         for use_binding in &index.uses {
-            let rendered_path = use_binding.path.iter().map(|segment| sanitize_ident(segment)).collect::<Vec<_>>().join("::");
+            let rendered_path = render_canonical_path(index, &use_binding.path);
             if let Some(alias) = &use_binding.alias {
                 writeln!(&mut out, "{}use {} as {};", indent(depth), rendered_path, sanitize_ident(alias)).ok();
             } else {
@@ -258,21 +134,41 @@ fn render_rust_file(parsed_source: &ParsedSource) -> Result<String, CodegenError
     for item in &parsed_source.file.items {
         match item {
             Item::Struct(item) => {
-                write_struct_declaration(&mut out, &index, item, depth);
+                write_struct_declaration(&mut out, index, item, depth);
                 writeln!(&mut out).ok();
-                write_struct_codec_impl(&mut out, &index, item, depth)?;
+                write_struct_codec_impl(&mut out, index, item, depth)?;
+                if options.emit_schema {
+                    writeln!(&mut out).ok();
+                    write_struct_schema_impl(&mut out, index, item, depth)?;
+                }
             }
             Item::Enum(item) => {
-                write_enum_declaration(&mut out, &index, item, depth);
+                write_enum_declaration(&mut out, index, item, depth);
+                writeln!(&mut out).ok();
+                write_enum_codec_impl(&mut out, index, item, depth)?;
+                if options.emit_schema {
+                    writeln!(&mut out).ok();
+                    write_enum_schema_impl(&mut out, index, item, depth)?;
+                }
+            }
+            Item::TypeAlias(item) => write_type_alias_declaration(&mut out, index, item, depth),
+            Item::Const(item) => write_const_declaration(&mut out, index, item, depth)?,
+            Item::Service(item) => {
+                write_service_client(&mut out, index, item, depth);
+                writeln!(&mut out).ok();
+                write_service_server(&mut out, index, item, depth);
                 writeln!(&mut out).ok();
-                write_enum_codec_impl(&mut out, &index, item, depth)?;
+                write_service_dispatch(&mut out, index, item, depth);
             }
-            Item::TypeAlias(item) => write_type_alias_declaration(&mut out, &index, item, depth),
-            Item::Const(item) => write_const_declaration(&mut out, &index, item, depth)?,
         }
         writeln!(&mut out).ok();
     }
 
+    if options.emit_schema {
+        write_schema_registry(&mut out, index, parsed_source, package_sources, depth);
+        writeln!(&mut out).ok();
+    }
+
     for close_depth in (0..depth).rev() {
         writeln!(&mut out, "{}}}", indent(close_depth)).ok();
     }
@@ -281,10 +177,19 @@ fn render_rust_file(parsed_source: &ParsedSource) -> Result<String, CodegenError
 }
 
 fn write_struct_declaration(out: &mut String, index: &SchemaIndex, item: &Struct, depth: usize) {
-    writeln!(out, "{}#[derive(Debug, Clone, PartialEq)]", indent(depth)).ok();
-    writeln!(out, "{}pub struct {} {{", indent(depth), sanitize_ident(&item.name.value)).ok();
+    write_attributes(out, &item.docs, &item.attributes, depth);
+    writeln!(out, "{}{}", indent(depth), render_derive(&item.attributes)).ok();
+    writeln!(
+        out,
+        "{}pub struct {}{} {{",
+        indent(depth),
+        sanitize_ident(&item.name.value),
+        render_type_params(&item.type_params)
+    )
+    .ok();
 
     for field in &item.fields {
+        write_attributes(out, &field.docs, &field.attributes, depth + 1);
         writeln!(
             out,
             "{}pub {}: {},",
@@ -299,10 +204,9 @@ fn write_struct_declaration(out: &mut String, index: &SchemaIndex, item: &Struct
 }
 
 fn write_struct_codec_impl(out: &mut String, index: &SchemaIndex, item: &Struct, depth: usize) -> Result<(), CodegenError> {
-    let struct_name = sanitize_ident(&item.name.value);
     let sorted_fields = resolve_sorted_struct_fields(index, item)?;
 
-    writeln!(out, "{}impl omnius_core_rocketpack::RocketPackStruct for {} {{", indent(depth), struct_name).ok();
+    write_codec_impl_header(out, &item.name.value, &item.type_params, depth);
     write_struct_pack_fn(out, index, item, &sorted_fields, depth + 1)?;
     writeln!(out).ok();
     write_struct_unpack_fn(out, index, item, &sorted_fields, depth + 1)?;
@@ -312,16 +216,42 @@ fn write_struct_codec_impl(out: &mut String, index: &SchemaIndex, item: &Struct,
 }
 
 fn resolve_sorted_struct_fields<'a>(index: &SchemaIndex, item: &'a Struct) -> Result<Vec<(&'a Field, ResolvedType)>, CodegenError> {
+    let params = type_param_names(&item.type_params);
     let mut sorted_fields = Vec::with_capacity(item.fields.len());
 
     for field in &item.fields {
-        sorted_fields.push((field, resolve_type(index, &field.ty.value)?));
+        sorted_fields.push((field, resolve_type_with_params(index, &field.ty.value, &params)?));
     }
 
     sorted_fields.sort_by_key(|(field, _)| field.tag.value);
     Ok(sorted_fields)
 }
 
+// Type parameters are packed as nested values, so every argument only needs to be a RocketPack struct itself.
+fn write_codec_impl_header(out: &mut String, name: &str, type_params: &[Spanned<String>], depth: usize) {
+    let name = sanitize_ident(name);
+    if type_params.is_empty() {
+        writeln!(out, "{}impl omnius_core_rocketpack::RocketPackStruct for {} {{", indent(depth), name).ok();
+        return;
+    }
+
+    let params = render_type_params(type_params);
+    writeln!(out, "{}impl{} omnius_core_rocketpack::RocketPackStruct for {}{}", indent(depth), params, name, params).ok();
+    writeln!(out, "{}where", indent(depth)).ok();
+    for param in type_params {
+        writeln!(out, "{}{}: omnius_core_rocketpack::RocketPackStruct,", indent(depth + 1), sanitize_ident(&param.value)).ok();
+    }
+    writeln!(out, "{}{{", indent(depth)).ok();
+}
+
+fn render_type_params(type_params: &[Spanned<String>]) -> String {
+    if type_params.is_empty() {
+        return String::new();
+    }
+    let params = type_params.iter().map(|param| sanitize_ident(&param.value)).collect::<Vec<_>>().join(", ");
+    format!("<{params}>")
+}
+
 fn write_struct_pack_fn(out: &mut String, index: &SchemaIndex, _item: &Struct, fields: &[(&Field, ResolvedType)], depth: usize) -> Result<(), CodegenError> {
     writeln!(out, "{}fn pack(", indent(depth)).ok();
     writeln!(out, "{}encoder: &mut impl omnius_core_rocketpack::RocketPackEncoder,", indent(depth + 1)).ok();
@@ -386,6 +316,9 @@ fn write_encode_value(out: &mut String, resolved: &ResolvedType, expr: &str, dep
             BuiltinType::U64 => {
                 writeln!(out, "{}encoder.write_u64(*({}))?;", indent(depth), expr).ok();
             }
+            BuiltinType::U128 => {
+                writeln!(out, "{}encoder.write_u128(*({}))?;", indent(depth), expr).ok();
+            }
             BuiltinType::I8 => {
                 writeln!(out, "{}encoder.write_i8(*({}))?;", indent(depth), expr).ok();
             }
@@ -398,6 +331,12 @@ fn write_encode_value(out: &mut String, resolved: &ResolvedType, expr: &str, dep
             BuiltinType::I64 => {
                 writeln!(out, "{}encoder.write_i64(*({}))?;", indent(depth), expr).ok();
             }
+            BuiltinType::I128 => {
+                writeln!(out, "{}encoder.write_i128(*({}))?;", indent(depth), expr).ok();
+            }
+            BuiltinType::F16 => {
+                writeln!(out, "{}encoder.write_f16(*({}))?;", indent(depth), expr).ok();
+            }
             BuiltinType::F32 => {
                 writeln!(out, "{}encoder.write_f32(*({}))?;", indent(depth), expr).ok();
             }
@@ -410,22 +349,6 @@ fn write_encode_value(out: &mut String, resolved: &ResolvedType, expr: &str, dep
             BuiltinType::Bytes => {
                 writeln!(out, "{}encoder.write_bytes(({}).as_slice())?;", indent(depth), expr).ok();
             }
-            BuiltinType::U128 => {
-                writeln!(
-                    out,
-                    "{}return Err(omnius_core_rocketpack::RocketPackEncoderError::IoError(std::io::Error::new(std::io::ErrorKind::Unsupported, \"u128 encode is not supported\")));",
-                    indent(depth)
-                )
-                .ok();
-            }
-            BuiltinType::I128 => {
-                writeln!(
-                    out,
-                    "{}return Err(omnius_core_rocketpack::RocketPackEncoderError::IoError(std::io::Error::new(std::io::ErrorKind::Unsupported, \"i128 encode is not supported\")));",
-                    indent(depth)
-                )
-                .ok();
-            }
         },
         ResolvedType::Named(_) => {
             writeln!(out, "{}encoder.write_struct({})?;", indent(depth), expr).ok();
@@ -490,7 +413,8 @@ fn write_struct_unpack_fn(out: &mut String, index: &SchemaIndex, item: &Struct,
         };
 
         writeln!(out, "{}{} => {{", indent(depth + 3), field.tag.value).ok();
-        let value_expr = write_decode_value(out, decode_target, "decoder", depth + 4, &field.name.value, &mut temp_counter)?;
+        write_max_len_check(out, field, depth + 4);
+        let value_expr = write_decode_value(out, index, decode_target, "decoder", depth + 4, &field.name.value, &mut temp_counter)?;
         writeln!(out, "{}{} = Some({});", indent(depth + 4), field_ident, value_expr).ok();
         writeln!(out, "{}}}", indent(depth + 3)).ok();
     }
@@ -521,7 +445,7 @@ fn write_struct_unpack_fn(out: &mut String, index: &SchemaIndex, item: &Struct,
 fn render_storage_type(index: &SchemaIndex, original_type: &Type, resolved: &ResolvedType) -> String {
     match (original_type, resolved) {
         (Type::Option(inner), _) => render_declaration_type(index, inner),
-        (_, ResolvedType::Option(inner)) => render_resolved_type(inner),
+        (_, ResolvedType::Option(inner)) => render_resolved_type(index, inner),
         _ => render_declaration_type(index, original_type),
     }
 }
@@ -536,7 +460,7 @@ fn render_value_init(value_ident: &str, field_name: &str, default: Option<&Liter
     }
 
     if let Some(default) = default {
-        return Ok(format!("{value_ident}.unwrap_or({})", render_literal(default)?));
+        return Ok(format!("{value_ident}.unwrap_or({})", render_typed_literal(default, resolved)?));
     }
 
     Ok(format!(
@@ -549,7 +473,15 @@ fn render_struct_field_init(_index: &SchemaIndex, field: &Field, resolved: &Reso
     render_value_init(&field_ident, &field.name.value, field.default.as_ref().map(|default| &default.value), resolved)
 }
 
-fn write_decode_value(out: &mut String, resolved: &ResolvedType, decoder_ident: &str, depth: usize, context_name: &str, temp_counter: &mut usize) -> Result<String, CodegenError> {
+fn write_decode_value(
+    out: &mut String,
+    index: &SchemaIndex,
+    resolved: &ResolvedType,
+    decoder_ident: &str,
+    depth: usize,
+    context_name: &str,
+    temp_counter: &mut usize,
+) -> Result<String, CodegenError> {
     match resolved {
         ResolvedType::Builtin(builtin) => Ok(match builtin {
             BuiltinType::Bool => format!("{decoder_ident}.read_bool()?"),
@@ -557,34 +489,36 @@ fn write_decode_value(out: &mut String, resolved: &ResolvedType, decoder_ident:
             BuiltinType::U16 => format!("{decoder_ident}.read_u16()?"),
             BuiltinType::U32 => format!("{decoder_ident}.read_u32()?"),
             BuiltinType::U64 => format!("{decoder_ident}.read_u64()?"),
+            BuiltinType::U128 => format!("{decoder_ident}.read_u128()?"),
             BuiltinType::I8 => format!("{decoder_ident}.read_i8()?"),
             BuiltinType::I16 => format!("{decoder_ident}.read_i16()?"),
             BuiltinType::I32 => format!("{decoder_ident}.read_i32()?"),
             BuiltinType::I64 => format!("{decoder_ident}.read_i64()?"),
+            BuiltinType::I128 => format!("{decoder_ident}.read_i128()?"),
+            BuiltinType::F16 => format!("{decoder_ident}.read_f16()?"),
             BuiltinType::F32 => format!("{decoder_ident}.read_f32()?"),
             BuiltinType::F64 => format!("{decoder_ident}.read_f64()?"),
             BuiltinType::String => format!("{decoder_ident}.read_string()?"),
             BuiltinType::Bytes => format!("{decoder_ident}.read_bytes_vec()?"),
-            BuiltinType::U128 => "return Err(omnius_core_rocketpack::RocketPackDecoderError::Other(\"u128 decode is not supported\"))".to_string(),
-            BuiltinType::I128 => "return Err(omnius_core_rocketpack::RocketPackDecoderError::Other(\"i128 decode is not supported\"))".to_string(),
         }),
-        ResolvedType::Named(named) => Ok(format!("{decoder_ident}.read_struct::<{}>()?", render_named_type(named))),
-        ResolvedType::Option(inner) => write_decode_value(out, inner, decoder_ident, depth, context_name, temp_counter),
+        ResolvedType::Named(named) => Ok(format!("{decoder_ident}.read_struct::<{}>()?", render_named_type(index, named))),
+        ResolvedType::Option(inner) => write_decode_value(out, index, inner, decoder_ident, depth, context_name, temp_counter),
         ResolvedType::Vec(inner) => {
             let count_name = next_temp_name(temp_counter, "count");
             let value_name = next_temp_name(temp_counter, "values");
             writeln!(out, "{}let {} = {}.read_array()?;", indent(depth), count_name, decoder_ident).ok();
             writeln!(
                 out,
-                "{}let mut {}: Vec<{}> = Vec::with_capacity({} as usize);",
+                "{}let mut {}: Vec<{}> = {}.alloc_vec({})?;",
                 indent(depth),
                 value_name,
-                render_resolved_type(inner),
+                render_resolved_type(index, inner),
+                decoder_ident,
                 count_name
             )
             .ok();
             writeln!(out, "{}for _ in 0..{} {{", indent(depth), count_name).ok();
-            let inner_expr = write_decode_value(out, inner, decoder_ident, depth + 1, context_name, temp_counter)?;
+            let inner_expr = write_decode_value(out, index, inner, decoder_ident, depth + 1, context_name, temp_counter)?;
             writeln!(out, "{}{}.push({});", indent(depth + 1), value_name, inner_expr).ok();
             writeln!(out, "{}}}", indent(depth)).ok();
             Ok(value_name)
@@ -598,14 +532,17 @@ fn write_decode_value(out: &mut String, resolved: &ResolvedType, decoder_ident:
                 "{}let mut {}: std::collections::BTreeMap<{}, {}> = std::collections::BTreeMap::new();",
                 indent(depth),
                 map_name,
-                render_resolved_type(key),
-                render_resolved_type(value)
+                render_resolved_type(index, key),
+                render_resolved_type(index, value)
             )
             .ok();
             writeln!(out, "{}for _ in 0..{} {{", indent(depth), count_name).ok();
-            let key_expr = write_decode_value(out, key, decoder_ident, depth + 1, context_name, temp_counter)?;
-            let value_expr = write_decode_value(out, value, decoder_ident, depth + 1, context_name, temp_counter)?;
-            writeln!(out, "{}{}.insert({}, {});", indent(depth + 1), map_name, key_expr, value_expr).ok();
+            // The key is bound first, since decoding a collection value emits statements that would otherwise run before it.
+            let key_expr = write_decode_value(out, index, key, decoder_ident, depth + 1, context_name, temp_counter)?;
+            let key_name = next_temp_name(temp_counter, "key");
+            writeln!(out, "{}let {} = {};", indent(depth + 1), key_name, key_expr).ok();
+            let value_expr = write_decode_value(out, index, value, decoder_ident, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}.insert({}, {});", indent(depth + 1), map_name, key_name, value_expr).ok();
             writeln!(out, "{}}}", indent(depth)).ok();
             Ok(map_name)
         }
@@ -625,15 +562,16 @@ fn write_decode_value(out: &mut String, resolved: &ResolvedType, decoder_ident:
             writeln!(out, "{}}}", indent(depth)).ok();
             writeln!(
                 out,
-                "{}let mut {}: Vec<{}> = Vec::with_capacity({} as usize);",
+                "{}let mut {}: Vec<{}> = {}.alloc_vec({})?;",
                 indent(depth),
                 values_name,
-                render_resolved_type(inner),
+                render_resolved_type(index, inner),
+                decoder_ident,
                 count_name
             )
             .ok();
             writeln!(out, "{}for _ in 0..{} {{", indent(depth), count_name).ok();
-            let inner_expr = write_decode_value(out, inner, decoder_ident, depth + 1, context_name, temp_counter)?;
+            let inner_expr = write_decode_value(out, index, inner, decoder_ident, depth + 1, context_name, temp_counter)?;
             writeln!(out, "{}{}.push({});", indent(depth + 1), values_name, inner_expr).ok();
             writeln!(out, "{}}}", indent(depth)).ok();
             writeln!(
@@ -641,7 +579,7 @@ fn write_decode_value(out: &mut String, resolved: &ResolvedType, decoder_ident:
                 "{}let {}: {} = {}.try_into().map_err(|_| omnius_core_rocketpack::RocketPackDecoderError::Other(\"array length mismatch: {}\"))?;",
                 indent(depth),
                 array_name,
-                render_resolved_type(resolved),
+                render_resolved_type(index, resolved),
                 values_name,
                 context_name
             )
@@ -657,7 +595,7 @@ fn next_temp_name(counter: &mut usize, prefix: &str) -> String {
     format!("__{}_{}", prefix, current)
 }
 
-fn render_resolved_type(resolved: &ResolvedType) -> String {
+fn render_resolved_type(index: &SchemaIndex, resolved: &ResolvedType) -> String {
     match resolved {
         ResolvedType::Builtin(builtin) => match builtin {
             BuiltinType::Bool => "bool".to_string(),
@@ -671,27 +609,42 @@ fn render_resolved_type(resolved: &ResolvedType) -> String {
             BuiltinType::I32 => "i32".to_string(),
             BuiltinType::I64 => "i64".to_string(),
             BuiltinType::I128 => "i128".to_string(),
+            BuiltinType::F16 => "omnius_core_rocketpack::f16".to_string(),
             BuiltinType::F32 => "f32".to_string(),
             BuiltinType::F64 => "f64".to_string(),
             BuiltinType::String => "String".to_string(),
             BuiltinType::Bytes => "Vec<u8>".to_string(),
         },
-        ResolvedType::Named(named) => render_named_type(named),
-        ResolvedType::Option(inner) => format!("Option<{}>", render_resolved_type(inner)),
-        ResolvedType::Vec(inner) => format!("Vec<{}>", render_resolved_type(inner)),
-        ResolvedType::Map(key, value) => format!("std::collections::BTreeMap<{}, {}>", render_resolved_type(key), render_resolved_type(value)),
-        ResolvedType::Array(inner, len) => format!("[{}; {}]", render_resolved_type(inner), len),
+        ResolvedType::Named(named) => render_named_type(index, named),
+        ResolvedType::Option(inner) => format!("Option<{}>", render_resolved_type(index, inner)),
+        ResolvedType::Vec(inner) => format!("Vec<{}>", render_resolved_type(index, inner)),
+        ResolvedType::Map(key, value) => format!("std::collections::BTreeMap<{}, {}>", render_resolved_type(index, key), render_resolved_type(index, value)),
+        ResolvedType::Array(inner, len) => format!("[{}; {}]", render_resolved_type(index, inner), len),
     }
 }
 
-fn render_named_type(named: &NamedType) -> String {
-    named.rust_path.join("::")
+fn render_named_type(index: &SchemaIndex, named: &NamedType) -> String {
+    let path = named.path.iter().map(|segment| sanitize_ident(segment)).collect::<Vec<_>>().join("::");
+    let rendered = match &named.origin {
+        Some(origin) => format!("{}::{}", render_module_path(index, origin), path),
+        None => path,
+    };
+    if named.args.is_empty() {
+        return rendered;
+    }
+    let args = named.args.iter().map(|arg| render_resolved_type(index, arg)).collect::<Vec<_>>().join(", ");
+    format!("{rendered}<{args}>")
 }
 
-fn write_enum_codec_impl(out: &mut String, index: &SchemaIndex, item: &Enum, depth: usize) -> Result<(), CodegenError> {
-    let enum_name = sanitize_ident(&item.name.value);
+// Generated files are expected to be mounted as sibling modules named after their source file stem, so types of other
+// files are reached by leaving the package modules and the file module of the current file.
+fn render_module_path(index: &SchemaIndex, origin: &DiscoveredSource) -> String {
+    let file_stem = origin.relative_path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    format!("{}{}", "super::".repeat(index.package.len() + 1), sanitize_ident(&file_stem))
+}
 
-    writeln!(out, "{}impl omnius_core_rocketpack::RocketPackStruct for {} {{", indent(depth), enum_name).ok();
+fn write_enum_codec_impl(out: &mut String, index: &SchemaIndex, item: &Enum, depth: usize) -> Result<(), CodegenError> {
+    write_codec_impl_header(out, &item.name.value, &item.type_params, depth);
     write_enum_pack_fn(out, index, item, depth + 1)?;
     writeln!(out).ok();
     write_enum_unpack_fn(out, index, item, depth + 1)?;
@@ -709,8 +662,9 @@ fn write_enum_pack_fn(out: &mut String, index: &SchemaIndex, item: &Enum, depth:
     writeln!(out).ok();
     writeln!(out, "{}match value {{", indent(depth + 1)).ok();
 
+    let params = type_param_names(&item.type_params);
     for variant in &item.variants {
-        write_enum_pack_variant_arm(out, index, variant, depth + 2)?;
+        write_enum_pack_variant_arm(out, index, &params, variant, depth + 2)?;
     }
 
     writeln!(out, "{}}}", indent(depth + 1)).ok();
@@ -721,7 +675,7 @@ fn write_enum_pack_fn(out: &mut String, index: &SchemaIndex, item: &Enum, depth:
     Ok(())
 }
 
-fn write_enum_pack_variant_arm(out: &mut String, index: &SchemaIndex, variant: &crate::parser::ast::Variant, depth: usize) -> Result<(), CodegenError> {
+fn write_enum_pack_variant_arm(out: &mut String, index: &SchemaIndex, params: &[String], variant: &crate::parser::ast::Variant, depth: usize) -> Result<(), CodegenError> {
     let variant_name = sanitize_ident(&variant.name.value);
 
     match &variant.kind {
@@ -736,7 +690,7 @@ fn write_enum_pack_variant_arm(out: &mut String, index: &SchemaIndex, variant: &
             writeln!(out, "{}Self::{} {{ {} }} => {{", indent(depth), variant_name, bindings).ok();
             writeln!(out, "{}encoder.write_u64({})?;", indent(depth + 1), variant.tag.value).ok();
 
-            let resolved_fields = resolve_tuple_fields(index, fields)?;
+            let resolved_fields = resolve_tuple_fields(index, params, fields)?;
             write_tuple_variant_inner_map_count(out, fields, &resolved_fields, depth + 1);
             write_tuple_variant_encode_body(out, fields, &resolved_fields, depth + 1)?;
             writeln!(out, "{}}}", indent(depth)).ok();
@@ -746,7 +700,7 @@ fn write_enum_pack_variant_arm(out: &mut String, index: &SchemaIndex, variant: &
             writeln!(out, "{}Self::{} {{ {} }} => {{", indent(depth), variant_name, bindings).ok();
             writeln!(out, "{}encoder.write_u64({})?;", indent(depth + 1), variant.tag.value).ok();
 
-            let resolved_fields = resolve_sorted_record_fields(index, fields)?;
+            let resolved_fields = resolve_sorted_record_fields(index, params, fields)?;
             write_record_variant_inner_map_count(out, &resolved_fields, depth + 1);
             write_record_variant_encode_body(out, &resolved_fields, depth + 1)?;
             writeln!(out, "{}}}", indent(depth)).ok();
@@ -859,9 +813,10 @@ fn write_enum_unpack_fn(out: &mut String, index: &SchemaIndex, item: &Enum, dept
     writeln!(out, "{}for _ in 0..count {{", indent(depth + 1)).ok();
     writeln!(out, "{}match decoder.read_u64()? {{", indent(depth + 2)).ok();
 
+    let params = type_param_names(&item.type_params);
     let mut temp_counter = 0usize;
     for variant in &item.variants {
-        write_enum_unpack_variant_arm(out, index, variant, depth + 3, &mut temp_counter)?;
+        write_enum_unpack_variant_arm(out, index, &params, variant, depth + 3, &mut temp_counter)?;
     }
 
     writeln!(out, "{}_ => decoder.skip_field()?,", indent(depth + 3)).ok();
@@ -879,7 +834,14 @@ fn write_enum_unpack_fn(out: &mut String, index: &SchemaIndex, item: &Enum, dept
     Ok(())
 }
 
-fn write_enum_unpack_variant_arm(out: &mut String, index: &SchemaIndex, variant: &crate::parser::ast::Variant, depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+fn write_enum_unpack_variant_arm(
+    out: &mut String,
+    index: &SchemaIndex,
+    params: &[String],
+    variant: &crate::parser::ast::Variant,
+    depth: usize,
+    temp_counter: &mut usize,
+) -> Result<(), CodegenError> {
     let variant_name = sanitize_ident(&variant.name.value);
     writeln!(out, "{}{} => {{", indent(depth), variant.tag.value).ok();
     let inner_count = next_temp_name(temp_counter, "inner_count");
@@ -894,7 +856,7 @@ fn write_enum_unpack_variant_arm(out: &mut String, index: &SchemaIndex, variant:
             writeln!(out, "{}result = Some(Self::{});", indent(depth + 1), variant_name).ok();
         }
         VariantKind::Tuple(fields) => {
-            let resolved_fields = resolve_tuple_fields(index, fields)?;
+            let resolved_fields = resolve_tuple_fields(index, params, fields)?;
             let tuple_bindings = declare_tuple_variant_storage(out, index, fields, &resolved_fields, depth + 1);
             writeln!(out, "{}for _ in 0..{} {{", indent(depth + 1), inner_count).ok();
             writeln!(out, "{}match decoder.read_u64()? {{", indent(depth + 2)).ok();
@@ -905,7 +867,7 @@ fn write_enum_unpack_variant_arm(out: &mut String, index: &SchemaIndex, variant:
                     _ => resolved,
                 };
                 writeln!(out, "{}{} => {{", indent(depth + 3), tuple_index).ok();
-                let value_expr = write_decode_value(out, decode_target, "decoder", depth + 4, &name.value, temp_counter)?;
+                let value_expr = write_decode_value(out, index, decode_target, "decoder", depth + 4, &name.value, temp_counter)?;
                 writeln!(out, "{}{} = Some({});", indent(depth + 4), binding_name, value_expr).ok();
                 writeln!(out, "{}}}", indent(depth + 3)).ok();
             }
@@ -929,7 +891,7 @@ fn write_enum_unpack_variant_arm(out: &mut String, index: &SchemaIndex, variant:
             writeln!(out, "{}result = Some(Self::{} {{ {} }});", indent(depth + 1), variant_name, init_fields.join(", ")).ok();
         }
         VariantKind::Record(fields) => {
-            let resolved_fields = resolve_sorted_record_fields(index, fields)?;
+            let resolved_fields = resolve_sorted_record_fields(index, params, fields)?;
             declare_record_variant_storage(out, index, &resolved_fields, depth + 1);
             writeln!(out, "{}for _ in 0..{} {{", indent(depth + 1), inner_count).ok();
             writeln!(out, "{}match decoder.read_u64()? {{", indent(depth + 2)).ok();
@@ -941,7 +903,8 @@ fn write_enum_unpack_variant_arm(out: &mut String, index: &SchemaIndex, variant:
                     _ => resolved,
                 };
                 writeln!(out, "{}{} => {{", indent(depth + 3), field.tag.value).ok();
-                let value_expr = write_decode_value(out, decode_target, "decoder", depth + 4, &field.name.value, temp_counter)?;
+                write_max_len_check(out, field, depth + 4);
+                let value_expr = write_decode_value(out, index, decode_target, "decoder", depth + 4, &field.name.value, temp_counter)?;
                 writeln!(out, "{}{} = Some({});", indent(depth + 4), binding_name, value_expr).ok();
                 writeln!(out, "{}}}", indent(depth + 3)).ok();
             }
@@ -971,19 +934,20 @@ fn write_enum_unpack_variant_arm(out: &mut String, index: &SchemaIndex, variant:
 
 fn resolve_tuple_fields(
     index: &SchemaIndex,
+    params: &[String],
     fields: &[(crate::parser::ast::Spanned<String>, crate::parser::ast::Spanned<Type>)],
 ) -> Result<Vec<(usize, ResolvedType)>, CodegenError> {
     let mut resolved = Vec::with_capacity(fields.len());
     for (index_in_tuple, (_, ty)) in fields.iter().enumerate() {
-        resolved.push((index_in_tuple, resolve_type(index, &ty.value)?));
+        resolved.push((index_in_tuple, resolve_type_with_params(index, &ty.value, params)?));
     }
     Ok(resolved)
 }
 
-fn resolve_sorted_record_fields<'a>(index: &SchemaIndex, fields: &'a [Field]) -> Result<Vec<(&'a Field, ResolvedType)>, CodegenError> {
+fn resolve_sorted_record_fields<'a>(index: &SchemaIndex, params: &[String], fields: &'a [Field]) -> Result<Vec<(&'a Field, ResolvedType)>, CodegenError> {
     let mut resolved = Vec::with_capacity(fields.len());
     for field in fields {
-        resolved.push((field, resolve_type(index, &field.ty.value)?));
+        resolved.push((field, resolve_type_with_params(index, &field.ty.value, params)?));
     }
     resolved.sort_by_key(|(field, _)| field.tag.value);
     Ok(resolved)
@@ -1026,10 +990,19 @@ fn declare_record_variant_storage(out: &mut String, index: &SchemaIndex, fields:
 }
 
 fn write_enum_declaration(out: &mut String, index: &SchemaIndex, item: &Enum, depth: usize) {
-    writeln!(out, "{}#[derive(Debug, Clone, PartialEq)]", indent(depth)).ok();
-    writeln!(out, "{}pub enum {} {{", indent(depth), sanitize_ident(&item.name.value)).ok();
+    write_attributes(out, &item.docs, &item.attributes, depth);
+    writeln!(out, "{}{}", indent(depth), render_derive(&item.attributes)).ok();
+    writeln!(
+        out,
+        "{}pub enum {}{} {{",
+        indent(depth),
+        sanitize_ident(&item.name.value),
+        render_type_params(&item.type_params)
+    )
+    .ok();
 
     for variant in &item.variants {
+        write_attributes(out, &variant.docs, &variant.attributes, depth + 1);
         match &variant.kind {
             VariantKind::Unit => {
                 writeln!(out, "{}{},", indent(depth + 1), sanitize_ident(&variant.name.value)).ok();
@@ -1044,6 +1017,7 @@ fn write_enum_declaration(out: &mut String, index: &SchemaIndex, item: &Enum, de
             VariantKind::Record(fields) => {
                 writeln!(out, "{}{} {{", indent(depth + 1), sanitize_ident(&variant.name.value)).ok();
                 for field in fields {
+                    write_attributes(out, &field.docs, &field.attributes, depth + 2);
                     writeln!(
                         out,
                         "{}{}: {},",
@@ -1062,6 +1036,7 @@ fn write_enum_declaration(out: &mut String, index: &SchemaIndex, item: &Enum, de
 }
 
 fn write_type_alias_declaration(out: &mut String, index: &SchemaIndex, item: &crate::parser::ast::TypeAlias, depth: usize) {
+    write_attributes(out, &item.docs, &item.attributes, depth);
     writeln!(
         out,
         "{}pub type {} = {};",
@@ -1073,22 +1048,470 @@ fn write_type_alias_declaration(out: &mut String, index: &SchemaIndex, item: &cr
 }
 
 fn write_const_declaration(out: &mut String, index: &SchemaIndex, item: &Const, depth: usize) -> Result<(), CodegenError> {
+    write_attributes(out, &item.docs, &item.attributes, depth);
     writeln!(
         out,
         "{}pub const {}: {} = {};",
         indent(depth),
         sanitize_ident(&item.name.value),
         render_declaration_type(index, &item.ty.value),
-        render_literal(&item.value.value)?
+        render_typed_literal(&item.value.value, &resolve_type(index, &item.ty.value)?)?
+    )
+    .ok();
+
+    Ok(())
+}
+
+const REMOTING_PATH: &str = "omnius_core_omnikit::service::remoting";
+
+// The function id of the handshake carries the service id in its high 16 bits and the rpc tag in its low 16 bits, so
+// several services can share one listener.
+fn function_id(item: &Service, rpc: &Rpc) -> String {
+    format!("0x{:04x}_{:04x}", item.tag.value, rpc.tag.value)
+}
+
+// Each call opens its own connection: unary calls exchange exactly one request and one response, while a streaming side
+// ends with `finish`, which the peer sees as `recv_next` returning `None`.
+fn write_service_client(out: &mut String, index: &SchemaIndex, item: &Service, depth: usize) {
+    let client_name = format!("{}Client", sanitize_ident(&item.name.value));
+
+    write_attributes(out, &item.docs, &item.attributes, depth);
+    writeln!(out, "{}pub struct {} {{", indent(depth), client_name).ok();
+    writeln!(out, "{}max_frame_length: usize,", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}impl {} {{", indent(depth), client_name).ok();
+    writeln!(out, "{}pub fn new(max_frame_length: usize) -> Self {{", indent(depth + 1)).ok();
+    writeln!(out, "{}Self {{ max_frame_length }}", indent(depth + 2)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+
+    for rpc in &item.rpcs {
+        let request_type = render_declaration_type(index, &rpc.request.ty.value);
+        let response_type = render_declaration_type(index, &rpc.response.ty.value);
+        let is_streaming = rpc.request.stream || rpc.response.stream;
+
+        let mut params = vec!["&self".to_string(), "stream: T".to_string()];
+        if !rpc.request.stream {
+            params.push(format!("request: {request_type}"));
+        }
+        let output = if is_streaming {
+            format!("{REMOTING_PATH}::OmniRemotingChannel<{request_type}, {response_type}>")
+        } else {
+            response_type.clone()
+        };
+
+        writeln!(out).ok();
+        write_attributes(out, &rpc.docs, &rpc.attributes, depth + 1);
+        writeln!(
+            out,
+            "{}pub async fn {}<T>({}) -> omnius_core_omnikit::Result<{}>",
+            indent(depth + 1),
+            rpc_method_name(rpc),
+            params.join(", "),
+            output
+        )
+        .ok();
+        writeln!(out, "{}where", indent(depth + 1)).ok();
+        writeln!(out, "{}T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + 'static,", indent(depth + 2)).ok();
+        writeln!(out, "{}{{", indent(depth + 1)).ok();
+        writeln!(
+            out,
+            "{}let caller = {REMOTING_PATH}::OmniRemotingCaller::new(stream, self.max_frame_length, {}).await?;",
+            indent(depth + 2),
+            function_id(item, rpc)
+        )
+        .ok();
+        writeln!(out, "{}let stream = caller.call_stream();", indent(depth + 2)).ok();
+        if !rpc.request.stream {
+            writeln!(out, "{}stream.send(request).await?;", indent(depth + 2)).ok();
+        }
+        if is_streaming {
+            writeln!(out, "{}Ok({REMOTING_PATH}::OmniRemotingChannel::new(stream))", indent(depth + 2)).ok();
+        } else {
+            writeln!(out, "{}stream.recv::<{}>().await", indent(depth + 2), response_type).ok();
+        }
+        writeln!(out, "{}}}", indent(depth + 1)).ok();
+    }
+
+    writeln!(out, "{}}}", indent(depth)).ok();
+}
+
+// Unary rpcs return their response; rpcs with a streaming side get a channel to answer through instead, which the
+// dispatcher finishes once the method returns.
+fn write_service_server(out: &mut String, index: &SchemaIndex, item: &Service, depth: usize) {
+    write_attributes(out, &item.docs, &item.attributes, depth);
+    writeln!(out, "{}pub trait {}Server: Send + Sync {{", indent(depth), sanitize_ident(&item.name.value)).ok();
+
+    for (position, rpc) in item.rpcs.iter().enumerate() {
+        let request_type = render_declaration_type(index, &rpc.request.ty.value);
+        let response_type = render_declaration_type(index, &rpc.response.ty.value);
+
+        let mut params = vec!["&self".to_string()];
+        if !rpc.request.stream {
+            params.push(format!("request: {request_type}"));
+        }
+        let output = if rpc.request.stream || rpc.response.stream {
+            params.push(format!("channel: &{REMOTING_PATH}::OmniRemotingChannel<{response_type}, {request_type}>"));
+            "()".to_string()
+        } else {
+            response_type
+        };
+
+        if position > 0 {
+            writeln!(out).ok();
+        }
+        write_attributes(out, &rpc.docs, &rpc.attributes, depth + 1);
+        writeln!(
+            out,
+            "{}fn {}({}) -> impl std::future::Future<Output = omnius_core_omnikit::Result<{}>> + Send;",
+            indent(depth + 1),
+            rpc_method_name(rpc),
+            params.join(", "),
+            output
+        )
+        .ok();
+    }
+
+    writeln!(out, "{}}}", indent(depth)).ok();
+}
+
+fn write_service_dispatch(out: &mut String, index: &SchemaIndex, item: &Service, depth: usize) {
+    let service_name = sanitize_ident(&item.name.value);
+
+    writeln!(
+        out,
+        "{}pub async fn dispatch_{}<S>(server: &S, listener: &{REMOTING_PATH}::OmniRemotingListener) -> omnius_core_omnikit::Result<()>",
+        indent(depth),
+        to_snake_case(&item.name.value)
+    )
+    .ok();
+    writeln!(out, "{}where", indent(depth)).ok();
+    writeln!(out, "{}S: {}Server,", indent(depth + 1), service_name).ok();
+    writeln!(out, "{}{{", indent(depth)).ok();
+    writeln!(out, "{}use omnius_core_base::error::OmniError as _;", indent(depth + 1)).ok();
+    writeln!(out).ok();
+    // The listener drops the result of its callback, so it is carried out through this binding.
+    writeln!(out, "{}let mut result: omnius_core_omnikit::Result<()> = Ok(());", indent(depth + 1)).ok();
+    writeln!(out, "{}match listener.function_id() {{", indent(depth + 1)).ok();
+
+    for rpc in &item.rpcs {
+        let request_type = render_declaration_type(index, &rpc.request.ty.value);
+        let method_name = rpc_method_name(rpc);
+
+        writeln!(out, "{}{} => {{", indent(depth + 2), function_id(item, rpc)).ok();
+        writeln!(out, "{}listener", indent(depth + 3)).ok();
+        writeln!(out, "{}.listen_stream(async |stream| {{", indent(depth + 4)).ok();
+        writeln!(out, "{}result = async {{", indent(depth + 5)).ok();
+        if !rpc.request.stream {
+            writeln!(out, "{}let request = stream.recv::<{}>().await?;", indent(depth + 6), request_type).ok();
+        }
+        if rpc.request.stream || rpc.response.stream {
+            let request_arg = if rpc.request.stream { "" } else { "request, " };
+            writeln!(out, "{}let channel = {REMOTING_PATH}::OmniRemotingChannel::new(stream);", indent(depth + 6)).ok();
+            writeln!(out, "{}server.{}({}&channel).await?;", indent(depth + 6), method_name, request_arg).ok();
+            writeln!(out, "{}channel.finish().await", indent(depth + 6)).ok();
+        } else {
+            writeln!(out, "{}let response = server.{}(request).await?;", indent(depth + 6), method_name).ok();
+            writeln!(out, "{}stream.send(response).await", indent(depth + 6)).ok();
+        }
+        writeln!(out, "{}}}", indent(depth + 5)).ok();
+        writeln!(out, "{}.await;", indent(depth + 5)).ok();
+        writeln!(out, "{}}})", indent(depth + 4)).ok();
+        writeln!(out, "{}.await?;", indent(depth + 4)).ok();
+        writeln!(out, "{}}}", indent(depth + 2)).ok();
+    }
+
+    writeln!(out, "{}function_id => {{", indent(depth + 2)).ok();
+    writeln!(
+        out,
+        "{}return Err(omnius_core_omnikit::Error::new(omnius_core_omnikit::ErrorKind::UnsupportedType).with_message(format!(\"unknown function id: {{function_id}}\")));",
+        indent(depth + 3)
     )
     .ok();
+    writeln!(out, "{}}}", indent(depth + 2)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}result", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+}
+
+// Doc comments and `#[deprecated]` carry over as they are; the other attributes only steer the generated code.
+fn write_struct_schema_impl(out: &mut String, index: &SchemaIndex, item: &Struct, depth: usize) -> Result<(), CodegenError> {
+    let params = type_param_names(&item.type_params);
+
+    write_schema_impl_header(out, index, &item.name.value, &item.type_params, depth);
+    writeln!(out, "{}kind: omnius_core_rocketpack::ItemKind::Struct {{", indent(depth + 2)).ok();
+    let fields = item
+        .fields
+        .iter()
+        .map(|field| (field.tag.value, &field.name.value, &field.ty.value, field.default.as_ref().map(|default| &default.value)));
+    write_field_descriptors(out, index, &params, fields, depth + 3)?;
+    writeln!(out, "{}}},", indent(depth + 2)).ok();
+    writeln!(out, "{}}};", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
 
     Ok(())
 }
 
+fn write_enum_schema_impl(out: &mut String, index: &SchemaIndex, item: &Enum, depth: usize) -> Result<(), CodegenError> {
+    let params = type_param_names(&item.type_params);
+
+    write_schema_impl_header(out, index, &item.name.value, &item.type_params, depth);
+    writeln!(out, "{}kind: omnius_core_rocketpack::ItemKind::Enum {{", indent(depth + 2)).ok();
+    writeln!(out, "{}variants: &[", indent(depth + 3)).ok();
+    for variant in &item.variants {
+        writeln!(out, "{}omnius_core_rocketpack::VariantDescriptor {{", indent(depth + 4)).ok();
+        writeln!(out, "{}tag: {},", indent(depth + 5), variant.tag.value).ok();
+        writeln!(out, "{}name: {:?},", indent(depth + 5), variant.name.value).ok();
+        match &variant.kind {
+            VariantKind::Unit => {
+                writeln!(out, "{}fields: &[],", indent(depth + 5)).ok();
+            }
+            // Tuple fields are tagged from 0 in declaration order, like their encoding.
+            VariantKind::Tuple(fields) => {
+                let fields = fields.iter().enumerate().map(|(tag, (name, ty))| (tag as u32, &name.value, &ty.value, None));
+                write_field_descriptors(out, index, &params, fields, depth + 5)?;
+            }
+            VariantKind::Record(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| (field.tag.value, &field.name.value, &field.ty.value, field.default.as_ref().map(|default| &default.value)));
+                write_field_descriptors(out, index, &params, fields, depth + 5)?;
+            }
+        }
+        writeln!(out, "{}}},", indent(depth + 4)).ok();
+    }
+    writeln!(out, "{}],", indent(depth + 3)).ok();
+    writeln!(out, "{}}},", indent(depth + 2)).ok();
+    writeln!(out, "{}}};", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    Ok(())
+}
+
+// Opens the impl and the descriptor literal up to its `kind`, which the caller writes and closes.
+fn write_schema_impl_header(out: &mut String, index: &SchemaIndex, name: &str, type_params: &[Spanned<String>], depth: usize) {
+    let params = render_type_params(type_params);
+    writeln!(
+        out,
+        "{}impl{} omnius_core_rocketpack::RocketPackSchema for {}{} {{",
+        indent(depth),
+        params,
+        sanitize_ident(name),
+        params
+    )
+    .ok();
+    writeln!(
+        out,
+        "{}const SCHEMA: omnius_core_rocketpack::ItemDescriptor = omnius_core_rocketpack::ItemDescriptor {{",
+        indent(depth + 1)
+    )
+    .ok();
+    writeln!(out, "{}package: {:?},", indent(depth + 2), index.package.join("::")).ok();
+    writeln!(out, "{}name: {:?},", indent(depth + 2), name).ok();
+    writeln!(
+        out,
+        "{}type_params: &[{}],",
+        indent(depth + 2),
+        type_param_names(type_params).iter().map(|param| format!("{param:?}")).collect::<Vec<_>>().join(", ")
+    )
+    .ok();
+}
+
+fn write_field_descriptors<'a>(
+    out: &mut String,
+    index: &SchemaIndex,
+    params: &[String],
+    fields: impl ExactSizeIterator<Item = (u32, &'a String, &'a Type, Option<&'a Literal>)>,
+    depth: usize,
+) -> Result<(), CodegenError> {
+    if fields.len() == 0 {
+        writeln!(out, "{}fields: &[],", indent(depth)).ok();
+        return Ok(());
+    }
+
+    writeln!(out, "{}fields: &[", indent(depth)).ok();
+    for (tag, name, ty, default) in fields {
+        let ty = render_type_descriptor(index, &resolve_type_with_params(index, ty, params)?);
+        let default = match default {
+            Some(default) => format!("Some({:?})", format_literal(default)),
+            None => "None".to_string(),
+        };
+        writeln!(out, "{}omnius_core_rocketpack::FieldDescriptor {{", indent(depth + 1)).ok();
+        writeln!(out, "{}tag: {},", indent(depth + 2), tag).ok();
+        writeln!(out, "{}name: {:?},", indent(depth + 2), name).ok();
+        writeln!(out, "{}ty: {},", indent(depth + 2), ty).ok();
+        writeln!(out, "{}default: {},", indent(depth + 2), default).ok();
+        writeln!(out, "{}}},", indent(depth + 1)).ok();
+    }
+    writeln!(out, "{}],", indent(depth)).ok();
+
+    Ok(())
+}
+
+fn render_type_descriptor(index: &SchemaIndex, resolved: &ResolvedType) -> String {
+    const DESCRIPTOR_PATH: &str = "omnius_core_rocketpack::TypeDescriptor";
+
+    match resolved {
+        ResolvedType::Builtin(builtin) => {
+            let variant = match builtin {
+                BuiltinType::Bool => "Bool",
+                BuiltinType::U8 => "U8",
+                BuiltinType::U16 => "U16",
+                BuiltinType::U32 => "U32",
+                BuiltinType::U64 => "U64",
+                BuiltinType::U128 => "U128",
+                BuiltinType::I8 => "I8",
+                BuiltinType::I16 => "I16",
+                BuiltinType::I32 => "I32",
+                BuiltinType::I64 => "I64",
+                BuiltinType::I128 => "I128",
+                BuiltinType::F16 => "F16",
+                BuiltinType::F32 => "F32",
+                BuiltinType::F64 => "F64",
+                BuiltinType::String => "String",
+                BuiltinType::Bytes => "Bytes",
+            };
+            format!("{DESCRIPTOR_PATH}::{variant}")
+        }
+        ResolvedType::Named(named) if named.kind == NamedTypeKind::Param => format!("{DESCRIPTOR_PATH}::Param({:?})", named.path.join("::")),
+        ResolvedType::Named(named) => format!(
+            "{DESCRIPTOR_PATH}::Named {{ name: {:?}, args: &[{}] }}",
+            qualified_path(index, resolved).join("::"),
+            named.args.iter().map(|arg| render_type_descriptor(index, arg)).collect::<Vec<_>>().join(", ")
+        ),
+        ResolvedType::Option(inner) => format!("{DESCRIPTOR_PATH}::Option(&{})", render_type_descriptor(index, inner)),
+        ResolvedType::Vec(inner) => format!("{DESCRIPTOR_PATH}::Vec(&{})", render_type_descriptor(index, inner)),
+        ResolvedType::Map(key, value) => format!("{DESCRIPTOR_PATH}::Map(&{}, &{})", render_type_descriptor(index, key), render_type_descriptor(index, value)),
+        ResolvedType::Array(inner, len) => format!("{DESCRIPTOR_PATH}::Array(&{}, {})", render_type_descriptor(index, inner), len),
+    }
+}
+
+// The registry of a package lives in its first source that emits schemas, and the other sources of the package
+// re-export it. Generic items are listed with `()` for every type parameter, since their descriptor does not depend on
+// the arguments.
+fn write_schema_registry(out: &mut String, index: &SchemaIndex, parsed_source: &ParsedSource, package_sources: &[&ParsedSource], depth: usize) {
+    let package_path = index.package.iter().map(|segment| format!("{}::", sanitize_ident(segment))).collect::<String>();
+
+    let Some(owner) = package_sources.first() else {
+        return;
+    };
+    if owner.source != parsed_source.source {
+        writeln!(
+            out,
+            "{}pub use {}::{}SCHEMA_REGISTRY;",
+            indent(depth),
+            render_module_path(index, &owner.source),
+            package_path
+        )
+        .ok();
+        return;
+    }
+
+    writeln!(
+        out,
+        "{}pub static SCHEMA_REGISTRY: omnius_core_rocketpack::SchemaRegistry = omnius_core_rocketpack::SchemaRegistry::new(&[",
+        indent(depth)
+    )
+    .ok();
+    for package_source in package_sources {
+        let module_path = if package_source.source == parsed_source.source {
+            String::new()
+        } else {
+            format!("{}::{}", render_module_path(index, &package_source.source), package_path)
+        };
+
+        for item in &package_source.file.items {
+            let (name, type_params) = match item {
+                Item::Struct(item) => (&item.name.value, &item.type_params),
+                Item::Enum(item) => (&item.name.value, &item.type_params),
+                _ => continue,
+            };
+            let args = if type_params.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", vec!["()"; type_params.len()].join(", "))
+            };
+            writeln!(
+                out,
+                "{}&<{}{}{} as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,",
+                indent(depth + 1),
+                module_path,
+                sanitize_ident(name),
+                args
+            )
+            .ok();
+        }
+    }
+    writeln!(out, "{}]);", indent(depth)).ok();
+}
+
+fn write_attributes(out: &mut String, docs: &[String], attributes: &[Spanned<Attribute>], depth: usize) {
+    for doc in docs {
+        if doc.is_empty() {
+            writeln!(out, "{}///", indent(depth)).ok();
+        } else {
+            writeln!(out, "{}/// {}", indent(depth), doc).ok();
+        }
+    }
+
+    if let Some(deprecated) = find_attribute(attributes, "deprecated") {
+        match &deprecated.value.args {
+            AttributeArgs::Value(Spanned { value: Literal::String(note), .. }) => writeln!(out, "{}#[deprecated(note = {:?})]", indent(depth), note).ok(),
+            _ => writeln!(out, "{}#[deprecated]", indent(depth)).ok(),
+        };
+    }
+}
+
+// `#[rust(derive = "Eq, Hash")]` adds to the derives every generated type has.
+fn render_derive(attributes: &[Spanned<Attribute>]) -> String {
+    let mut derives = vec!["Debug".to_string(), "Clone".to_string(), "PartialEq".to_string()];
+    if let Some(rust) = find_attribute(attributes, "rust")
+        && let AttributeArgs::List(args) = &rust.value.args
+    {
+        for arg in args {
+            let Literal::String(value) = &arg.value.value else {
+                continue;
+            };
+            for derive in value.split(',').map(str::trim).filter(|derive| !derive.is_empty()) {
+                if !derives.iter().any(|existing| existing == derive) {
+                    derives.push(derive.to_string());
+                }
+            }
+        }
+    }
+    format!("#[derive({})]", derives.join(", "))
+}
+
+// The declared length is checked before the value is read, so an oversized value is rejected without being decoded.
+// Strings are limited by their length in UTF-8 bytes, which is also what the wire format counts.
+fn write_max_len_check(out: &mut String, field: &Field, depth: usize) {
+    let Some(AttributeArgs::Value(Spanned { value: Literal::Int(limit), .. })) = find_attribute(&field.attributes, "max_len").map(|attribute| &attribute.value.args) else {
+        return;
+    };
+
+    writeln!(out, "{}if decoder.current_len()? > {} {{", indent(depth), limit).ok();
+    writeln!(
+        out,
+        "{}return Err(omnius_core_rocketpack::RocketPackDecoderError::Other(\"max_len exceeded: {}\"));",
+        indent(depth + 1),
+        field.name.value
+    )
+    .ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+}
+
+fn rpc_method_name(rpc: &Rpc) -> String {
+    sanitize_ident(&to_snake_case(&rpc.name.value))
+}
+
 fn render_declaration_type(index: &SchemaIndex, ty: &Type) -> String {
     match ty {
         Type::Path(path) => render_path_type(index, path),
+        Type::Generic(path, args) => {
+            let args = args.iter().map(|arg| render_declaration_type(index, arg)).collect::<Vec<_>>().join(", ");
+            format!("{}<{}>", render_path_type(index, path), args)
+        }
         Type::Option(inner) => format!("Option<{}>", render_declaration_type(index, inner)),
         Type::Vec(inner) => format!("Vec<{}>", render_declaration_type(index, inner)),
         Type::Map(key, value) => format!(
@@ -1100,7 +1523,7 @@ fn render_declaration_type(index: &SchemaIndex, ty: &Type) -> String {
     }
 }
 
-fn render_path_type(_index: &SchemaIndex, path: &AstPath) -> String {
+fn render_path_type(index: &SchemaIndex, path: &AstPath) -> String {
     if let Some(builtin) = builtin_type(path) {
         return match builtin {
             BuiltinType::Bool => "bool".to_string(),
@@ -1114,6 +1537,7 @@ fn render_path_type(_index: &SchemaIndex, path: &AstPath) -> String {
             BuiltinType::I32 => "i32".to_string(),
             BuiltinType::I64 => "i64".to_string(),
             BuiltinType::I128 => "i128".to_string(),
+            BuiltinType::F16 => "omnius_core_rocketpack::f16".to_string(),
             BuiltinType::F32 => "f32".to_string(),
             BuiltinType::F64 => "f64".to_string(),
             BuiltinType::String => "String".to_string(),
@@ -1121,66 +1545,29 @@ fn render_path_type(_index: &SchemaIndex, path: &AstPath) -> String {
         };
     }
 
-    path_segments(path).iter().map(|segment| sanitize_ident(segment)).collect::<Vec<_>>().join("::")
-}
-
-fn resolve_type(index: &SchemaIndex, ty: &Type) -> Result<ResolvedType, CodegenError> {
-    let mut resolving_aliases = Vec::<String>::new();
-    resolve_type_inner(index, ty, &mut resolving_aliases)
-}
-
-fn resolve_type_inner(index: &SchemaIndex, ty: &Type, resolving_aliases: &mut Vec<String>) -> Result<ResolvedType, CodegenError> {
-    match ty {
-        Type::Path(path) => resolve_path_type(index, path, resolving_aliases),
-        Type::Option(inner) => Ok(ResolvedType::Option(Box::new(resolve_type_inner(index, inner, resolving_aliases)?))),
-        Type::Vec(inner) => Ok(ResolvedType::Vec(Box::new(resolve_type_inner(index, inner, resolving_aliases)?))),
-        Type::Map(key, value) => Ok(ResolvedType::Map(
-            Box::new(resolve_type_inner(index, key, resolving_aliases)?),
-            Box::new(resolve_type_inner(index, value, resolving_aliases)?),
-        )),
-        Type::Array(inner, len) => Ok(ResolvedType::Array(Box::new(resolve_type_inner(index, inner, resolving_aliases)?), *len)),
+    // Single names are either local or brought in by the rendered `use` declarations.
+    let segments = path_segments(path);
+    match canonical_path(index, path) {
+        Ok(canonical) if segments.len() > 1 => render_canonical_path(index, &canonical),
+        _ => segments.iter().map(|segment| sanitize_ident(segment)).collect::<Vec<_>>().join("::"),
     }
 }
 
-fn resolve_path_type(index: &SchemaIndex, path: &AstPath, resolving_aliases: &mut Vec<String>) -> Result<ResolvedType, CodegenError> {
-    if let Some(builtin) = builtin_type(path) {
-        return Ok(ResolvedType::Builtin(builtin));
+fn render_canonical_path(index: &SchemaIndex, canonical: &[String]) -> String {
+    let rendered_path = canonical.iter().map(|segment| sanitize_ident(segment)).collect::<Vec<_>>().join("::");
+    match index.external_types.get(canonical) {
+        Some(external) => format!("{}::{}", render_module_path(index, &external.origin), rendered_path),
+        None => rendered_path,
     }
+}
 
-    let segments = path_segments(path);
-    if segments.len() == 1 {
-        let name = &segments[0];
-
-        if let Some(alias_ty) = index.type_aliases.get(name) {
-            if resolving_aliases.iter().any(|current| current == name) {
-                return Err(CodegenError::Other(format!("cyclic type alias: {name}")));
-            }
-
-            resolving_aliases.push(name.clone());
-            let resolved = resolve_type_inner(index, alias_ty, resolving_aliases)?;
-            resolving_aliases.pop();
-            return Ok(resolved);
-        }
-
-        if let Some(kind) = index.user_types.get(name) {
-            return Ok(ResolvedType::Named(NamedType {
-                rust_path: vec![sanitize_ident(name)],
-                kind: kind.clone(),
-            }));
-        }
-
-        if let Some(imported_path) = index.imported_paths.get(name) {
-            return Ok(ResolvedType::Named(NamedType {
-                rust_path: imported_path.iter().map(|segment| sanitize_ident(segment)).collect(),
-                kind: NamedTypeKind::External,
-            }));
-        }
+// Numeric literals for `f16` have no Rust literal syntax, so they go through a const conversion.
+fn render_typed_literal(literal: &Literal, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    match (literal, resolved) {
+        (Literal::Int(value), ResolvedType::Builtin(BuiltinType::F16)) => Ok(format!("omnius_core_rocketpack::f16::from_f64_const({value}.0)")),
+        (Literal::Float(_), ResolvedType::Builtin(BuiltinType::F16)) => Ok(format!("omnius_core_rocketpack::f16::from_f64_const({})", render_literal(literal)?)),
+        _ => render_literal(literal),
     }
-
-    Ok(ResolvedType::Named(NamedType {
-        rust_path: segments.iter().map(|segment| sanitize_ident(segment)).collect(),
-        kind: NamedTypeKind::External,
-    }))
 }
 
 fn render_literal(literal: &Literal) -> Result<String, CodegenError> {
@@ -1202,153 +1589,159 @@ fn render_literal(literal: &Literal) -> Result<String, CodegenError> {
     })
 }
 
-fn discover_source_files(root_dir: &FsPath, sources: &[SourceConfig]) -> Result<Vec<DiscoveredSource>, CodegenError> {
-    let mut discovered = BTreeMap::<PathBuf, DiscoveredSource>::new();
-
-    for source in sources {
-        let base_dir = root_dir.join(&source.base_dir);
-        if !base_dir.exists() {
-            return Err(CodegenError::Other(format!("source base_dir not found: {}", base_dir.display())));
-        }
+pub(super) fn sanitize_ident(value: &str) -> String {
+    match value {
+        "type" | "const" | "struct" | "enum" | "fn" | "mod" | "use" | "crate" | "super" | "self" | "match" | "loop" | "for" | "while" | "in" | "where" | "impl" | "trait"
+        | "move" | "async" | "await" | "ref" | "mut" | "pub" | "let" | "break" | "continue" | "return" => format!("{value}_"),
+        _ => value.to_string(),
+    }
+}
 
-        if !base_dir.is_dir() {
-            return Err(CodegenError::Other(format!("source base_dir is not a directory: {}", base_dir.display())));
-        }
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
 
-        let mut relative_paths = Vec::new();
-        collect_relative_files(&base_dir, &base_dir, &mut relative_paths)?;
+    use testresult::TestResult;
 
-        for relative_path in relative_paths {
-            let normalized = normalize_path(&relative_path);
-            let included = source.includes.is_empty() || source.includes.iter().any(|pattern| glob_matches(pattern, &normalized));
-            let excluded = source.excludes.iter().any(|pattern| glob_matches(pattern, &normalized));
+    use super::*;
+    use crate::parser;
 
-            if !included || excluded {
-                continue;
-            }
-
-            let absolute_path = base_dir.join(&relative_path);
-            discovered.entry(absolute_path.clone()).or_insert_with(|| DiscoveredSource {
-                base_dir: base_dir.clone(),
-                absolute_path,
-                relative_path,
-            });
-        }
+    fn render(source: &str, options: &RustOptions) -> Result<String, CodegenError> {
+        Ok(render_files(&[("sample.rpf", source)], options)?.remove(0))
     }
 
-    Ok(discovered.into_values().collect())
-}
-
-fn collect_relative_files(base_dir: &FsPath, current_dir: &FsPath, out: &mut Vec<PathBuf>) -> Result<(), CodegenError> {
-    for entry in fs::read_dir(current_dir).map_err(|err| CodegenError::Other(format!("failed to read directory: {}: {}", current_dir.display(), err)))? {
-        let entry = entry.map_err(|err| CodegenError::Other(format!("failed to read directory entry: {}: {}", current_dir.display(), err)))?;
-
-        let path = entry.path();
-        if path.is_dir() {
-            collect_relative_files(base_dir, &path, out)?;
-        } else if path.is_file() {
-            let relative_path = path
-                .strip_prefix(base_dir)
-                .map_err(|err| CodegenError::Other(format!("failed to build relative path: {}: {}", path.display(), err)))?
-                .to_path_buf();
-            out.push(relative_path);
-        }
+    fn render_files(sources: &[(&str, &str)], options: &RustOptions) -> Result<Vec<String>, CodegenError> {
+        let parsed_sources = sources
+            .iter()
+            .map(|(name, source)| {
+                Ok(ParsedSource {
+                    source: DiscoveredSource {
+                        base_dir: PathBuf::from("."),
+                        absolute_path: PathBuf::from(".").join(name),
+                        relative_path: PathBuf::from(name),
+                    },
+                    text: source.to_string(),
+                    file: parser::parse_source(name, source)?,
+                })
+            })
+            .collect::<Result<Vec<_>, CodegenError>>()?;
+        Ok(render_sources(&parsed_sources, |_| Ok(options.clone()))?.into_iter().map(|file| file.contents).collect())
     }
 
-    Ok(())
-}
+    #[test]
+    fn render_service_test() -> TestResult {
+        let rendered = render(
+            r#"
+version 1;
 
-fn normalize_path(path: &FsPath) -> String {
-    path.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+struct Request {
+  @1 id: u64;
 }
 
-fn path_segments(path: &AstPath) -> Vec<String> {
-    path.segments.iter().map(|segment| segment.value.clone()).collect()
+struct Response {
+  @1 name: string;
 }
 
-fn builtin_type(path: &AstPath) -> Option<BuiltinType> {
-    let segments = path_segments(path);
-    if segments.len() != 1 {
-        return None;
+@2 service Users {
+  @1 rpc Get(Request) returns (Response);
+  @3 rpc Upload(stream Request) returns (Response);
+  @4 rpc Watch(Request) returns (stream Response);
+}
+"#,
+            &RustOptions::default(),
+        )?;
+        println!("{rendered}");
+
+        // The service id fills the high half of the function id, so services sharing a listener do not collide.
+        assert!(rendered.contains("OmniRemotingCaller::new(stream, self.max_frame_length, 0x0002_0001).await?;"));
+        assert!(rendered.contains("        0x0002_0001 => {"));
+        assert!(rendered.contains("        0x0002_0004 => {"));
+
+        assert!(rendered.contains("    pub async fn get<T>(&self, stream: T, request: Request) -> omnius_core_omnikit::Result<Response>"));
+        assert!(rendered.contains(
+            "    pub async fn upload<T>(&self, stream: T) -> omnius_core_omnikit::Result<omnius_core_omnikit::service::remoting::OmniRemotingChannel<Request, Response>>"
+        ));
+        assert!(rendered.contains(
+            "    fn watch(&self, request: Request, channel: &omnius_core_omnikit::service::remoting::OmniRemotingChannel<Response, Request>) -> impl std::future::Future<Output = omnius_core_omnikit::Result<()>> + Send;"
+        ));
+
+        // Streaming rpcs end with `finish` once the server method returns; unary rpcs just answer.
+        assert!(rendered.contains("                        server.upload(&channel).await?;\n                        channel.finish().await\n"));
+        assert!(rendered.contains("                        server.watch(request, &channel).await?;\n                        channel.finish().await\n"));
+        assert!(rendered.contains("                        let response = server.get(request).await?;\n                        stream.send(response).await\n"));
+
+        Ok(())
     }
 
-    match segments[0].as_str() {
-        "bool" => Some(BuiltinType::Bool),
-        "u8" => Some(BuiltinType::U8),
-        "u16" => Some(BuiltinType::U16),
-        "u32" => Some(BuiltinType::U32),
-        "u64" => Some(BuiltinType::U64),
-        "u128" => Some(BuiltinType::U128),
-        "i8" => Some(BuiltinType::I8),
-        "i16" => Some(BuiltinType::I16),
-        "i32" => Some(BuiltinType::I32),
-        "i64" => Some(BuiltinType::I64),
-        "i128" => Some(BuiltinType::I128),
-        "f32" => Some(BuiltinType::F32),
-        "f64" => Some(BuiltinType::F64),
-        "string" => Some(BuiltinType::String),
-        "bytes" => Some(BuiltinType::Bytes),
-        _ => None,
-    }
-}
+    #[test]
+    fn render_map_decode_order_test() -> TestResult {
+        let rendered = render(
+            r#"
+version 1;
 
-fn glob_matches(pattern: &str, candidate: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let candidate_chars: Vec<char> = candidate.chars().collect();
-    let mut memo = BTreeMap::<(usize, usize), bool>::new();
-    glob_matches_inner(&pattern_chars, &candidate_chars, 0, 0, &mut memo)
+struct Groups {
+  @1 members: Map<string, Vec<u32>>;
 }
+"#,
+            &RustOptions::default(),
+        )?;
 
-fn glob_matches_inner(pattern: &[char], candidate: &[char], pattern_index: usize, candidate_index: usize, memo: &mut BTreeMap<(usize, usize), bool>) -> bool {
-    if let Some(value) = memo.get(&(pattern_index, candidate_index)) {
-        return *value;
-    }
+        // The key precedes its value on the wire, so it has to be read before the loop over the value's elements.
+        let key_position = rendered.find("let __key_2 = decoder.read_string()?;").ok_or("missing key binding")?;
+        let value_position = rendered.find("let __count_3 = decoder.read_array()?;").ok_or("missing value decode")?;
+        assert!(key_position < value_position);
+        assert!(rendered.contains("__map_1.insert(__key_2, __values_4);"));
 
-    let result = if pattern_index == pattern.len() {
-        candidate_index == candidate.len()
-    } else if pattern[pattern_index] == '*' {
-        let mut next_index = pattern_index;
-        while next_index < pattern.len() && pattern[next_index] == '*' {
-            next_index += 1;
-        }
-
-        let is_double_star = next_index - pattern_index >= 2;
-        if is_double_star {
-            let mut matched = glob_matches_inner(pattern, candidate, next_index, candidate_index, memo);
-
-            if !matched && next_index < pattern.len() && pattern[next_index] == '/' {
-                matched = glob_matches_inner(pattern, candidate, next_index + 1, candidate_index, memo);
-            }
+        Ok(())
+    }
 
-            if !matched && candidate_index < candidate.len() {
-                matched = glob_matches_inner(pattern, candidate, pattern_index, candidate_index + 1, memo);
-            }
+    #[test]
+    fn render_package_schema_registry_test() -> TestResult {
+        let options = RustOptions { emit_schema: true };
+        let rendered = render_files(
+            &[
+                ("a.rpf", "version 1;\npackage demo;\n\nstruct A {\n  @1 pages: Vec<Option<demo::B>>;\n}\n"),
+                ("b.rpf", "version 1;\npackage demo;\n\nstruct B {\n  @1 sizes: Map<string, [u8; 2]>;\n}\n"),
+            ],
+            &options,
+        )?;
+
+        // The first file of the package owns the registry and lists the items of both files.
+        assert!(rendered[0].contains("        &<A as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,\n"));
+        assert!(rendered[0].contains("        &<super::super::b::demo::B as omnius_core_rocketpack::RocketPackSchema>::SCHEMA,\n"));
+        assert!(rendered[1].contains("    pub use super::super::a::demo::SCHEMA_REGISTRY;\n"));
+        assert!(!rendered[1].contains("pub static SCHEMA_REGISTRY"));
+
+        assert!(rendered[0].contains(
+            "ty: omnius_core_rocketpack::TypeDescriptor::Vec(&omnius_core_rocketpack::TypeDescriptor::Option(&omnius_core_rocketpack::TypeDescriptor::Named { name: \"demo::B\", args: &[] })),"
+        ));
+        assert!(rendered[1].contains(
+            "ty: omnius_core_rocketpack::TypeDescriptor::Map(&omnius_core_rocketpack::TypeDescriptor::String, &omnius_core_rocketpack::TypeDescriptor::Array(&omnius_core_rocketpack::TypeDescriptor::U8, 2)),"
+        ));
+
+        Ok(())
+    }
 
-            matched
-        } else {
-            glob_matches_inner(pattern, candidate, pattern_index + 1, candidate_index, memo)
-                || (candidate_index < candidate.len() && candidate[candidate_index] != '/' && glob_matches_inner(pattern, candidate, pattern_index, candidate_index + 1, memo))
-        }
-    } else if pattern[pattern_index] == '?' {
-        candidate_index < candidate.len() && candidate[candidate_index] != '/' && glob_matches_inner(pattern, candidate, pattern_index + 1, candidate_index + 1, memo)
-    } else {
-        candidate_index < candidate.len()
-            && pattern[pattern_index] == candidate[candidate_index]
-            && glob_matches_inner(pattern, candidate, pattern_index + 1, candidate_index + 1, memo)
-    };
+    #[test]
+    fn render_max_len_check_test() -> TestResult {
+        let rendered = render(
+            r#"
+version 1;
 
-    memo.insert((pattern_index, candidate_index), result);
-    result
+struct Limited {
+  #[max_len = 4]
+  @1 tags: Vec<string>;
 }
+"#,
+            &RustOptions::default(),
+        )?;
 
-fn indent(level: usize) -> String {
-    "    ".repeat(level)
-}
+        // The declared length is checked before the elements are allocated or read.
+        let check_position = rendered.find("if decoder.current_len()? > 4 {").ok_or("missing max_len check")?;
+        let decode_position = rendered.find("let __count_0 = decoder.read_array()?;").ok_or("missing value decode")?;
+        assert!(check_position < decode_position);
+        assert!(rendered.contains("RocketPackDecoderError::Other(\"max_len exceeded: tags\")"));
 
-fn sanitize_ident(value: &str) -> String {
-    match value {
-        "type" | "const" | "struct" | "enum" | "fn" | "mod" | "use" | "crate" | "super" | "self" | "match" | "loop" | "for" | "while" | "in" | "where" | "impl" | "trait"
-        | "move" | "async" | "await" | "ref" | "mut" | "pub" | "let" | "break" | "continue" | "return" => format!("{value}_"),
-        _ => value.to_string(),
+        Ok(())
     }
 }
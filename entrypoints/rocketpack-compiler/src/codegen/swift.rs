@@ -0,0 +1,1014 @@
+use std::{fmt::Write as _, path::Path as FsPath};
+
+use tracing::info;
+
+use super::{
+    common::{
+        BuiltinType, DiscoveredSource, NamedType, ParsedSource, ResolvedType, SchemaIndex, builtin_type, canonical_path, ensure_no_generics, find_target, indent, normalize_path,
+        option_bool, option_string, resolve_output_path, resolve_type, to_camel_case,
+    },
+    output::OutputWriter,
+    resolver::resolve_sources,
+};
+use crate::{
+    config::{GeneratorConfig, GeneratorTargetConfig},
+    error::CodegenError,
+    parser::ast::{Const, Enum, Field, Item, Literal, Path as AstPath, Spanned, Struct, Type, TypeAlias, Variant, VariantKind},
+};
+
+// The generated code depends on a small runtime whose `RocketPackEncoder` / `RocketPackDecoder` classes mirror the Rust
+// traits method for method (`writeU32`, `readMap`, `skipField`, ...). It is written next to the generated files, so it
+// becomes part of the same module, unless `emit_runtime: false` is set; `runtime_module` imports it from another module.
+const RUNTIME_SOURCE: &str = include_str!("swift_runtime.swift");
+const RUNTIME_FILE_NAME: &str = "RocketPackRuntime.swift";
+const DEFAULT_SWIFT_VERSION: &str = "6.0";
+
+#[derive(Debug, Clone)]
+struct GeneratedSwiftFile {
+    source: DiscoveredSource,
+    emit_runtime: bool,
+    contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SwiftOptions {
+    visibility: String,
+    swift_major_version: u32,
+    runtime_module: Option<String>,
+    emit_runtime: bool,
+}
+
+impl Default for SwiftOptions {
+    fn default() -> Self {
+        Self {
+            visibility: "public".to_string(),
+            swift_major_version: parse_swift_major_version(DEFAULT_SWIFT_VERSION).unwrap_or(6),
+            runtime_module: None,
+            emit_runtime: true,
+        }
+    }
+}
+
+impl SwiftOptions {
+    fn load(conf: &GeneratorConfig, target: Option<&GeneratorTargetConfig>) -> Result<Self, CodegenError> {
+        let visibility = option_string(conf, target, "visibility")?.unwrap_or_else(|| "public".to_string());
+        if visibility != "public" && visibility != "internal" {
+            return Err(CodegenError::Other(format!("unsupported swift visibility: {visibility}")));
+        }
+
+        let swift_version = option_string(conf, target, "swift_version")?.unwrap_or_else(|| DEFAULT_SWIFT_VERSION.to_string());
+        let swift_major_version = parse_swift_major_version(&swift_version).ok_or_else(|| CodegenError::Other(format!("invalid swift_version: {swift_version}")))?;
+
+        Ok(Self {
+            visibility,
+            swift_major_version,
+            runtime_module: option_string(conf, target, "runtime_module")?,
+            emit_runtime: option_bool(conf, target, "emit_runtime")?.unwrap_or(true),
+        })
+    }
+
+    // `Int128` / `UInt128` only exist in the Swift 6 standard library.
+    fn supports_int128(&self) -> bool {
+        self.swift_major_version >= 6
+    }
+}
+
+// Accepts `6`, `6.0` and the `6.x` form used in `rocketpack.yaml`.
+fn parse_swift_major_version(swift_version: &str) -> Option<u32> {
+    swift_version.split('.').next()?.parse().ok()
+}
+
+struct SwiftContext<'a> {
+    index: &'a SchemaIndex,
+    options: &'a SwiftOptions,
+}
+
+pub(super) async fn generate(root_dir: &FsPath, parsed_sources: &[ParsedSource], conf: &GeneratorConfig, writer: &mut OutputWriter) -> Result<(), CodegenError> {
+    let generated_files = render_sources(conf, parsed_sources)?;
+    let changed_count = write_generated_files(root_dir, conf, &generated_files, writer)?;
+
+    info!(
+        generator_id = %conf.id,
+        plugin = %conf.plugin,
+        source_count = parsed_sources.len(),
+        rendered_count = generated_files.len(),
+        changed_count,
+        "generated rocketpack swift files"
+    );
+
+    Ok(())
+}
+
+fn render_sources(conf: &GeneratorConfig, parsed_sources: &[ParsedSource]) -> Result<Vec<GeneratedSwiftFile>, CodegenError> {
+    ensure_no_generics(parsed_sources, "Swift")?;
+    let indexes = resolve_sources(parsed_sources)?;
+    let mut generated_files = Vec::with_capacity(parsed_sources.len());
+
+    for (parsed_source, index) in parsed_sources.iter().zip(&indexes) {
+        let Some(target) = find_target(conf, &parsed_source.source) else {
+            info!(
+                generator_id = %conf.id,
+                source = normalize_path(&parsed_source.source.relative_path),
+                "skip swift source without matching target"
+            );
+            continue;
+        };
+
+        let options = SwiftOptions::load(conf, Some(target))?;
+        generated_files.push(GeneratedSwiftFile {
+            source: parsed_source.source.clone(),
+            emit_runtime: options.emit_runtime,
+            contents: render_swift_file(parsed_source, index, &options)?,
+        });
+    }
+
+    Ok(generated_files)
+}
+
+fn write_generated_files(root_dir: &FsPath, conf: &GeneratorConfig, generated_files: &[GeneratedSwiftFile], writer: &mut OutputWriter) -> Result<usize, CodegenError> {
+    let mut changed_count = 0usize;
+
+    for generated_file in generated_files {
+        let Some(output_path) = resolve_output_path(root_dir, conf, &generated_file.source, ".swift")? else {
+            continue;
+        };
+
+        if generated_file.emit_runtime
+            && let Some(parent_dir) = output_path.parent()
+            && writer.write(&parent_dir.join(RUNTIME_FILE_NAME), RUNTIME_SOURCE)?
+        {
+            changed_count += 1;
+        }
+
+        if writer.write(&output_path, &generated_file.contents)? {
+            changed_count += 1;
+        }
+    }
+
+    Ok(changed_count)
+}
+
+fn render_swift_file(parsed_source: &ParsedSource, index: &SchemaIndex, options: &SwiftOptions) -> Result<String, CodegenError> {
+    let ctx = SwiftContext { index, options };
+    let mut out = String::new();
+
+    writeln!(&mut out, "// @generated by rocketpack-compiler").ok();
+    if !index.package.is_empty() {
+        // Swift has no namespaces below the module, so the package is only recorded here.
+        writeln!(&mut out, "// package: {}", index.package.join("::")).ok();
+    }
+    writeln!(&mut out).ok();
+    writeln!(&mut out, "import Foundation").ok();
+    if let Some(runtime_module) = &options.runtime_module {
+        writeln!(&mut out, "import {runtime_module}").ok();
+    }
+    writeln!(&mut out).ok();
+
+    for item in &parsed_source.file.items {
+        match item {
+            Item::Struct(item) => {
+                write_struct_declaration(&mut out, &ctx, item)?;
+                writeln!(&mut out).ok();
+                write_struct_codec_extension(&mut out, &ctx, item)?;
+            }
+            Item::Enum(item) => {
+                write_enum_declaration(&mut out, &ctx, item)?;
+                writeln!(&mut out).ok();
+                write_enum_codec_extension(&mut out, &ctx, item)?;
+            }
+            Item::TypeAlias(item) => write_type_alias_declaration(&mut out, &ctx, item)?,
+            Item::Const(item) => write_const_declaration(&mut out, &ctx, item)?,
+            // Remoting stubs are only generated for Rust.
+            Item::Service(_) => continue,
+        }
+        writeln!(&mut out).ok();
+    }
+
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+
+    Ok(out)
+}
+
+// Struct fields, tuple variant elements (tagged by position) and record variant fields share one shape.
+#[derive(Debug, Clone)]
+struct SwiftField<'a> {
+    tag: usize,
+    name: &'a str,
+    ty: &'a Type,
+    default: Option<&'a Literal>,
+    resolved: ResolvedType,
+}
+
+fn resolve_fields<'a>(index: &SchemaIndex, fields: &'a [Field]) -> Result<Vec<SwiftField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for field in fields {
+        resolved.push(SwiftField {
+            tag: field.tag.value as usize,
+            name: &field.name.value,
+            ty: &field.ty.value,
+            default: field.default.as_ref().map(|default| &default.value),
+            resolved: resolve_type(index, &field.ty.value)?,
+        });
+    }
+    Ok(resolved)
+}
+
+fn resolve_tuple_fields<'a>(index: &SchemaIndex, fields: &'a [(Spanned<String>, Spanned<Type>)]) -> Result<Vec<SwiftField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for (tag, (name, ty)) in fields.iter().enumerate() {
+        resolved.push(SwiftField {
+            tag,
+            name: &name.value,
+            ty: &ty.value,
+            default: None,
+            resolved: resolve_type(index, &ty.value)?,
+        });
+    }
+    Ok(resolved)
+}
+
+fn resolve_variant_fields<'a>(index: &SchemaIndex, variant: &'a Variant) -> Result<Vec<SwiftField<'a>>, CodegenError> {
+    match &variant.kind {
+        VariantKind::Unit => Ok(Vec::new()),
+        VariantKind::Tuple(fields) => resolve_tuple_fields(index, fields),
+        VariantKind::Record(fields) => resolve_fields(index, fields),
+    }
+}
+
+// Declaration order is kept for properties and initializer parameters; the wire order is always by tag.
+fn sorted_by_tag<'a, 'b>(fields: &'b [SwiftField<'a>]) -> Vec<&'b SwiftField<'a>> {
+    let mut sorted = fields.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|field| field.tag);
+    sorted
+}
+
+fn property_name(field_name: &str) -> String {
+    sanitize_ident(&to_camel_case(field_name))
+}
+
+fn local_name(field_name: &str) -> String {
+    let name = to_camel_case(field_name);
+    match name.as_str() {
+        "encoder" | "decoder" | "value" => format!("{name}_"),
+        _ => sanitize_ident(&name),
+    }
+}
+
+// Decoded values are collected into optional `_name` slots and unwrapped into `name` afterwards, since Swift does not
+// allow rebinding a name in the same scope.
+fn storage_name(field_name: &str) -> String {
+    format!("_{}", to_camel_case(field_name))
+}
+
+fn write_struct_declaration(out: &mut String, ctx: &SwiftContext<'_>, item: &Struct) -> Result<(), CodegenError> {
+    let visibility = &ctx.options.visibility;
+    let fields = resolve_fields(ctx.index, &item.fields)?;
+
+    writeln!(out, "{} struct {}: Hashable, Sendable {{", visibility, sanitize_ident(&item.name.value)).ok();
+    for field in &fields {
+        writeln!(
+            out,
+            "{}{} var {}: {}",
+            indent(1),
+            visibility,
+            property_name(field.name),
+            render_declaration_type(ctx, field.ty)?
+        )
+        .ok();
+    }
+
+    // The memberwise initializer is internal, so a public one has to be spelled out.
+    if !fields.is_empty() {
+        writeln!(out).ok();
+    }
+    let parameters = fields.iter().map(|field| render_parameter(ctx, field)).collect::<Result<Vec<_>, _>>()?;
+    writeln!(out, "{}{} init({}) {{", indent(1), visibility, parameters.join(", ")).ok();
+    for field in &fields {
+        let name = property_name(field.name);
+        writeln!(out, "{}self.{} = {}", indent(2), name, name).ok();
+    }
+    writeln!(out, "{}}}", indent(1)).ok();
+    writeln!(out, "}}").ok();
+
+    Ok(())
+}
+
+fn render_parameter(ctx: &SwiftContext<'_>, field: &SwiftField<'_>) -> Result<String, CodegenError> {
+    let mut rendered = format!("{}: {}", property_name(field.name), render_declaration_type(ctx, field.ty)?);
+    match (field.default, &field.resolved) {
+        (_, ResolvedType::Option(_)) => rendered.push_str(" = nil"),
+        (Some(default), resolved) => {
+            write!(&mut rendered, " = {}", render_literal(default, resolved)).ok();
+        }
+        (None, _) => {}
+    }
+    Ok(rendered)
+}
+
+fn write_struct_codec_extension(out: &mut String, ctx: &SwiftContext<'_>, item: &Struct) -> Result<(), CodegenError> {
+    let visibility = &ctx.options.visibility;
+    let type_name = sanitize_ident(&item.name.value);
+    let fields = resolve_fields(ctx.index, &item.fields)?;
+    let mut temp_counter = 0usize;
+
+    writeln!(out, "extension {}: RocketPackStruct {{", type_name).ok();
+    writeln!(
+        out,
+        "{}{} static func pack(encoder: RocketPackEncoder, value: {}) throws {{",
+        indent(1),
+        visibility,
+        type_name
+    )
+    .ok();
+    write_fields_pack_body(out, ctx, "value.", &fields, 2, &mut temp_counter)?;
+    writeln!(out, "{}}}", indent(1)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}{} static func unpack(decoder: RocketPackDecoder) throws -> {} {{", indent(1), visibility, type_name).ok();
+    write_fields_unpack_body(out, ctx, &fields, 2, &mut temp_counter)?;
+    writeln!(out).ok();
+    write_initializer_call(out, &format!("return {type_name}"), &fields, 2);
+    writeln!(out, "{}}}", indent(1)).ok();
+    writeln!(out, "}}").ok();
+
+    Ok(())
+}
+
+// `prefix` is `value.` for struct properties and empty for enum payload bindings.
+fn write_fields_pack_body(out: &mut String, ctx: &SwiftContext<'_>, prefix: &str, fields: &[SwiftField<'_>], depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+    let required_count = fields.iter().filter(|field| !matches!(field.resolved, ResolvedType::Option(_))).count();
+    let has_optional = fields.iter().any(|field| matches!(field.resolved, ResolvedType::Option(_)));
+    let binding_name = |field: &SwiftField<'_>| {
+        if prefix.is_empty() {
+            local_name(field.name)
+        } else {
+            format!("{prefix}{}", property_name(field.name))
+        }
+    };
+
+    if has_optional {
+        let count_name = next_temp_name(temp_counter, "count");
+        writeln!(out, "{}var {} = {}", indent(depth), count_name, required_count).ok();
+        for field in fields {
+            if matches!(field.resolved, ResolvedType::Option(_)) {
+                writeln!(out, "{}if {} != nil {{ {} += 1 }}", indent(depth), binding_name(field), count_name).ok();
+            }
+        }
+        writeln!(out, "{}try encoder.writeMap({})", indent(depth), count_name).ok();
+    } else {
+        writeln!(out, "{}try encoder.writeMap({})", indent(depth), required_count).ok();
+    }
+
+    for field in sorted_by_tag(fields) {
+        let expr = binding_name(field);
+        match &field.resolved {
+            ResolvedType::Option(inner) => {
+                let unwrapped = next_temp_name(temp_counter, "value");
+                writeln!(out, "{}if let {} = {} {{", indent(depth), unwrapped, expr).ok();
+                writeln!(out, "{}try encoder.writeU64({})", indent(depth + 1), field.tag).ok();
+                write_encode_value(out, inner, &unwrapped, field.name, depth + 1, temp_counter)?;
+                writeln!(out, "{}}}", indent(depth)).ok();
+            }
+            resolved => {
+                writeln!(out, "{}try encoder.writeU64({})", indent(depth), field.tag).ok();
+                write_encode_value(out, resolved, &expr, field.name, depth, temp_counter)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_encode_value(out: &mut String, resolved: &ResolvedType, expr: &str, context_name: &str, depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+    match resolved {
+        ResolvedType::Builtin(builtin) => {
+            writeln!(out, "{}try encoder.{}({})", indent(depth), encoder_method(builtin), expr).ok();
+        }
+        ResolvedType::Named(_) => {
+            writeln!(out, "{}try encoder.writeStruct({})", indent(depth), expr).ok();
+        }
+        ResolvedType::Option(inner) => {
+            write_encode_value(out, inner, expr, context_name, depth, temp_counter)?;
+        }
+        ResolvedType::Vec(inner) => {
+            let item_name = next_temp_name(temp_counter, "item");
+            writeln!(out, "{}try encoder.writeArray({}.count)", indent(depth), expr).ok();
+            writeln!(out, "{}for {} in {} {{", indent(depth), item_name, expr).ok();
+            write_encode_value(out, inner, &item_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+        ResolvedType::Map(key, value) => {
+            let key_name = next_temp_name(temp_counter, "key");
+            let value_name = next_temp_name(temp_counter, "value");
+            writeln!(out, "{}try encoder.writeMap({}.count)", indent(depth), expr).ok();
+            writeln!(out, "{}for ({}, {}) in {} {{", indent(depth), key_name, value_name, render_sorted_entries(expr, key)).ok();
+            write_encode_value(out, key, &key_name, context_name, depth + 1, temp_counter)?;
+            write_encode_value(out, value, &value_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+        ResolvedType::Array(inner, len) => {
+            let item_name = next_temp_name(temp_counter, "item");
+            writeln!(
+                out,
+                "{}guard {}.count == {} else {{ throw RocketPackEncoderError.other(\"array length mismatch: {}\") }}",
+                indent(depth),
+                expr,
+                len,
+                context_name
+            )
+            .ok();
+            writeln!(out, "{}try encoder.writeArray({}.count)", indent(depth), expr).ok();
+            writeln!(out, "{}for {} in {} {{", indent(depth), item_name, expr).ok();
+            write_encode_value(out, inner, &item_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+    }
+
+    Ok(())
+}
+
+// Dictionaries are unordered, so entries are sorted the way the Rust `BTreeMap` iterates: numerically, or bytewise for
+// strings and bytes. Keys without a natural order are written in dictionary order.
+fn render_sorted_entries(expr: &str, key: &ResolvedType) -> String {
+    let comparator = match key {
+        ResolvedType::Builtin(BuiltinType::Bool) => "!$0.key && $1.key",
+        ResolvedType::Builtin(BuiltinType::String) => "$0.key.utf8.lexicographicallyPrecedes($1.key.utf8)",
+        ResolvedType::Builtin(BuiltinType::Bytes) => "$0.key.lexicographicallyPrecedes($1.key)",
+        ResolvedType::Builtin(_) => "$0.key < $1.key",
+        _ => return expr.to_string(),
+    };
+    format!("{expr}.sorted(by: {{ {comparator} }})")
+}
+
+fn encoder_method(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "writeBool",
+        BuiltinType::U8 => "writeU8",
+        BuiltinType::U16 => "writeU16",
+        BuiltinType::U32 => "writeU32",
+        BuiltinType::U64 => "writeU64",
+        BuiltinType::U128 => "writeU128",
+        BuiltinType::I8 => "writeI8",
+        BuiltinType::I16 => "writeI16",
+        BuiltinType::I32 => "writeI32",
+        BuiltinType::I64 => "writeI64",
+        BuiltinType::I128 => "writeI128",
+        BuiltinType::F16 => "writeF16",
+        BuiltinType::F32 => "writeF32",
+        BuiltinType::F64 => "writeF64",
+        BuiltinType::String => "writeString",
+        BuiltinType::Bytes => "writeBytes",
+    }
+}
+
+fn decoder_method(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "readBool",
+        BuiltinType::U8 => "readU8",
+        BuiltinType::U16 => "readU16",
+        BuiltinType::U32 => "readU32",
+        BuiltinType::U64 => "readU64",
+        BuiltinType::U128 => "readU128",
+        BuiltinType::I8 => "readI8",
+        BuiltinType::I16 => "readI16",
+        BuiltinType::I32 => "readI32",
+        BuiltinType::I64 => "readI64",
+        BuiltinType::I128 => "readI128",
+        BuiltinType::F16 => "readF16",
+        BuiltinType::F32 => "readF32",
+        BuiltinType::F64 => "readF64",
+        BuiltinType::String => "readString",
+        BuiltinType::Bytes => "readBytes",
+    }
+}
+
+fn write_fields_unpack_body(out: &mut String, ctx: &SwiftContext<'_>, fields: &[SwiftField<'_>], depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+    for field in fields {
+        writeln!(
+            out,
+            "{}var {}: {} = nil",
+            indent(depth),
+            storage_name(field.name),
+            render_storage_type(ctx, &field.resolved)?
+        )
+        .ok();
+    }
+
+    let count_name = next_temp_name(temp_counter, "count");
+    writeln!(out, "{}let {} = try decoder.readMap()", indent(depth), count_name).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}for _ in 0..<{} {{", indent(depth), count_name).ok();
+
+    if fields.is_empty() {
+        writeln!(out, "{}_ = try decoder.readU64()", indent(depth + 1)).ok();
+        writeln!(out, "{}try decoder.skipField()", indent(depth + 1)).ok();
+        writeln!(out, "{}}}", indent(depth)).ok();
+        return Ok(());
+    }
+
+    writeln!(out, "{}switch try decoder.readU64() {{", indent(depth + 1)).ok();
+    for field in sorted_by_tag(fields) {
+        let decode_target = match &field.resolved {
+            ResolvedType::Option(inner) => inner.as_ref(),
+            resolved => resolved,
+        };
+
+        writeln!(out, "{}case {}:", indent(depth + 1), field.tag).ok();
+        let value_expr = write_decode_value(out, ctx, decode_target, depth + 2, field.name, temp_counter)?;
+        writeln!(out, "{}{} = {}", indent(depth + 2), storage_name(field.name), value_expr).ok();
+    }
+    writeln!(out, "{}default:", indent(depth + 1)).ok();
+    writeln!(out, "{}try decoder.skipField()", indent(depth + 2)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    let mut has_checks = false;
+    for field in fields {
+        if matches!(field.resolved, ResolvedType::Option(_)) {
+            continue;
+        }
+        if !has_checks {
+            writeln!(out).ok();
+            has_checks = true;
+        }
+
+        let local = local_name(field.name);
+        let storage = storage_name(field.name);
+        match field.default {
+            Some(default) => {
+                writeln!(out, "{}let {} = {} ?? {}", indent(depth), local, storage, render_literal(default, &field.resolved)).ok();
+            }
+            None => {
+                writeln!(
+                    out,
+                    "{}guard let {} = {} else {{ throw RocketPackDecoderError.other(\"missing field: {}\") }}",
+                    indent(depth),
+                    local,
+                    storage,
+                    field.name
+                )
+                .ok();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_initializer_call(out: &mut String, prefix: &str, fields: &[SwiftField<'_>], depth: usize) {
+    if fields.is_empty() {
+        writeln!(out, "{}{}()", indent(depth), prefix).ok();
+        return;
+    }
+
+    writeln!(out, "{}{}(", indent(depth), prefix).ok();
+    for (position, field) in fields.iter().enumerate() {
+        let separator = if position + 1 < fields.len() { "," } else { "" };
+        let value = match field.resolved {
+            ResolvedType::Option(_) => storage_name(field.name),
+            _ => local_name(field.name),
+        };
+        writeln!(out, "{}{}: {}{}", indent(depth + 1), property_name(field.name), value, separator).ok();
+    }
+    writeln!(out, "{})", indent(depth)).ok();
+}
+
+fn render_storage_type(ctx: &SwiftContext<'_>, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    match resolved {
+        ResolvedType::Option(_) => render_type(ctx, resolved),
+        _ => Ok(format!("{}?", render_type(ctx, resolved)?)),
+    }
+}
+
+fn write_decode_value(
+    out: &mut String,
+    ctx: &SwiftContext<'_>,
+    resolved: &ResolvedType,
+    depth: usize,
+    context_name: &str,
+    temp_counter: &mut usize,
+) -> Result<String, CodegenError> {
+    match resolved {
+        ResolvedType::Builtin(builtin) => Ok(format!("try decoder.{}()", decoder_method(builtin))),
+        ResolvedType::Named(named) => Ok(format!("try decoder.readStruct({}.self)", render_named_type(named))),
+        ResolvedType::Option(inner) => write_decode_value(out, ctx, inner, depth, context_name, temp_counter),
+        ResolvedType::Vec(inner) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let values_name = next_temp_name(temp_counter, "values");
+            writeln!(out, "{}let {} = try decoder.readArray()", indent(depth), count_name).ok();
+            writeln!(out, "{}var {}: {} = []", indent(depth), values_name, render_type(ctx, resolved)?).ok();
+            writeln!(out, "{}for _ in 0..<{} {{", indent(depth), count_name).ok();
+            let inner_expr = write_decode_value(out, ctx, inner, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}.append({})", indent(depth + 1), values_name, inner_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(values_name)
+        }
+        ResolvedType::Map(key, value) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let map_name = next_temp_name(temp_counter, "map");
+            writeln!(out, "{}let {} = try decoder.readMap()", indent(depth), count_name).ok();
+            writeln!(out, "{}var {}: {} = [:]", indent(depth), map_name, render_type(ctx, resolved)?).ok();
+            writeln!(out, "{}for _ in 0..<{} {{", indent(depth), count_name).ok();
+            let key_expr = write_decode_value(out, ctx, key, depth + 1, context_name, temp_counter)?;
+            let key_name = next_temp_name(temp_counter, "key");
+            writeln!(out, "{}let {} = {}", indent(depth + 1), key_name, key_expr).ok();
+            let value_expr = write_decode_value(out, ctx, value, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}[{}] = {}", indent(depth + 1), map_name, key_name, value_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(map_name)
+        }
+        ResolvedType::Array(inner, len) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let values_name = next_temp_name(temp_counter, "values");
+            writeln!(out, "{}let {} = try decoder.readArray()", indent(depth), count_name).ok();
+            writeln!(
+                out,
+                "{}guard {} == {} else {{ throw RocketPackDecoderError.other(\"array length mismatch: {}\") }}",
+                indent(depth),
+                count_name,
+                len,
+                context_name
+            )
+            .ok();
+            writeln!(out, "{}var {}: {} = []", indent(depth), values_name, render_type(ctx, resolved)?).ok();
+            writeln!(out, "{}for _ in 0..<{} {{", indent(depth), count_name).ok();
+            let inner_expr = write_decode_value(out, ctx, inner, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}.append({})", indent(depth + 1), values_name, inner_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(values_name)
+        }
+    }
+}
+
+fn next_temp_name(counter: &mut usize, prefix: &str) -> String {
+    let current = *counter;
+    *counter += 1;
+    format!("__{}_{}", prefix, current)
+}
+
+fn write_enum_declaration(out: &mut String, ctx: &SwiftContext<'_>, item: &Enum) -> Result<(), CodegenError> {
+    writeln!(out, "{} enum {}: Hashable, Sendable {{", ctx.options.visibility, sanitize_ident(&item.name.value)).ok();
+
+    for variant in &item.variants {
+        let case_name = case_name(variant);
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        if fields.is_empty() {
+            writeln!(out, "{}case {}", indent(1), case_name).ok();
+            continue;
+        }
+
+        let parameters = fields.iter().map(|field| render_parameter(ctx, field)).collect::<Result<Vec<_>, _>>()?;
+        writeln!(out, "{}case {}({})", indent(1), case_name, parameters.join(", ")).ok();
+    }
+
+    writeln!(out, "}}").ok();
+
+    Ok(())
+}
+
+fn case_name(variant: &Variant) -> String {
+    sanitize_ident(&to_camel_case(&variant.name.value))
+}
+
+fn write_enum_codec_extension(out: &mut String, ctx: &SwiftContext<'_>, item: &Enum) -> Result<(), CodegenError> {
+    let visibility = &ctx.options.visibility;
+    let type_name = sanitize_ident(&item.name.value);
+    let mut temp_counter = 0usize;
+
+    writeln!(out, "extension {}: RocketPackStruct {{", type_name).ok();
+    writeln!(
+        out,
+        "{}{} static func pack(encoder: RocketPackEncoder, value: {}) throws {{",
+        indent(1),
+        visibility,
+        type_name
+    )
+    .ok();
+    writeln!(out, "{}try encoder.writeMap(1)", indent(2)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}switch value {{", indent(2)).ok();
+
+    for variant in &item.variants {
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        if fields.is_empty() {
+            writeln!(out, "{}case .{}:", indent(2), case_name(variant)).ok();
+        } else {
+            let bindings = fields.iter().map(|field| local_name(field.name)).collect::<Vec<_>>();
+            writeln!(out, "{}case let .{}({}):", indent(2), case_name(variant), bindings.join(", ")).ok();
+        }
+        writeln!(out, "{}try encoder.writeU64({})", indent(3), variant.tag.value).ok();
+        write_fields_pack_body(out, ctx, "", &fields, 3, &mut temp_counter)?;
+    }
+
+    writeln!(out, "{}}}", indent(2)).ok();
+    writeln!(out, "{}}}", indent(1)).ok();
+    writeln!(out).ok();
+
+    writeln!(out, "{}{} static func unpack(decoder: RocketPackDecoder) throws -> {} {{", indent(1), visibility, type_name).ok();
+    let count_name = next_temp_name(&mut temp_counter, "count");
+    writeln!(out, "{}var __result: {}? = nil", indent(2), type_name).ok();
+    writeln!(out, "{}let {} = try decoder.readMap()", indent(2), count_name).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}for _ in 0..<{} {{", indent(2), count_name).ok();
+    writeln!(out, "{}switch try decoder.readU64() {{", indent(3)).ok();
+
+    for variant in &item.variants {
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        writeln!(out, "{}case {}:", indent(3), variant.tag.value).ok();
+        write_fields_unpack_body(out, ctx, &fields, 4, &mut temp_counter)?;
+        if fields.is_empty() {
+            writeln!(out, "{}__result = .{}", indent(4), case_name(variant)).ok();
+        } else {
+            writeln!(out).ok();
+            write_initializer_call(out, &format!("__result = .{}", case_name(variant)), &fields, 4);
+        }
+    }
+
+    writeln!(out, "{}default:", indent(3)).ok();
+    writeln!(out, "{}try decoder.skipField()", indent(4)).ok();
+    writeln!(out, "{}}}", indent(3)).ok();
+    writeln!(out, "{}}}", indent(2)).ok();
+    writeln!(out).ok();
+    writeln!(
+        out,
+        "{}guard let __result = __result else {{ throw RocketPackDecoderError.other(\"missing enum variant\") }}",
+        indent(2)
+    )
+    .ok();
+    writeln!(out, "{}return __result", indent(2)).ok();
+    writeln!(out, "{}}}", indent(1)).ok();
+    writeln!(out, "}}").ok();
+
+    Ok(())
+}
+
+fn write_type_alias_declaration(out: &mut String, ctx: &SwiftContext<'_>, item: &TypeAlias) -> Result<(), CodegenError> {
+    writeln!(
+        out,
+        "{} typealias {} = {}",
+        ctx.options.visibility,
+        sanitize_ident(&item.name.value),
+        render_declaration_type(ctx, &item.ty.value)?
+    )
+    .ok();
+
+    Ok(())
+}
+
+fn write_const_declaration(out: &mut String, ctx: &SwiftContext<'_>, item: &Const) -> Result<(), CodegenError> {
+    let resolved = resolve_type(ctx.index, &item.ty.value)?;
+    writeln!(
+        out,
+        "{} let {}: {} = {}",
+        ctx.options.visibility,
+        property_name(&item.name.value),
+        render_declaration_type(ctx, &item.ty.value)?,
+        render_literal(&item.value.value, &resolved)
+    )
+    .ok();
+
+    Ok(())
+}
+
+fn render_declaration_type(ctx: &SwiftContext<'_>, ty: &Type) -> Result<String, CodegenError> {
+    Ok(match ty {
+        Type::Path(path) => render_path_type(ctx, path)?,
+        Type::Generic(..) => return Err(CodegenError::Other("generic types are not supported by the Swift generator".to_string())),
+        Type::Option(inner) => format!("{}?", render_declaration_type(ctx, inner)?),
+        Type::Vec(inner) => format!("[{}]", render_declaration_type(ctx, inner)?),
+        Type::Map(key, value) => format!("[{}: {}]", render_declaration_type(ctx, key)?, render_declaration_type(ctx, value)?),
+        Type::Array(inner, _) => format!("[{}]", render_declaration_type(ctx, inner)?),
+    })
+}
+
+fn render_path_type(ctx: &SwiftContext<'_>, path: &AstPath) -> Result<String, CodegenError> {
+    if let Some(builtin) = builtin_type(path) {
+        return Ok(render_builtin_type(ctx, &builtin)?.to_string());
+    }
+
+    // Everything lives in one Swift module, so only the declared name of the type is needed.
+    let canonical = canonical_path(ctx.index, path)?;
+    Ok(canonical.last().map(|segment| sanitize_ident(segment)).unwrap_or_default())
+}
+
+fn render_type(ctx: &SwiftContext<'_>, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    Ok(match resolved {
+        ResolvedType::Builtin(builtin) => render_builtin_type(ctx, builtin)?.to_string(),
+        ResolvedType::Named(named) => render_named_type(named),
+        ResolvedType::Option(inner) => format!("{}?", render_type(ctx, inner)?),
+        ResolvedType::Vec(inner) => format!("[{}]", render_type(ctx, inner)?),
+        ResolvedType::Map(key, value) => format!("[{}: {}]", render_type(ctx, key)?, render_type(ctx, value)?),
+        ResolvedType::Array(inner, _) => format!("[{}]", render_type(ctx, inner)?),
+    })
+}
+
+fn render_builtin_type(ctx: &SwiftContext<'_>, builtin: &BuiltinType) -> Result<&'static str, CodegenError> {
+    Ok(match builtin {
+        BuiltinType::Bool => "Bool",
+        BuiltinType::U8 => "UInt8",
+        BuiltinType::U16 => "UInt16",
+        BuiltinType::U32 => "UInt32",
+        BuiltinType::U64 => "UInt64",
+        BuiltinType::I8 => "Int8",
+        BuiltinType::I16 => "Int16",
+        BuiltinType::I32 => "Int32",
+        BuiltinType::I64 => "Int64",
+        BuiltinType::F16 => "Float16",
+        BuiltinType::F32 => "Float",
+        BuiltinType::F64 => "Double",
+        BuiltinType::String => "String",
+        BuiltinType::Bytes => "Data",
+        BuiltinType::U128 if ctx.options.supports_int128() => "UInt128",
+        BuiltinType::I128 if ctx.options.supports_int128() => "Int128",
+        BuiltinType::U128 | BuiltinType::I128 => return Err(CodegenError::Other("u128/i128 require swift_version 6 or later".to_string())),
+    })
+}
+
+fn render_named_type(named: &NamedType) -> String {
+    named.path.last().map(|segment| sanitize_ident(segment)).unwrap_or_default()
+}
+
+// Swift literals are untyped until they meet their declared type, so only bytes need a conversion.
+fn render_literal(literal: &Literal, resolved: &ResolvedType) -> String {
+    match literal {
+        Literal::Bool(value) => value.to_string(),
+        Literal::Int(value) => match resolved {
+            ResolvedType::Builtin(BuiltinType::F16 | BuiltinType::F32 | BuiltinType::F64) => format!("{value}.0"),
+            _ => value.to_string(),
+        },
+        Literal::Float(value) => {
+            let mut rendered = value.to_string();
+            if !rendered.contains('.') && !rendered.contains('e') && !rendered.contains('E') {
+                rendered.push_str(".0");
+            }
+            rendered
+        }
+        Literal::String(value) => render_string_literal(value),
+        Literal::Bytes(bytes) => {
+            let rendered = bytes.iter().map(|byte| byte.to_string()).collect::<Vec<_>>().join(", ");
+            format!("Data([{rendered}])")
+        }
+    }
+}
+
+fn render_string_literal(value: &str) -> String {
+    let mut rendered = String::with_capacity(value.len() + 2);
+    rendered.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => rendered.push_str("\\\""),
+            '\\' => rendered.push_str("\\\\"),
+            '\n' => rendered.push_str("\\n"),
+            '\r' => rendered.push_str("\\r"),
+            '\t' => rendered.push_str("\\t"),
+            '\0' => rendered.push_str("\\0"),
+            c if c.is_control() => {
+                write!(&mut rendered, "\\u{{{:x}}}", c as u32).ok();
+            }
+            c => rendered.push(c),
+        }
+    }
+    rendered.push('"');
+    rendered
+}
+
+fn sanitize_ident(value: &str) -> String {
+    match value {
+        "associatedtype" | "class" | "deinit" | "enum" | "extension" | "fileprivate" | "func" | "import" | "init" | "inout" | "internal" | "let" | "open" | "operator"
+        | "private" | "precedencegroup" | "protocol" | "public" | "rethrows" | "static" | "struct" | "subscript" | "typealias" | "var" | "break" | "case" | "catch"
+        | "continue" | "default" | "defer" | "do" | "else" | "fallthrough" | "for" | "guard" | "if" | "in" | "repeat" | "return" | "throw" | "switch" | "where" | "while"
+        | "Any" | "as" | "await" | "false" | "is" | "nil" | "self" | "Self" | "super" | "throws" | "true" | "try" | "Type" | "Protocol" => format!("`{value}`"),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use super::*;
+    use crate::parser;
+
+    fn render(source: &str, options: &SwiftOptions) -> Result<String, CodegenError> {
+        let file = parser::parse_source("test.rpf", source)?;
+        let parsed_source = ParsedSource {
+            source: DiscoveredSource {
+                base_dir: PathBuf::from("."),
+                absolute_path: PathBuf::from("./sample.rpf"),
+                relative_path: PathBuf::from("sample.rpf"),
+            },
+            text: source.to_string(),
+            file,
+        };
+        let index = resolve_sources(std::slice::from_ref(&parsed_source))?.remove(0);
+        render_swift_file(&parsed_source, &index, options)
+    }
+
+    #[test]
+    fn render_struct_test() -> TestResult {
+        let rendered = render(
+            r#"
+version 1;
+package omnius::demo::v1;
+
+struct Message {
+  @2 names: Names;
+  @1 id: u32 = 7;
+  @3 note: Option<string>;
+  @4 labels: Map<string, u8>;
+}
+
+type Names = Vec<string>;
+const MAX_SIZE: u64 = 16;
+"#,
+            &SwiftOptions::default(),
+        )?;
+        println!("{rendered}");
+
+        assert!(rendered.contains("// package: omnius::demo::v1"));
+        assert!(rendered.contains("import Foundation\n\npublic struct Message"));
+        assert!(rendered.contains("public struct Message: Hashable, Sendable {"));
+        assert!(rendered.contains("    public var names: Names"));
+        assert!(rendered.contains("    public init(names: Names, id: UInt32 = 7, note: String? = nil, labels: [String: UInt8]) {"));
+        assert!(rendered.contains("        if value.note != nil { __count_0 += 1 }"));
+        assert!(rendered.contains("        for (__key_3, __value_4) in value.labels.sorted(by: { $0.key.utf8.lexicographicallyPrecedes($1.key.utf8) }) {"));
+        assert!(rendered.contains("        let id = _id ?? 7"));
+        assert!(rendered.contains("        guard let names = _names else { throw RocketPackDecoderError.other(\"missing field: names\") }"));
+        assert!(rendered.contains("public typealias Names = [String]"));
+        assert!(rendered.contains("public let maxSize: UInt64 = 16"));
+
+        // Fields are written in tag order, like the Rust output.
+        let id_position = rendered.find("try encoder.writeU32(value.id)").ok_or("missing id")?;
+        let names_position = rendered.find("try encoder.writeArray(value.names.count)").ok_or("missing names")?;
+        assert!(id_position < names_position);
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_enum_test() -> TestResult {
+        let options = SwiftOptions {
+            visibility: "internal".to_string(),
+            swift_major_version: 5,
+            ..SwiftOptions::default()
+        };
+        let rendered = render(
+            r#"
+version 1;
+
+enum Shape {
+  @1 Empty;
+  @2 Line(length: f64, label: Option<string>);
+  @3 Rect {
+    @2 height: f64;
+    @1 width: f64 = 1;
+  };
+}
+"#,
+            &options,
+        )?;
+        println!("{rendered}");
+
+        assert!(rendered.contains("internal enum Shape: Hashable, Sendable {"));
+        assert!(rendered.contains("    case empty\n"));
+        assert!(rendered.contains("    case line(length: Double, label: String? = nil)"));
+        assert!(rendered.contains("    case rect(height: Double, width: Double = 1.0)"));
+        assert!(rendered.contains("        case let .line(length, label):"));
+        assert!(rendered.contains("                __result = .empty"));
+        assert!(rendered.contains("                __result = .rect(\n                    height: height,\n                    width: width\n                )"));
+
+        // Record variants are written in tag order, whatever the declaration order.
+        let width_position = rendered.find("try encoder.writeF64(width)").ok_or("missing width")?;
+        let height_position = rendered.find("try encoder.writeF64(height)").ok_or("missing height")?;
+        assert!(width_position < height_position);
+
+        let error = render("version 1;\nstruct Big { @1 value: u128; }", &options).err().ok_or("u128 must be rejected")?;
+        assert!(error.to_string().contains("swift_version 6"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_runtime_module_test() -> TestResult {
+        let options = SwiftOptions {
+            runtime_module: Some("RocketPackRuntime".to_string()),
+            ..SwiftOptions::default()
+        };
+        let rendered = render("version 1;\nstruct Empty {}", &options)?;
+
+        assert!(rendered.contains("import Foundation\nimport RocketPackRuntime\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_swift_major_version_test() {
+        assert_eq!(parse_swift_major_version("6.x"), Some(6));
+        assert_eq!(parse_swift_major_version("5.9"), Some(5));
+        assert_eq!(parse_swift_major_version("6"), Some(6));
+        assert_eq!(parse_swift_major_version("x"), None);
+    }
+}
@@ -0,0 +1,573 @@
+// @generated by rocketpack-compiler
+
+// Encoder and decoder for the rocketpack wire format, byte-compatible with `RocketPackBytesEncoder` /
+// `RocketPackBytesDecoder`. The methods mirror the Rust `RocketPackEncoder` / `RocketPackDecoder` traits.
+
+import Foundation
+
+private let tagPositiveBignum: UInt64 = 2
+private let tagNegativeBignum: UInt64 = 3
+private let maxDepth = 128
+
+public enum RocketPackEncoderError: Error, Equatable, Sendable {
+    case other(String)
+}
+
+public enum RocketPackDecoderError: Error, Equatable, Sendable {
+    case unexpectedEof(position: Int)
+    case mismatchFieldType(position: Int, expected: String)
+    case outOfRange(position: Int, type: String)
+    case lengthOverflow(position: Int)
+    case invalidUtf8(position: Int)
+    case depthLimitExceeded(position: Int, limit: Int)
+    case other(String)
+}
+
+public protocol RocketPackStruct {
+    static func pack(encoder: RocketPackEncoder, value: Self) throws
+    static func unpack(decoder: RocketPackDecoder) throws -> Self
+}
+
+public final class RocketPackEncoder {
+    private var buf: [UInt8] = []
+
+    public init() {
+        buf.reserveCapacity(64)
+    }
+
+    public static func encode<T: RocketPackStruct>(_ value: T) throws -> Data {
+        let encoder = RocketPackEncoder()
+        try T.pack(encoder: encoder, value: value)
+        return encoder.toData()
+    }
+
+    public func toData() -> Data {
+        Data(buf)
+    }
+
+    public func writeBool(_ value: Bool) throws {
+        buf.append(value ? 0xf5 : 0xf4)
+    }
+
+    public func writeU8(_ value: UInt8) throws {
+        writeHead(0, UInt64(value))
+    }
+
+    public func writeU16(_ value: UInt16) throws {
+        writeHead(0, UInt64(value))
+    }
+
+    public func writeU32(_ value: UInt32) throws {
+        writeHead(0, UInt64(value))
+    }
+
+    public func writeU64(_ value: UInt64) throws {
+        writeHead(0, value)
+    }
+
+    public func writeI8(_ value: Int8) throws {
+        try writeI64(Int64(value))
+    }
+
+    public func writeI16(_ value: Int16) throws {
+        try writeI64(Int64(value))
+    }
+
+    public func writeI32(_ value: Int32) throws {
+        try writeI64(Int64(value))
+    }
+
+    public func writeI64(_ value: Int64) throws {
+        if value >= 0 {
+            writeHead(0, UInt64(value))
+        } else {
+            writeHead(1, UInt64(~value))
+        }
+    }
+
+    #if compiler(>=6.0)
+    // Values that fit in 64 bits are plain integers, larger ones a positive bignum (tag 2).
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    public func writeU128(_ value: UInt128) throws {
+        if value <= UInt128(UInt64.max) {
+            writeHead(0, UInt64(value))
+            return
+        }
+
+        writeHead(6, tagPositiveBignum)
+        writeBignum(value)
+    }
+
+    // Values in the `Int64` range are plain integers, others a positive or negative bignum (tag 2 or 3).
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    public func writeI128(_ value: Int128) throws {
+        if value >= Int128(Int64.min) && value <= Int128(Int64.max) {
+            try writeI64(Int64(value))
+            return
+        }
+        if value >= 0 {
+            try writeU128(UInt128(value))
+            return
+        }
+
+        writeHead(6, tagNegativeBignum)
+        writeBignum(UInt128(~value))
+    }
+
+    // Big-endian bytes without leading zeros, as bignums are written in preferred serialization.
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    private func writeBignum(_ value: UInt128) {
+        var bytes: [UInt8] = []
+        var rest = value
+        while rest != 0 {
+            bytes.insert(UInt8(truncatingIfNeeded: rest), at: 0)
+            rest >>= 8
+        }
+        writeHead(2, UInt64(bytes.count))
+        buf.append(contentsOf: bytes)
+    }
+    #endif
+
+    #if !(os(macOS) && arch(x86_64))
+    @available(macOS 11.0, iOS 14.0, watchOS 7.0, tvOS 14.0, *)
+    public func writeF16(_ value: Float16) throws {
+        buf.append(0xf9)
+        appendBigEndian(value.bitPattern)
+    }
+    #endif
+
+    public func writeF32(_ value: Float) throws {
+        buf.append(0xfa)
+        appendBigEndian(value.bitPattern)
+    }
+
+    public func writeF64(_ value: Double) throws {
+        buf.append(0xfb)
+        appendBigEndian(value.bitPattern)
+    }
+
+    public func writeBytes(_ value: Data) throws {
+        writeHead(2, UInt64(value.count))
+        buf.append(contentsOf: value)
+    }
+
+    public func writeString(_ value: String) throws {
+        let bytes = Array(value.utf8)
+        writeHead(3, UInt64(bytes.count))
+        buf.append(contentsOf: bytes)
+    }
+
+    public func writeArray(_ len: Int) throws {
+        writeHead(4, UInt64(len))
+    }
+
+    public func writeMap(_ len: Int) throws {
+        writeHead(5, UInt64(len))
+    }
+
+    public func writeNull() throws {
+        buf.append(0xf6)
+    }
+
+    public func writeTag(_ tag: UInt64) throws {
+        writeHead(6, tag)
+    }
+
+    public func writeStruct<T: RocketPackStruct>(_ value: T) throws {
+        try T.pack(encoder: self, value: value)
+    }
+
+    // Writes the major type with the argument in its shortest form.
+    private func writeHead(_ major: UInt8, _ value: UInt64) {
+        let prefix = major << 5
+        if value <= 23 {
+            buf.append(prefix | UInt8(value))
+        } else if value <= UInt64(UInt8.max) {
+            buf.append(prefix | 24)
+            buf.append(UInt8(value))
+        } else if value <= UInt64(UInt16.max) {
+            buf.append(prefix | 25)
+            appendBigEndian(UInt16(value))
+        } else if value <= UInt64(UInt32.max) {
+            buf.append(prefix | 26)
+            appendBigEndian(UInt32(value))
+        } else {
+            buf.append(prefix | 27)
+            appendBigEndian(value)
+        }
+    }
+
+    private func appendBigEndian<T: FixedWidthInteger>(_ value: T) {
+        withUnsafeBytes(of: value.bigEndian) { buf.append(contentsOf: $0) }
+    }
+}
+
+public final class RocketPackDecoder {
+    private let buf: [UInt8]
+    private var pos = 0
+    private var depth = 0
+
+    public init(_ bytes: Data) {
+        buf = [UInt8](bytes)
+    }
+
+    public static func decode<T: RocketPackStruct>(_ type: T.Type, from bytes: Data) throws -> T {
+        try T.unpack(decoder: RocketPackDecoder(bytes))
+    }
+
+    public var position: Int {
+        pos
+    }
+
+    public var remaining: Int {
+        buf.count - pos
+    }
+
+    public func readBool() throws -> Bool {
+        let start = pos
+        switch try readByte() {
+        case 0xf4:
+            return false
+        case 0xf5:
+            return true
+        default:
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "bool")
+        }
+    }
+
+    public func readU8() throws -> UInt8 {
+        try UInt8(readUnsigned(UInt64(UInt8.max), "u8"))
+    }
+
+    public func readU16() throws -> UInt16 {
+        try UInt16(readUnsigned(UInt64(UInt16.max), "u16"))
+    }
+
+    public func readU32() throws -> UInt32 {
+        try UInt32(readUnsigned(UInt64(UInt32.max), "u32"))
+    }
+
+    public func readU64() throws -> UInt64 {
+        try readUnsigned(UInt64.max, "u64")
+    }
+
+    public func readI8() throws -> Int8 {
+        try Int8(readSigned(Int64(Int8.min), Int64(Int8.max), "i8"))
+    }
+
+    public func readI16() throws -> Int16 {
+        try Int16(readSigned(Int64(Int16.min), Int64(Int16.max), "i16"))
+    }
+
+    public func readI32() throws -> Int32 {
+        try Int32(readSigned(Int64(Int32.min), Int64(Int32.max), "i32"))
+    }
+
+    public func readI64() throws -> Int64 {
+        try readSigned(Int64.min, Int64.max, "i64")
+    }
+
+    #if compiler(>=6.0)
+    // Reads a plain unsigned integer or a positive bignum (tag 2).
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    public func readU128() throws -> UInt128 {
+        let start = pos
+        guard try peekMajor() == 6 else {
+            return try UInt128(readU64())
+        }
+
+        guard try readTag() == tagPositiveBignum else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "u128")
+        }
+        return try readBignum(start, "u128")
+    }
+
+    // Reads a plain integer or a positive or negative bignum (tag 2 or 3).
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    public func readI128() throws -> Int128 {
+        let start = pos
+        switch try peekMajor() {
+        case 0:
+            return try Int128(readU64())
+        case 1:
+            let (_, argument) = try readHead()
+            return ~Int128(argument)
+        case 6:
+            let tag = try readTag()
+            guard tag == tagPositiveBignum || tag == tagNegativeBignum else {
+                throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "i128")
+            }
+            let magnitude = try readBignum(start, "i128")
+            guard magnitude <= UInt128(Int128.max) else {
+                throw RocketPackDecoderError.outOfRange(position: start, type: "i128")
+            }
+            return tag == tagPositiveBignum ? Int128(magnitude) : ~Int128(magnitude)
+        default:
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "i128")
+        }
+    }
+
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    private func readBignum(_ start: Int, _ name: String) throws -> UInt128 {
+        var value: UInt128 = 0
+        for byte in try readBytes() {
+            guard value >> 120 == 0 else {
+                throw RocketPackDecoderError.outOfRange(position: start, type: name)
+            }
+            value = value << 8 | UInt128(byte)
+        }
+        return value
+    }
+    #endif
+
+    #if !(os(macOS) && arch(x86_64))
+    @available(macOS 11.0, iOS 14.0, watchOS 7.0, tvOS 14.0, *)
+    public func readF16() throws -> Float16 {
+        let start = pos
+        guard try readByte() == 0xf9 else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "f16")
+        }
+        return try Float16(bitPattern: readBigEndian(UInt16.self))
+    }
+    #endif
+
+    // Narrower floats are widened, as the Rust decoder does.
+    public func readF32() throws -> Float {
+        let start = pos
+        switch try readByte() {
+        case 0xf9:
+            return try halfToFloat(readBigEndian(UInt16.self))
+        case 0xfa:
+            return try Float(bitPattern: readBigEndian(UInt32.self))
+        default:
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "f32")
+        }
+    }
+
+    public func readF64() throws -> Double {
+        let start = pos
+        switch try readByte() {
+        case 0xf9:
+            return try Double(halfToFloat(readBigEndian(UInt16.self)))
+        case 0xfa:
+            return try Double(Float(bitPattern: readBigEndian(UInt32.self)))
+        case 0xfb:
+            return try Double(bitPattern: readBigEndian(UInt64.self))
+        default:
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "f64")
+        }
+    }
+
+    public func readBytes() throws -> Data {
+        let len = try readLength(2, "bytes", 1)
+        return try Data(readRaw(len))
+    }
+
+    public func readString() throws -> String {
+        let start = pos
+        let len = try readLength(3, "string", 1)
+        let bytes = try readRaw(len)
+        // Decoding replaces invalid sequences, so anything that does not round-trip was not valid UTF-8.
+        let value = String(decoding: bytes, as: UTF8.self)
+        guard value.utf8.elementsEqual(bytes) else {
+            throw RocketPackDecoderError.invalidUtf8(position: start)
+        }
+        return value
+    }
+
+    public func readArray() throws -> Int {
+        try readLength(4, "array", 1)
+    }
+
+    public func readMap() throws -> Int {
+        try readLength(5, "map", 2)
+    }
+
+    public func readNull() throws {
+        let start = pos
+        guard try readByte() == 0xf6 else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "null")
+        }
+    }
+
+    public func readTag() throws -> UInt64 {
+        let start = pos
+        let (major, argument) = try readHead()
+        guard major == 6 else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "tag")
+        }
+        return argument
+    }
+
+    public func readStruct<T: RocketPackStruct>(_ type: T.Type) throws -> T {
+        guard depth < maxDepth else {
+            throw RocketPackDecoderError.depthLimitExceeded(position: pos, limit: maxDepth)
+        }
+
+        depth += 1
+        defer { depth -= 1 }
+        return try T.unpack(decoder: self)
+    }
+
+    public func skipField() throws {
+        var remain = 1
+
+        while remain > 0 {
+            let start = pos
+            let major = try peekMajor()
+
+            if major == 7 {
+                let info = buf[pos] & 0x1f
+                pos += 1
+                switch info {
+                case 20, 21, 22:
+                    break
+                case 25:
+                    _ = try readRaw(2)
+                case 26:
+                    _ = try readRaw(4)
+                case 27:
+                    _ = try readRaw(8)
+                default:
+                    throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "field")
+                }
+            } else {
+                let (_, argument) = try readHead()
+                switch major {
+                case 2, 3:
+                    _ = try readRaw(toLength(start, argument))
+                case 4:
+                    remain += try toLength(start, argument)
+                case 5:
+                    remain += try toLength(start, argument) * 2
+                // The tagged value follows the tag and still has to be skipped.
+                case 6:
+                    remain += 1
+                default:
+                    break
+                }
+            }
+
+            remain -= 1
+        }
+    }
+
+    // Reads a collection or string header, rejecting lengths the remaining input cannot hold.
+    private func readLength(_ expectedMajor: UInt8, _ name: String, _ minItemSize: Int) throws -> Int {
+        let start = pos
+        let (major, argument) = try readHead()
+        guard major == expectedMajor else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: name)
+        }
+        guard argument <= UInt64(remaining / minItemSize) else {
+            throw RocketPackDecoderError.unexpectedEof(position: start)
+        }
+        return Int(argument)
+    }
+
+    private func readUnsigned(_ max: UInt64, _ name: String) throws -> UInt64 {
+        let start = pos
+        let (major, argument) = try readHead()
+        guard major == 0 else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: name)
+        }
+        guard argument <= max else {
+            throw RocketPackDecoderError.outOfRange(position: start, type: name)
+        }
+        return argument
+    }
+
+    private func readSigned(_ min: Int64, _ max: Int64, _ name: String) throws -> Int64 {
+        let start = pos
+        let (major, argument) = try readHead()
+        guard major == 0 || major == 1 else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: name)
+        }
+        guard argument <= UInt64(Int64.max) else {
+            throw RocketPackDecoderError.outOfRange(position: start, type: name)
+        }
+
+        let value = major == 0 ? Int64(argument) : ~Int64(argument)
+        guard value >= min && value <= max else {
+            throw RocketPackDecoderError.outOfRange(position: start, type: name)
+        }
+        return value
+    }
+
+    // Reads an initial byte of major type 0-6 and its argument.
+    private func readHead() throws -> (UInt8, UInt64) {
+        let start = pos
+        let initial = try readByte()
+        let major = initial >> 5
+        let info = initial & 0x1f
+        guard major != 7 else {
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "integer")
+        }
+
+        switch info {
+        case 0...23:
+            return (major, UInt64(info))
+        case 24:
+            return try (major, UInt64(readByte()))
+        case 25:
+            return try (major, UInt64(readBigEndian(UInt16.self)))
+        case 26:
+            return try (major, UInt64(readBigEndian(UInt32.self)))
+        case 27:
+            return try (major, readBigEndian(UInt64.self))
+        default:
+            throw RocketPackDecoderError.mismatchFieldType(position: start, expected: "integer")
+        }
+    }
+
+    private func peekMajor() throws -> UInt8 {
+        guard pos < buf.count else {
+            throw RocketPackDecoderError.unexpectedEof(position: pos)
+        }
+        return buf[pos] >> 5
+    }
+
+    private func readByte() throws -> UInt8 {
+        guard pos < buf.count else {
+            throw RocketPackDecoderError.unexpectedEof(position: pos)
+        }
+        defer { pos += 1 }
+        return buf[pos]
+    }
+
+    private func readRaw(_ len: Int) throws -> ArraySlice<UInt8> {
+        guard len <= remaining else {
+            throw RocketPackDecoderError.unexpectedEof(position: pos)
+        }
+        defer { pos += len }
+        return buf[pos..<(pos + len)]
+    }
+
+    private func readBigEndian<T: FixedWidthInteger>(_ type: T.Type) throws -> T {
+        try readRaw(MemoryLayout<T>.size).reduce(T.zero) { $0 << 8 | T($1) }
+    }
+
+    private func toLength(_ start: Int, _ value: UInt64) throws -> Int {
+        guard value <= UInt64(Int32.max) else {
+            throw RocketPackDecoderError.lengthOverflow(position: start)
+        }
+        return Int(value)
+    }
+
+    // Converted by hand, since `Float16` is not available on every platform the other readers support.
+    private func halfToFloat(_ bits: UInt16) -> Float {
+        let exponent = Int((bits >> 10) & 0x1f)
+        let mantissa = bits & 0x3ff
+
+        let magnitude: Float
+        switch exponent {
+        case 0:
+            magnitude = Float(mantissa) * 0x1p-24
+        case 0x1f:
+            magnitude = mantissa == 0 ? .infinity : .nan
+        default:
+            magnitude = Float(mantissa | 0x400) * Float(sign: .plus, exponent: exponent - 25, significand: 1)
+        }
+        return bits & 0x8000 != 0 ? -magnitude : magnitude
+    }
+}
@@ -0,0 +1,1056 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    path::{Path as FsPath, PathBuf},
+};
+
+use tracing::info;
+
+use super::{
+    common::{
+        BuiltinType, DiscoveredSource, NamedType, ParsedSource, ResolvedType, SchemaIndex, builtin_type, canonical_path, ensure_no_generics, find_attribute, find_target, indent,
+        normalize_path, option_bool, option_string, resolve_output_path, resolve_type, to_camel_case,
+    },
+    output::OutputWriter,
+    resolver::resolve_sources,
+};
+use crate::{
+    config::{GeneratorConfig, GeneratorTargetConfig},
+    error::CodegenError,
+    parser::ast::{AttributeArgs, Const, Enum, Field, Item, Literal, Path as AstPath, Spanned, Struct, Type, TypeAlias, Variant, VariantKind},
+};
+
+// The generated code imports its encoder / decoder from a small runtime module that is written next to it unless
+// `emit_runtime: false` is set, e.g. when `runtime_import` points at a shared copy.
+const RUNTIME_SOURCE: &str = include_str!("typescript_runtime.ts");
+const RUNTIME_FILE_NAME: &str = "rocketpack_runtime.ts";
+const DEFAULT_RUNTIME_IMPORT: &str = "./rocketpack_runtime";
+
+// Enum values are discriminated unions over this property.
+const DISCRIMINATOR: &str = "kind";
+
+#[derive(Debug, Clone)]
+struct GeneratedTypeScriptFile {
+    source: DiscoveredSource,
+    emit_runtime: bool,
+    contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TypeScriptOptions {
+    runtime_import: String,
+    emit_runtime: bool,
+}
+
+impl Default for TypeScriptOptions {
+    fn default() -> Self {
+        Self {
+            runtime_import: DEFAULT_RUNTIME_IMPORT.to_string(),
+            emit_runtime: true,
+        }
+    }
+}
+
+impl TypeScriptOptions {
+    fn load(conf: &GeneratorConfig, target: Option<&GeneratorTargetConfig>) -> Result<Self, CodegenError> {
+        Ok(Self {
+            runtime_import: option_string(conf, target, "runtime_import")?.unwrap_or_else(|| DEFAULT_RUNTIME_IMPORT.to_string()),
+            emit_runtime: option_bool(conf, target, "emit_runtime")?.unwrap_or(true),
+        })
+    }
+}
+
+struct TypeScriptContext<'a> {
+    index: &'a SchemaIndex,
+    source: &'a DiscoveredSource,
+    // Output paths of every source with a typescript target, keyed by the absolute source path.
+    output_paths: &'a BTreeMap<PathBuf, PathBuf>,
+    // Runtime symbols referenced by the rendered code; `type` marks the ones only used in type positions.
+    imports: RefCell<BTreeSet<(&'static str, bool)>>,
+    // Modules of other source files, imported as namespaces and keyed by their module specifier.
+    modules: RefCell<BTreeMap<String, String>>,
+}
+
+impl TypeScriptContext<'_> {
+    fn use_runtime(&self, name: &'static str) -> &'static str {
+        self.imports.borrow_mut().insert((name, false));
+        name
+    }
+
+    fn use_runtime_type(&self, name: &'static str) -> &'static str {
+        self.imports.borrow_mut().insert((name, true));
+        name
+    }
+
+    fn use_module(&self, origin: &DiscoveredSource) -> Result<String, CodegenError> {
+        let missing_target = |source: &DiscoveredSource| CodegenError::Other(format!("no typescript target for imported source: {}", normalize_path(&source.relative_path)));
+        let from = self.output_paths.get(&self.source.absolute_path).ok_or_else(|| missing_target(self.source))?;
+        let to = self.output_paths.get(&origin.absolute_path).ok_or_else(|| missing_target(origin))?;
+        let specifier = relative_module_specifier(from, to);
+
+        let mut modules = self.modules.borrow_mut();
+        if let Some(namespace) = modules.get(&specifier) {
+            return Ok(namespace.clone());
+        }
+
+        let file_stem = origin.relative_path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+        let base_name = sanitize_ident(&file_stem);
+        let mut namespace = base_name.clone();
+        let mut suffix = 2usize;
+        while self.index.user_types.contains_key(&namespace) || modules.values().any(|current| *current == namespace) {
+            namespace = format!("{base_name}_{suffix}");
+            suffix += 1;
+        }
+
+        modules.insert(specifier, namespace.clone());
+        Ok(namespace)
+    }
+}
+
+pub(super) async fn generate(root_dir: &FsPath, parsed_sources: &[ParsedSource], conf: &GeneratorConfig, writer: &mut OutputWriter) -> Result<(), CodegenError> {
+    let generated_files = render_sources(conf, parsed_sources)?;
+    let changed_count = write_generated_files(root_dir, conf, &generated_files, writer)?;
+
+    info!(
+        generator_id = %conf.id,
+        plugin = %conf.plugin,
+        source_count = parsed_sources.len(),
+        rendered_count = generated_files.len(),
+        changed_count,
+        "generated rocketpack typescript files"
+    );
+
+    Ok(())
+}
+
+fn render_sources(conf: &GeneratorConfig, parsed_sources: &[ParsedSource]) -> Result<Vec<GeneratedTypeScriptFile>, CodegenError> {
+    ensure_no_generics(parsed_sources, "TypeScript")?;
+    let indexes = resolve_sources(parsed_sources)?;
+    let output_paths = collect_output_paths(conf, parsed_sources)?;
+    let mut generated_files = Vec::with_capacity(parsed_sources.len());
+
+    for (parsed_source, index) in parsed_sources.iter().zip(&indexes) {
+        let Some(target) = find_target(conf, &parsed_source.source) else {
+            info!(
+                generator_id = %conf.id,
+                source = normalize_path(&parsed_source.source.relative_path),
+                "skip typescript source without matching target"
+            );
+            continue;
+        };
+
+        let options = TypeScriptOptions::load(conf, Some(target))?;
+        generated_files.push(GeneratedTypeScriptFile {
+            source: parsed_source.source.clone(),
+            emit_runtime: options.emit_runtime,
+            contents: render_typescript_file(parsed_source, index, &output_paths, &options)?,
+        });
+    }
+
+    Ok(generated_files)
+}
+
+// Imports between generated files are relative, so the paths only need a common root.
+fn collect_output_paths(conf: &GeneratorConfig, parsed_sources: &[ParsedSource]) -> Result<BTreeMap<PathBuf, PathBuf>, CodegenError> {
+    let mut output_paths = BTreeMap::new();
+    for parsed_source in parsed_sources {
+        if let Some(output_path) = resolve_output_path(FsPath::new(""), conf, &parsed_source.source, ".ts")? {
+            output_paths.insert(parsed_source.source.absolute_path.clone(), output_path);
+        }
+    }
+    Ok(output_paths)
+}
+
+fn write_generated_files(root_dir: &FsPath, conf: &GeneratorConfig, generated_files: &[GeneratedTypeScriptFile], writer: &mut OutputWriter) -> Result<usize, CodegenError> {
+    let mut changed_count = 0usize;
+
+    for generated_file in generated_files {
+        let Some(output_path) = resolve_output_path(root_dir, conf, &generated_file.source, ".ts")? else {
+            continue;
+        };
+
+        if generated_file.emit_runtime
+            && let Some(parent_dir) = output_path.parent()
+            && writer.write(&parent_dir.join(RUNTIME_FILE_NAME), RUNTIME_SOURCE)?
+        {
+            changed_count += 1;
+        }
+
+        if writer.write(&output_path, &generated_file.contents)? {
+            changed_count += 1;
+        }
+    }
+
+    Ok(changed_count)
+}
+
+fn render_typescript_file(
+    parsed_source: &ParsedSource,
+    index: &SchemaIndex,
+    output_paths: &BTreeMap<PathBuf, PathBuf>,
+    options: &TypeScriptOptions,
+) -> Result<String, CodegenError> {
+    let ctx = TypeScriptContext {
+        index,
+        source: &parsed_source.source,
+        output_paths,
+        imports: RefCell::new(BTreeSet::new()),
+        modules: RefCell::new(BTreeMap::new()),
+    };
+    let mut body = String::new();
+
+    for item in &parsed_source.file.items {
+        match item {
+            Item::Struct(item) => {
+                write_struct_declaration(&mut body, &ctx, item)?;
+                writeln!(&mut body).ok();
+                write_struct_codec(&mut body, &ctx, item)?;
+            }
+            Item::Enum(item) => {
+                write_enum_declaration(&mut body, &ctx, item)?;
+                writeln!(&mut body).ok();
+                write_enum_codec(&mut body, &ctx, item)?;
+            }
+            Item::TypeAlias(item) => write_type_alias_declaration(&mut body, &ctx, item)?,
+            Item::Const(item) => write_const_declaration(&mut body, &ctx, item)?,
+            // Remoting stubs are only generated for Rust.
+            Item::Service(_) => continue,
+        }
+        writeln!(&mut body).ok();
+    }
+
+    while body.ends_with("\n\n") {
+        body.pop();
+    }
+
+    let mut out = String::new();
+    writeln!(&mut out, "// @generated by rocketpack-compiler").ok();
+    if !index.package.is_empty() {
+        // ES modules are scoped by file, so the package is only recorded here.
+        writeln!(&mut out, "// package: {}", index.package.join("::")).ok();
+    }
+    writeln!(&mut out).ok();
+
+    let imports = ctx.imports.borrow();
+    if !imports.is_empty() {
+        let names = imports
+            .iter()
+            .filter(|(name, type_only)| !*type_only || !imports.contains(&(*name, false)))
+            .map(|(name, type_only)| if *type_only { format!("type {name}") } else { name.to_string() })
+            .collect::<Vec<_>>();
+        writeln!(&mut out, "import {{ {} }} from {};", names.join(", "), render_string_literal(&options.runtime_import)).ok();
+    }
+    let modules = ctx.modules.borrow();
+    for (specifier, namespace) in modules.iter() {
+        writeln!(&mut out, "import * as {} from {};", namespace, render_string_literal(specifier)).ok();
+    }
+    if !imports.is_empty() || !modules.is_empty() {
+        writeln!(&mut out).ok();
+    }
+
+    out.push_str(&body);
+
+    Ok(out)
+}
+
+// Struct fields, tuple variant elements (tagged by position) and record variant fields share one shape.
+#[derive(Debug, Clone)]
+struct TypeScriptField<'a> {
+    tag: usize,
+    name: &'a str,
+    ty: &'a Type,
+    default: Option<&'a Literal>,
+    max_len: Option<u128>,
+    resolved: ResolvedType,
+}
+
+fn resolve_fields<'a>(index: &SchemaIndex, fields: &'a [Field]) -> Result<Vec<TypeScriptField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for field in fields {
+        resolved.push(TypeScriptField {
+            tag: field.tag.value as usize,
+            name: &field.name.value,
+            ty: &field.ty.value,
+            default: field.default.as_ref().map(|default| &default.value),
+            max_len: match find_attribute(&field.attributes, "max_len").map(|attribute| &attribute.value.args) {
+                Some(AttributeArgs::Value(Spanned { value: Literal::Int(limit), .. })) => Some(*limit),
+                _ => None,
+            },
+            resolved: resolve_type(index, &field.ty.value)?,
+        });
+    }
+    Ok(resolved)
+}
+
+fn resolve_tuple_fields<'a>(index: &SchemaIndex, fields: &'a [(Spanned<String>, Spanned<Type>)]) -> Result<Vec<TypeScriptField<'a>>, CodegenError> {
+    let mut resolved = Vec::with_capacity(fields.len());
+    for (tag, (name, ty)) in fields.iter().enumerate() {
+        resolved.push(TypeScriptField {
+            tag,
+            name: &name.value,
+            ty: &ty.value,
+            default: None,
+            max_len: None,
+            resolved: resolve_type(index, &ty.value)?,
+        });
+    }
+    Ok(resolved)
+}
+
+fn resolve_variant_fields<'a>(index: &SchemaIndex, variant: &'a Variant) -> Result<Vec<TypeScriptField<'a>>, CodegenError> {
+    let fields = match &variant.kind {
+        VariantKind::Unit => Vec::new(),
+        VariantKind::Tuple(fields) => resolve_tuple_fields(index, fields)?,
+        VariantKind::Record(fields) => resolve_fields(index, fields)?,
+    };
+
+    if let Some(field) = fields.iter().find(|field| property_name(field.name) == DISCRIMINATOR) {
+        return Err(CodegenError::Other(format!(
+            "variant field `{}` of `{}` collides with the typescript discriminator `{DISCRIMINATOR}`",
+            field.name, variant.name.value
+        )));
+    }
+
+    Ok(fields)
+}
+
+// Declaration order is kept for properties; the wire order is always by tag.
+fn sorted_by_tag<'a, 'b>(fields: &'b [TypeScriptField<'a>]) -> Vec<&'b TypeScriptField<'a>> {
+    let mut sorted = fields.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|field| field.tag);
+    sorted
+}
+
+fn is_optional(field: &TypeScriptField<'_>) -> bool {
+    matches!(field.resolved, ResolvedType::Option(_))
+}
+
+// Reserved words are valid property names, so fields only need camelCase.
+fn property_name(field_name: &str) -> String {
+    to_camel_case(field_name)
+}
+
+// Decoded values are collected into `_name` slots before the object is built.
+fn storage_name(field_name: &str) -> String {
+    format!("_{}", to_camel_case(field_name))
+}
+
+fn write_struct_declaration(out: &mut String, ctx: &TypeScriptContext<'_>, item: &Struct) -> Result<(), CodegenError> {
+    let fields = resolve_fields(ctx.index, &item.fields)?;
+
+    writeln!(out, "export interface {} {{", sanitize_ident(&item.name.value)).ok();
+    for field in &fields {
+        writeln!(out, "{}{};", indent(1), render_property(ctx, field)?).ok();
+    }
+    writeln!(out, "}}").ok();
+
+    Ok(())
+}
+
+fn render_property(ctx: &TypeScriptContext<'_>, field: &TypeScriptField<'_>) -> Result<String, CodegenError> {
+    Ok(match field.ty {
+        Type::Option(inner) => format!("{}?: {}", property_name(field.name), render_declaration_type(ctx, inner)?),
+        ty => format!("{}: {}", property_name(field.name), render_declaration_type(ctx, ty)?),
+    })
+}
+
+fn write_struct_codec(out: &mut String, ctx: &TypeScriptContext<'_>, item: &Struct) -> Result<(), CodegenError> {
+    let type_name = sanitize_ident(&item.name.value);
+    let fields = resolve_fields(ctx.index, &item.fields)?;
+    let mut temp_counter = 0usize;
+
+    write_codec_header(out, ctx, &type_name);
+    write_fields_pack_body(out, ctx, &fields, 2, &mut temp_counter)?;
+    writeln!(out, "{}}},", indent(1)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}unpack(decoder: {}): {} {{", indent(1), ctx.use_runtime_type("RocketPackDecoder"), type_name).ok();
+    write_fields_unpack_body(out, ctx, &fields, 2, &mut temp_counter)?;
+    writeln!(out).ok();
+    write_object_literal(out, "return ", None, &fields, 2);
+    writeln!(out, "{}}},", indent(1)).ok();
+    writeln!(out, "}};").ok();
+
+    Ok(())
+}
+
+// A codec shares its name with the type it encodes, so `Message` is usable both as a type and as a value.
+fn write_codec_header(out: &mut String, ctx: &TypeScriptContext<'_>, type_name: &str) {
+    writeln!(out, "export const {}: {}<{}> = {{", type_name, ctx.use_runtime_type("RocketPackCodec"), type_name).ok();
+    writeln!(
+        out,
+        "{}pack(encoder: {}, value: {}): void {{",
+        indent(1),
+        ctx.use_runtime_type("RocketPackEncoder"),
+        type_name
+    )
+    .ok();
+}
+
+// Field tags are `u32` in the schema; `writeU32` / `readU32` produce the same bytes as the Rust `u64` calls.
+fn write_fields_pack_body(out: &mut String, ctx: &TypeScriptContext<'_>, fields: &[TypeScriptField<'_>], depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+    let required_count = fields.iter().filter(|field| !is_optional(field)).count();
+    let value_expr = |field: &TypeScriptField<'_>| format!("value.{}", property_name(field.name));
+
+    if fields.iter().any(is_optional) {
+        let count_name = next_temp_name(temp_counter, "count");
+        writeln!(out, "{}let {} = {};", indent(depth), count_name, required_count).ok();
+        for field in fields.iter().filter(|field| is_optional(field)) {
+            writeln!(out, "{}if ({} !== undefined) {}++;", indent(depth), value_expr(field), count_name).ok();
+        }
+        writeln!(out, "{}encoder.writeMap({});", indent(depth), count_name).ok();
+    } else {
+        writeln!(out, "{}encoder.writeMap({});", indent(depth), required_count).ok();
+    }
+
+    for field in sorted_by_tag(fields) {
+        let expr = value_expr(field);
+        match &field.resolved {
+            ResolvedType::Option(inner) => {
+                writeln!(out, "{}if ({} !== undefined) {{", indent(depth), expr).ok();
+                writeln!(out, "{}encoder.writeU32({});", indent(depth + 1), field.tag).ok();
+                write_encode_value(out, ctx, inner, &expr, field.name, depth + 1, temp_counter)?;
+                writeln!(out, "{}}}", indent(depth)).ok();
+            }
+            resolved => {
+                writeln!(out, "{}encoder.writeU32({});", indent(depth), field.tag).ok();
+                write_encode_value(out, ctx, resolved, &expr, field.name, depth, temp_counter)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_encode_value(
+    out: &mut String,
+    ctx: &TypeScriptContext<'_>,
+    resolved: &ResolvedType,
+    expr: &str,
+    context_name: &str,
+    depth: usize,
+    temp_counter: &mut usize,
+) -> Result<(), CodegenError> {
+    match resolved {
+        ResolvedType::Builtin(builtin) => {
+            writeln!(out, "{}encoder.{}({});", indent(depth), encoder_method(builtin), expr).ok();
+        }
+        ResolvedType::Named(named) => {
+            writeln!(out, "{}encoder.writeStruct({}, {});", indent(depth), render_named_type(ctx, named)?, expr).ok();
+        }
+        ResolvedType::Option(inner) => {
+            write_encode_value(out, ctx, inner, expr, context_name, depth, temp_counter)?;
+        }
+        ResolvedType::Vec(inner) => {
+            let item_name = next_temp_name(temp_counter, "item");
+            writeln!(out, "{}encoder.writeArray({}.length);", indent(depth), expr).ok();
+            writeln!(out, "{}for (const {} of {}) {{", indent(depth), item_name, expr).ok();
+            write_encode_value(out, ctx, inner, &item_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+        ResolvedType::Map(key, value) => {
+            let key_name = next_temp_name(temp_counter, "key");
+            let value_name = next_temp_name(temp_counter, "value");
+            writeln!(out, "{}encoder.writeMap({}.size);", indent(depth), expr).ok();
+            writeln!(
+                out,
+                "{}for (const [{}, {}] of {}) {{",
+                indent(depth),
+                key_name,
+                value_name,
+                render_sorted_entries(ctx, expr, key)
+            )
+            .ok();
+            write_encode_value(out, ctx, key, &key_name, context_name, depth + 1, temp_counter)?;
+            write_encode_value(out, ctx, value, &value_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+        ResolvedType::Array(inner, len) => {
+            let item_name = next_temp_name(temp_counter, "item");
+            writeln!(
+                out,
+                "{}if ({}.length !== {}) throw new {}(\"array length mismatch: {}\");",
+                indent(depth),
+                expr,
+                len,
+                ctx.use_runtime("RocketPackEncoderError"),
+                context_name
+            )
+            .ok();
+            writeln!(out, "{}encoder.writeArray({}.length);", indent(depth), expr).ok();
+            writeln!(out, "{}for (const {} of {}) {{", indent(depth), item_name, expr).ok();
+            write_encode_value(out, ctx, inner, &item_name, context_name, depth + 1, temp_counter)?;
+            writeln!(out, "{}}}", indent(depth)).ok();
+        }
+    }
+
+    Ok(())
+}
+
+// `Map` iterates in insertion order, so entries are sorted the way the Rust `BTreeMap` iterates: numerically, or
+// bytewise for strings and bytes. Keys without a natural order are written in insertion order.
+fn render_sorted_entries(ctx: &TypeScriptContext<'_>, expr: &str, key: &ResolvedType) -> String {
+    let comparator = match key {
+        ResolvedType::Builtin(BuiltinType::Bool) => ctx.use_runtime("compareBooleans"),
+        ResolvedType::Builtin(BuiltinType::String) => ctx.use_runtime("compareStrings"),
+        ResolvedType::Builtin(BuiltinType::Bytes) => ctx.use_runtime("compareBytes"),
+        ResolvedType::Builtin(_) => ctx.use_runtime("compareNumbers"),
+        _ => return format!("{}({})", ctx.use_runtime("sortedEntries"), expr),
+    };
+    format!("{}({}, {})", ctx.use_runtime("sortedEntries"), expr, comparator)
+}
+
+fn encoder_method(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "writeBool",
+        BuiltinType::U8 => "writeU8",
+        BuiltinType::U16 => "writeU16",
+        BuiltinType::U32 => "writeU32",
+        BuiltinType::U64 => "writeU64",
+        BuiltinType::U128 => "writeU128",
+        BuiltinType::I8 => "writeI8",
+        BuiltinType::I16 => "writeI16",
+        BuiltinType::I32 => "writeI32",
+        BuiltinType::I64 => "writeI64",
+        BuiltinType::I128 => "writeI128",
+        BuiltinType::F16 => "writeF16",
+        BuiltinType::F32 => "writeF32",
+        BuiltinType::F64 => "writeF64",
+        BuiltinType::String => "writeString",
+        BuiltinType::Bytes => "writeBytes",
+    }
+}
+
+fn decoder_method(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "readBool",
+        BuiltinType::U8 => "readU8",
+        BuiltinType::U16 => "readU16",
+        BuiltinType::U32 => "readU32",
+        BuiltinType::U64 => "readU64",
+        BuiltinType::U128 => "readU128",
+        BuiltinType::I8 => "readI8",
+        BuiltinType::I16 => "readI16",
+        BuiltinType::I32 => "readI32",
+        BuiltinType::I64 => "readI64",
+        BuiltinType::I128 => "readI128",
+        BuiltinType::F16 => "readF16",
+        BuiltinType::F32 => "readF32",
+        BuiltinType::F64 => "readF64",
+        BuiltinType::String => "readString",
+        BuiltinType::Bytes => "readBytes",
+    }
+}
+
+fn write_fields_unpack_body(out: &mut String, ctx: &TypeScriptContext<'_>, fields: &[TypeScriptField<'_>], depth: usize, temp_counter: &mut usize) -> Result<(), CodegenError> {
+    for field in fields {
+        writeln!(
+            out,
+            "{}let {}: {} | undefined;",
+            indent(depth),
+            storage_name(field.name),
+            render_type(ctx, strip_option(&field.resolved))?
+        )
+        .ok();
+    }
+
+    let count_name = next_temp_name(temp_counter, "count");
+    let index_name = next_temp_name(temp_counter, "i");
+    writeln!(out, "{}const {} = decoder.readMap();", indent(depth), count_name).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}for (let {} = 0; {} < {}; {}++) {{", indent(depth), index_name, index_name, count_name, index_name).ok();
+
+    if fields.is_empty() {
+        writeln!(out, "{}decoder.skipField();", indent(depth + 1)).ok();
+        writeln!(out, "{}decoder.skipField();", indent(depth + 1)).ok();
+        writeln!(out, "{}}}", indent(depth)).ok();
+        return Ok(());
+    }
+
+    writeln!(out, "{}switch (decoder.readU32()) {{", indent(depth + 1)).ok();
+    for field in sorted_by_tag(fields) {
+        writeln!(out, "{}case {}: {{", indent(depth + 2), field.tag).ok();
+        // The declared length is checked before the value is read, so an oversized value is rejected without being
+        // decoded, as in the Rust output.
+        if let Some(limit) = field.max_len {
+            writeln!(
+                out,
+                "{}if (decoder.currentLen() > {}) throw new {}(\"max_len exceeded: {}\");",
+                indent(depth + 3),
+                limit,
+                ctx.use_runtime("RocketPackDecoderError"),
+                field.name
+            )
+            .ok();
+        }
+        let value_expr = write_decode_value(out, ctx, strip_option(&field.resolved), depth + 3, field.name, temp_counter)?;
+        writeln!(out, "{}{} = {};", indent(depth + 3), storage_name(field.name), value_expr).ok();
+        writeln!(out, "{}break;", indent(depth + 3)).ok();
+        writeln!(out, "{}}}", indent(depth + 2)).ok();
+    }
+    writeln!(out, "{}default:", indent(depth + 2)).ok();
+    writeln!(out, "{}decoder.skipField();", indent(depth + 3)).ok();
+    writeln!(out, "{}}}", indent(depth + 1)).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+
+    let missing = fields.iter().filter(|field| !is_optional(field) && field.default.is_none()).collect::<Vec<_>>();
+    if !missing.is_empty() {
+        writeln!(out).ok();
+    }
+    for field in missing {
+        writeln!(
+            out,
+            "{}if ({} === undefined) throw new {}(\"missing field: {}\");",
+            indent(depth),
+            storage_name(field.name),
+            ctx.use_runtime("RocketPackDecoderError"),
+            field.name
+        )
+        .ok();
+    }
+
+    Ok(())
+}
+
+fn write_object_literal(out: &mut String, prefix: &str, kind: Option<&str>, fields: &[TypeScriptField<'_>], depth: usize) {
+    let discriminator = kind.map(|kind| format!("{DISCRIMINATOR}: {}", render_string_literal(kind)));
+    if fields.is_empty() {
+        writeln!(out, "{}{}{{ {} }};", indent(depth), prefix, discriminator.unwrap_or_default()).ok();
+        return;
+    }
+
+    writeln!(out, "{}{}{{", indent(depth), prefix).ok();
+    if let Some(discriminator) = discriminator {
+        writeln!(out, "{}{},", indent(depth + 1), discriminator).ok();
+    }
+    for field in fields {
+        let value = match field.default {
+            Some(default) if !is_optional(field) => format!("{} ?? {}", storage_name(field.name), render_literal(default, &field.resolved)),
+            _ => storage_name(field.name),
+        };
+        writeln!(out, "{}{}: {},", indent(depth + 1), property_name(field.name), value).ok();
+    }
+    writeln!(out, "{}}};", indent(depth)).ok();
+}
+
+fn strip_option(resolved: &ResolvedType) -> &ResolvedType {
+    match resolved {
+        ResolvedType::Option(inner) => inner,
+        resolved => resolved,
+    }
+}
+
+fn write_decode_value(
+    out: &mut String,
+    ctx: &TypeScriptContext<'_>,
+    resolved: &ResolvedType,
+    depth: usize,
+    context_name: &str,
+    temp_counter: &mut usize,
+) -> Result<String, CodegenError> {
+    match resolved {
+        ResolvedType::Builtin(builtin) => Ok(format!("decoder.{}()", decoder_method(builtin))),
+        ResolvedType::Named(named) => Ok(format!("decoder.readStruct({})", render_named_type(ctx, named)?)),
+        ResolvedType::Option(inner) => write_decode_value(out, ctx, inner, depth, context_name, temp_counter),
+        ResolvedType::Vec(inner) => write_decode_array(out, ctx, resolved, inner, None, depth, context_name, temp_counter),
+        ResolvedType::Array(inner, len) => write_decode_array(out, ctx, resolved, inner, Some(*len), depth, context_name, temp_counter),
+        ResolvedType::Map(key, value) => {
+            let count_name = next_temp_name(temp_counter, "count");
+            let map_name = next_temp_name(temp_counter, "map");
+            let index_name = next_temp_name(temp_counter, "i");
+            writeln!(out, "{}const {} = decoder.readMap();", indent(depth), count_name).ok();
+            writeln!(out, "{}const {}: {} = new Map();", indent(depth), map_name, render_type(ctx, resolved)?).ok();
+            writeln!(out, "{}for (let {} = 0; {} < {}; {}++) {{", indent(depth), index_name, index_name, count_name, index_name).ok();
+            let key_expr = write_decode_value(out, ctx, key, depth + 1, context_name, temp_counter)?;
+            let key_name = next_temp_name(temp_counter, "key");
+            writeln!(out, "{}const {} = {};", indent(depth + 1), key_name, key_expr).ok();
+            let value_expr = write_decode_value(out, ctx, value, depth + 1, context_name, temp_counter)?;
+            writeln!(out, "{}{}.set({}, {});", indent(depth + 1), map_name, key_name, value_expr).ok();
+            writeln!(out, "{}}}", indent(depth)).ok();
+            Ok(map_name)
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_decode_array(
+    out: &mut String,
+    ctx: &TypeScriptContext<'_>,
+    resolved: &ResolvedType,
+    inner: &ResolvedType,
+    len: Option<u64>,
+    depth: usize,
+    context_name: &str,
+    temp_counter: &mut usize,
+) -> Result<String, CodegenError> {
+    let count_name = next_temp_name(temp_counter, "count");
+    let values_name = next_temp_name(temp_counter, "values");
+    let index_name = next_temp_name(temp_counter, "i");
+    writeln!(out, "{}const {} = decoder.readArray();", indent(depth), count_name).ok();
+    if let Some(len) = len {
+        writeln!(
+            out,
+            "{}if ({} !== {}) throw new {}(\"array length mismatch: {}\");",
+            indent(depth),
+            count_name,
+            len,
+            ctx.use_runtime("RocketPackDecoderError"),
+            context_name
+        )
+        .ok();
+    }
+    writeln!(out, "{}const {}: {} = [];", indent(depth), values_name, render_type(ctx, resolved)?).ok();
+    writeln!(out, "{}for (let {} = 0; {} < {}; {}++) {{", indent(depth), index_name, index_name, count_name, index_name).ok();
+    let inner_expr = write_decode_value(out, ctx, inner, depth + 1, context_name, temp_counter)?;
+    writeln!(out, "{}{}.push({});", indent(depth + 1), values_name, inner_expr).ok();
+    writeln!(out, "{}}}", indent(depth)).ok();
+    Ok(values_name)
+}
+
+fn next_temp_name(counter: &mut usize, prefix: &str) -> String {
+    let current = *counter;
+    *counter += 1;
+    format!("__{}_{}", prefix, current)
+}
+
+fn write_enum_declaration(out: &mut String, ctx: &TypeScriptContext<'_>, item: &Enum) -> Result<(), CodegenError> {
+    let type_name = sanitize_ident(&item.name.value);
+    if item.variants.is_empty() {
+        writeln!(out, "export type {type_name} = never;").ok();
+        return Ok(());
+    }
+
+    writeln!(out, "export type {type_name} =").ok();
+    for (position, variant) in item.variants.iter().enumerate() {
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        let mut members = vec![format!("{DISCRIMINATOR}: {}", render_string_literal(&variant_kind(variant)))];
+        for field in &fields {
+            members.push(render_property(ctx, field)?);
+        }
+
+        let terminator = if position + 1 < item.variants.len() { "" } else { ";" };
+        writeln!(out, "{}| {{ {} }}{}", indent(1), members.join("; "), terminator).ok();
+    }
+
+    Ok(())
+}
+
+fn variant_kind(variant: &Variant) -> String {
+    to_camel_case(&variant.name.value)
+}
+
+fn write_enum_codec(out: &mut String, ctx: &TypeScriptContext<'_>, item: &Enum) -> Result<(), CodegenError> {
+    let type_name = sanitize_ident(&item.name.value);
+    let mut temp_counter = 0usize;
+
+    write_codec_header(out, ctx, &type_name);
+    writeln!(out, "{}encoder.writeMap(1);", indent(2)).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}switch (value.{}) {{", indent(2), DISCRIMINATOR).ok();
+
+    for variant in &item.variants {
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        writeln!(out, "{}case {}: {{", indent(3), render_string_literal(&variant_kind(variant))).ok();
+        writeln!(out, "{}encoder.writeU32({});", indent(4), variant.tag.value).ok();
+        write_fields_pack_body(out, ctx, &fields, 4, &mut temp_counter)?;
+        writeln!(out, "{}break;", indent(4)).ok();
+        writeln!(out, "{}}}", indent(3)).ok();
+    }
+
+    writeln!(out, "{}}}", indent(2)).ok();
+    writeln!(out, "{}}},", indent(1)).ok();
+    writeln!(out).ok();
+
+    writeln!(out, "{}unpack(decoder: {}): {} {{", indent(1), ctx.use_runtime_type("RocketPackDecoder"), type_name).ok();
+    let count_name = next_temp_name(&mut temp_counter, "count");
+    let index_name = next_temp_name(&mut temp_counter, "i");
+    writeln!(out, "{}let __result: {} | undefined;", indent(2), type_name).ok();
+    writeln!(out, "{}const {} = decoder.readMap();", indent(2), count_name).ok();
+    writeln!(out).ok();
+    writeln!(out, "{}for (let {} = 0; {} < {}; {}++) {{", indent(2), index_name, index_name, count_name, index_name).ok();
+    writeln!(out, "{}switch (decoder.readU32()) {{", indent(3)).ok();
+
+    for variant in &item.variants {
+        let fields = resolve_variant_fields(ctx.index, variant)?;
+        writeln!(out, "{}case {}: {{", indent(4), variant.tag.value).ok();
+        write_fields_unpack_body(out, ctx, &fields, 5, &mut temp_counter)?;
+        if !fields.is_empty() {
+            writeln!(out).ok();
+        }
+        write_object_literal(out, "__result = ", Some(&variant_kind(variant)), &fields, 5);
+        writeln!(out, "{}break;", indent(5)).ok();
+        writeln!(out, "{}}}", indent(4)).ok();
+    }
+
+    writeln!(out, "{}default:", indent(4)).ok();
+    writeln!(out, "{}decoder.skipField();", indent(5)).ok();
+    writeln!(out, "{}}}", indent(3)).ok();
+    writeln!(out, "{}}}", indent(2)).ok();
+    writeln!(out).ok();
+    writeln!(
+        out,
+        "{}if (__result === undefined) throw new {}(\"missing enum variant\");",
+        indent(2),
+        ctx.use_runtime("RocketPackDecoderError")
+    )
+    .ok();
+    writeln!(out, "{}return __result;", indent(2)).ok();
+    writeln!(out, "{}}},", indent(1)).ok();
+    writeln!(out, "}};").ok();
+
+    Ok(())
+}
+
+fn write_type_alias_declaration(out: &mut String, ctx: &TypeScriptContext<'_>, item: &TypeAlias) -> Result<(), CodegenError> {
+    writeln!(out, "export type {} = {};", sanitize_ident(&item.name.value), render_declaration_type(ctx, &item.ty.value)?).ok();
+
+    Ok(())
+}
+
+fn write_const_declaration(out: &mut String, ctx: &TypeScriptContext<'_>, item: &Const) -> Result<(), CodegenError> {
+    let resolved = resolve_type(ctx.index, &item.ty.value)?;
+    writeln!(
+        out,
+        "export const {}: {} = {};",
+        sanitize_ident(&item.name.value),
+        render_declaration_type(ctx, &item.ty.value)?,
+        render_literal(&item.value.value, &resolved)
+    )
+    .ok();
+
+    Ok(())
+}
+
+fn render_declaration_type(ctx: &TypeScriptContext<'_>, ty: &Type) -> Result<String, CodegenError> {
+    Ok(match ty {
+        Type::Path(path) => render_path_type(ctx, path)?,
+        Type::Generic(..) => return Err(CodegenError::Other("generic types are not supported by the TypeScript generator".to_string())),
+        Type::Option(inner) => format!("{} | undefined", render_declaration_type(ctx, inner)?),
+        Type::Vec(inner) | Type::Array(inner, _) => render_array_type(render_declaration_type(ctx, inner)?, matches!(**inner, Type::Option(_))),
+        Type::Map(key, value) => format!("Map<{}, {}>", render_declaration_type(ctx, key)?, render_declaration_type(ctx, value)?),
+    })
+}
+
+fn render_path_type(ctx: &TypeScriptContext<'_>, path: &AstPath) -> Result<String, CodegenError> {
+    if let Some(builtin) = builtin_type(path) {
+        return Ok(render_builtin_type(&builtin).to_string());
+    }
+
+    let canonical = canonical_path(ctx.index, path)?;
+    let name = canonical.last().map(|segment| sanitize_ident(segment)).unwrap_or_default();
+    match ctx.index.external_types.get(&canonical) {
+        Some(external) => Ok(format!("{}.{}", ctx.use_module(&external.origin)?, name)),
+        None => Ok(name),
+    }
+}
+
+fn render_type(ctx: &TypeScriptContext<'_>, resolved: &ResolvedType) -> Result<String, CodegenError> {
+    Ok(match resolved {
+        ResolvedType::Builtin(builtin) => render_builtin_type(builtin).to_string(),
+        ResolvedType::Named(named) => render_named_type(ctx, named)?,
+        ResolvedType::Option(inner) => format!("{} | undefined", render_type(ctx, inner)?),
+        ResolvedType::Vec(inner) | ResolvedType::Array(inner, _) => render_array_type(render_type(ctx, inner)?, matches!(**inner, ResolvedType::Option(_))),
+        ResolvedType::Map(key, value) => format!("Map<{}, {}>", render_type(ctx, key)?, render_type(ctx, value)?),
+    })
+}
+
+fn render_array_type(element: String, needs_parens: bool) -> String {
+    if needs_parens { format!("({element})[]") } else { format!("{element}[]") }
+}
+
+// 64-bit and wider integers do not fit in a `number` without losing precision.
+fn render_builtin_type(builtin: &BuiltinType) -> &'static str {
+    match builtin {
+        BuiltinType::Bool => "boolean",
+        BuiltinType::U8 | BuiltinType::U16 | BuiltinType::U32 | BuiltinType::I8 | BuiltinType::I16 | BuiltinType::I32 | BuiltinType::F16 | BuiltinType::F32 | BuiltinType::F64 => {
+            "number"
+        }
+        BuiltinType::U64 | BuiltinType::U128 | BuiltinType::I64 | BuiltinType::I128 => "bigint",
+        BuiltinType::String => "string",
+        BuiltinType::Bytes => "Uint8Array",
+    }
+}
+
+// Types of other files are referenced through the namespace import of their generated module.
+fn render_named_type(ctx: &TypeScriptContext<'_>, named: &NamedType) -> Result<String, CodegenError> {
+    let name = named.path.last().map(|segment| sanitize_ident(segment)).unwrap_or_default();
+    match &named.origin {
+        Some(origin) => Ok(format!("{}.{}", ctx.use_module(origin)?, name)),
+        None => Ok(name),
+    }
+}
+
+// Specifiers are relative to the importing file and drop the `.ts` extension, like the runtime import.
+fn relative_module_specifier(from: &FsPath, to: &FsPath) -> String {
+    let from_dir = from.parent().map(|dir| dir.components().collect::<Vec<_>>()).unwrap_or_default();
+    let to_path = to.with_extension("");
+    let to_components = to_path.components().collect::<Vec<_>>();
+    let common_len = from_dir.iter().zip(&to_components).take_while(|(a, b)| a == b).count();
+
+    let mut segments = vec!["..".to_string(); from_dir.len() - common_len];
+    if segments.is_empty() {
+        segments.push(".".to_string());
+    }
+    segments.extend(to_components[common_len..].iter().map(|component| component.as_os_str().to_string_lossy().to_string()));
+    segments.join("/")
+}
+
+fn render_literal(literal: &Literal, resolved: &ResolvedType) -> String {
+    match literal {
+        Literal::Bool(value) => value.to_string(),
+        Literal::Int(value) => match resolved {
+            ResolvedType::Builtin(BuiltinType::U64 | BuiltinType::U128 | BuiltinType::I64 | BuiltinType::I128) => format!("{value}n"),
+            _ => value.to_string(),
+        },
+        Literal::Float(value) => value.to_string(),
+        Literal::String(value) => render_string_literal(value),
+        Literal::Bytes(bytes) => {
+            let rendered = bytes.iter().map(|byte| byte.to_string()).collect::<Vec<_>>().join(", ");
+            format!("new Uint8Array([{rendered}])")
+        }
+    }
+}
+
+fn render_string_literal(value: &str) -> String {
+    let mut rendered = String::with_capacity(value.len() + 2);
+    rendered.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => rendered.push_str("\\\""),
+            '\\' => rendered.push_str("\\\\"),
+            '\n' => rendered.push_str("\\n"),
+            '\r' => rendered.push_str("\\r"),
+            '\t' => rendered.push_str("\\t"),
+            '\0' => rendered.push_str("\\0"),
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                write!(&mut rendered, "\\u{{{:x}}}", c as u32).ok();
+            }
+            c => rendered.push(c),
+        }
+    }
+    rendered.push('"');
+    rendered
+}
+
+// Type and const names share the value namespace, where reserved words cannot be escaped.
+fn sanitize_ident(value: &str) -> String {
+    match value {
+        "break" | "case" | "catch" | "class" | "const" | "continue" | "debugger" | "default" | "delete" | "do" | "else" | "enum" | "export" | "extends" | "false" | "finally"
+        | "for" | "function" | "if" | "import" | "in" | "instanceof" | "new" | "null" | "return" | "super" | "switch" | "this" | "throw" | "true" | "try" | "typeof" | "var"
+        | "void" | "while" | "with" | "let" | "static" | "yield" | "await" | "implements" | "interface" | "package" | "private" | "protected" | "public" | "any" | "boolean"
+        | "never" | "number" | "bigint" | "object" | "string" | "symbol" | "undefined" | "unknown" => format!("{value}_"),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use super::*;
+    use crate::parser;
+
+    fn render(source: &str, options: &TypeScriptOptions) -> Result<String, CodegenError> {
+        let file = parser::parse_source("test.rpf", source)?;
+        let parsed_source = ParsedSource {
+            source: DiscoveredSource {
+                base_dir: PathBuf::from("."),
+                absolute_path: PathBuf::from("./sample.rpf"),
+                relative_path: PathBuf::from("sample.rpf"),
+            },
+            text: source.to_string(),
+            file,
+        };
+        let index = resolve_sources(std::slice::from_ref(&parsed_source))?.remove(0);
+        render_typescript_file(&parsed_source, &index, &BTreeMap::new(), options)
+    }
+
+    #[test]
+    fn render_struct_test() -> TestResult {
+        let rendered = render(
+            r#"
+version 1;
+package omnius::demo::v1;
+
+struct Message {
+  @2 names: Names;
+  @1 id: u32 = 7;
+  @3 note: Option<string>;
+  @4 labels: Map<string, u8>;
+  @5 size: u64;
+}
+
+type Names = Vec<string>;
+const MAX_SIZE: u64 = 16;
+"#,
+            &TypeScriptOptions::default(),
+        )?;
+        println!("{rendered}");
+
+        assert!(rendered.contains("// package: omnius::demo::v1"));
+        assert!(rendered.contains(
+            "import { type RocketPackCodec, type RocketPackDecoder, RocketPackDecoderError, type RocketPackEncoder, compareStrings, sortedEntries } from \"./rocketpack_runtime\";"
+        ));
+        assert!(rendered.contains("export interface Message {\n    names: Names;\n    id: number;\n    note?: string;\n    labels: Map<string, number>;\n    size: bigint;\n}"));
+        assert!(rendered.contains("export const Message: RocketPackCodec<Message> = {"));
+        assert!(rendered.contains("        if (value.note !== undefined) __count_0++;"));
+        assert!(rendered.contains("        for (const [__key_2, __value_3] of sortedEntries(value.labels, compareStrings)) {"));
+        assert!(rendered.contains("        if (_names === undefined) throw new RocketPackDecoderError(\"missing field: names\");"));
+        assert!(rendered.contains("            id: _id ?? 7,"));
+        assert!(rendered.contains("export type Names = string[];"));
+        assert!(rendered.contains("export const MAX_SIZE: bigint = 16n;"));
+
+        // Fields are written in tag order, like the Rust output.
+        let id_position = rendered.find("encoder.writeU32(value.id);").ok_or("missing id")?;
+        let names_position = rendered.find("encoder.writeArray(value.names.length);").ok_or("missing names")?;
+        assert!(id_position < names_position);
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_enum_test() -> TestResult {
+        let options = TypeScriptOptions {
+            runtime_import: "@omnius/rocketpack".to_string(),
+            emit_runtime: false,
+        };
+        let rendered = render(
+            r#"
+version 1;
+
+enum Shape {
+  @1 Empty;
+  @2 Line(length: f64, label: Option<string>);
+  @3 Rect {
+    @2 height: f64;
+    @1 width: f64 = 1;
+  };
+}
+"#,
+            &options,
+        )?;
+        println!("{rendered}");
+
+        assert!(rendered.contains("from \"@omnius/rocketpack\";"));
+        assert!(rendered.contains(
+            "export type Shape =\n    | { kind: \"empty\" }\n    | { kind: \"line\"; length: number; label?: string }\n    | { kind: \"rect\"; height: number; width: number };"
+        ));
+        assert!(rendered.contains("        switch (value.kind) {\n            case \"empty\": {\n                encoder.writeU32(1);\n                encoder.writeMap(0);"));
+        assert!(rendered.contains("                    __result = { kind: \"empty\" };"));
+        assert!(rendered.contains("                    __result = {\n                        kind: \"rect\",\n                        height: _height,\n                        width: _width ?? 1,\n                    };"));
+
+        // Record variants are written in tag order, whatever the declaration order.
+        let width_position = rendered.find("encoder.writeF64(value.width);").ok_or("missing width")?;
+        let height_position = rendered.find("encoder.writeF64(value.height);").ok_or("missing height")?;
+        assert!(width_position < height_position);
+
+        let error = render("version 1;\nenum Tagged { @1 Named { @1 kind: string; }; }", &options)
+            .err()
+            .ok_or("discriminator collision must be rejected")?;
+        assert!(error.to_string().contains("discriminator"));
+
+        Ok(())
+    }
+}
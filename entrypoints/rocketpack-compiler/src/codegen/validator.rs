@@ -0,0 +1,681 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::common::{
+    BuiltinType, ResolvedType, SchemaIndex, builtin_type, canonical_path, find_attribute, format_literal, format_type, integer_range, path_segments, path_span, resolve_type,
+    resolve_type_with_params, type_param_count, type_param_names,
+};
+use crate::{
+    error::{ParseError, ParseErrorKind},
+    parser::ast::{Attribute, AttributeArgs, Enum, Field, File, Item, Literal, Path as AstPath, Spanned, Struct, Type, TypeAlias, VariantKind},
+};
+
+// Checks that only need the syntax tree of one file: unique names, unique non-zero tags, known attributes and acyclic aliases.
+pub(super) fn validate_file(file: &File) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+
+    let mut item_names = BTreeSet::<&str>::new();
+    let mut service_tags = BTreeSet::<u32>::new();
+    for item in &file.items {
+        let (name, attributes, target) = match item {
+            Item::Struct(item) => (&item.name, &item.attributes, AttributeTarget::Struct),
+            Item::Enum(item) => (&item.name, &item.attributes, AttributeTarget::Enum),
+            Item::TypeAlias(item) => (&item.name, &item.attributes, AttributeTarget::TypeAlias),
+            Item::Const(item) => (&item.name, &item.attributes, AttributeTarget::Const),
+            Item::Service(item) => (&item.name, &item.attributes, AttributeTarget::Service),
+        };
+        check_name(&mut item_names, name, &mut errors);
+        check_attributes(attributes, target, &mut errors);
+
+        if let Item::Struct(Struct { type_params, .. }) | Item::Enum(Enum { type_params, .. }) = item {
+            check_type_params(type_params, &item_types(item), &mut errors);
+        }
+
+        match item {
+            Item::Struct(item) => check_fields(&item.fields, &mut errors),
+            Item::Enum(item) => {
+                let mut tags = BTreeSet::<u32>::new();
+                let mut names = BTreeSet::<&str>::new();
+                for variant in &item.variants {
+                    check_tag(&mut tags, &variant.tag, &mut errors);
+                    check_name(&mut names, &variant.name, &mut errors);
+                    check_attributes(&variant.attributes, AttributeTarget::Variant, &mut errors);
+
+                    match &variant.kind {
+                        VariantKind::Unit => {}
+                        VariantKind::Tuple(fields) => {
+                            let mut field_names = BTreeSet::<&str>::new();
+                            for (name, _) in fields {
+                                check_name(&mut field_names, name, &mut errors);
+                            }
+                        }
+                        VariantKind::Record(fields) => check_fields(fields, &mut errors),
+                    }
+                }
+            }
+            Item::Service(item) => {
+                check_tag(&mut service_tags, &item.tag, &mut errors);
+                check_rpc_tag_range(&item.tag, &mut errors);
+
+                let mut tags = BTreeSet::<u32>::new();
+                let mut names = BTreeSet::<&str>::new();
+                for rpc in &item.rpcs {
+                    check_tag(&mut tags, &rpc.tag, &mut errors);
+                    check_rpc_tag_range(&rpc.tag, &mut errors);
+                    check_name(&mut names, &rpc.name, &mut errors);
+                    check_attributes(&rpc.attributes, AttributeTarget::Rpc, &mut errors);
+                }
+            }
+            Item::TypeAlias(_) | Item::Const(_) => {}
+        }
+    }
+
+    check_alias_cycles(file, &mut errors);
+
+    errors
+}
+
+// Checks that need resolved types: generic types get the right type arguments, field defaults and const values must fit
+// the declared type, length limits need a type with a length, and rpc messages must be structs or enums.
+pub(super) fn validate_types(file: &File, index: &SchemaIndex) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+
+    for item in &file.items {
+        let params = match item {
+            Item::Struct(item) => type_param_names(&item.type_params),
+            Item::Enum(item) => type_param_names(&item.type_params),
+            _ => Vec::new(),
+        };
+        for ty in item_types(item) {
+            check_type_args(index, ty, &params, &mut errors);
+        }
+
+        match item {
+            Item::Struct(item) => item.fields.iter().for_each(|field| check_field_types(index, field, &params, &mut errors)),
+            Item::Enum(item) => {
+                for variant in &item.variants {
+                    if let VariantKind::Record(fields) = &variant.kind {
+                        fields.iter().for_each(|field| check_field_types(index, field, &params, &mut errors));
+                    }
+                }
+            }
+            Item::TypeAlias(_) => {}
+            Item::Const(item) => check_literal(index, &item.ty.value, &params, &item.value, &mut errors),
+            Item::Service(item) => {
+                for rpc in &item.rpcs {
+                    check_rpc_type(index, &rpc.request.ty, &mut errors);
+                    check_rpc_type(index, &rpc.response.ty, &mut errors);
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+// Every type written in an item, outermost first.
+fn item_types(item: &Item) -> Vec<&Type> {
+    match item {
+        Item::Struct(item) => item.fields.iter().map(|field| &field.ty.value).collect(),
+        Item::Enum(item) => item
+            .variants
+            .iter()
+            .flat_map(|variant| match &variant.kind {
+                VariantKind::Unit => Vec::new(),
+                VariantKind::Tuple(fields) => fields.iter().map(|(_, ty)| &ty.value).collect(),
+                VariantKind::Record(fields) => fields.iter().map(|field| &field.ty.value).collect(),
+            })
+            .collect(),
+        Item::TypeAlias(item) => vec![&item.ty.value],
+        Item::Const(item) => vec![&item.ty.value],
+        Item::Service(item) => item.rpcs.iter().flat_map(|rpc| [&rpc.request.ty.value, &rpc.response.ty.value]).collect(),
+    }
+}
+
+// Generated code has no use for a parameter that no field mentions, and Rust rejects it outright.
+fn check_type_params(type_params: &[Spanned<String>], types: &[&Type], errors: &mut Vec<ParseError>) {
+    let mut names = BTreeSet::<&str>::new();
+    for param in type_params {
+        check_name(&mut names, param, errors);
+        if !types.iter().any(|ty| mentions_name(ty, &param.value)) {
+            errors.push(ParseError::new(ParseErrorKind::UnusedTypeParam(param.value.clone()), param.span.start, param.span.end));
+        }
+    }
+}
+
+fn mentions_name(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => is_single_name(path, name),
+        Type::Generic(path, args) => is_single_name(path, name) || args.iter().any(|arg| mentions_name(arg, name)),
+        Type::Option(inner) | Type::Vec(inner) | Type::Array(inner, _) => mentions_name(inner, name),
+        Type::Map(key, value) => mentions_name(key, name) || mentions_name(value, name),
+    }
+}
+
+fn is_single_name(path: &AstPath, name: &str) -> bool {
+    matches!(path.segments.as_slice(), [segment] if segment.value == name)
+}
+
+// Type arguments are encoded as nested values, so each one must be a struct, an enum or a type parameter standing for one.
+fn check_type_args(index: &SchemaIndex, ty: &Type, params: &[String], errors: &mut Vec<ParseError>) {
+    let (path, args) = match ty {
+        Type::Path(path) => (path, &[][..]),
+        Type::Generic(path, args) => (path, args.as_slice()),
+        Type::Option(inner) | Type::Vec(inner) | Type::Array(inner, _) => return check_type_args(index, inner, params, errors),
+        Type::Map(key, value) => {
+            check_type_args(index, key, params, errors);
+            check_type_args(index, value, params, errors);
+            return;
+        }
+    };
+
+    let is_param = params.iter().any(|param| is_single_name(path, param));
+    // Unknown types are reported by the resolver.
+    if !is_param && builtin_type(path).is_none() && canonical_path(index, path).is_err() {
+        return;
+    }
+
+    let expected = if is_param { 0 } else { type_param_count(index, path) };
+    let span = path_span(path);
+    if expected != args.len() {
+        let kind = ParseErrorKind::TypeArgumentCount {
+            name: path_segments(path).join("::"),
+            expected,
+            found: args.len(),
+        };
+        errors.push(ParseError::new(kind, span.start, span.end));
+        return;
+    }
+
+    for arg in args {
+        if let Ok(resolved) = resolve_type_with_params(index, arg, params)
+            && !matches!(resolved, ResolvedType::Named(_))
+        {
+            errors.push(ParseError::new(ParseErrorKind::InvalidTypeArgument(format_type(arg)), span.start, span.end));
+        }
+        check_type_args(index, arg, params, errors);
+    }
+}
+
+fn check_fields(fields: &[Field], errors: &mut Vec<ParseError>) {
+    let mut tags = BTreeSet::<u32>::new();
+    let mut names = BTreeSet::<&str>::new();
+    for field in fields {
+        check_tag(&mut tags, &field.tag, errors);
+        check_name(&mut names, &field.name, errors);
+        check_attributes(&field.attributes, AttributeTarget::Field, errors);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttributeTarget {
+    Struct,
+    Enum,
+    TypeAlias,
+    Const,
+    Service,
+    Field,
+    Variant,
+    Rpc,
+}
+
+impl AttributeTarget {
+    fn name(self) -> &'static str {
+        match self {
+            AttributeTarget::Struct => "struct",
+            AttributeTarget::Enum => "enum",
+            AttributeTarget::TypeAlias => "type alias",
+            AttributeTarget::Const => "const",
+            AttributeTarget::Service => "service",
+            AttributeTarget::Field => "field",
+            AttributeTarget::Variant => "variant",
+            AttributeTarget::Rpc => "rpc",
+        }
+    }
+}
+
+// `deprecated` may mark anything, `rust(derive = "...")` adds derives to generated Rust types and `max_len` limits the
+// length of a field. Each attribute may be given once per element.
+fn check_attributes(attributes: &[Spanned<Attribute>], target: AttributeTarget, errors: &mut Vec<ParseError>) {
+    let mut names = BTreeSet::<&str>::new();
+    for attribute in attributes {
+        let name = &attribute.value.name;
+        if !names.insert(&name.value) {
+            errors.push(ParseError::new(ParseErrorKind::Duplicate, name.span.start, name.span.end));
+            continue;
+        }
+
+        let allowed = match name.value.as_str() {
+            "deprecated" => true,
+            "rust" => matches!(target, AttributeTarget::Struct | AttributeTarget::Enum),
+            "max_len" => target == AttributeTarget::Field,
+            _ => {
+                errors.push(ParseError::new(ParseErrorKind::UnknownAttribute(name.value.clone()), name.span.start, name.span.end));
+                continue;
+            }
+        };
+        if !allowed {
+            let kind = ParseErrorKind::MisplacedAttribute {
+                name: name.value.clone(),
+                target: target.name(),
+            };
+            errors.push(ParseError::new(kind, name.span.start, name.span.end));
+            continue;
+        }
+
+        let expected = match (name.value.as_str(), &attribute.value.args) {
+            ("deprecated", AttributeArgs::Empty) => None,
+            ("deprecated", AttributeArgs::Value(value)) if matches!(value.value, Literal::String(_)) => None,
+            ("deprecated", _) => Some("no value or a string note"),
+            ("max_len", AttributeArgs::Value(value)) if matches!(value.value, Literal::Int(limit) if limit <= u64::MAX as u128) => None,
+            ("max_len", _) => Some("an integer limit"),
+            ("rust", AttributeArgs::List(args)) if !args.is_empty() && args.iter().all(|arg| arg.name.value == "derive" && matches!(arg.value.value, Literal::String(_))) => None,
+            _ => Some("derive = \"Trait, ...\""),
+        };
+        if let Some(expected) = expected {
+            let kind = ParseErrorKind::InvalidAttributeArgs {
+                name: name.value.clone(),
+                expected,
+            };
+            errors.push(ParseError::new(kind, name.span.start, name.span.end));
+        }
+    }
+}
+
+fn check_tag(tags: &mut BTreeSet<u32>, tag: &Spanned<u32>, errors: &mut Vec<ParseError>) {
+    if tag.value == 0 {
+        errors.push(ParseError::new(ParseErrorKind::ZeroTag, tag.span.start, tag.span.end));
+    } else if !tags.insert(tag.value) {
+        errors.push(ParseError::new(ParseErrorKind::DuplicateTag(tag.value), tag.span.start, tag.span.end));
+    }
+}
+
+// The function id of an rpc packs the service id into the high 16 bits and the rpc tag into the low 16 bits.
+fn check_rpc_tag_range(tag: &Spanned<u32>, errors: &mut Vec<ParseError>) {
+    if tag.value > u32::from(u16::MAX) {
+        errors.push(ParseError::new(
+            ParseErrorKind::RpcTagOutOfRange {
+                tag: tag.value,
+                max: u32::from(u16::MAX),
+            },
+            tag.span.start,
+            tag.span.end,
+        ));
+    }
+}
+
+fn check_name<'a>(names: &mut BTreeSet<&'a str>, name: &'a Spanned<String>, errors: &mut Vec<ParseError>) {
+    if !names.insert(&name.value) {
+        errors.push(ParseError::new(ParseErrorKind::DuplicateName(name.value.clone()), name.span.start, name.span.end));
+    }
+}
+
+fn check_alias_cycles(file: &File, errors: &mut Vec<ParseError>) {
+    let package = file.package.as_ref().map(|package| path_segments(&package.value)).unwrap_or_default();
+    let aliases = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::TypeAlias(item) => Some((item.name.value.as_str(), item)),
+            _ => None,
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let mut reported = BTreeSet::<&str>::new();
+    for (name, alias) in &aliases {
+        let mut stack = vec![*name];
+        if reaches_alias(&aliases, &package, &alias.ty.value, name, &mut stack) && reported.insert(name) {
+            errors.push(ParseError::new(ParseErrorKind::CyclicAlias(stack.join(" -> ")), alias.name.span.start, alias.name.span.end));
+        }
+    }
+}
+
+// Walks the aliases referenced by `ty` depth first and reports whether `target` can be reached, leaving the path on `stack`.
+fn reaches_alias<'a>(aliases: &BTreeMap<&'a str, &'a TypeAlias>, package: &[String], ty: &Type, target: &str, stack: &mut Vec<&'a str>) -> bool {
+    match ty {
+        Type::Path(path) => {
+            let segments = path_segments(path);
+            let name = match segments.as_slice() {
+                [name] => name,
+                [prefix @ .., name] if prefix == package => name,
+                _ => return false,
+            };
+            let Some((name, alias)) = aliases.get_key_value(name.as_str()) else {
+                return false;
+            };
+
+            stack.push(name);
+            if *name == target {
+                return true;
+            }
+            if stack[..stack.len() - 1].contains(name) {
+                stack.pop();
+                return false;
+            }
+            if reaches_alias(aliases, package, &alias.ty.value, target, stack) {
+                return true;
+            }
+            stack.pop();
+            false
+        }
+        Type::Generic(path, args) => {
+            reaches_alias(aliases, package, &Type::Path(path.clone()), target, stack) || args.iter().any(|arg| reaches_alias(aliases, package, arg, target, stack))
+        }
+        Type::Option(inner) | Type::Vec(inner) | Type::Array(inner, _) => reaches_alias(aliases, package, inner, target, stack),
+        Type::Map(key, value) => reaches_alias(aliases, package, key, target, stack) || reaches_alias(aliases, package, value, target, stack),
+    }
+}
+
+fn check_field_types(index: &SchemaIndex, field: &Field, params: &[String], errors: &mut Vec<ParseError>) {
+    if let Some(default) = &field.default {
+        check_literal(index, &field.ty.value, params, default, errors);
+    }
+    if let Some(max_len) = find_attribute(&field.attributes, "max_len") {
+        check_max_len(index, field, params, max_len, errors);
+    }
+}
+
+// The limit is compared with the length of the decoded value, so the field needs a type that has one.
+fn check_max_len(index: &SchemaIndex, field: &Field, params: &[String], attribute: &Spanned<Attribute>, errors: &mut Vec<ParseError>) {
+    // Unknown types are reported by the resolver.
+    let Ok(resolved) = resolve_type_with_params(index, &field.ty.value, params) else {
+        return;
+    };
+    let resolved = match resolved {
+        ResolvedType::Option(inner) => *inner,
+        resolved => resolved,
+    };
+
+    if !matches!(
+        resolved,
+        ResolvedType::Builtin(BuiltinType::String | BuiltinType::Bytes) | ResolvedType::Vec(_) | ResolvedType::Map(_, _)
+    ) {
+        let span = &attribute.value.name.span;
+        errors.push(ParseError::new(ParseErrorKind::InvalidMaxLenType(format_type(&field.ty.value)), span.start, span.end));
+    }
+}
+
+// Messages are sent as whole frames, so they must be types with their own encoding.
+fn check_rpc_type(index: &SchemaIndex, ty: &Spanned<Type>, errors: &mut Vec<ParseError>) {
+    // Unknown types are reported by the resolver.
+    let Ok(resolved) = resolve_type(index, &ty.value) else {
+        return;
+    };
+
+    if !matches!(resolved, ResolvedType::Named(_)) {
+        errors.push(ParseError::new(ParseErrorKind::InvalidRpcType(format_type(&ty.value)), ty.span.start, ty.span.end));
+    }
+}
+
+fn check_literal(index: &SchemaIndex, ty: &Type, params: &[String], literal: &Spanned<Literal>, errors: &mut Vec<ParseError>) {
+    // Unknown types are reported by the resolver.
+    let Ok(resolved) = resolve_type_with_params(index, ty, params) else {
+        return;
+    };
+    let resolved = match resolved {
+        ResolvedType::Option(inner) => *inner,
+        resolved => resolved,
+    };
+
+    let range = match &resolved {
+        ResolvedType::Builtin(builtin) => integer_range(builtin),
+        _ => None,
+    };
+
+    let matched = match (&literal.value, &resolved, range) {
+        (Literal::Bool(_), ResolvedType::Builtin(BuiltinType::Bool), _) => true,
+        (Literal::Int(value), _, Some((_, max))) => {
+            if *value > max {
+                let kind = ParseErrorKind::LiteralOutOfRange {
+                    literal: format_literal(&literal.value),
+                    ty: format_type(ty),
+                };
+                errors.push(ParseError::new(kind, literal.span.start, literal.span.end));
+            }
+            true
+        }
+        (Literal::Int(_) | Literal::Float(_), ResolvedType::Builtin(BuiltinType::F16 | BuiltinType::F32 | BuiltinType::F64), _) => true,
+        (Literal::String(_), ResolvedType::Builtin(BuiltinType::String), _) => true,
+        (Literal::Bytes(_), ResolvedType::Builtin(BuiltinType::Bytes), _) => true,
+        _ => false,
+    };
+
+    if !matched {
+        let kind = ParseErrorKind::MismatchedLiteral {
+            literal: format_literal(&literal.value),
+            ty: format_type(ty),
+        };
+        errors.push(ParseError::new(kind, literal.span.start, literal.span.end));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use testresult::TestResult;
+
+    use crate::{
+        codegen::{
+            common::{DiscoveredSource, ParsedSource},
+            resolver::resolve_sources,
+        },
+        error::CodegenError,
+        parser,
+    };
+
+    fn resolve(text: &str) -> Result<String, CodegenError> {
+        let source = DiscoveredSource {
+            base_dir: PathBuf::from("/rpfs"),
+            absolute_path: PathBuf::from("/rpfs/user.rpf"),
+            relative_path: PathBuf::from("user.rpf"),
+        };
+        let file = parser::parse_source(&source.absolute_path, text)?;
+        let parsed_source = ParsedSource {
+            source,
+            text: text.to_string(),
+            file,
+        };
+
+        match resolve_sources(&[parsed_source]) {
+            Ok(_) => Ok(String::new()),
+            Err(CodegenError::Validation(bundles)) => Ok(bundles.iter().map(|bundle| bundle.to_string()).collect()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn messages(rendered: &str) -> Vec<&str> {
+        rendered.lines().filter(|line| !line.starts_with("  ")).collect()
+    }
+
+    #[test]
+    fn validate_reports_all_errors_test() -> TestResult {
+        let rendered = resolve(
+            r#"package omnius::user;
+struct User {
+  @1 id: u64;
+  @1 name: string;
+  @0 age: u8;
+  @3 id: bool;
+  @4 retries: u32 = "x";
+  @5 group: Group;
+}
+enum User {
+  @1 A;
+}
+"#,
+        )?;
+
+        assert_eq!(
+            messages(&rendered),
+            vec![
+                "/rpfs/user.rpf:4:4: duplicate tag: @1",
+                "/rpfs/user.rpf:5:4: tag must be greater than 0",
+                "/rpfs/user.rpf:6:6: duplicate name: id",
+                "/rpfs/user.rpf:7:21: literal \"x\" does not match type u32",
+                "/rpfs/user.rpf:8:13: unknown type: Group",
+                "/rpfs/user.rpf:10:6: duplicate name: User",
+            ]
+        );
+        assert!(rendered.contains("    @4 retries: u32 = \"x\";\n                      ^~~\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_literals_test() -> TestResult {
+        let rendered = resolve(
+            r#"type Retries = Option<u8>;
+const LIMIT: i8 = 128;
+const RATIO: f32 = 1;
+const FLAG: bool = 1;
+struct Config {
+  @1 retries: Retries = 255;
+  @2 name: string = "default";
+  @3 key: bytes = b"\x01";
+  @4 ratio: Vec<f32> = 1.5;
+}
+"#,
+        )?;
+
+        assert_eq!(
+            messages(&rendered),
+            vec![
+                "/rpfs/user.rpf:2:19: literal 128 is out of range for i8",
+                "/rpfs/user.rpf:4:20: literal 1 does not match type bool",
+                "/rpfs/user.rpf:9:24: literal 1.5 does not match type Vec<f32>",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_alias_cycle_test() -> TestResult {
+        let rendered = resolve(
+            r#"type A = Vec<B>;
+type B = Option<A>;
+type C = A;
+"#,
+        )?;
+
+        assert_eq!(
+            messages(&rendered),
+            vec!["/rpfs/user.rpf:1:6: cyclic type alias: A -> B -> A", "/rpfs/user.rpf:2:6: cyclic type alias: B -> A -> B"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_service_test() -> TestResult {
+        let rendered = resolve(
+            r#"type Ids = Vec<u64>;
+struct Request {
+  @1 id: u64;
+}
+@1 service Users {
+  @1 rpc Get(Request) returns (Request);
+  @1 rpc List(stream Request) returns (stream Ids);
+  @0 rpc Get(u64) returns (Missing);
+  @65536 rpc Big(Request) returns (Request);
+}
+@1 service Groups {}
+@65536 service Huge {}
+"#,
+        )?;
+
+        assert_eq!(
+            messages(&rendered),
+            vec![
+                "/rpfs/user.rpf:7:4: duplicate tag: @1",
+                "/rpfs/user.rpf:7:47: rpc message must be a struct or enum, found Ids",
+                "/rpfs/user.rpf:8:4: tag must be greater than 0",
+                "/rpfs/user.rpf:8:10: duplicate name: Get",
+                "/rpfs/user.rpf:8:14: rpc message must be a struct or enum, found u64",
+                "/rpfs/user.rpf:8:28: unknown type: Missing",
+                "/rpfs/user.rpf:9:4: service id and rpc tag must be at most 65535, found @65536",
+                "/rpfs/user.rpf:11:2: duplicate tag: @1",
+                "/rpfs/user.rpf:12:2: service id and rpc tag must be at most 65535, found @65536",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_generics_test() -> TestResult {
+        let rendered = resolve(
+            r#"struct User {
+  @1 id: u64;
+}
+struct Page<T> {
+  @1 items: Vec<T>;
+}
+enum Either<L, R, L> {
+  @1 Left(value: L);
+}
+struct Listing {
+  @1 users: Page<User>;
+  @2 nested: Page<Page<User>>;
+  @3 ids: Page<u64>;
+  @4 pairs: Page<User, User>;
+  @5 plain: Page;
+  @6 user: User<u64>;
+}
+"#,
+        )?;
+
+        assert_eq!(
+            messages(&rendered),
+            vec![
+                "/rpfs/user.rpf:7:16: unused type parameter: R",
+                "/rpfs/user.rpf:7:19: duplicate name: L",
+                "/rpfs/user.rpf:13:11: type argument must be a struct, enum or type parameter, found u64",
+                "/rpfs/user.rpf:14:13: type Page expects 1 type argument(s), found 2",
+                "/rpfs/user.rpf:15:13: type Page expects 1 type argument(s), found 0",
+                "/rpfs/user.rpf:16:12: type User expects 0 type argument(s), found 1",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_attributes_test() -> TestResult {
+        let rendered = resolve(
+            r#"#[rust(derive = "Hash")]
+#[deprecated = "use Account"]
+struct User {
+  #[max_len = 64]
+  @1 name: string;
+  #[max_len = 4]
+  @2 id: u64;
+  #[deprecated]
+  #[deprecated]
+  @3 tags: Vec<string>;
+}
+#[max_len = 1]
+#[rust(clone = "no")]
+enum E {
+  #[unknown]
+  @1 A;
+  @2 B { #[deprecated = 1] @1 x: Option<bytes>; };
+}
+"#,
+        )?;
+
+        assert_eq!(
+            messages(&rendered),
+            vec![
+                "/rpfs/user.rpf:6:5: max_len requires a string, bytes, Vec or map type, found u64",
+                "/rpfs/user.rpf:9:5: duplicate attribute or item",
+                "/rpfs/user.rpf:12:3: attribute max_len cannot be applied to enum",
+                "/rpfs/user.rpf:13:3: attribute rust expects derive = \"Trait, ...\"",
+                "/rpfs/user.rpf:15:5: unknown attribute: unknown",
+                "/rpfs/user.rpf:17:12: attribute deprecated expects no value or a string note",
+            ]
+        );
+
+        Ok(())
+    }
+}
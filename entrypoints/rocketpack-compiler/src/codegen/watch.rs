@@ -0,0 +1,135 @@
+use std::{
+    path::{Path as FsPath, PathBuf},
+    time::Duration,
+};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use super::{OutputMode, SourceCache, common::source_includes, generate};
+use crate::{config::AppConfig, error::CodegenError};
+
+// Editors usually emit several events for one save, so changes are collected for a short while before recompiling.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
+
+// Compiles once, then recompiles whenever a source changes. Errors are printed and the watch continues, so a broken
+// schema can be fixed without restarting.
+pub async fn watch(conf: &AppConfig) -> Result<(), CodegenError> {
+    let mut cache = SourceCache::default();
+    compile(conf, &mut cache).await;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    })?;
+    let base_dirs = watched_dirs(conf);
+    for base_dir in &base_dirs {
+        watcher.watch(base_dir, RecursiveMode::Recursive)?;
+    }
+
+    info!(root_dir = %conf.root_dir.display(), "watching rocketpack sources");
+
+    while let Some(event) = rx.recv().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                warn!(error = %err, "failed to receive a file change");
+                continue;
+            }
+        };
+        if !is_source_event(conf, &base_dirs, &event) {
+            continue;
+        }
+
+        tokio::time::sleep(DEBOUNCE_DURATION).await;
+        while rx.try_recv().is_ok() {}
+
+        compile(conf, &mut cache).await;
+    }
+
+    Ok(())
+}
+
+async fn compile(conf: &AppConfig, cache: &mut SourceCache) {
+    match generate(conf, cache, OutputMode::Write).await {
+        Ok(changed) => info!(changed_count = changed.len(), "compiled rocketpack sources"),
+        Err(err) => eprintln!("{err}"),
+    }
+}
+
+// The base directory of each source, resolved like the paths the platform watcher reports.
+fn watched_dirs(conf: &AppConfig) -> Vec<PathBuf> {
+    conf.sources
+        .iter()
+        .map(|source| {
+            let base_dir = conf.root_dir.join(&source.base_dir);
+            base_dir.canonicalize().unwrap_or(base_dir)
+        })
+        .collect()
+}
+
+fn is_source_event(conf: &AppConfig, base_dirs: &[PathBuf], event: &Event) -> bool {
+    !matches!(event.kind, EventKind::Access(_)) && event.paths.iter().any(|path| is_source_path(conf, base_dirs, path))
+}
+
+// Matches the `includes` / `excludes` patterns the compile uses, so only files it would read trigger a rebuild.
+fn is_source_path(conf: &AppConfig, base_dirs: &[PathBuf], path: &FsPath) -> bool {
+    conf.sources
+        .iter()
+        .zip(base_dirs)
+        .any(|(source, base_dir)| path.strip_prefix(base_dir).is_ok_and(|relative_path| source_includes(source, relative_path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use testresult::TestResult;
+
+    use super::*;
+
+    #[test]
+    fn is_source_event_test() -> TestResult {
+        let root_dir = tempfile::tempdir()?;
+        std::fs::create_dir_all(root_dir.path().join("rpfs/example"))?;
+
+        let mut conf = AppConfig::from_yaml(
+            r#"
+version: 1
+sources:
+  - base_dir: rpfs
+    includes:
+      - "**/*.rpf"
+    excludes:
+      - "**/*.test.rpf"
+"#,
+        )?;
+        conf.root_dir = root_dir.path().to_path_buf();
+
+        let base_dirs = watched_dirs(&conf);
+        let source_dir = &base_dirs[0];
+        let event = |kind: EventKind, path: PathBuf| Event::new(kind).add_path(path);
+        let modify = EventKind::Modify(ModifyKind::Any);
+
+        assert!(is_source_event(&conf, &base_dirs, &event(modify, source_dir.join("example/first.rpf"))));
+        assert!(is_source_event(
+            &conf,
+            &base_dirs,
+            &event(EventKind::Create(CreateKind::File), source_dir.join("second.rpf"))
+        ));
+
+        // Excluded, not included and outside the base directory.
+        assert!(!is_source_event(&conf, &base_dirs, &event(modify, source_dir.join("example/first.test.rpf"))));
+        assert!(!is_source_event(&conf, &base_dirs, &event(modify, source_dir.join("example/notes.txt"))));
+        assert!(!is_source_event(&conf, &base_dirs, &event(modify, root_dir.path().join("other/first.rpf"))));
+
+        // Reads do not change anything.
+        assert!(!is_source_event(
+            &conf,
+            &base_dirs,
+            &event(EventKind::Access(AccessKind::Any), source_dir.join("example/first.rpf"))
+        ));
+
+        Ok(())
+    }
+}
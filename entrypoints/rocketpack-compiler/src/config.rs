@@ -44,6 +44,7 @@ pub struct GeneratorTargetConfig {
 }
 
 impl AppConfig {
+    #[cfg(feature = "cli")]
     pub async fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
         let path_buf: PathBuf = path.as_ref().into();
         let contents = tokio::fs::read_to_string(&path_buf).await?;
@@ -65,6 +66,7 @@ mod tests {
 
     use super::*;
 
+    #[cfg(feature = "cli")]
     #[tokio::test]
     async fn config_load_test() -> TestResult {
         let config_path = Path::new("./data/rocketpack.yaml");
@@ -12,16 +12,27 @@ pub enum CodegenError {
     #[error("parse error: {0}")]
     Parse(#[from] ParseErrorBundle),
 
+    #[error("validation error: {}", render_bundles(.0))]
+    Validation(Vec<ParseErrorBundle>),
+
     #[error("config error: {0}")]
     Config(#[from] ConfigError),
 
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[cfg(feature = "cli")]
+    #[error("watch error: {0}")]
+    Watch(#[from] notify::Error),
+
     #[error("other error: {0}")]
     Other(String),
 }
 
+fn render_bundles(bundles: &[ParseErrorBundle]) -> String {
+    bundles.iter().map(|bundle| bundle.to_string()).collect()
+}
+
 #[derive(Error, Debug)]
 pub struct ParseErrorBundle {
     path: PathBuf,
@@ -118,6 +129,60 @@ pub enum ParseErrorKind {
     #[error("unterminated block or structure")]
     Unterminated,
 
+    #[error("unknown import: {0}")]
+    UnknownImport(String),
+
+    #[error("unknown type: {0}")]
+    UnknownType(String),
+
+    #[error("import cycle: {0}")]
+    ImportCycle(String),
+
+    #[error("cyclic type alias: {0}")]
+    CyclicAlias(String),
+
+    #[error("duplicate tag: @{0}")]
+    DuplicateTag(u32),
+
+    #[error("duplicate name: {0}")]
+    DuplicateName(String),
+
+    #[error("tag must be greater than 0")]
+    ZeroTag,
+
+    #[error("service id and rpc tag must be at most {max}, found @{tag}")]
+    RpcTagOutOfRange { tag: u32, max: u32 },
+
+    #[error("literal {literal} does not match type {ty}")]
+    MismatchedLiteral { literal: String, ty: String },
+
+    #[error("literal {literal} is out of range for {ty}")]
+    LiteralOutOfRange { literal: String, ty: String },
+
+    #[error("rpc message must be a struct or enum, found {0}")]
+    InvalidRpcType(String),
+
+    #[error("type {name} expects {expected} type argument(s), found {found}")]
+    TypeArgumentCount { name: String, expected: usize, found: usize },
+
+    #[error("type argument must be a struct, enum or type parameter, found {0}")]
+    InvalidTypeArgument(String),
+
+    #[error("unused type parameter: {0}")]
+    UnusedTypeParam(String),
+
+    #[error("unknown attribute: {0}")]
+    UnknownAttribute(String),
+
+    #[error("attribute {name} cannot be applied to {target}")]
+    MisplacedAttribute { name: String, target: &'static str },
+
+    #[error("attribute {name} expects {expected}")]
+    InvalidAttributeArgs { name: String, expected: &'static str },
+
+    #[error("max_len requires a string, bytes, Vec or map type, found {0}")]
+    InvalidMaxLenType(String),
+
     #[error("other error: {0}")]
     Other(String),
 }
@@ -0,0 +1,6 @@
+pub mod codegen;
+pub mod config;
+pub mod error;
+mod parser;
+
+pub use codegen::Builder;
@@ -1,14 +1,14 @@
 use std::path::{Path, PathBuf};
 
 use clap::{Parser, Subcommand};
+use tracing::info;
 use tracing_subscriber::EnvFilter;
 
-use crate::{codegen::generate, config::AppConfig, error::CodegenError};
-
-mod codegen;
-mod config;
-mod error;
-mod parser;
+use omnius_core_rocketpack_compiler::{
+    codegen::{self, OutputMode, SourceCache, check, check_compat, format, generate, serve_lsp},
+    config::AppConfig,
+    error::CodegenError,
+};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about = "rocketpack format compiler", long_about = None)]
@@ -22,13 +22,42 @@ enum Commands {
     Compile {
         #[arg(value_name = "DIR", default_value = "./")]
         dir: PathBuf,
+        /// Recompiles whenever a source changes
+        #[arg(long)]
+        watch: bool,
+        /// Fails if any generated file is out of date instead of writing it
+        #[arg(long, conflicts_with = "watch")]
+        check: bool,
+    },
+    /// Validates the schemas and reports every error without generating code
+    Check {
+        #[arg(value_name = "DIR", default_value = "./")]
+        dir: PathBuf,
+    },
+    /// Reports wire-incompatible changes between two versions of the schemas
+    CheckCompat {
+        #[arg(long, value_name = "DIR")]
+        old: PathBuf,
+        #[arg(long, value_name = "DIR")]
+        new: PathBuf,
+    },
+    /// Rewrites the schemas in canonical layout
+    Fmt {
+        #[arg(value_name = "DIR", default_value = "./")]
+        dir: PathBuf,
+        /// Fails if any schema is not formatted instead of rewriting it
+        #[arg(long)]
+        check: bool,
     },
+    /// Runs the language server for `.rpf` schemas over stdio
+    Lsp,
 }
 
 #[tokio::main]
 async fn main() {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info,sqlx=off"));
-    tracing_subscriber::fmt().with_env_filter(filter).with_target(false).init();
+    // Logs go to stderr, since stdout carries the output of the commands and the language server protocol.
+    tracing_subscriber::fmt().with_env_filter(filter).with_target(false).with_writer(std::io::stderr).init();
 
     if let Err(err) = run().await {
         eprintln!("{err}");
@@ -40,14 +69,69 @@ async fn run() -> Result<(), CodegenError> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Compile { dir } => run_compile(&dir).await?,
+        Commands::Compile { dir, watch, check } => run_compile(&dir, watch, check).await?,
+        Commands::Check { dir } => run_check(&dir).await?,
+        Commands::CheckCompat { old, new } => run_check_compat(&old, &new).await?,
+        Commands::Fmt { dir, check } => run_fmt(&dir, check).await?,
+        Commands::Lsp => serve_lsp().await?,
+    }
+
+    Ok(())
+}
+
+async fn run_compile(dir: &Path, watch: bool, check: bool) -> Result<(), CodegenError> {
+    let conf = AppConfig::load(dir.join("rocketpack.yaml")).await?;
+    if watch {
+        return codegen::watch(&conf).await;
+    }
+
+    let mode = if check { OutputMode::Check } else { OutputMode::Write };
+    let changed = generate(&conf, &mut SourceCache::default(), mode).await?;
+    if check {
+        for path in &changed {
+            println!("{}", path.display());
+        }
+
+        if !changed.is_empty() {
+            return Err(CodegenError::Other(format!("{} generated file(s) are out of date", changed.len())));
+        }
     }
 
     Ok(())
 }
 
-async fn run_compile(dir: &Path) -> Result<(), CodegenError> {
+async fn run_check(dir: &Path) -> Result<(), CodegenError> {
     let conf = AppConfig::load(dir.join("rocketpack.yaml")).await?;
-    generate(conf).await?;
+    let count = check(&conf).await?;
+    info!(sources = count, "schemas are valid");
+    Ok(())
+}
+
+async fn run_fmt(dir: &Path, check: bool) -> Result<(), CodegenError> {
+    let mode = if check { OutputMode::Check } else { OutputMode::Write };
+    let changed = format(dir, mode).await?;
+    if check {
+        for path in &changed {
+            println!("{}", path.display());
+        }
+
+        if !changed.is_empty() {
+            return Err(CodegenError::Other(format!("{} schema file(s) are not formatted", changed.len())));
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_check_compat(old: &Path, new: &Path) -> Result<(), CodegenError> {
+    let issues = check_compat(old, new).await?;
+    for issue in &issues {
+        println!("{issue}");
+    }
+
+    if !issues.is_empty() {
+        return Err(CodegenError::Other(format!("found {} breaking change(s)", issues.len())));
+    }
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use std::{fs, path::PathBuf};
+use std::path::PathBuf;
 
 use crate::{
     error::{ParseError, ParseErrorBundle, ParseErrorKind},
@@ -8,28 +8,16 @@ use crate::{
 pub mod ast;
 pub mod lexer;
 
-pub fn parse(path: &std::path::Path) -> Result<File, ParseErrorBundle> {
-    let source = fs::read_to_string(path).map_err(|err| {
-        ParseErrorBundle::new(
-            path,
-            String::new(),
-            vec![ParseError::new(ParseErrorKind::Other(format!("failed to read file: {err}")), 0, 0)],
-        )
-    })?;
-
-    parse_source(path.to_path_buf(), &source)
-}
-
 pub fn parse_source(path: impl Into<PathBuf>, source: &str) -> Result<File, ParseErrorBundle> {
     let path = path.into();
     let text = source.to_owned();
 
-    let (tokens, lex_errors) = lexer::lex(source);
+    let (tokens, docs, lex_errors) = lexer::lex_with_docs(source);
     if !lex_errors.is_empty() {
         return Err(ParseErrorBundle::new(path, text, lex_errors));
     }
 
-    let mut p = Parser::new(tokens);
+    let mut p = Parser::new(tokens).with_docs(docs);
     let file = p.parse_file();
     if p.errors.is_empty() {
         Ok(file)
@@ -41,12 +29,26 @@ pub fn parse_source(path: impl Into<PathBuf>, source: &str) -> Result<File, Pars
 pub struct Parser {
     tokens: Vec<SpannedToken>,
     i: usize,
+    docs: Vec<Spanned<String>>,
+    next_doc: usize,
     pub errors: Vec<ParseError>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<SpannedToken>) -> Self {
-        Self { tokens, errors: Vec::new(), i: 0 }
+        Self {
+            tokens,
+            errors: Vec::new(),
+            i: 0,
+            docs: Vec::new(),
+            next_doc: 0,
+        }
+    }
+
+    // ドキュメントコメントは `lexer::lex_with_docs` がトークンとは別に返す
+    pub fn with_docs(mut self, docs: Vec<Spanned<String>>) -> Self {
+        self.docs = docs;
+        self
     }
 
     // ===== 基本ユーティリティ =====
@@ -92,6 +94,7 @@ impl Parser {
             Some(Token::Str(_)) => "string",
             Some(Token::Bytes(_)) => "bytes",
             Some(Token::At) => "@",
+            Some(Token::Hash) => "#",
             Some(Token::Semi) => ";",
             Some(Token::Colon) => ":",
             Some(Token::Comma) => ",",
@@ -106,6 +109,7 @@ impl Parser {
             Some(Token::Gt) => ">",
             Some(Token::Dots) => "..",
             Some(Token::PathSep) => "::",
+            Some(Token::DocComment(_)) => "doc comment",
             None => "EOF",
         }
     }
@@ -123,8 +127,15 @@ impl Parser {
 
         // 任意の順序でトップレベルを読み込む
         while let Some(_) = self.peek() {
-            // キーワードは Ident で来るので先読みして判定
-            if let Some(kw) = self.peek_keyword() {
+            // ドキュメントコメントと属性は後続の要素に付く
+            let (docs, attributes) = self.parse_attributes();
+            // トップレベルで @番号 が付くのは service だけ
+            if self.at(Token::At) {
+                file.items.push(Item::Service(self.parse_service(docs, attributes)));
+            } else if let Some(kw) = self.peek_keyword() {
+                if matches!(kw.as_str(), "version" | "package" | "use") {
+                    self.reject_attributes(&attributes);
+                }
                 match kw.as_str() {
                     "version" => {
                         if file.version.is_some() {
@@ -150,16 +161,20 @@ impl Parser {
                         file.uses.push(self.parse_use());
                     }
                     "struct" => {
-                        file.items.push(Item::Struct(self.parse_struct()));
+                        file.items.push(Item::Struct(self.parse_struct(docs, attributes)));
                     }
                     "enum" => {
-                        file.items.push(Item::Enum(self.parse_enum()));
+                        file.items.push(Item::Enum(self.parse_enum(docs, attributes)));
                     }
                     "type" => {
-                        file.items.push(Item::TypeAlias(self.parse_type_alias()));
+                        file.items.push(Item::TypeAlias(self.parse_type_alias(docs, attributes)));
                     }
                     "const" => {
-                        file.items.push(Item::Const(self.parse_const()));
+                        file.items.push(Item::Const(self.parse_const(docs, attributes)));
+                    }
+                    "service" => {
+                        self.error_here(ParseErrorKind::Unexpected("expected @id before service"));
+                        file.items.push(Item::Service(self.parse_service(docs, attributes)));
                     }
                     _ => {
                         // 未対応トップレベル
@@ -167,6 +182,9 @@ impl Parser {
                         self.bump();
                     }
                 }
+            } else if !attributes.is_empty() {
+                // 属性の後ろに要素が無い
+                self.reject_attributes(&attributes);
             } else {
                 // 何かしらのトークンを消費して前に進む
                 self.error_here(ParseErrorKind::Unexpected("unexpected token at top-level"));
@@ -214,50 +232,89 @@ impl Parser {
         Use { path, alias }
     }
 
-    fn parse_struct(&mut self) -> Struct {
+    fn parse_struct(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Struct {
         let _kw = self.expect_ident_kw("struct");
         let name = self.expect_ident();
+        let type_params = self.parse_type_params();
         self.expect(Token::LBrace, "{");
         let mut fields = Vec::new();
         while !self.at(Token::RBrace) && self.peek().is_some() {
+            let (field_docs, field_attributes) = self.parse_attributes();
             if self.at(Token::At) {
-                fields.push(self.parse_field());
+                fields.push(self.parse_field(field_docs, field_attributes));
+            } else if !field_attributes.is_empty() {
+                self.reject_attributes(&field_attributes);
             } else {
                 self.error_here(ParseErrorKind::Unexpected("expected field or reserved"));
                 self.bump();
             }
         }
         self.expect(Token::RBrace, "}");
-        Struct { name, fields }
+        Struct {
+            docs,
+            attributes,
+            name,
+            type_params,
+            fields,
+        }
     }
 
-    fn parse_enum(&mut self) -> Enum {
+    fn parse_enum(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Enum {
         let _kw = self.expect_ident_kw("enum");
         let name = self.expect_ident();
+        let type_params = self.parse_type_params();
         self.expect(Token::LBrace, "{");
         let mut variants = Vec::new();
         while !self.at(Token::RBrace) && self.peek().is_some() {
+            let (variant_docs, variant_attributes) = self.parse_attributes();
             if self.at(Token::At) {
-                variants.push(self.parse_variant());
+                variants.push(self.parse_variant(variant_docs, variant_attributes));
+            } else if !variant_attributes.is_empty() {
+                self.reject_attributes(&variant_attributes);
             } else {
                 self.error_here(ParseErrorKind::Unexpected("expected @tag for variant"));
                 self.bump();
             }
         }
         self.expect(Token::RBrace, "}");
-        Enum { name, variants }
+        Enum {
+            docs,
+            attributes,
+            name,
+            type_params,
+            variants,
+        }
     }
 
-    fn parse_type_alias(&mut self) -> TypeAlias {
+    // `<T, U>` after the name of a struct or enum
+    fn parse_type_params(&mut self) -> Vec<Spanned<String>> {
+        let mut params = Vec::new();
+        if !self.at(Token::Lt) {
+            return params;
+        }
+        self.bump();
+        loop {
+            params.push(self.expect_ident());
+            if self.at(Token::Comma) {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::Gt, ">");
+        params
+    }
+
+    fn parse_type_alias(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> TypeAlias {
         let _kw = self.expect_ident_kw("type");
         let name = self.expect_ident();
         self.expect(Token::Eq, "=");
         let ty = self.expect_type();
         self.expect(Token::Semi, ";");
-        TypeAlias { name, ty }
+        TypeAlias { docs, attributes, name, ty }
     }
 
-    fn parse_const(&mut self) -> Const {
+    fn parse_const(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Const {
         let _kw = self.expect_ident_kw("const");
         let name = self.expect_ident();
         self.expect(Token::Colon, ":");
@@ -265,12 +322,52 @@ impl Parser {
         self.expect(Token::Eq, "=");
         let value = self.expect_literal();
         self.expect(Token::Semi, ";");
-        Const { name, ty, value }
+        Const {
+            docs,
+            attributes,
+            name,
+            ty,
+            value,
+        }
+    }
+
+    fn parse_service(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Service {
+        // @番号 が無い場合はエラー報告済みなので 0 で埋める
+        let tag = if self.at(Token::At) {
+            self.bump();
+            self.expect_int_u32_spanned()
+        } else {
+            let start = self.curr_start();
+            Spanned::new(0, start, start)
+        };
+        let _kw = self.expect_ident_kw("service");
+        let name = self.expect_ident();
+        self.expect(Token::LBrace, "{");
+        let mut rpcs = Vec::new();
+        while !self.at(Token::RBrace) && self.peek().is_some() {
+            let (rpc_docs, rpc_attributes) = self.parse_attributes();
+            if self.at(Token::At) {
+                rpcs.push(self.parse_rpc(rpc_docs, rpc_attributes));
+            } else if !rpc_attributes.is_empty() {
+                self.reject_attributes(&rpc_attributes);
+            } else {
+                self.error_here(ParseErrorKind::Unexpected("expected @tag for rpc"));
+                self.bump();
+            }
+        }
+        self.expect(Token::RBrace, "}");
+        Service {
+            docs,
+            attributes,
+            tag,
+            name,
+            rpcs,
+        }
     }
 
     // ===== struct: field / reserved =====
 
-    fn parse_field(&mut self) -> Field {
+    fn parse_field(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Field {
         let _at = self.expect(Token::At, "@").unwrap();
         let tag = self.expect_int_u32_spanned();
         let name = self.expect_ident();
@@ -283,12 +380,19 @@ impl Parser {
             None
         };
         self.expect(Token::Semi, ";");
-        Field { tag, name, ty, default }
+        Field {
+            docs,
+            attributes,
+            tag,
+            name,
+            ty,
+            default,
+        }
     }
 
     // ===== enum: variant =====
 
-    fn parse_variant(&mut self) -> Variant {
+    fn parse_variant(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Variant {
         let _at = self.expect(Token::At, "@");
         let tag = self.expect_int_u32_spanned();
         let name = self.expect_ident();
@@ -304,8 +408,11 @@ impl Parser {
             self.bump();
             let mut fields = Vec::new();
             while !self.at(Token::RBrace) && self.peek().is_some() {
+                let (field_docs, field_attributes) = self.parse_attributes();
                 if self.at(Token::At) {
-                    fields.push(self.parse_field());
+                    fields.push(self.parse_field(field_docs, field_attributes));
+                } else if !field_attributes.is_empty() {
+                    self.reject_attributes(&field_attributes);
                 } else {
                     self.error_here(ParseErrorKind::Unexpected("expected @tag inside record variant"));
                     self.bump();
@@ -318,7 +425,46 @@ impl Parser {
             self.error_here(ParseErrorKind::Unexpected("expected ';', '(' or '{' after variant name"));
             VariantKind::Unit
         };
-        Variant { tag, name, kind }
+        Variant {
+            docs,
+            attributes,
+            tag,
+            name,
+            kind,
+        }
+    }
+
+    // ===== service: rpc =====
+
+    fn parse_rpc(&mut self, docs: Vec<String>, attributes: Vec<Spanned<Attribute>>) -> Rpc {
+        let _at = self.expect(Token::At, "@");
+        let tag = self.expect_int_u32_spanned();
+        let _kw = self.expect_ident_kw("rpc");
+        let name = self.expect_ident();
+        let request = self.parse_rpc_type();
+        self.expect_ident_kw("returns");
+        let response = self.parse_rpc_type();
+        self.expect(Token::Semi, ";");
+        Rpc {
+            docs,
+            attributes,
+            tag,
+            name,
+            request,
+            response,
+        }
+    }
+
+    fn parse_rpc_type(&mut self) -> RpcType {
+        self.expect(Token::LParen, "(");
+        // `stream` は後ろに型が続く場合だけキーワードとして扱う
+        let stream = matches!(self.peek_keyword().as_deref(), Some("stream")) && matches!(self.nth(1).map(|t| &t.token), Some(Token::Ident(_) | Token::LBracket));
+        if stream {
+            self.bump();
+        }
+        let ty = self.expect_type();
+        self.expect(Token::RParen, ")");
+        RpcType { stream, ty }
     }
 
     fn parse_tuple_params(&mut self) -> Vec<(Spanned<String>, Spanned<Type>)> {
@@ -341,6 +487,78 @@ impl Parser {
         v
     }
 
+    // ===== 属性 =====
+
+    // 要素の前に置かれたドキュメントコメントと `#[...]` をまとめて読む
+    fn parse_attributes(&mut self) -> (Vec<String>, Vec<Spanned<Attribute>>) {
+        let mut docs = self.take_docs();
+        let mut attributes = Vec::new();
+        while self.at(Token::Hash) {
+            attributes.push(self.parse_attribute());
+            docs.extend(self.take_docs());
+        }
+        (docs, attributes)
+    }
+
+    // #[name] / #[name = literal] / #[name(key = literal, ...)]
+    fn parse_attribute(&mut self) -> Spanned<Attribute> {
+        let start = self.curr_start();
+        self.expect(Token::Hash, "#");
+        self.expect(Token::LBracket, "[");
+        let name = self.expect_ident();
+        let args = if self.at(Token::Eq) {
+            self.bump();
+            AttributeArgs::Value(self.expect_literal())
+        } else if self.at(Token::LParen) {
+            self.bump();
+            let mut args = Vec::new();
+            while !self.at(Token::RParen) && self.peek().is_some() {
+                let name = self.expect_ident();
+                self.expect(Token::Eq, "=");
+                let value = self.expect_literal();
+                args.push(AttributeArg { name, value });
+                if self.at(Token::Comma) {
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+            self.expect(Token::RParen, ")");
+            AttributeArgs::List(args)
+        } else {
+            AttributeArgs::Empty
+        };
+        self.expect(Token::RBracket, "]");
+        Spanned::new(Attribute { name, args }, start, self.prev_end())
+    }
+
+    // 属性を付けられない位置に置かれた属性を報告する
+    fn reject_attributes(&mut self, attributes: &[Spanned<Attribute>]) {
+        for attribute in attributes {
+            self.errors.push(ParseError::new(
+                ParseErrorKind::Unexpected("attribute must precede an item, field, variant or rpc"),
+                attribute.span.start,
+                attribute.span.end,
+            ));
+        }
+    }
+
+    // 直前のトークンと現在のトークンの間にあるドキュメントコメントを取り出す
+    fn take_docs(&mut self) -> Vec<String> {
+        let (start, end) = (self.prev_end(), self.curr_start());
+        let mut docs = Vec::new();
+        while let Some(doc) = self.docs.get(self.next_doc) {
+            if doc.span.start >= end {
+                break;
+            }
+            if doc.span.start >= start {
+                docs.push(doc.value.clone());
+            }
+            self.next_doc += 1;
+        }
+        docs
+    }
+
     // ===== 型 =====
 
     fn expect_type(&mut self) -> Spanned<Type> {
@@ -378,7 +596,23 @@ impl Parser {
             self.expect(Token::RBracket, "]");
             Type::Array(Box::new(inner.value), n)
         } else {
-            Type::Path(self.parse_path())
+            let path = self.parse_path();
+            if !self.at(Token::Lt) {
+                return Type::Path(path);
+            }
+            // ユーザー定義のジェネリック型: Page<T>
+            self.bump();
+            let mut args = Vec::new();
+            loop {
+                args.push(self.expect_type().value);
+                if self.at(Token::Comma) {
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+            self.expect(Token::Gt, ">");
+            Type::Generic(path, args)
         }
     }
 
@@ -546,10 +780,14 @@ impl Parser {
     fn curr_start(&self) -> usize {
         self.peek().map(|t| t.span.start).unwrap_or(0)
     }
+    // `bump` moves past the end on truncated input, so the previous token is looked up within the tokens.
+    fn prev(&self) -> Option<&SpannedToken> {
+        self.tokens[..self.i.min(self.tokens.len())].last()
+    }
     fn prev_start(&self) -> usize {
-        if self.i == 0 { 0 } else { self.tokens[self.i - 1].span.start }
+        self.prev().map(|t| t.span.start).unwrap_or(0)
     }
     fn prev_end(&self) -> usize {
-        if self.i == 0 { 0 } else { self.tokens[self.i - 1].span.end }
+        self.prev().map(|t| t.span.end).unwrap_or(0)
     }
 }
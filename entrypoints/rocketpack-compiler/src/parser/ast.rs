@@ -38,6 +38,7 @@ pub enum Item {
     Enum(Enum),
     TypeAlias(TypeAlias),
     Const(Const),
+    Service(Service),
 }
 
 impl std::fmt::Display for Item {
@@ -47,6 +48,7 @@ impl std::fmt::Display for Item {
             Item::Enum(e) => write!(f, "enum {}", e.name.value),
             Item::TypeAlias(t) => write!(f, "type {}", t.name.value),
             Item::Const(c) => write!(f, "const {}", c.name.value),
+            Item::Service(s) => write!(f, "service {}", s.name.value),
         }
     }
 }
@@ -64,7 +66,29 @@ pub enum Type {
     Option(Box<Type>),
     Vec(Box<Type>),
     Map(Box<Type>, Box<Type>),
-    Array(Box<Type>, u64), // [T; N]
+    Array(Box<Type>, u64),    // [T; N]
+    Generic(Path, Vec<Type>), // Page<T>, pkg::Pair<K, V>
+}
+
+// ===== 属性 =====
+
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    pub name: Spanned<String>,
+    pub args: AttributeArgs,
+}
+
+#[derive(Debug, Clone)]
+pub enum AttributeArgs {
+    Empty,                   // #[deprecated]
+    Value(Spanned<Literal>), // #[max_len = 256]
+    List(Vec<AttributeArg>), // #[rust(derive = "Hash")]
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributeArg {
+    pub name: Spanned<String>,
+    pub value: Spanned<Literal>,
 }
 
 #[derive(Debug, Clone)]
@@ -80,12 +104,17 @@ pub enum Literal {
 
 #[derive(Debug, Clone)]
 pub struct Struct {
+    pub docs: Vec<String>,                   // /// text
+    pub attributes: Vec<Spanned<Attribute>>, // #[name], #[name = value], #[name(key = value)]
     pub name: Spanned<String>,
+    pub type_params: Vec<Spanned<String>>, // struct Page<T>
     pub fields: Vec<Field>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Field {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
     pub tag: Spanned<u32>, // @N
     pub name: Spanned<String>,
     pub ty: Spanned<Type>,
@@ -96,12 +125,17 @@ pub struct Field {
 
 #[derive(Debug, Clone)]
 pub struct Enum {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
     pub name: Spanned<String>,
+    pub type_params: Vec<Spanned<String>>,
     pub variants: Vec<Variant>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Variant {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
     pub tag: Spanned<u32>,
     pub name: Spanned<String>,
     pub kind: VariantKind,
@@ -118,13 +152,44 @@ pub enum VariantKind {
 
 #[derive(Debug, Clone)]
 pub struct TypeAlias {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
     pub name: Spanned<String>,
     pub ty: Spanned<Type>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Const {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
     pub name: Spanned<String>,
     pub ty: Spanned<Type>,
     pub value: Spanned<Literal>,
 }
+
+// ===== service =====
+
+#[derive(Debug, Clone)]
+pub struct Service {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
+    pub tag: Spanned<u32>, // @N, the high half of the function ids of its rpcs
+    pub name: Spanned<String>,
+    pub rpcs: Vec<Rpc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rpc {
+    pub docs: Vec<String>,
+    pub attributes: Vec<Spanned<Attribute>>,
+    pub tag: Spanned<u32>, // @N, used as the function id
+    pub name: Spanned<String>,
+    pub request: RpcType,
+    pub response: RpcType,
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcType {
+    pub stream: bool, // stream T
+    pub ty: Spanned<Type>,
+}
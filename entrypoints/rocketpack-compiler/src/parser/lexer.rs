@@ -2,7 +2,7 @@ use logos::Logos;
 
 use crate::{
     error::{ParseError, ParseErrorKind},
-    parser::ast::Span,
+    parser::ast::{Span, Spanned},
 };
 
 #[derive(Logos, Debug, Clone, PartialEq)]
@@ -13,6 +13,8 @@ pub enum Token {
     // Separators / punctuation
     #[token("@")]
     At,
+    #[token("#")]
+    Hash,
     #[token(";")]
     Semi,
     #[token(":")]
@@ -59,6 +61,10 @@ pub enum Token {
     // Identifiers
     #[regex(r"[A-Za-z_][A-Za-z0-9_]*", parse_ident, priority = 2)]
     Ident(String),
+
+    // `/// text` is kept apart from the other tokens by `lex_with_docs`, so the grammar never sees it.
+    #[regex(r"///[^\n]*", parse_doc_comment, allow_greedy = true)]
+    DocComment(String),
 }
 
 fn parse_string(lex: &mut logos::Lexer<Token>) -> Option<String> {
@@ -201,6 +207,15 @@ fn parse_float(lex: &mut logos::Lexer<Token>) -> Option<f64> {
 fn parse_ident(lex: &mut logos::Lexer<Token>) -> Option<String> {
     Some(lex.slice().to_owned())
 }
+// `////` and longer runs of slashes are plain comments, as in Rust.
+fn parse_doc_comment(lex: &mut logos::Lexer<Token>) -> logos::Filter<String> {
+    let text = &lex.slice()[3..];
+    if text.starts_with('/') {
+        return logos::Filter::Skip;
+    }
+    let text = text.strip_prefix(' ').unwrap_or(text);
+    logos::Filter::Emit(text.trim_end().to_owned())
+}
 
 #[derive(Debug, Clone)]
 pub struct SpannedToken {
@@ -209,12 +224,20 @@ pub struct SpannedToken {
 }
 
 pub fn lex(input: &str) -> (Vec<SpannedToken>, Vec<ParseError>) {
+    let (tokens, _, errors) = lex_with_docs(input);
+    (tokens, errors)
+}
+
+// Doc comments are returned separately from the tokens, in source order.
+pub fn lex_with_docs(input: &str) -> (Vec<SpannedToken>, Vec<Spanned<String>>, Vec<ParseError>) {
     let mut errors = Vec::new();
     let mut lexer = Token::lexer(input);
     let mut out = Vec::new();
+    let mut docs = Vec::new();
     while let Some(result) = lexer.next() {
         let range = lexer.span();
         match result {
+            Ok(Token::DocComment(text)) => docs.push(Spanned::new(text, range.start, range.end)),
             Ok(tok) => out.push(SpannedToken {
                 token: tok,
                 span: Span {
@@ -227,5 +250,5 @@ pub fn lex(input: &str) -> (Vec<SpannedToken>, Vec<ParseError>) {
             }
         }
     }
-    (out, errors)
+    (out, docs, errors)
 }
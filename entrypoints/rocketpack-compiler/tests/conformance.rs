@@ -0,0 +1,254 @@
+// Conformance vectors shared by all generators. Each `tests/conformance/vectors/<name>.yaml` lists encodings of types
+// declared in `tests/conformance/rpfs/conformance/<name>.rpf` (package `conformance::<name>`):
+//
+// - name: unique within the file
+//   type: fully qualified name of a non-generic struct or enum
+//   hex: the encoded bytes
+//   diagnostic: the same bytes in diagnostic notation, as rendered by `to_diagnostic` (optional)
+//   reencoded: the bytes `export` writes after decoding, when they differ from `hex` (optional)
+//   canonical: the bytes `export_canonical` writes after decoding, when they differ from `reencoded` (optional)
+//   strict: whether strict decoding accepts `hex` (default: true)
+//   error: whether decoding `hex` must fail (default: false)
+//   error_message: text the decode error must contain, for vectors that must fail for a particular reason (optional)
+//
+// `rust_conformance_test` compiles the corpus with the Rust generator and round-trips every vector through the generated
+// code. `typescript_conformance_test` does the same with the TypeScript generator, leaving out `generics`, which it
+// rejects, and the checks the TypeScript runtime has no counterpart for (`diagnostic`, `canonical` and `strict`). It
+// needs `tsc` and `node` on the PATH.
+#[cfg(all(test, feature = "stable-test"))]
+mod tests {
+    use std::{
+        fmt::Write as _,
+        path::{Path, PathBuf},
+        process::Command,
+    };
+
+    use serde::Deserialize;
+    use testresult::TestResult;
+
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Vector {
+        name: String,
+        #[serde(rename = "type")]
+        type_name: String,
+        hex: String,
+        diagnostic: Option<String>,
+        reencoded: Option<String>,
+        canonical: Option<String>,
+        #[serde(default = "default_strict")]
+        strict: bool,
+        #[serde(default)]
+        error: bool,
+        error_message: Option<String>,
+    }
+
+    fn default_strict() -> bool {
+        true
+    }
+
+    #[test]
+    fn rust_conformance_test() -> TestResult {
+        let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance");
+        let work_dir = tempfile::tempdir()?;
+
+        let config = format!(
+            "version: 1\n\nsources:\n  - base_dir: {}\n    includes:\n      - \"**/*.rpf\"\n\ngenerators:\n  - id: rust\n    plugin: rocketpack-rust\n    options:\n      emit_schema: true\n    targets:\n      - pattern: conformance/*.rpf\n        options:\n          dir: gen\n",
+            corpus_dir.join("rpfs").display()
+        );
+        std::fs::write(work_dir.path().join("rocketpack.yaml"), config)?;
+
+        let output = Command::new(env!("CARGO_BIN_EXE_omnius-core-rocketpack-compiler"))
+            .arg("compile")
+            .arg(work_dir.path())
+            .output()?;
+        assert!(output.status.success(), "compile failed:\n{}", String::from_utf8_lossy(&output.stderr));
+
+        let main = render_main(&corpus_dir, &work_dir.path().join("gen"))?;
+        write_harness_crate(work_dir.path(), &main)?;
+
+        // Reuses one target directory across runs, so only the generated code is rebuilt.
+        let output = Command::new(env!("CARGO"))
+            .args(["run", "--quiet", "--offline"])
+            .current_dir(work_dir.path())
+            .env("CARGO_TARGET_DIR", Path::new(env!("CARGO_TARGET_TMPDIR")).join("conformance"))
+            .output()?;
+        assert!(
+            output.status.success(),
+            "conformance vectors failed:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn typescript_conformance_test() -> TestResult {
+        let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance");
+        let work_dir = tempfile::tempdir()?;
+
+        let config = format!(
+            "version: 1\n\nsources:\n  - base_dir: {}\n    includes:\n      - \"**/*.rpf\"\n    excludes:\n      - \"conformance/generics.rpf\"\n\ngenerators:\n  - id: typescript\n    plugin: rocketpack-typescript\n    targets:\n      - pattern: conformance/*.rpf\n        options:\n          dir: gen\n",
+            corpus_dir.join("rpfs").display()
+        );
+        std::fs::write(work_dir.path().join("rocketpack.yaml"), config)?;
+
+        let output = Command::new(env!("CARGO_BIN_EXE_omnius-core-rocketpack-compiler"))
+            .arg("compile")
+            .arg(work_dir.path())
+            .output()?;
+        assert!(output.status.success(), "compile failed:\n{}", String::from_utf8_lossy(&output.stderr));
+
+        std::fs::copy(corpus_dir.join("harness.ts"), work_dir.path().join("harness.ts"))?;
+        std::fs::write(work_dir.path().join("main.ts"), render_typescript_main(&corpus_dir)?)?;
+        std::fs::write(work_dir.path().join("tsconfig.json"), TSCONFIG)?;
+
+        let output = Command::new(TSC).args(["--project", "."]).current_dir(work_dir.path()).output()?;
+        assert!(
+            output.status.success(),
+            "tsc failed:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let output = Command::new("node").arg("out/main.js").current_dir(work_dir.path()).output()?;
+        assert!(
+            output.status.success(),
+            "conformance vectors failed:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        Ok(())
+    }
+
+    // npm installs `tsc` as a batch file on Windows, which is only found by its full name.
+    const TSC: &str = if cfg!(windows) { "tsc.cmd" } else { "tsc" };
+
+    // `dom` declares `console` and `TextEncoder` without pulling in the Node type definitions.
+    const TSCONFIG: &str = r#"{
+  "compilerOptions": {
+    "target": "es2022",
+    "lib": ["es2022", "dom"],
+    "module": "commonjs",
+    "strict": true,
+    "outDir": "out"
+  },
+  "files": ["main.ts"]
+}
+"#;
+
+    // The vector files by stem, in file name order.
+    fn load_vectors(corpus_dir: &Path) -> TestResult<Vec<(String, Vec<Vector>)>> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(corpus_dir.join("vectors"))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()?;
+        paths.sort();
+
+        let mut files = Vec::new();
+        for path in &paths {
+            let stem = path.file_stem().and_then(|stem| stem.to_str()).ok_or("invalid vector file name")?;
+            let vectors: Vec<Vector> = serde_yaml_ng::from_str(&std::fs::read_to_string(path)?)?;
+            assert!(!vectors.is_empty(), "{} has no vectors", path.display());
+            files.push((stem.to_string(), vectors));
+        }
+
+        Ok(files)
+    }
+
+    fn local_type_name<'a>(stem: &str, vector: &'a Vector) -> TestResult<&'a str> {
+        let prefix = format!("conformance::{stem}::");
+        let type_name = vector
+            .type_name
+            .strip_prefix(&prefix)
+            .ok_or_else(|| format!("{}/{}: type must be declared in package conformance::{stem}", stem, vector.name))?;
+        Ok(type_name)
+    }
+
+    fn render_main(corpus_dir: &Path, gen_dir: &Path) -> TestResult<String> {
+        let mut modules = String::new();
+        let mut checks = String::new();
+        writeln!(modules, "#[path = {:?}]\nmod harness;", corpus_dir.join("harness.rs").display().to_string())?;
+
+        for (stem, vectors) in load_vectors(corpus_dir)? {
+            writeln!(modules, "#[path = {:?}]\nmod {stem};", gen_dir.join(format!("{stem}.rs")).display().to_string())?;
+
+            for vector in &vectors {
+                let type_name = local_type_name(&stem, vector)?;
+                writeln!(
+                    checks,
+                    "    report.check::<{stem}::conformance::{stem}::{type_name}>(Vector {{ name: {:?}, hex: {:?}, reencoded: {:?}, canonical: {:?}, diagnostic: {:?}, strict: {}, error: {}, error_message: {:?} }});",
+                    format!("{stem}/{}", vector.name),
+                    vector.hex,
+                    vector.reencoded,
+                    vector.canonical,
+                    vector.diagnostic,
+                    vector.strict,
+                    vector.error,
+                    vector.error_message
+                )?;
+            }
+        }
+
+        Ok(format!(
+            "{modules}\nuse harness::{{Report, Vector}};\n\nfn main() {{\n    let mut report = Report::default();\n{checks}    report.finish();\n}}\n"
+        ))
+    }
+
+    fn render_typescript_main(corpus_dir: &Path) -> TestResult<String> {
+        let mut imports = String::new();
+        let mut checks = String::new();
+        writeln!(imports, "import {{ Report }} from \"./harness\";")?;
+
+        for (stem, vectors) in load_vectors(corpus_dir)? {
+            if stem == "generics" {
+                continue;
+            }
+            writeln!(imports, "import * as {stem} from \"./gen/{stem}\";")?;
+
+            for vector in &vectors {
+                let type_name = local_type_name(&stem, vector)?;
+                writeln!(
+                    checks,
+                    "report.check({stem}.{type_name}, {{ name: {}, hex: {}, reencoded: {}, error: {}, errorMessage: {} }});",
+                    ts_string(Some(&format!("{stem}/{}", vector.name))),
+                    ts_string(Some(&vector.hex)),
+                    ts_string(vector.reencoded.as_deref()),
+                    vector.error,
+                    ts_string(vector.error_message.as_deref())
+                )?;
+            }
+        }
+
+        Ok(format!("{imports}\nconst report = new Report();\n{checks}report.finish();\n"))
+    }
+
+    // The strings in the vectors are plain ASCII, so Rust's debug quoting is also a valid TypeScript literal.
+    fn ts_string(value: Option<&str>) -> String {
+        match value {
+            Some(value) => format!("{value:?}"),
+            None => "undefined".to_string(),
+        }
+    }
+
+    fn write_harness_crate(dir: &Path, main: &str) -> TestResult {
+        let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..").canonicalize()?;
+        let manifest = format!(
+            "[package]\nname = \"rocketpack-conformance\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nomnius-core-rocketpack = {{ path = {:?}, features = [\"diagnostic\"] }}\n\n[workspace]\n",
+            workspace_dir.join("modules/rocketpack").display().to_string()
+        );
+        std::fs::write(dir.join("Cargo.toml"), manifest)?;
+
+        // Pins the dependencies to the versions the workspace already resolved, so the build works offline.
+        let lock_path = workspace_dir.join("Cargo.lock");
+        if lock_path.exists() {
+            std::fs::copy(lock_path, dir.join("Cargo.lock"))?;
+        }
+
+        std::fs::create_dir_all(dir.join("src"))?;
+        std::fs::write(dir.join("src/main.rs"), main)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+// Runtime of the round-trip program written by `tests/conformance.rs`. Every vector becomes one `Report::check` call
+// against the type generated from the corpus, and mismatches are collected so that one run reports all of them.
+
+use omnius_core_rocketpack::{RocketPackStruct, from_diagnostic, to_diagnostic};
+
+pub struct Vector {
+    pub name: &'static str,
+    pub hex: &'static str,
+    pub reencoded: Option<&'static str>,
+    pub canonical: Option<&'static str>,
+    pub diagnostic: Option<&'static str>,
+    pub strict: bool,
+    pub error: bool,
+    pub error_message: Option<&'static str>,
+}
+
+#[derive(Default)]
+pub struct Report {
+    passed: usize,
+    failures: Vec<String>,
+}
+
+impl Report {
+    pub fn check<T: RocketPackStruct>(&mut self, vector: Vector) {
+        match run::<T>(&vector) {
+            Ok(()) => self.passed += 1,
+            Err(reason) => self.failures.push(format!("{}: {}", vector.name, reason)),
+        }
+    }
+
+    pub fn finish(self) {
+        for failure in &self.failures {
+            println!("{failure}");
+        }
+        println!("{} passed, {} failed", self.passed, self.failures.len());
+
+        if !self.failures.is_empty() {
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run<T: RocketPackStruct>(vector: &Vector) -> Result<(), String> {
+    let bytes = decode_hex(vector.hex)?;
+
+    if let Some(diagnostic) = vector.diagnostic {
+        let parsed = from_diagnostic(diagnostic).map_err(|e| format!("invalid diagnostic: {e}"))?;
+        if parsed != bytes {
+            return Err(format!("diagnostic encodes to {}, expected {}", encode_hex(&parsed), encode_hex(&bytes)));
+        }
+
+        let rendered = to_diagnostic(&bytes).map_err(|e| format!("failed to render diagnostic: {e}"))?;
+        if rendered != diagnostic {
+            return Err(format!("bytes render as {rendered}, expected {diagnostic}"));
+        }
+    }
+
+    if vector.error {
+        return match T::import(&bytes) {
+            Ok(_) => Err("decoded, expected an error".to_string()),
+            Err(e) => match vector.error_message {
+                Some(message) if !e.to_string().contains(message) => Err(format!("failed with {e}, expected {message}")),
+                _ => Ok(()),
+            },
+        };
+    }
+
+    let expected = match vector.reencoded {
+        Some(reencoded) => decode_hex(reencoded)?,
+        None => bytes.clone(),
+    };
+
+    let value = T::import(&bytes).map_err(|e| format!("failed to decode: {e}"))?;
+    let exported = value.export().map_err(|e| format!("failed to encode: {e}"))?;
+    if exported != expected {
+        return Err(format!("re-encoded to {}, expected {}", encode_hex(&exported), encode_hex(&expected)));
+    }
+
+    let expected = match vector.canonical {
+        Some(canonical) => decode_hex(canonical)?,
+        None => expected,
+    };
+    let exported = value.export_canonical().map_err(|e| format!("failed to encode canonically: {e}"))?;
+    if exported != expected {
+        return Err(format!("canonically re-encoded to {}, expected {}", encode_hex(&exported), encode_hex(&expected)));
+    }
+
+    match (T::import_strict(&bytes), vector.strict) {
+        (Err(e), true) => return Err(format!("strict decoding failed: {e}")),
+        (Ok(_), false) => return Err("strict decoding accepted a non-canonical encoding".to_string()),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if digits.len() % 2 != 0 {
+        return Err(format!("odd number of hex digits: {text}"));
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).map_err(|_| format!("invalid hex: {text}"))?;
+            u8::from_str_radix(pair, 16).map_err(|_| format!("invalid hex: {text}"))
+        })
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
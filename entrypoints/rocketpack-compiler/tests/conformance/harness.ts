@@ -0,0 +1,90 @@
+// Runtime of the TypeScript round-trip program written by `tests/conformance.rs`, the counterpart of `harness.rs`. The
+// TypeScript runtime has no strict decoding, canonical encoding or diagnostic notation, so only decoding, re-encoding
+// and the expected errors are checked.
+
+import { decode, encode, type RocketPackCodec } from "./gen/rocketpack_runtime";
+
+export interface Vector {
+    name: string;
+    hex: string;
+    reencoded?: string;
+    error: boolean;
+    errorMessage?: string;
+}
+
+export class Report {
+    private passed = 0;
+    private readonly failures: string[] = [];
+
+    check<T>(codec: RocketPackCodec<T>, vector: Vector): void {
+        const failure = run(codec, vector);
+        if (failure === undefined) {
+            this.passed++;
+        } else {
+            this.failures.push(`${vector.name}: ${failure}`);
+        }
+    }
+
+    finish(): void {
+        for (const failure of this.failures) {
+            console.log(failure);
+        }
+        console.log(`${this.passed} passed, ${this.failures.length} failed`);
+
+        if (this.failures.length > 0) {
+            throw new Error(`${this.failures.length} conformance vectors failed`);
+        }
+    }
+}
+
+function run<T>(codec: RocketPackCodec<T>, vector: Vector): string | undefined {
+    const bytes = decodeHex(vector.hex);
+
+    let value: T;
+    try {
+        value = decode(codec, bytes);
+    } catch (e) {
+        if (!vector.error) {
+            return `failed to decode: ${describe(e)}`;
+        }
+        if (vector.errorMessage !== undefined && !describe(e).includes(vector.errorMessage)) {
+            return `failed with ${describe(e)}, expected ${vector.errorMessage}`;
+        }
+        return undefined;
+    }
+
+    if (vector.error) {
+        return "decoded, expected an error";
+    }
+
+    let exported: Uint8Array;
+    try {
+        exported = encode(codec, value);
+    } catch (e) {
+        return `failed to encode: ${describe(e)}`;
+    }
+
+    const expected = vector.reencoded ?? vector.hex;
+    if (encodeHex(exported) !== expected) {
+        return `re-encoded to ${encodeHex(exported)}, expected ${expected}`;
+    }
+
+    return undefined;
+}
+
+function describe(e: unknown): string {
+    return e instanceof Error ? e.message : String(e);
+}
+
+function decodeHex(text: string): Uint8Array {
+    const digits = text.replace(/\s/g, "");
+    const bytes = new Uint8Array(digits.length / 2);
+    for (let i = 0; i < bytes.length; i++) {
+        bytes[i] = parseInt(digits.slice(i * 2, i * 2 + 2), 16);
+    }
+    return bytes;
+}
+
+function encodeHex(bytes: Uint8Array): string {
+    return Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("");
+}
@@ -0,0 +1,48 @@
+// Builds the C# output for the conformance corpus and the example schemas, together with the runtime the generator
+// writes next to it, with `dotnet build`. Generic schemas are left out, since the C# generator rejects them.
+#[cfg(all(test, feature = "stable-test"))]
+mod tests {
+    use std::{path::Path, process::Command};
+
+    use testresult::TestResult;
+
+    const PROJECT: &str = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <Nullable>enable</Nullable>
+  </PropertyGroup>
+</Project>
+"#;
+
+    #[test]
+    fn csharp_build_test() -> TestResult {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let work_dir = tempfile::tempdir()?;
+
+        let config = format!(
+            "version: 1\n\nsources:\n  - base_dir: {}\n    includes:\n      - \"conformance/*.rpf\"\n    excludes:\n      - \"conformance/generics.rpf\"\n  - base_dir: {}\n    includes:\n      - \"example/*.rpf\"\n\ngenerators:\n  - id: csharp\n    plugin: rocketpack-csharp\n    options:\n      target_framework: net8.0\n    targets:\n      - pattern: \"**/*.rpf\"\n        options:\n          dir: gen\n",
+            manifest_dir.join("tests/conformance/rpfs").display(),
+            manifest_dir.join("../rocketpack-compiled-example/rpfs").display()
+        );
+        std::fs::write(work_dir.path().join("rocketpack.yaml"), config)?;
+
+        let output = Command::new(env!("CARGO_BIN_EXE_omnius-core-rocketpack-compiler"))
+            .arg("compile")
+            .arg(work_dir.path())
+            .output()?;
+        assert!(output.status.success(), "compile failed:\n{}", String::from_utf8_lossy(&output.stderr));
+        assert!(work_dir.path().join("gen/RocketPackRuntime.cs").exists());
+
+        std::fs::write(work_dir.path().join("Generated.csproj"), PROJECT)?;
+
+        let output = Command::new("dotnet").args(["build", "--nologo"]).current_dir(work_dir.path()).output()?;
+        assert!(
+            output.status.success(),
+            "dotnet build failed:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+// Keeps the schemas shipped in the repository in the layout `fmt` produces.
+#[cfg(all(test, feature = "stable-test"))]
+mod tests {
+    use std::{path::Path, process::Command};
+
+    use testresult::TestResult;
+
+    #[test]
+    fn fmt_check_test() -> TestResult {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        for dir in ["data", "tests/conformance", "../rocketpack-compiled-example"] {
+            let output = Command::new(env!("CARGO_BIN_EXE_omnius-core-rocketpack-compiler"))
+                .arg("fmt")
+                .arg("--check")
+                .arg(manifest_dir.join(dir))
+                .output()?;
+            assert!(
+                output.status.success(),
+                "{dir} is not formatted:\n{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+// Builds the Swift output for the conformance corpus and the example schemas, together with the runtime the generator
+// writes next to it, with `swift build`. Generic schemas are left out, since the Swift generator rejects them. Windows
+// runners do not ship a Swift toolchain, so the test only runs elsewhere.
+#[cfg(all(test, feature = "stable-test", not(windows)))]
+mod tests {
+    use std::{path::Path, process::Command};
+
+    use testresult::TestResult;
+
+    // `Int128` / `UInt128` need macOS 15 on Apple platforms.
+    const PACKAGE: &str = r#"// swift-tools-version:6.0
+import PackageDescription
+
+let package = Package(
+    name: "Generated",
+    platforms: [.macOS(.v15)],
+    targets: [
+        .target(name: "Generated", path: "gen"),
+    ]
+)
+"#;
+
+    #[test]
+    fn swift_build_test() -> TestResult {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let work_dir = tempfile::tempdir()?;
+
+        let config = format!(
+            "version: 1\n\nsources:\n  - base_dir: {}\n    includes:\n      - \"conformance/*.rpf\"\n    excludes:\n      - \"conformance/generics.rpf\"\n  - base_dir: {}\n    includes:\n      - \"example/*.rpf\"\n\ngenerators:\n  - id: swift\n    plugin: rocketpack-swift\n    options:\n      swift_version: 6.x\n    targets:\n      - pattern: \"**/*.rpf\"\n        options:\n          dir: gen\n",
+            manifest_dir.join("tests/conformance/rpfs").display(),
+            manifest_dir.join("../rocketpack-compiled-example/rpfs").display()
+        );
+        std::fs::write(work_dir.path().join("rocketpack.yaml"), config)?;
+
+        let output = Command::new(env!("CARGO_BIN_EXE_omnius-core-rocketpack-compiler"))
+            .arg("compile")
+            .arg(work_dir.path())
+            .output()?;
+        assert!(output.status.success(), "compile failed:\n{}", String::from_utf8_lossy(&output.stderr));
+        assert!(work_dir.path().join("gen/RocketPackRuntime.swift").exists());
+
+        std::fs::write(work_dir.path().join("Package.swift"), PACKAGE)?;
+
+        let output = Command::new("swift").arg("build").current_dir(work_dir.path()).output()?;
+        assert!(
+            output.status.success(),
+            "swift build failed:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use futures_util::SinkExt as _;
+use futures_util::SinkExt;
 use tokio::io::AsyncWrite;
 use tokio_util::bytes::Bytes;
 
@@ -43,7 +43,8 @@ where
 {
     async fn send(&mut self, buffer: Bytes) -> Result<()> {
         self.framed.send(buffer).await?;
-        self.framed.flush().await?;
+        // Newer tokio-util also encodes `&[u8]`, so the item type of the sink has to be named.
+        SinkExt::<Bytes>::flush(&mut self.framed).await?;
         Ok(())
     }
 }
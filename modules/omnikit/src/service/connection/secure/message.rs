@@ -120,13 +120,11 @@ impl RocketPackStruct for ProfileMessage {
 
 #[cfg(test)]
 mod tests {
-    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL};
     use enumflags2::make_bitflags;
     use testresult::TestResult;
 
     use super::*;
 
-    #[ignore]
     #[test]
     fn simple_test() -> TestResult {
         let p = ProfileMessage {
@@ -143,8 +141,7 @@ mod tests {
 
         assert_eq!(p, p2);
 
-        let v = b.to_vec();
-        println!("{:?}", BASE64_URL.encode(v.as_slice()));
+        assert_eq!(omnius_core_rocketpack::to_diagnostic(&b)?, "{0: h'01020304', 1: 1, 2: 1, 3: 2, 4: 1, 5: 1}");
 
         Ok(())
     }
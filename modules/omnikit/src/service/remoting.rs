@@ -1,9 +1,11 @@
 mod caller;
+mod channel;
 mod hello_message;
 mod listener;
 mod stream;
 
 pub use caller::*;
+pub use channel::*;
 use hello_message::*;
 pub use listener::*;
 pub use stream::*;
@@ -12,6 +14,7 @@ pub use stream::*;
 mod tests {
     use std::time::Duration;
 
+    use omnius_core_rocketpack::EmptyRocketMessage;
     use testresult::TestResult;
 
     use crate::prelude::*;
@@ -66,39 +69,83 @@ mod tests {
         Ok(())
     }
 
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct TestMessage {
-        pub value: i32,
+    #[tokio::test]
+    async fn finish_test() -> TestResult {
+        let (client_side, server_side) = tokio::io::duplex(4096);
+
+        let listener_task = tokio::spawn(async {
+            let listener = OmniRemotingListener::new(server_side, 1024 * 1024).await.unwrap();
+
+            listener
+                .listen_stream(async |stream| {
+                    let channel = OmniRemotingChannel::<TestMessage, TestMessage>::new(stream);
+                    let mut sum = 0;
+                    while let Some(received) = channel.recv_next().await.unwrap() {
+                        sum += received.value;
+                    }
+
+                    channel.send(TestMessage { value: sum }).await.unwrap();
+                    channel.finish().await.unwrap();
+                })
+                .await
+                .unwrap();
+        });
+
+        let caller = OmniRemotingCaller::new(client_side, 1024 * 1024, 1).await?;
+        let channel = OmniRemotingChannel::<TestMessage, TestMessage>::new(caller.call_stream());
+        for value in 1..=3 {
+            channel.send(TestMessage { value }).await?;
+        }
+        channel.finish().await?;
+
+        assert_eq!(channel.recv().await?, TestMessage { value: 6 });
+        assert_eq!(channel.recv_next().await?, None);
+        assert_eq!(channel.recv().await.err().map(|err| err.kind().clone()), Some(ErrorKind::EndOfStream));
+
+        tokio::time::timeout(Duration::from_secs(30), listener_task).await??;
+
+        Ok(())
     }
 
-    impl RocketPackStruct for TestMessage {
-        fn pack(encoder: &mut impl RocketPackEncoder, value: &Self) -> std::result::Result<(), RocketPackEncoderError> {
-            encoder.write_map(1)?;
+    #[tokio::test]
+    async fn empty_message_test() -> TestResult {
+        let (client_side, server_side) = tokio::io::duplex(4096);
 
-            encoder.write_u64(0)?;
-            encoder.write_i32(value.value)?;
+        let listener_task = tokio::spawn(async {
+            let listener = OmniRemotingListener::new(server_side, 1024 * 1024).await.unwrap();
 
-            Ok(())
-        }
+            listener
+                .listen_stream(async |stream| {
+                    let channel = OmniRemotingChannel::<EmptyRocketMessage, EmptyRocketMessage>::new(stream);
+                    let mut count = 0;
+                    while channel.recv_next().await.unwrap().is_some() {
+                        count += 1;
+                    }
+                    assert_eq!(count, 2);
 
-        fn unpack(decoder: &mut impl RocketPackDecoder) -> std::result::Result<Self, RocketPackDecoderError>
-        where
-            Self: Sized,
-        {
-            let count = decoder.read_map()?;
+                    channel.send(EmptyRocketMessage).await.unwrap();
+                    channel.finish().await.unwrap();
+                })
+                .await
+                .unwrap();
+        });
 
-            let mut value: Option<i32> = None;
+        let caller = OmniRemotingCaller::new(client_side, 1024 * 1024, 1).await?;
+        let channel = OmniRemotingChannel::<EmptyRocketMessage, EmptyRocketMessage>::new(caller.call_stream());
+        channel.send(EmptyRocketMessage).await?;
+        channel.send(EmptyRocketMessage).await?;
+        channel.finish().await?;
 
-            for _ in 0..count {
-                match decoder.read_u64()? {
-                    0 => value = Some(decoder.read_i32()?),
-                    _ => decoder.skip_field()?,
-                }
-            }
+        assert_eq!(channel.recv().await?, EmptyRocketMessage);
+        assert_eq!(channel.recv_next().await?, None);
 
-            Ok(Self {
-                value: value.ok_or(RocketPackDecoderError::Other("missing field: test"))?,
-            })
-        }
+        tokio::time::timeout(Duration::from_secs(30), listener_task).await??;
+
+        Ok(())
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, RocketPackStruct)]
+    pub struct TestMessage {
+        pub value: i32,
     }
 }
@@ -0,0 +1,41 @@
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+
+use super::OmniRemotingStream;
+
+// A stream that only sends `TSend` and receives `TRecv`, used by the generated service stubs.
+pub struct OmniRemotingChannel<TSend, TRecv> {
+    stream: OmniRemotingStream,
+    _phantom: PhantomData<fn(TSend) -> TRecv>,
+}
+
+impl<TSend, TRecv> OmniRemotingChannel<TSend, TRecv>
+where
+    TSend: RocketPackStruct + Send + Sync + 'static,
+    TRecv: RocketPackStruct + Send + Sync + 'static,
+{
+    pub fn new(stream: OmniRemotingStream) -> Self {
+        Self { stream, _phantom: PhantomData }
+    }
+
+    pub async fn send(&self, message: TSend) -> Result<()> {
+        self.stream.send(message).await
+    }
+
+    pub async fn finish(&self) -> Result<()> {
+        self.stream.finish().await
+    }
+
+    pub async fn recv(&self) -> Result<TRecv> {
+        self.stream.recv::<TRecv>().await
+    }
+
+    pub async fn recv_next(&self) -> Result<Option<TRecv>> {
+        self.stream.recv_next::<TRecv>().await
+    }
+
+    pub fn into_stream(self) -> OmniRemotingStream {
+        self.stream
+    }
+}
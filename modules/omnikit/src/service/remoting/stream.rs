@@ -1,12 +1,18 @@
 use std::sync::Arc;
 
 use tokio::sync::Mutex as TokioMutex;
+use tokio_util::bytes::Bytes;
 
 use crate::{
     prelude::*,
     service::connection::codec::{FramedRecv, FramedSend},
 };
 
+// Every frame starts with one of these bytes, so the end-of-stream marker cannot collide with a message that encodes to
+// nothing, such as `EmptyRocketMessage`.
+const FRAME_MESSAGE: u8 = 0;
+const FRAME_FINISH: u8 = 1;
+
 pub struct OmniRemotingStream {
     receiver: Arc<TokioMutex<Box<dyn FramedRecv + Send>>>,
     sender: Arc<TokioMutex<Box<dyn FramedSend + Send>>>,
@@ -21,19 +27,39 @@ impl OmniRemotingStream {
     where
         T: RocketPackStruct + Send + Sync + 'static,
     {
-        let bytes = message.export()?;
+        let mut bytes = vec![FRAME_MESSAGE];
+        bytes.extend(message.export()?);
         self.sender.lock().await.send(bytes.into()).await?;
 
         Ok(())
     }
 
+    // Tells the other side that no more messages follow.
+    pub async fn finish(&self) -> Result<()> {
+        self.sender.lock().await.send(Bytes::from_static(&[FRAME_FINISH])).await?;
+
+        Ok(())
+    }
+
     pub async fn recv<T>(&self) -> Result<T>
     where
         T: RocketPackStruct + Send + Sync + 'static,
     {
-        let bytes = self.receiver.lock().await.recv().await?;
-        let message = T::import(&bytes)?;
+        self.recv_next::<T>()
+            .await?
+            .ok_or_else(|| Error::new(ErrorKind::EndOfStream).with_message("stream finished"))
+    }
 
-        Ok(message)
+    // Returns `None` once the other side has called `finish`.
+    pub async fn recv_next<T>(&self) -> Result<Option<T>>
+    where
+        T: RocketPackStruct + Send + Sync + 'static,
+    {
+        let bytes = self.receiver.lock().await.recv().await?;
+        match bytes.split_first() {
+            Some((&FRAME_MESSAGE, body)) => Ok(Some(T::import(body)?)),
+            Some((&FRAME_FINISH, [])) => Ok(None),
+            _ => Err(Error::new(ErrorKind::InvalidFormat).with_message("invalid stream frame")),
+        }
     }
 }
@@ -0,0 +1,37 @@
+use syn::{Field, LitInt};
+
+#[derive(Debug, Default)]
+pub struct FieldAttribute {
+    pub tag: Option<u64>,
+    pub default: bool,
+    pub skip: bool,
+}
+
+impl FieldAttribute {
+    pub fn parse(field: &Field) -> syn::Result<Self> {
+        let mut result = Self::default();
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("rocketpack")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    if result.tag.is_some() {
+                        return Err(meta.error("duplicate `tag` attribute"));
+                    }
+                    let lit: LitInt = meta.value()?.parse()?;
+                    result.tag = Some(lit.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    result.default = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    result.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported rocketpack attribute, expected `tag`, `default` or `skip`"))
+                }
+            })?;
+        }
+
+        Ok(result)
+    }
+}
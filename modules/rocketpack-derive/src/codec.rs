@@ -0,0 +1,276 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::{Expr, GenericArgument, PathArguments, Type};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeMode {
+    Owned,
+    Borrowed,
+}
+
+// Wire representation of a field type, derived from its syntax.
+// `Vec<u8>` is treated as bytes, matching the hand-written impls.
+pub enum FieldKind {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F16,
+    F32,
+    F64,
+    String,
+    Bytes,
+    Str(Type),
+    ByteSlice(Type),
+    CowStr,
+    CowBytes,
+    Option(Box<FieldKind>),
+    Vec(Box<FieldKind>),
+    Map(Type, Box<FieldKind>, Box<FieldKind>),
+    Array(Type, Box<FieldKind>, Expr),
+    Pointer(Type, Box<FieldKind>),
+    Struct(Type),
+}
+
+impl FieldKind {
+    pub fn of(ty: &Type) -> Self {
+        match ty {
+            Type::Array(array) => Self::Array(ty.clone(), Box::new(Self::of(&array.elem)), array.len.clone()),
+            Type::Group(group) => Self::of(&group.elem),
+            Type::Paren(paren) => Self::of(&paren.elem),
+            Type::Reference(reference) if reference.mutability.is_none() => match reference.elem.as_ref() {
+                Type::Path(path) if path.path.is_ident("str") => Self::Str(ty.clone()),
+                Type::Slice(slice) if matches!(Self::of(&slice.elem), Self::U8) => Self::ByteSlice(ty.clone()),
+                _ => Self::Struct(ty.clone()),
+            },
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let Some(last) = type_path.path.segments.last() else {
+                    return Self::Struct(ty.clone());
+                };
+                let args = generic_args(&last.arguments);
+
+                match (last.ident.to_string().as_str(), args.as_slice()) {
+                    ("bool", []) => Self::Bool,
+                    ("u8", []) => Self::U8,
+                    ("u16", []) => Self::U16,
+                    ("u32", []) => Self::U32,
+                    ("u64", []) => Self::U64,
+                    ("i8", []) => Self::I8,
+                    ("i16", []) => Self::I16,
+                    ("i32", []) => Self::I32,
+                    ("i64", []) => Self::I64,
+                    ("f16", []) => Self::F16,
+                    ("f32", []) => Self::F32,
+                    ("f64", []) => Self::F64,
+                    ("String", []) => Self::String,
+                    ("Option", [inner]) => Self::Option(Box::new(Self::of(inner))),
+                    ("Vec", [inner]) => match Self::of(inner) {
+                        Self::U8 => Self::Bytes,
+                        inner => Self::Vec(Box::new(inner)),
+                    },
+                    ("BTreeMap" | "HashMap", [key, value]) => Self::Map(ty.clone(), Box::new(Self::of(key)), Box::new(Self::of(value))),
+                    ("Cow", [Type::Path(inner)]) if inner.path.is_ident("str") => Self::CowStr,
+                    ("Cow", [Type::Slice(inner)]) if matches!(Self::of(&inner.elem), Self::U8) => Self::CowBytes,
+                    ("Box" | "Rc" | "Arc", [inner]) => Self::Pointer(ty.clone(), Box::new(Self::of(inner))),
+                    _ => Self::Struct(ty.clone()),
+                }
+            }
+            _ => Self::Struct(ty.clone()),
+        }
+    }
+
+    pub fn is_option(&self) -> bool {
+        matches!(self, Self::Option(_))
+    }
+
+    // `expr` must evaluate to a reference to the value.
+    pub fn encode(&self, expr: TokenStream, depth: usize) -> TokenStream {
+        let expr = quote! { (#expr) };
+        match self {
+            Self::Bool => quote! { encoder.write_bool(*#expr)?; },
+            Self::U8 => quote! { encoder.write_u8(*#expr)?; },
+            Self::U16 => quote! { encoder.write_u16(*#expr)?; },
+            Self::U32 => quote! { encoder.write_u32(*#expr)?; },
+            Self::U64 => quote! { encoder.write_u64(*#expr)?; },
+            Self::I8 => quote! { encoder.write_i8(*#expr)?; },
+            Self::I16 => quote! { encoder.write_i16(*#expr)?; },
+            Self::I32 => quote! { encoder.write_i32(*#expr)?; },
+            Self::I64 => quote! { encoder.write_i64(*#expr)?; },
+            Self::F16 => quote! { encoder.write_f16(*#expr)?; },
+            Self::F32 => quote! { encoder.write_f32(*#expr)?; },
+            Self::F64 => quote! { encoder.write_f64(*#expr)?; },
+            Self::String => quote! { encoder.write_string(#expr.as_str())?; },
+            Self::Bytes => quote! { encoder.write_bytes(#expr.as_slice())?; },
+            Self::Str(_) => quote! { encoder.write_string(*#expr)?; },
+            Self::ByteSlice(_) => quote! { encoder.write_bytes(*#expr)?; },
+            Self::CowStr => quote! { encoder.write_string(#expr.as_ref())?; },
+            Self::CowBytes => quote! { encoder.write_bytes(#expr.as_ref())?; },
+            Self::Option(inner) => {
+                let item = format_ident!("__item_{}", depth);
+                let inner = inner.encode(quote! { #item }, depth + 1);
+                quote! {
+                    match #expr {
+                        ::std::option::Option::Some(#item) => { #inner }
+                        ::std::option::Option::None => encoder.write_null()?,
+                    }
+                }
+            }
+            Self::Vec(inner) | Self::Array(_, inner, _) => {
+                let item = format_ident!("__item_{}", depth);
+                let inner = inner.encode(quote! { #item }, depth + 1);
+                quote! {
+                    encoder.write_array(#expr.len())?;
+                    for #item in #expr.iter() {
+                        #inner
+                    }
+                }
+            }
+            Self::Map(_, key, value) => {
+                let key_ident = format_ident!("__key_{}", depth);
+                let value_ident = format_ident!("__value_{}", depth);
+                let key = key.encode(quote! { #key_ident }, depth + 1);
+                let value = value.encode(quote! { #value_ident }, depth + 1);
+                quote! {
+                    encoder.write_map(#expr.len())?;
+                    for (#key_ident, #value_ident) in #expr.iter() {
+                        #key
+                        #value
+                    }
+                }
+            }
+            Self::Pointer(_, inner) => inner.encode(quote! { #expr.as_ref() }, depth),
+            Self::Struct(_) => quote! { encoder.write_struct(#expr)?; },
+        }
+    }
+
+    // Returns an expression that reads one value of this kind from `decoder`.
+    pub fn decode(&self, field_name: &str, depth: usize, mode: DecodeMode) -> syn::Result<TokenStream> {
+        Ok(match self {
+            Self::Bool => quote! { decoder.read_bool()? },
+            Self::U8 => quote! { decoder.read_u8()? },
+            Self::U16 => quote! { decoder.read_u16()? },
+            Self::U32 => quote! { decoder.read_u32()? },
+            Self::U64 => quote! { decoder.read_u64()? },
+            Self::I8 => quote! { decoder.read_i8()? },
+            Self::I16 => quote! { decoder.read_i16()? },
+            Self::I32 => quote! { decoder.read_i32()? },
+            Self::I64 => quote! { decoder.read_i64()? },
+            Self::F16 => quote! { decoder.read_f16()? },
+            Self::F32 => quote! { decoder.read_f32()? },
+            Self::F64 => quote! { decoder.read_f64()? },
+            Self::String => quote! { decoder.read_string()? },
+            Self::Bytes => quote! { decoder.read_bytes_vec()? },
+            Self::Str(ty) | Self::ByteSlice(ty) if mode == DecodeMode::Owned => {
+                return Err(syn::Error::new_spanned(ty, "borrowed fields require `#[derive(RocketPackBorrowed)]`"));
+            }
+            Self::Str(_) => quote! { decoder.read_str()? },
+            Self::ByteSlice(_) => quote! { decoder.read_bytes_borrowed()? },
+            Self::CowStr => match mode {
+                DecodeMode::Owned => quote! { ::std::borrow::Cow::Owned(decoder.read_string()?) },
+                DecodeMode::Borrowed => quote! { ::std::borrow::Cow::Borrowed(decoder.read_str()?) },
+            },
+            Self::CowBytes => match mode {
+                DecodeMode::Owned => quote! { ::std::borrow::Cow::Owned(decoder.read_bytes_vec()?) },
+                DecodeMode::Borrowed => quote! { ::std::borrow::Cow::Borrowed(decoder.read_bytes_borrowed()?) },
+            },
+            Self::Option(inner) => {
+                let inner = inner.decode(field_name, depth + 1, mode)?;
+                quote! {
+                    if decoder.current_type()? == (::omnius_core_rocketpack::FieldType::Unknown { major: 7, info: 22 }) {
+                        decoder.read_null()?;
+                        ::std::option::Option::None
+                    } else {
+                        ::std::option::Option::Some(#inner)
+                    }
+                }
+            }
+            Self::Vec(inner) => {
+                let count = format_ident!("__count_{}", depth);
+                let values = format_ident!("__values_{}", depth);
+                let inner = inner.decode(field_name, depth + 1, mode)?;
+                quote! {
+                    {
+                        let #count = decoder.read_array()?;
+                        let mut #values = decoder.alloc_vec(#count)?;
+                        for _ in 0..#count {
+                            #values.push(#inner);
+                        }
+                        #values
+                    }
+                }
+            }
+            Self::Array(ty, inner, len) => {
+                let count = format_ident!("__count_{}", depth);
+                let values = format_ident!("__values_{}", depth);
+                let inner = inner.decode(field_name, depth + 1, mode)?;
+                let message = format!("array length mismatch: {field_name}");
+                quote! {
+                    {
+                        let #count = decoder.read_array()?;
+                        if #count != (#len) as u64 {
+                            return Err(::omnius_core_rocketpack::RocketPackDecoderError::Other(#message));
+                        }
+                        let mut #values = decoder.alloc_vec(#count)?;
+                        for _ in 0..#count {
+                            #values.push(#inner);
+                        }
+                        let array: #ty = #values.try_into().map_err(|_| ::omnius_core_rocketpack::RocketPackDecoderError::Other(#message))?;
+                        array
+                    }
+                }
+            }
+            Self::Map(ty, key, value) => {
+                let count = format_ident!("__count_{}", depth);
+                let map = format_ident!("__map_{}", depth);
+                let key = key.decode(field_name, depth + 1, mode)?;
+                let value = value.decode(field_name, depth + 1, mode)?;
+                quote! {
+                    {
+                        let #count = decoder.read_map()?;
+                        let mut #map: #ty = ::std::default::Default::default();
+                        for _ in 0..#count {
+                            let key = #key;
+                            let value = #value;
+                            #map.insert(key, value);
+                        }
+                        #map
+                    }
+                }
+            }
+            Self::Pointer(ty, inner) => {
+                let inner = inner.decode(field_name, depth, mode)?;
+                quote! { <#ty>::new(#inner) }
+            }
+            // Nested types carrying a lifetime are decoded through `RocketPackBorrowed`, everything else through `RocketPackStruct`.
+            Self::Struct(ty) if mode == DecodeMode::Borrowed && has_lifetime(ty.to_token_stream()) => quote! { decoder.read_struct_borrowed::<#ty>()? },
+            Self::Struct(ty) => quote! { decoder.read_struct::<#ty>()? },
+        })
+    }
+}
+
+fn has_lifetime(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Punct(punct) => punct.as_char() == '\'',
+        TokenTree::Group(group) => has_lifetime(group.stream()),
+        _ => false,
+    })
+}
+
+fn generic_args(arguments: &PathArguments) -> Vec<&Type> {
+    let PathArguments::AngleBracketed(args) = arguments else {
+        return Vec::new();
+    };
+
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericParam, Ident, Lifetime, LifetimeParam};
+
+use crate::{
+    attribute::FieldAttribute,
+    codec::{DecodeMode, FieldKind},
+};
+
+struct FieldInfo {
+    ident: Ident,
+    tag: u64,
+    kind: FieldKind,
+    attr: FieldAttribute,
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let (infos, is_unit) = collect_fields(input)?;
+    let pack_body = expand_pack(&infos);
+    let unpack_body = expand_unpack(&infos, is_unit, DecodeMode::Owned)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::omnius_core_rocketpack::RocketPackStruct for #name #ty_generics #where_clause {
+            fn pack(
+                encoder: &mut impl ::omnius_core_rocketpack::RocketPackEncoder,
+                value: &Self,
+            ) -> ::std::result::Result<(), ::omnius_core_rocketpack::RocketPackEncoderError> {
+                #pack_body
+            }
+
+            fn unpack(
+                decoder: &mut impl ::omnius_core_rocketpack::RocketPackDecoder,
+            ) -> ::std::result::Result<Self, ::omnius_core_rocketpack::RocketPackDecoderError>
+            where
+                Self: Sized,
+            {
+                #unpack_body
+            }
+        }
+    })
+}
+
+pub fn expand_borrowed(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let (infos, is_unit) = collect_fields(input)?;
+    let unpack_body = expand_unpack(&infos, is_unit, DecodeMode::Borrowed)?;
+
+    let name = &input.ident;
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Borrow from the buffer for the struct's first lifetime, or for a fresh one when it has none.
+    let mut impl_generics = input.generics.clone();
+    let lifetime = match input.generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime = Lifetime::new("'__a", Span::call_site());
+            impl_generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+            lifetime
+        }
+    };
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::omnius_core_rocketpack::RocketPackBorrowed<#lifetime> for #name #ty_generics #where_clause {
+            fn unpack_borrowed(
+                decoder: &mut ::omnius_core_rocketpack::RocketPackBytesDecoder<#lifetime>,
+            ) -> ::std::result::Result<Self, ::omnius_core_rocketpack::RocketPackDecoderError>
+            where
+                Self: Sized,
+            {
+                use ::omnius_core_rocketpack::RocketPackDecoder as _;
+
+                #unpack_body
+            }
+        }
+    })
+}
+
+fn collect_fields(input: &DeriveInput) -> syn::Result<(Vec<FieldInfo>, bool)> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "rocketpack derives can only be used on structs"));
+    };
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new_spanned(&input.ident, "rocketpack derives can not be used on tuple structs"));
+        }
+    };
+
+    let mut infos = Vec::with_capacity(fields.len());
+    let mut used_tags = BTreeMap::<u64, Ident>::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let ident = field.ident.clone().expect("named field");
+        let attr = FieldAttribute::parse(field)?;
+        let tag = attr.tag.unwrap_or(index as u64);
+        let kind = FieldKind::of(&field.ty);
+
+        if attr.skip && (attr.tag.is_some() || attr.default) {
+            return Err(syn::Error::new_spanned(field, "`skip` can not be combined with `tag` or `default`"));
+        }
+
+        if !attr.skip {
+            if let Some(other) = used_tags.get(&tag) {
+                return Err(syn::Error::new_spanned(field, format!("duplicate rocketpack tag {tag} (already used by `{other}`)")));
+            }
+            used_tags.insert(tag, ident.clone());
+        }
+
+        infos.push(FieldInfo { ident, tag, kind, attr });
+    }
+
+    Ok((infos, matches!(data.fields, Fields::Unit)))
+}
+
+fn expand_pack(infos: &[FieldInfo]) -> TokenStream {
+    let mut sorted = infos.iter().filter(|info| !info.attr.skip).collect::<Vec<_>>();
+    sorted.sort_by_key(|info| info.tag);
+
+    let required_count = sorted.iter().filter(|info| !info.kind.is_option()).count();
+    let optional_counts = sorted.iter().filter(|info| info.kind.is_option()).map(|info| {
+        let ident = &info.ident;
+        quote! {
+            if value.#ident.is_some() {
+                count += 1;
+            }
+        }
+    });
+
+    let writes = sorted.iter().map(|info| {
+        let ident = &info.ident;
+        let tag = info.tag;
+        match &info.kind {
+            FieldKind::Option(inner) => {
+                let encode = inner.encode(quote! { v }, 0);
+                quote! {
+                    if let ::std::option::Option::Some(v) = &value.#ident {
+                        encoder.write_u64(#tag)?;
+                        #encode
+                    }
+                }
+            }
+            kind => {
+                let encode = kind.encode(quote! { &value.#ident }, 0);
+                quote! {
+                    encoder.write_u64(#tag)?;
+                    #encode
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[allow(unused_mut)]
+        let mut count: usize = #required_count;
+        #(#optional_counts)*
+        encoder.write_map(count)?;
+
+        #(#writes)*
+
+        Ok(())
+    }
+}
+
+fn expand_unpack(infos: &[FieldInfo], is_unit: bool, mode: DecodeMode) -> syn::Result<TokenStream> {
+    let decoded = infos.iter().filter(|info| !info.attr.skip).collect::<Vec<_>>();
+
+    let declarations = decoded.iter().map(|info| {
+        let ident = &info.ident;
+        quote! { let mut #ident = ::std::option::Option::None; }
+    });
+
+    let arms = decoded
+        .iter()
+        .map(|info| {
+            let ident = &info.ident;
+            let tag = info.tag;
+            let field_name = ident.to_string();
+            let decode = match &info.kind {
+                FieldKind::Option(inner) => inner.decode(&field_name, 0, mode)?,
+                kind => kind.decode(&field_name, 0, mode)?,
+            };
+            Ok(quote! { #tag => #ident = ::std::option::Option::Some(#decode), })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let inits = infos.iter().map(|info| {
+        let ident = &info.ident;
+        let missing = format!("missing field: {ident}");
+        if info.attr.skip {
+            quote! { #ident: ::std::default::Default::default() }
+        } else if info.kind.is_option() {
+            quote! { #ident }
+        } else if info.attr.default {
+            quote! { #ident: #ident.unwrap_or_default() }
+        } else {
+            quote! { #ident: #ident.ok_or(::omnius_core_rocketpack::RocketPackDecoderError::Other(#missing))? }
+        }
+    });
+
+    let construct = if is_unit {
+        quote! { Self }
+    } else {
+        quote! { Self { #(#inits),* } }
+    };
+
+    Ok(quote! {
+        #(#declarations)*
+
+        let count = decoder.read_map()?;
+
+        for _ in 0..count {
+            match decoder.read_u64()? {
+                #(#arms)*
+                _ => decoder.skip_field()?,
+            }
+        }
+
+        Ok(#construct)
+    })
+}
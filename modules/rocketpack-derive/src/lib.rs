@@ -0,0 +1,30 @@
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+mod attribute;
+mod codec;
+mod expand;
+
+/// Derives `RocketPackStruct` for a struct with named fields.
+///
+/// Fields are encoded as an integer-tagged map, in the same layout produced by the
+/// hand-written impls and by `rocketpack-compiler`. Supported field attributes:
+///
+/// - `#[rocketpack(tag = N)]`: wire tag of the field (defaults to the declaration index)
+/// - `#[rocketpack(default)]`: use `Default::default()` when the field is missing
+/// - `#[rocketpack(skip)]`: never encode the field and fill it with `Default::default()`
+#[proc_macro_derive(RocketPackStruct, attributes(rocketpack))]
+pub fn derive_rocket_pack_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `RocketPackBorrowed` for a struct with named fields.
+///
+/// Accepts the same field attributes as `RocketPackStruct`. `&'a str`, `&'a [u8]` and `Cow` fields
+/// borrow from the input buffer, and nested types with a lifetime are decoded as `RocketPackBorrowed`.
+#[proc_macro_derive(RocketPackBorrowed, attributes(rocketpack))]
+pub fn derive_rocket_pack_borrowed(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::expand_borrowed(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -17,6 +17,7 @@ pub enum FieldType {
     String,
     Array,
     Map,
+    Tag,
     Unknown { major: u8, info: u8 },
 }
 
@@ -39,6 +40,7 @@ impl std::fmt::Display for FieldType {
             FieldType::String => write!(f, "string"),
             FieldType::Array => write!(f, "array"),
             FieldType::Map => write!(f, "map"),
+            FieldType::Tag => write!(f, "tag"),
             FieldType::Unknown { major, info } => write!(f, "unknown(major={major}, info={info})"),
         }
     }
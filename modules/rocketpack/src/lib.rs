@@ -2,13 +2,43 @@ mod empty_rocket_pack;
 mod field_type;
 mod prelude;
 pub mod primitive;
+#[cfg(feature = "tokio")]
+mod rocket_pack_async_read_decoder;
+mod rocket_pack_borrowed;
+mod rocket_pack_canonical_test;
 mod rocket_pack_codec_test;
 mod rocket_pack_decoder;
+mod rocket_pack_decoder_limits;
+mod rocket_pack_derive_test;
+#[cfg(feature = "diagnostic")]
+mod rocket_pack_diagnostic;
 mod rocket_pack_encoder;
+mod rocket_pack_read_decoder;
+mod rocket_pack_schema;
+mod rocket_pack_serde;
 mod rocket_pack_struct;
+mod rocket_pack_tag;
+mod rocket_pack_value;
 
 pub use empty_rocket_pack::*;
 pub use field_type::*;
+#[cfg(feature = "tokio")]
+pub use rocket_pack_async_read_decoder::*;
+pub use rocket_pack_borrowed::*;
 pub use rocket_pack_decoder::*;
+pub use rocket_pack_decoder_limits::RocketPackDecoderLimits;
+#[cfg(feature = "diagnostic")]
+pub use rocket_pack_diagnostic::*;
 pub use rocket_pack_encoder::*;
+pub use rocket_pack_read_decoder::RocketPackReadDecoder;
+pub use rocket_pack_schema::*;
+pub use rocket_pack_serde::*;
 pub use rocket_pack_struct::*;
+pub use rocket_pack_tag::{TAG_EPOCH_DATETIME, TAG_EXTENDED_TIME, TAG_NEGATIVE_BIGNUM, TAG_POSITIVE_BIGNUM};
+pub use rocket_pack_value::*;
+
+pub use half::f16;
+pub use omnius_core_rocketpack_derive::{RocketPackBorrowed, RocketPackStruct};
+
+// Lets code generated by the derive macro refer to this crate by name from within its own tests.
+extern crate self as omnius_core_rocketpack;
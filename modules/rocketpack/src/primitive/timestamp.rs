@@ -1,6 +1,6 @@
 use chrono::{DateTime, Timelike, Utc};
 
-use crate::{RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, RocketPackEncoderError, RocketPackStruct};
+use crate::{FieldType, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, RocketPackEncoderError, RocketPackStruct, TAG_EPOCH_DATETIME, TAG_EXTENDED_TIME};
 
 pub struct Timestamp64 {
     pub seconds: i64,
@@ -33,6 +33,11 @@ impl RocketPackStruct for Timestamp64 {
     where
         Self: Sized,
     {
+        if decoder.current_type()? == FieldType::Tag {
+            let (seconds, _) = unpack_epoch(decoder)?;
+            return Ok(Self::new(seconds));
+        }
+
         let value = decoder.read_i64()?;
 
         Ok(Self::new(value))
@@ -77,6 +82,11 @@ impl RocketPackStruct for Timestamp96 {
     where
         Self: Sized,
     {
+        if decoder.current_type()? == FieldType::Tag {
+            let (seconds, nanos) = unpack_epoch(decoder)?;
+            return Ok(Self::new(seconds, nanos));
+        }
+
         let count = decoder.read_map()?;
 
         let mut seconds: Option<i64> = None;
@@ -97,6 +107,114 @@ impl RocketPackStruct for Timestamp96 {
     }
 }
 
+/// A timestamp in the standard CBOR form: tag 1 with integer seconds, or tag 1001 (extended time) when it has nanoseconds.
+///
+/// `Timestamp64` and `Timestamp96` keep their own encodings but also read this form.
+pub struct EpochTimestamp {
+    pub seconds: i64,
+    pub nanos: u32,
+}
+
+impl EpochTimestamp {
+    pub fn new(seconds: i64, nanos: u32) -> Self {
+        EpochTimestamp { seconds, nanos }
+    }
+
+    pub fn to_date_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::<Utc>::from_timestamp(self.seconds, self.nanos)
+    }
+}
+
+impl From<DateTime<Utc>> for EpochTimestamp {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::new(value.timestamp(), value.nanosecond())
+    }
+}
+
+impl From<Timestamp64> for EpochTimestamp {
+    fn from(value: Timestamp64) -> Self {
+        Self::new(value.seconds, 0)
+    }
+}
+
+impl From<Timestamp96> for EpochTimestamp {
+    fn from(value: Timestamp96) -> Self {
+        Self::new(value.seconds, value.nanos)
+    }
+}
+
+impl RocketPackStruct for EpochTimestamp {
+    fn pack(encoder: &mut impl RocketPackEncoder, value: &Self) -> std::result::Result<(), RocketPackEncoderError> {
+        if value.nanos == 0 {
+            encoder.write_tag(TAG_EPOCH_DATETIME)?;
+            encoder.write_i64(value.seconds)?;
+
+            return Ok(());
+        }
+
+        encoder.write_tag(TAG_EXTENDED_TIME)?;
+        encoder.write_map(2)?;
+
+        encoder.write_i64(1)?;
+        encoder.write_i64(value.seconds)?;
+
+        encoder.write_i64(-9)?;
+        encoder.write_u32(value.nanos)?;
+
+        Ok(())
+    }
+
+    fn unpack(decoder: &mut impl RocketPackDecoder) -> std::result::Result<Self, RocketPackDecoderError>
+    where
+        Self: Sized,
+    {
+        let (seconds, nanos) = unpack_epoch(decoder)?;
+
+        Ok(Self::new(seconds, nanos))
+    }
+}
+
+// Reads tag 1 (integer or float seconds) or tag 1001 (extended time) into seconds and nanoseconds.
+fn unpack_epoch(decoder: &mut impl RocketPackDecoder) -> std::result::Result<(i64, u32), RocketPackDecoderError> {
+    let position = decoder.position();
+
+    match decoder.read_tag()? {
+        TAG_EPOCH_DATETIME => match decoder.current_type()? {
+            FieldType::F16 | FieldType::F32 | FieldType::F64 => {
+                let value = decoder.read_f64()?;
+                if !value.is_finite() || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+                    return Err(RocketPackDecoderError::Other("epoch datetime out of range"));
+                }
+
+                let seconds = value.floor();
+                let nanos = (((value - seconds) * 1e9).round() as u32).min(999_999_999);
+                Ok((seconds as i64, nanos))
+            }
+            _ => Ok((decoder.read_i64()?, 0)),
+        },
+        TAG_EXTENDED_TIME => {
+            let count = decoder.read_map()?;
+
+            let mut seconds: Option<i64> = None;
+            let mut nanos: Option<u32> = None;
+
+            for _ in 0..count {
+                match decoder.read_i64()? {
+                    1 => seconds = Some(decoder.read_i64()?),
+                    -9 => nanos = Some(decoder.read_u32()?),
+                    _ => decoder.skip_field()?,
+                }
+            }
+
+            Ok((seconds.unwrap_or(0), nanos.unwrap_or(0)))
+        }
+        _ => Err(RocketPackDecoderError::MismatchFieldType {
+            position,
+            field_type: FieldType::Tag,
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, Utc};
@@ -114,6 +232,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn epoch_timestamp_test() -> TestResult {
+        let t = EpochTimestamp::new(1_700_000_000, 0);
+        let bytes = t.export()?;
+        assert_eq!(hex::encode(&bytes), "c11a6553f100");
+        assert_eq!(Timestamp64::import(&bytes)?.seconds, 1_700_000_000);
+
+        let t = EpochTimestamp::new(-1_234, 5_678);
+        let bytes = t.export()?;
+        assert_eq!(hex::encode(&bytes), "d903e9a2013904d12819162e");
+
+        let imported = Timestamp96::import(&bytes)?;
+        assert_eq!((imported.seconds, imported.nanos), (-1_234, 5_678));
+
+        // Float seconds are also accepted.
+        let imported = Timestamp96::import(&hex::decode("c1fb41d954fc40000000")?)?;
+        assert_eq!((imported.seconds, imported.nanos), (1_700_000_000, 0));
+        let imported = EpochTimestamp::import(&hex::decode("c1f93e00")?)?;
+        assert_eq!((imported.seconds, imported.nanos), (1, 500_000_000));
+
+        // The existing encodings are unchanged.
+        let t = Timestamp96::new(-1_234, 5_678);
+        let imported = Timestamp96::import(&t.export()?)?;
+        assert_eq!((imported.seconds, imported.nanos), (-1_234, 5_678));
+
+        Ok(())
+    }
+
     #[test]
     fn normal_timestamp96_test() -> TestResult {
         let example_time: DateTime<Utc> = DateTime::parse_from_rfc3339("2000-01-01T01:01:01.001Z")?.to_utc();
@@ -0,0 +1,237 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{
+    RocketPackBytesDecoder, RocketPackDecoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackStruct,
+    rocket_pack_decoder_limits::DecoderBudget,
+    rocket_pack_read_decoder::{decompose, head_len},
+};
+
+type Result<T> = std::result::Result<T, RocketPackDecoderError>;
+
+/// Decodes rocketpack values from a tokio `AsyncRead`, one top-level value at a time.
+///
+/// Unlike `RocketPackReadDecoder`, a value is not decoded item by item: its bytes are read asynchronously until the
+/// value is complete, and only then decoded from memory. Nothing beyond the current value is read from the stream.
+/// The buffered bytes count against `max_total_allocation`, together with what decoding the value allocates.
+/// Positions in errors are absolute offsets from the start of the stream.
+pub struct RocketPackAsyncReadDecoder<R: AsyncRead + Unpin> {
+    reader: R,
+    pos: usize,
+    limits: RocketPackDecoderLimits,
+}
+
+impl<R: AsyncRead + Unpin> RocketPackAsyncReadDecoder<R> {
+    /// Creates a decoder without resource limits, for trusted input. Use `with_limits` for input from peers.
+    pub fn new(reader: R) -> Self {
+        Self::with_limits(reader, RocketPackDecoderLimits::unlimited())
+    }
+
+    pub fn with_limits(reader: R, limits: RocketPackDecoderLimits) -> Self {
+        Self { reader, pos: 0, limits }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    pub async fn read_struct<T: RocketPackStruct>(&mut self) -> Result<T> {
+        let offset = self.pos;
+        let (bytes, budget) = self.buffer_value().await?;
+
+        let mut decoder = RocketPackBytesDecoder::with_budget(&bytes, budget);
+        T::unpack(&mut decoder).map_err(|e| e.with_offset(offset))
+    }
+
+    /// Reads the raw bytes of the next complete value.
+    pub async fn read_value(&mut self) -> Result<Vec<u8>> {
+        Ok(self.buffer_value().await?.0)
+    }
+
+    // Returns the bytes of the next value and the budget left after buffering them.
+    async fn buffer_value(&mut self) -> Result<(Vec<u8>, DecoderBudget)> {
+        let mut budget = DecoderBudget::new(self.limits);
+        let mut buf = Vec::new();
+        let mut remain: u64 = 1;
+
+        while remain > 0 {
+            let position = self.pos + buf.len();
+            let start = buf.len();
+
+            budget.allocate(position, 1)?;
+            self.fill(&mut buf, 1).await?;
+            let (major, info) = decompose(buf[start]);
+            budget.allocate(position, head_len(major, info) - 1)?;
+            self.fill(&mut buf, head_len(major, info) - 1).await?;
+
+            let field_type = RocketPackBytesDecoder::new(&buf[start..]).current_type().map_err(|e| e.with_offset(position))?;
+
+            let len = match major {
+                0 | 1 => (info <= 28).then_some(0),
+                2..=6 => RocketPackBytesDecoder::new(&buf[start + 1..]).read_raw_len(info)?,
+                7 => matches!(info, 20..=22 | 25 | 26 | 27).then_some(0),
+                _ => None,
+            };
+            let Some(len) = len else {
+                return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+            };
+
+            match major {
+                2 | 3 => budget.check_bytes_len(position, len)?,
+                4 | 5 => budget.check_collection_len(position, len)?,
+                _ => {}
+            }
+
+            match major {
+                2 | 3 => {
+                    let len: usize = len.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position })?;
+                    budget.allocate(position, len)?;
+                    self.fill(&mut buf, len).await?;
+                }
+                4 => remain = remain.checked_add(len).ok_or(RocketPackDecoderError::LengthOverflow { position })?,
+                6 => remain = remain.checked_add(1).ok_or(RocketPackDecoderError::LengthOverflow { position })?,
+                5 => {
+                    let count = len.checked_mul(2).ok_or(RocketPackDecoderError::LengthOverflow { position })?;
+                    remain = remain.checked_add(count).ok_or(RocketPackDecoderError::LengthOverflow { position })?;
+                }
+                _ => {}
+            }
+
+            remain -= 1;
+        }
+
+        self.pos += buf.len();
+        Ok((buf, budget))
+    }
+
+    async fn fill(&mut self, buf: &mut Vec<u8>, len: usize) -> Result<()> {
+        if len == 0 {
+            return Ok(());
+        }
+
+        let position = self.pos + buf.len();
+        let read = (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(buf)
+            .await
+            .map_err(|error| RocketPackDecoderError::IoError { position, error })?;
+
+        if read < len {
+            return Err(RocketPackDecoderError::UnexpectedEof);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+    use tokio::io::AsyncWriteExt as _;
+
+    use crate::{FieldType, RocketPackBytesEncoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackEncoder, RocketPackStruct};
+
+    use super::RocketPackAsyncReadDecoder;
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct AsyncTestStruct {
+        pub id: u32,
+        pub labels: Vec<Option<String>>,
+        pub payload: Vec<u8>,
+    }
+
+    fn test_value(id: u32) -> AsyncTestStruct {
+        AsyncTestStruct {
+            id,
+            labels: vec![Some("a".to_string()), None],
+            payload: vec![0x55; 1024],
+        }
+    }
+
+    #[tokio::test]
+    async fn normal_test() -> TestResult {
+        let mut bytes = test_value(1).export()?;
+        bytes.extend(test_value(2).export()?);
+
+        // A small duplex buffer forces the decoder to wait for data in several chunks.
+        let (mut writer, reader) = tokio::io::duplex(16);
+        let write_task = tokio::spawn(async move { writer.write_all(&bytes).await });
+
+        let mut decoder = RocketPackAsyncReadDecoder::new(reader);
+        assert_eq!(decoder.read_struct::<AsyncTestStruct>().await?, test_value(1));
+        assert_eq!(decoder.read_struct::<AsyncTestStruct>().await?, test_value(2));
+        write_task.await??;
+
+        assert!(matches!(decoder.read_struct::<AsyncTestStruct>().await, Err(RocketPackDecoderError::UnexpectedEof)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn allocation_limit_test() -> TestResult {
+        // Every string is within `max_bytes_len` and the array within `max_collection_len`, but not their total.
+        let mut bytes = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_array(4)?;
+        for _ in 0..4 {
+            encoder.write_bytes(&[0x55; 100])?;
+        }
+
+        let limits = RocketPackDecoderLimits {
+            max_collection_len: 4,
+            max_bytes_len: 100,
+            max_total_allocation: 256,
+            ..Default::default()
+        };
+
+        // The third string would exceed the limit, so it is rejected before its payload is read.
+        let mut decoder = RocketPackAsyncReadDecoder::with_limits(bytes.as_slice(), limits);
+        let result = decoder.read_value().await;
+        assert!(matches!(result, Err(RocketPackDecoderError::AllocationLimitExceeded { position: 205, limit: 256 })));
+
+        let mut decoder = RocketPackAsyncReadDecoder::with_limits(
+            bytes.as_slice(),
+            RocketPackDecoderLimits {
+                max_total_allocation: 512,
+                ..limits
+            },
+        );
+        assert_eq!(decoder.read_value().await?, bytes);
+
+        // Buffering a value and decoding it share the limit.
+        let bytes = test_value(1).export()?;
+        let limits = RocketPackDecoderLimits {
+            max_total_allocation: bytes.len() + 512,
+            ..Default::default()
+        };
+        let mut decoder = RocketPackAsyncReadDecoder::with_limits(bytes.as_slice(), limits);
+        let result = decoder.read_struct::<AsyncTestStruct>().await;
+        assert!(matches!(result, Err(RocketPackDecoderError::AllocationLimitExceeded { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn error_position_test() -> TestResult {
+        let mut bytes = test_value(1).export()?;
+        let offset = bytes.len();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_map(1)?;
+        encoder.write_u64(0)?;
+        encoder.write_bool(true)?;
+
+        let mut decoder = RocketPackAsyncReadDecoder::new(bytes.as_slice());
+        decoder.read_struct::<AsyncTestStruct>().await?;
+
+        let result = decoder.read_struct::<AsyncTestStruct>().await;
+        assert!(matches!(
+            result,
+            Err(RocketPackDecoderError::MismatchFieldType { position, field_type: FieldType::Bool }) if position == offset + 2
+        ));
+
+        Ok(())
+    }
+}
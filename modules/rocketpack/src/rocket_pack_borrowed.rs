@@ -0,0 +1,107 @@
+use crate::{RocketPackBytesDecoder, RocketPackDecoderError, RocketPackDecoderLimits};
+
+/// Decodes a value that borrows `&'a [u8]` / `&'a str` data directly from the input buffer.
+pub trait RocketPackBorrowed<'a> {
+    fn unpack_borrowed(decoder: &mut RocketPackBytesDecoder<'a>) -> std::result::Result<Self, RocketPackDecoderError>
+    where
+        Self: Sized;
+
+    /// Decodes `bytes` within the default `RocketPackDecoderLimits`.
+    fn import_borrowed(bytes: &'a [u8]) -> std::result::Result<Self, RocketPackDecoderError>
+    where
+        Self: Sized,
+    {
+        let mut decoder = RocketPackBytesDecoder::with_limits(bytes, RocketPackDecoderLimits::default());
+        Self::unpack_borrowed(&mut decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use testresult::TestResult;
+
+    use crate::{RocketPackBorrowed, RocketPackBytesDecoder, RocketPackStruct};
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct OwnedMessage {
+        pub name: String,
+        pub payload: Vec<u8>,
+        pub labels: Vec<String>,
+        pub note: Option<String>,
+        pub inner: OwnedInner,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct OwnedInner {
+        pub value: String,
+    }
+
+    #[derive(Debug, PartialEq, RocketPackBorrowed)]
+    struct BorrowedMessage<'a> {
+        pub name: &'a str,
+        pub payload: &'a [u8],
+        pub labels: Vec<&'a str>,
+        pub note: Option<Cow<'a, str>>,
+        pub inner: BorrowedInner<'a>,
+    }
+
+    #[derive(Debug, PartialEq, RocketPackBorrowed)]
+    struct BorrowedInner<'a> {
+        pub value: Cow<'a, str>,
+    }
+
+    #[derive(Debug, PartialEq, RocketPackBorrowed)]
+    struct MixedMessage {
+        pub name: String,
+        #[rocketpack(tag = 4)]
+        pub inner: OwnedInner,
+    }
+
+    #[test]
+    fn normal_test() -> TestResult {
+        let owned = OwnedMessage {
+            name: "name".to_string(),
+            payload: vec![0xAA, 0xBB, 0xCC],
+            labels: vec!["a".to_string(), "b".to_string()],
+            note: Some("note".to_string()),
+            inner: OwnedInner { value: "inner".to_string() },
+        };
+        let bytes = owned.export()?;
+
+        let borrowed = BorrowedMessage::import_borrowed(bytes.as_slice())?;
+        assert_eq!(
+            borrowed,
+            BorrowedMessage {
+                name: "name",
+                payload: &[0xAA, 0xBB, 0xCC],
+                labels: vec!["a", "b"],
+                note: Some(Cow::Borrowed("note")),
+                inner: BorrowedInner { value: Cow::Borrowed("inner") },
+            }
+        );
+
+        let range = bytes.as_ptr_range();
+        assert!(range.contains(&borrowed.name.as_ptr()));
+        assert!(range.contains(&borrowed.payload.as_ptr()));
+        assert!(matches!(borrowed.inner.value, Cow::Borrowed(_)));
+
+        let mixed = MixedMessage::import_borrowed(bytes.as_slice())?;
+        assert_eq!(mixed.name, owned.name);
+        assert_eq!(mixed.inner, owned.inner);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_str_test() -> TestResult {
+        let bytes = OwnedInner { value: "value".to_string() }.export()?;
+        let mut decoder = RocketPackBytesDecoder::new(bytes.as_slice());
+
+        let inner: BorrowedInner = decoder.read_struct_borrowed()?;
+        assert_eq!(inner.value, "value");
+
+        Ok(())
+    }
+}
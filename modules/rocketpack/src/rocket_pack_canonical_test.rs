@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use testresult::TestResult;
+
+    use crate::{RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, RocketPackEncoderError, RocketPackStruct};
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct CanonicalTestStruct {
+        pub numbers: BTreeMap<i32, String>,
+        pub names: BTreeMap<String, u8>,
+        pub nested: Vec<BTreeMap<i64, f64>>,
+        pub ratio: f64,
+    }
+
+    fn test_value() -> CanonicalTestStruct {
+        CanonicalTestStruct {
+            numbers: BTreeMap::from([(-1, "minus".to_string()), (0, "zero".to_string()), (100, "hundred".to_string())]),
+            names: BTreeMap::from([("aa".to_string(), 1), ("b".to_string(), 2)]),
+            nested: vec![BTreeMap::from([(-5, 0.5), (5, 0.1)])],
+            ratio: 1.5,
+        }
+    }
+
+    fn encode_canonical(f: impl FnOnce(&mut RocketPackBytesEncoder<&mut Vec<u8>>) -> Result<(), RocketPackEncoderError>) -> Result<Vec<u8>, RocketPackEncoderError> {
+        let mut buf = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new_canonical(&mut buf);
+        f(&mut encoder)?;
+        encoder.finish()?;
+        Ok(buf)
+    }
+
+    #[test]
+    fn canonical_roundtrip_test() -> TestResult {
+        let value = test_value();
+        let canonical = value.export_canonical()?;
+
+        assert_eq!(CanonicalTestStruct::import_strict(&canonical)?, value);
+        assert_eq!(CanonicalTestStruct::import(&canonical)?, value);
+        assert_eq!(value.clone().export_canonical()?, canonical);
+
+        // The default encoder keeps `BTreeMap` order, which puts -1 before 0, and writes `ratio` as f64.
+        let result = CanonicalTestStruct::import_strict(&value.export()?);
+        assert!(matches!(result, Err(RocketPackDecoderError::NonCanonical { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_map_order_test() -> TestResult {
+        let buf = encode_canonical(|encoder| {
+            encoder.write_map(3)?;
+            encoder.write_i32(-1)?;
+            encoder.write_bool(true)?;
+            encoder.write_string("aa")?;
+            encoder.write_bool(true)?;
+            encoder.write_string("b")?;
+            encoder.write_bool(false)?;
+            Ok(())
+        })?;
+
+        // Keys are ordered by their encoded bytes: 0x20 (-1), 0x61 'b' ("b"), 0x62 'a' 'a' ("aa").
+        assert_eq!(hex::encode(&buf), "a320f56162f4626161f5");
+
+        let result = encode_canonical(|encoder| {
+            encoder.write_map(2)?;
+            encoder.write_u8(1)?;
+            encoder.write_u8(1)?;
+            encoder.write_u8(1)?;
+            encoder.write_u8(2)?;
+            Ok(())
+        });
+        assert!(matches!(result, Err(RocketPackEncoderError::DuplicateMapKey)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_incomplete_test() -> TestResult {
+        // A map with a missing value, inside an array with a missing element.
+        let result = encode_canonical(|encoder| {
+            encoder.write_array(2)?;
+            encoder.write_map(2)?;
+            encoder.write_u8(1)?;
+            encoder.write_bool(true)?;
+            encoder.write_u8(2)?;
+            Ok(())
+        });
+        assert!(matches!(result, Err(RocketPackEncoderError::IncompleteContainer { remaining: 1 })));
+
+        let result = encode_canonical(|encoder| {
+            encoder.write_array(2)?;
+            encoder.write_u8(1)?;
+            Ok(())
+        });
+        assert!(matches!(result, Err(RocketPackEncoderError::IncompleteContainer { remaining: 1 })));
+
+        let result = ShortPackTestStruct.export_canonical();
+        assert!(matches!(result, Err(RocketPackEncoderError::IncompleteContainer { remaining: 2 })));
+
+        Ok(())
+    }
+
+    // Declares two fields but packs only one.
+    struct ShortPackTestStruct;
+
+    impl RocketPackStruct for ShortPackTestStruct {
+        fn pack(encoder: &mut impl RocketPackEncoder, _value: &Self) -> Result<(), RocketPackEncoderError> {
+            encoder.write_map(2)?;
+            encoder.write_u64(0)?;
+            encoder.write_bool(true)
+        }
+
+        fn unpack(_decoder: &mut impl RocketPackDecoder) -> Result<Self, RocketPackDecoderError> {
+            Ok(Self)
+        }
+    }
+
+    #[test]
+    fn canonical_float_test() -> TestResult {
+        let cases: Vec<(f64, &str)> = vec![
+            (1.5, "f93e00"),
+            (100000.0, "fa47c35000"),
+            (0.1, "fb3fb999999999999a"),
+            (f64::INFINITY, "f97c00"),
+            (f64::NAN, "f97e00"),
+        ];
+
+        for (value, expected) in cases {
+            let buf = encode_canonical(|encoder| encoder.write_f64(value))?;
+            assert_eq!(hex::encode(&buf), expected);
+
+            let decoded = RocketPackBytesDecoder::new_strict(&buf).read_f64()?;
+            assert!(decoded == value || (decoded.is_nan() && value.is_nan()));
+        }
+
+        let buf = encode_canonical(|encoder| encoder.write_f32(-2.0))?;
+        assert_eq!(hex::encode(&buf), "f9c000");
+        assert_eq!(RocketPackBytesDecoder::new_strict(&buf).read_f32()?, -2.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_rejects_non_minimal_test() -> TestResult {
+        let cases: Vec<Vec<u8>> = vec![
+            hex::decode("1805")?,               // 5 with a one-byte argument
+            hex::decode("1900ff")?,             // 255 with a two-byte argument
+            hex::decode("780161")?,             // string length 1 with a one-byte argument
+            hex::decode("fa3fc00000")?,         // 1.5 as f32
+            hex::decode("fb3ff8000000000000")?, // 1.5 as f64
+            hex::decode("a2016161016162")?,     // duplicate map key 1
+        ];
+
+        for bytes in cases {
+            let result = RocketPackBytesDecoder::new_strict(&bytes).skip_field();
+            assert!(matches!(result, Err(RocketPackDecoderError::NonCanonical { .. })), "hex: {}", hex::encode(&bytes));
+
+            let mut decoder = RocketPackBytesDecoder::new(&bytes);
+            decoder.skip_field()?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_nested_map_keys_test() -> TestResult {
+        let cases: Vec<(Vec<u8>, usize, &str)> = vec![
+            (hex::decode("81a2026161016162")?, 5, "map keys are not sorted"),   // [{2: "a", 1: "b"}]
+            (hex::decode("a101a2016161016162")?, 6, "duplicate map key"),       // {1: {1: "a", 1: "b"}}
+            (hex::decode("a2a10100f6a10000f6")?, 5, "map keys are not sorted"), // {{1: 0}: null, {0: 0}: null}
+            (hex::decode("a2c24101f6c24100f6")?, 5, "map keys are not sorted"), // {2(h'01'): null, 2(h'00'): null}
+        ];
+
+        for (bytes, expected_position, expected_reason) in cases {
+            let result = RocketPackBytesDecoder::new_strict(&bytes).skip_field();
+            assert!(
+                matches!(result, Err(RocketPackDecoderError::NonCanonical { position, reason }) if position == expected_position && reason == expected_reason),
+                "hex: {}, result: {result:?}",
+                hex::encode(&bytes)
+            );
+        }
+
+        // {1: {0: 0, 1: 1}, 2: []} is canonical.
+        let bytes = hex::decode("a201a2000001010280")?;
+        RocketPackBytesDecoder::new_strict(&bytes).skip_field()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_deeply_nested_maps_test() -> TestResult {
+        // {0: {0: ... {0: 0} ...}}, where every map would be rescanned by each enclosing map if keys were checked by
+        // scanning ahead.
+        const DEPTH: usize = 100_000;
+        let mut bytes = Vec::with_capacity(DEPTH * 2 + 1);
+        for _ in 0..DEPTH {
+            bytes.extend_from_slice(&[0xa1, 0x00]);
+        }
+        bytes.push(0x00);
+
+        RocketPackBytesDecoder::new_strict(&bytes).skip_field()?;
+
+        // The innermost map repeats its key, which is found at the end of the input.
+        let mut bytes = bytes[..bytes.len() - 3].to_vec();
+        bytes.extend_from_slice(&[0xa2, 0x00, 0x00, 0x00, 0x00]);
+        let result = RocketPackBytesDecoder::new_strict(&bytes).skip_field();
+        assert!(matches!(result, Err(RocketPackDecoderError::NonCanonical { reason: "duplicate map key", .. })));
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ mod tests {
 
     use testresult::TestResult;
 
-    use crate::{FieldType, RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder};
+    use crate::{FieldType, RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, f16};
 
     fn compose(major: u8, info: u8) -> u8 {
         (major << 5) | info
@@ -218,6 +218,173 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn signed_out_of_range_test() -> TestResult {
+        let bytes = [compose(0, 24), 128];
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(decoder.read_i8(), Err(RocketPackDecoderError::MismatchFieldType { position: 0, .. })));
+
+        let bytes = [compose(0, 25), 128, 0];
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(decoder.read_i16(), Err(RocketPackDecoderError::MismatchFieldType { position: 0, .. })));
+
+        let bytes = [compose(0, 26), 128, 0, 0, 0];
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(decoder.read_i32(), Err(RocketPackDecoderError::MismatchFieldType { position: 0, .. })));
+
+        let bytes = [compose(0, 27), 128, 0, 0, 0, 0, 0, 0, 0];
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(decoder.read_i64(), Err(RocketPackDecoderError::MismatchFieldType { position: 0, .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn normal_u128_test() -> TestResult {
+        let cases: Vec<(Vec<u8>, u128)> = vec![
+            (vec![compose(0, 0)], 0),
+            (vec![compose(0, 27), 255, 255, 255, 255, 255, 255, 255, 255], u64::MAX as u128),
+            (vec![compose(6, 2), compose(2, 9), 1, 0, 0, 0, 0, 0, 0, 0, 0], u64::MAX as u128 + 1),
+            (
+                vec![
+                    compose(6, 2),
+                    compose(2, 16),
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                ],
+                u128::MAX,
+            ),
+        ];
+
+        for (bytes, value) in cases {
+            encode_test!(write_u128, value, &bytes);
+            decode_test!(read_u128, &bytes, value);
+        }
+
+        // Leading zeros are accepted, but the significant bytes must fit in 128 bits.
+        let bytes = [compose(6, 2), compose(2, 3), 0, 0, 1];
+        decode_test!(read_u128, &bytes, 1_u128);
+
+        let mut bytes = vec![compose(6, 2), compose(2, 17), 1];
+        bytes.extend([0; 16]);
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(decoder.read_u128(), Err(RocketPackDecoderError::LengthOverflow { position: 0 })));
+
+        let bytes = [compose(6, 3), compose(2, 1), 1];
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(
+            decoder.read_u128(),
+            Err(RocketPackDecoderError::MismatchFieldType {
+                position: 0,
+                field_type: FieldType::Tag
+            })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn normal_i128_test() -> TestResult {
+        let cases: Vec<(Vec<u8>, i128)> = vec![
+            (vec![compose(1, 0)], -1),
+            (vec![compose(0, 27), 255, 255, 255, 255, 255, 255, 255, 255], u64::MAX as i128),
+            (vec![compose(1, 27), 127, 255, 255, 255, 255, 255, 255, 255], i64::MIN as i128),
+            (vec![compose(6, 3), compose(2, 8), 128, 0, 0, 0, 0, 0, 0, 0], i64::MIN as i128 - 1),
+            (
+                vec![
+                    compose(6, 2),
+                    compose(2, 16),
+                    127,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                ],
+                i128::MAX,
+            ),
+            (
+                vec![
+                    compose(6, 3),
+                    compose(2, 16),
+                    127,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                    255,
+                ],
+                i128::MIN,
+            ),
+        ];
+
+        for (bytes, value) in cases {
+            encode_test!(write_i128, value, &bytes);
+            decode_test!(read_i128, &bytes, value);
+        }
+
+        let bytes = [compose(6, 2), compose(2, 16), 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        assert!(matches!(decoder.read_i128(), Err(RocketPackDecoderError::LengthOverflow { position: 0 })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn normal_f16_test() -> TestResult {
+        let cases: Vec<(Vec<u8>, f16)> = vec![
+            (vec![compose(7, 25), 0, 0], f16::from_f32(0.0)),
+            (vec![compose(7, 25), 0x3e, 0x00], f16::from_f32(1.5)),
+            (vec![compose(7, 25), 0xfb, 0xff], f16::MIN),
+        ];
+
+        for (bytes, value) in cases {
+            encode_test!(write_f16, value, &bytes);
+            decode_test!(read_f16, &bytes, value);
+        }
+
+        // Half floats widen losslessly into the wider float reads.
+        let bytes = [compose(7, 25), 0x3e, 0x00];
+        decode_test!(read_f32, &bytes, 1.5_f32);
+        decode_test!(read_f64, &bytes, 1.5_f64);
+
+        Ok(())
+    }
+
     #[test]
     fn normal_f32_test() -> TestResult {
         let cases: Vec<(Vec<u8>, f32)> = vec![
@@ -427,6 +594,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn normal_decoder_skip_f16_test() -> TestResult {
+        let mut buf = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut buf);
+        encoder.write_f16(f16::from_f32(15.5))?;
+        encoder.write_bool(true)?;
+
+        let mut decoder = RocketPackBytesDecoder::new(&buf);
+        decoder.skip_field()?;
+        assert!(decoder.read_bool()?);
+        assert_eq!(decoder.remaining(), 0);
+
+        Ok(())
+    }
+
     #[test]
     fn truncated_negative_number_reports_eof() -> TestResult {
         let bytes = vec![compose(1, 24)];
@@ -1,6 +1,12 @@
+use half::f16;
 use thiserror::Error;
 
-use crate::{FieldType, RocketPackStruct};
+use crate::{
+    FieldType, RocketPackBorrowed, RocketPackDecoderLimits, RocketPackStruct,
+    rocket_pack_decoder_limits::DecoderBudget,
+    rocket_pack_encoder::{narrow_to_f16, narrow_to_f32},
+    rocket_pack_tag::{TAG_NEGATIVE_BIGNUM, TAG_POSITIVE_BIGNUM, bignum_from_bytes},
+};
 
 type Result<T> = std::result::Result<T, RocketPackDecoderError>;
 
@@ -16,14 +22,73 @@ pub enum RocketPackDecoderError {
     LengthOverflow { position: usize },
     #[error("string is not valid UTF-8 (position: {position}, error: {error})")]
     Utf8 { position: usize, error: std::str::Utf8Error },
+    #[error("I/O error occurred (position: {position})")]
+    IoError { position: usize, error: std::io::Error },
+    #[error("depth limit exceeded (position: {position}, limit: {limit})")]
+    DepthLimitExceeded { position: usize, limit: usize },
+    #[error("collection length limit exceeded (position: {position}, len: {len}, limit: {limit})")]
+    CollectionLengthLimitExceeded { position: usize, len: u64, limit: u64 },
+    #[error("bytes length limit exceeded (position: {position}, len: {len}, limit: {limit})")]
+    BytesLengthLimitExceeded { position: usize, len: u64, limit: u64 },
+    #[error("allocation limit exceeded (position: {position}, limit: {limit})")]
+    AllocationLimitExceeded { position: usize, limit: usize },
+    #[error("non-canonical encoding (position: {position}, reason: {reason})")]
+    NonCanonical { position: usize, reason: &'static str },
     #[error("other decode error: {0}")]
     Other(&'static str),
 }
 
+impl RocketPackDecoderError {
+    // Shifts positions reported by a decoder over a partial buffer to absolute stream offsets.
+    pub(crate) fn with_offset(self, offset: usize) -> Self {
+        match self {
+            Self::MismatchFieldType { position, field_type } => Self::MismatchFieldType {
+                position: position + offset,
+                field_type,
+            },
+            Self::LengthOverflow { position } => Self::LengthOverflow { position: position + offset },
+            Self::Utf8 { position, error } => Self::Utf8 {
+                position: position + offset,
+                error,
+            },
+            Self::IoError { position, error } => Self::IoError {
+                position: position + offset,
+                error,
+            },
+            Self::DepthLimitExceeded { position, limit } => Self::DepthLimitExceeded {
+                position: position + offset,
+                limit,
+            },
+            Self::CollectionLengthLimitExceeded { position, len, limit } => Self::CollectionLengthLimitExceeded {
+                position: position + offset,
+                len,
+                limit,
+            },
+            Self::BytesLengthLimitExceeded { position, len, limit } => Self::BytesLengthLimitExceeded {
+                position: position + offset,
+                len,
+                limit,
+            },
+            Self::AllocationLimitExceeded { position, limit } => Self::AllocationLimitExceeded {
+                position: position + offset,
+                limit,
+            },
+            Self::NonCanonical { position, reason } => Self::NonCanonical {
+                position: position + offset,
+                reason,
+            },
+            error => error,
+        }
+    }
+}
+
 pub trait RocketPackDecoder {
     fn remaining(&self) -> usize;
     fn position(&self) -> usize;
     fn current_type(&self) -> Result<FieldType>;
+    /// Returns the declared length of the byte string, text string, array or map at the current position without
+    /// reading its content, so a caller can reject an oversized item before decoding it.
+    fn current_len(&self) -> Result<u64>;
     fn read_bool(&mut self) -> Result<bool>;
     fn read_u8(&mut self) -> Result<u8>;
     fn read_u16(&mut self) -> Result<u16>;
@@ -33,6 +98,7 @@ pub trait RocketPackDecoder {
     fn read_i16(&mut self) -> Result<i16>;
     fn read_i32(&mut self) -> Result<i32>;
     fn read_i64(&mut self) -> Result<i64>;
+    fn read_f16(&mut self) -> Result<f16>;
     fn read_f32(&mut self) -> Result<f32>;
     fn read_f64(&mut self) -> Result<f64>;
     fn read_bytes(&mut self) -> Result<&[u8]>;
@@ -41,18 +107,168 @@ pub trait RocketPackDecoder {
     fn read_array(&mut self) -> Result<u64>;
     fn read_map(&mut self) -> Result<u64>;
     fn read_null(&mut self) -> Result<()>;
+    fn read_tag(&mut self) -> Result<u64>;
     fn read_struct<T: RocketPackStruct>(&mut self) -> Result<T>;
     fn skip_field(&mut self) -> Result<()>;
+    /// Allocates a `Vec` for `count` elements announced by `read_array`, charging it against the decoder limits.
+    ///
+    /// The default implementation enforces no limits and reserves at most one element per remaining byte.
+    fn alloc_vec<T>(&mut self, count: u64) -> Result<Vec<T>> {
+        let capacity = count.min(self.remaining() as u64);
+        Ok(Vec::with_capacity(capacity as usize))
+    }
+
+    /// Reads a plain unsigned integer or a positive bignum (tag 2).
+    fn read_u128(&mut self) -> Result<u128> {
+        let position = self.position();
+        if self.current_type()? != FieldType::Tag {
+            return Ok(self.read_u64()? as u128);
+        }
+
+        match self.read_tag()? {
+            TAG_POSITIVE_BIGNUM => bignum_from_bytes(position, self.read_bytes()?),
+            _ => Err(RocketPackDecoderError::MismatchFieldType {
+                position,
+                field_type: FieldType::Tag,
+            }),
+        }
+    }
+
+    /// Reads a plain integer or a positive or negative bignum (tag 2 or 3).
+    fn read_i128(&mut self) -> Result<i128> {
+        let position = self.position();
+        let overflow = || RocketPackDecoderError::LengthOverflow { position };
+
+        match self.current_type()? {
+            FieldType::U16 | FieldType::U32 | FieldType::U64 => Ok(self.read_u64()? as i128),
+            FieldType::Tag => match self.read_tag()? {
+                TAG_POSITIVE_BIGNUM => i128::try_from(bignum_from_bytes(position, self.read_bytes()?)?).map_err(|_| overflow()),
+                TAG_NEGATIVE_BIGNUM => {
+                    let value = i128::try_from(bignum_from_bytes(position, self.read_bytes()?)?).map_err(|_| overflow())?;
+                    Ok(-1 - value)
+                }
+                _ => Err(RocketPackDecoderError::MismatchFieldType {
+                    position,
+                    field_type: FieldType::Tag,
+                }),
+            },
+            _ => Ok(self.read_i64()? as i128),
+        }
+    }
 }
 
 pub struct RocketPackBytesDecoder<'a> {
     buf: &'a [u8],
     pos: usize,
+    budget: DecoderBudget,
+    strict: bool,
+    // The arrays and maps being read in strict mode, innermost last.
+    frames: Vec<StrictFrame>,
+    // The start of the tags read since the last item started, which belong to the next item.
+    tag_start: Option<usize>,
+}
+
+// An array or map whose items are still being read. Map keys are compared as soon as they end, so every map is
+// checked once while it is read.
+struct StrictFrame {
+    // The items whose head has not been read yet, counting map keys and values separately.
+    remaining: u64,
+    is_map: bool,
+    key_start: usize,
+    previous_key: Option<(usize, usize)>,
 }
 
 impl<'a> RocketPackBytesDecoder<'a> {
+    /// Creates a decoder without resource limits, for trusted input. Use `with_limits` for input from peers.
     pub fn new(buf: &'a [u8]) -> Self {
-        Self { buf, pos: 0 }
+        Self::with_limits(buf, RocketPackDecoderLimits::unlimited())
+    }
+
+    pub fn with_limits(buf: &'a [u8], limits: RocketPackDecoderLimits) -> Self {
+        Self::with_budget(buf, DecoderBudget::new(limits))
+    }
+
+    /// Creates a decoder with the default limits that rejects input which is not in the canonical encoding produced
+    /// by `RocketPackBytesEncoder::new_canonical`, including unknown fields.
+    pub fn new_strict(buf: &'a [u8]) -> Self {
+        Self {
+            strict: true,
+            ..Self::with_limits(buf, RocketPackDecoderLimits::default())
+        }
+    }
+
+    pub(crate) fn with_budget(buf: &'a [u8], budget: DecoderBudget) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            budget,
+            strict: false,
+            frames: Vec::new(),
+            tag_start: None,
+        }
+    }
+
+    pub(crate) fn budget(&self) -> DecoderBudget {
+        self.budget
+    }
+
+    pub(crate) fn enter(&mut self) -> Result<()> {
+        self.budget.enter(self.pos)
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.budget.leave();
+    }
+
+    /// Reads a byte string without copying it out of the input buffer.
+    pub fn read_bytes_borrowed(&mut self) -> Result<&'a [u8]> {
+        let position = self.pos;
+        let (major, info) = self.decompose(self.current_raw_byte()?);
+        let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
+        self.skip_raw_bytes(1)?;
+
+        if major != 2 {
+            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        }
+
+        let Some(len) = self.read_raw_len(info)? else {
+            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        };
+
+        self.budget.check_bytes_len(position, len)?;
+        let len: usize = len.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position })?;
+        self.read_raw_bytes(len)
+    }
+
+    /// Reads a text string without copying it out of the input buffer.
+    pub fn read_str(&mut self) -> Result<&'a str> {
+        let position = self.pos;
+        let (major, info) = self.decompose(self.current_raw_byte()?);
+        let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
+        self.skip_raw_bytes(1)?;
+
+        if major != 3 {
+            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        }
+
+        let Some(len) = self.read_raw_len(info)? else {
+            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        };
+
+        self.budget.check_bytes_len(position, len)?;
+        let len: usize = len.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position })?;
+        let bytes = self.read_raw_bytes(len)?;
+
+        std::str::from_utf8(bytes).map_err(|e| RocketPackDecoderError::Utf8 { position, error: e })
+    }
+
+    pub fn read_struct_borrowed<T: RocketPackBorrowed<'a>>(&mut self) -> Result<T> {
+        self.budget.enter(self.pos)?;
+        let value = T::unpack_borrowed(self)?;
+        self.budget.leave();
+        Ok(value)
     }
 }
 
@@ -70,10 +286,22 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         self.type_of(major, info)
     }
 
+    fn current_len(&self) -> Result<u64> {
+        let position = self.pos;
+        let (major, info) = self.decompose(self.current_raw_byte()?);
+        let field_type = self.type_of(major, info)?;
+
+        match major {
+            2..=5 => self.peek_raw_len(info)?.ok_or(RocketPackDecoderError::MismatchFieldType { position, field_type }),
+            _ => Err(RocketPackDecoderError::MismatchFieldType { position, field_type }),
+        }
+    }
+
     fn read_bool(&mut self) -> Result<bool> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         Ok(match (major, info) {
@@ -89,6 +317,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         Ok(match (major, info) {
@@ -104,6 +333,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         Ok(match (major, info) {
@@ -120,6 +350,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         Ok(match (major, info) {
@@ -137,6 +368,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         Ok(match (major, info) {
@@ -155,11 +387,15 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         match (major, info) {
             (0, 0..=23) => return Ok(info as i8),
-            (0, 24) => return Ok(u8::from_be_bytes(self.read_raw_fixed_bytes()?) as i8),
+            (0, 24) => {
+                let value = u8::from_be_bytes(self.read_raw_fixed_bytes()?);
+                return i8::try_from(value).map_err(|_| RocketPackDecoderError::MismatchFieldType { position, field_type });
+            }
             (1, 0..=23) => return Ok(-1 - (info as i8)),
             (1, 24..=28) => {
                 // Determine the smallest signed integer type the value fits in.
@@ -182,12 +418,16 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         match (major, info) {
             (0, 0..=23) => return Ok(info as i16),
             (0, 24) => return Ok(u8::from_be_bytes(self.read_raw_fixed_bytes()?) as i16),
-            (0, 25) => return Ok(u16::from_be_bytes(self.read_raw_fixed_bytes()?) as i16),
+            (0, 25) => {
+                let value = u16::from_be_bytes(self.read_raw_fixed_bytes()?);
+                return i16::try_from(value).map_err(|_| RocketPackDecoderError::MismatchFieldType { position, field_type });
+            }
             (1, 0..=23) => return Ok(-1 - (info as i16)),
             (1, 24..=28) => {
                 // Determine the smallest signed integer type the value fits in.
@@ -215,13 +455,17 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         match (major, info) {
             (0, 0..=23) => return Ok(info as i32),
             (0, 24) => return Ok(u8::from_be_bytes(self.read_raw_fixed_bytes()?) as i32),
             (0, 25) => return Ok(u16::from_be_bytes(self.read_raw_fixed_bytes()?) as i32),
-            (0, 26) => return Ok(u32::from_be_bytes(self.read_raw_fixed_bytes()?) as i32),
+            (0, 26) => {
+                let value = u32::from_be_bytes(self.read_raw_fixed_bytes()?);
+                return i32::try_from(value).map_err(|_| RocketPackDecoderError::MismatchFieldType { position, field_type });
+            }
             (1, 0..=23) => return Ok(-1 - (info as i32)),
             (1, 24..=28) => {
                 // Determine the smallest signed integer type the value fits in.
@@ -250,6 +494,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         match (major, info) {
@@ -257,7 +502,10 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
             (0, 24) => return Ok(u8::from_be_bytes(self.read_raw_fixed_bytes()?) as i64),
             (0, 25) => return Ok(u16::from_be_bytes(self.read_raw_fixed_bytes()?) as i64),
             (0, 26) => return Ok(u32::from_be_bytes(self.read_raw_fixed_bytes()?) as i64),
-            (0, 27) => return Ok(u64::from_be_bytes(self.read_raw_fixed_bytes()?) as i64),
+            (0, 27) => {
+                let value = u64::from_be_bytes(self.read_raw_fixed_bytes()?);
+                return i64::try_from(value).map_err(|_| RocketPackDecoderError::MismatchFieldType { position, field_type });
+            }
             (1, 0..=23) => return Ok(-1 - (info as i64)),
             (1, 24..=28) => {
                 // Determine the smallest signed integer type the value fits in.
@@ -284,80 +532,76 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         Err(RocketPackDecoderError::MismatchFieldType { position, field_type })
     }
 
-    fn read_f32(&mut self) -> Result<f32> {
+    fn read_f16(&mut self) -> Result<f16> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
-        if (major, info) == (7, 26) {
-            return Ok(f32::from_be_bytes(self.read_raw_fixed_bytes()?));
+        if (major, info) == (7, 25) {
+            return Ok(f16::from_bits(u16::from_be_bytes(self.read_raw_fixed_bytes()?)));
         }
 
         Err(RocketPackDecoderError::MismatchFieldType { position, field_type })
     }
 
-    fn read_f64(&mut self) -> Result<f64> {
+    fn read_f32(&mut self) -> Result<f32> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
-        if (major, info) == (7, 27) {
-            return Ok(f64::from_be_bytes(self.read_raw_fixed_bytes()?));
+        match (major, info) {
+            (7, 25) => return Ok(f16::from_bits(u16::from_be_bytes(self.read_raw_fixed_bytes()?)).to_f32()),
+            (7, 26) => return Ok(f32::from_be_bytes(self.read_raw_fixed_bytes()?)),
+            _ => {}
         }
 
         Err(RocketPackDecoderError::MismatchFieldType { position, field_type })
     }
 
-    fn read_bytes(&mut self) -> Result<&'a [u8]> {
+    fn read_f64(&mut self) -> Result<f64> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
-        if major != 2 {
-            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        match (major, info) {
+            (7, 25) => return Ok(f16::from_bits(u16::from_be_bytes(self.read_raw_fixed_bytes()?)).to_f64()),
+            (7, 26) => return Ok(f32::from_be_bytes(self.read_raw_fixed_bytes()?) as f64),
+            (7, 27) => return Ok(f64::from_be_bytes(self.read_raw_fixed_bytes()?)),
+            _ => {}
         }
 
-        let Some(len) = self.read_raw_len(info)? else {
-            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
-        };
+        Err(RocketPackDecoderError::MismatchFieldType { position, field_type })
+    }
 
-        let len: usize = len.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position })?;
-        self.read_raw_bytes(len)
+    fn read_bytes(&mut self) -> Result<&'a [u8]> {
+        self.read_bytes_borrowed()
     }
 
     fn read_bytes_vec(&mut self) -> Result<Vec<u8>> {
-        Ok(self.read_bytes()?.to_vec())
+        let position = self.pos;
+        let bytes = self.read_bytes_borrowed()?;
+        self.budget.allocate(position, bytes.len())?;
+        Ok(bytes.to_vec())
     }
 
     fn read_string(&mut self) -> Result<String> {
         let position = self.pos;
-        let (major, info) = self.decompose(self.current_raw_byte()?);
-        let field_type = self.type_of(major, info)?;
-        self.skip_raw_bytes(1)?;
-
-        if major != 3 {
-            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
-        }
-
-        let Some(len) = self.read_raw_len(info)? else {
-            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
-        };
-
-        let len: usize = len.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position })?;
-        let bytes = self.read_raw_bytes(len)?;
-
-        std::str::from_utf8(bytes)
-            .map(|n| n.to_owned())
-            .map_err(|e| RocketPackDecoderError::Utf8 { position, error: e })
+        let value = self.read_str()?;
+        self.budget.allocate(position, value.len())?;
+        Ok(value.to_owned())
     }
 
     fn read_array(&mut self) -> Result<u64> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         if major != 4 {
@@ -367,6 +611,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let Some(len) = self.read_raw_len(info)? else {
             return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
         };
+        self.budget.check_collection_len(position, len)?;
 
         Ok(len)
     }
@@ -375,6 +620,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         if major != 5 {
@@ -384,6 +630,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let Some(len) = self.read_raw_len(info)? else {
             return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
         };
+        self.budget.check_collection_len(position, len)?;
 
         Ok(len)
     }
@@ -392,6 +639,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         let position = self.pos;
         let (major, info) = self.decompose(self.current_raw_byte()?);
         let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
         self.skip_raw_bytes(1)?;
 
         #[allow(clippy::unit_arg)]
@@ -403,8 +651,29 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
         })
     }
 
+    fn read_tag(&mut self) -> Result<u64> {
+        let position = self.pos;
+        let (major, info) = self.decompose(self.current_raw_byte()?);
+        let field_type = self.type_of(major, info)?;
+        self.check_canonical()?;
+        self.skip_raw_bytes(1)?;
+
+        if major != 6 {
+            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        }
+
+        let Some(tag) = self.read_raw_len(info)? else {
+            return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+        };
+
+        Ok(tag)
+    }
+
     fn read_struct<T: RocketPackStruct>(&mut self) -> Result<T> {
-        T::unpack(self)
+        self.budget.enter(self.pos)?;
+        let value = T::unpack(self)?;
+        self.budget.leave();
+        Ok(value)
     }
 
     fn skip_field(&mut self) -> Result<()> {
@@ -414,6 +683,7 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
             let position = self.pos;
             let (major, info) = self.decompose(self.current_raw_byte()?);
             let field_type = self.type_of(major, info)?;
+            self.check_canonical()?;
             self.skip_raw_bytes(1)?;
 
             let len = match major {
@@ -442,8 +712,16 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
                     }
                     _ => None,
                 },
+                // The tagged value follows the tag and still has to be skipped.
+                6 => match self.read_raw_len(info)? {
+                    Some(_) => {
+                        remain = remain.checked_add(1).ok_or(RocketPackDecoderError::LengthOverflow { position })?;
+                        Some(0)
+                    }
+                    _ => None,
+                },
                 7 => match info {
-                    20 | 21 => Some(0),
+                    20..=22 => Some(0),
                     25 => Some(2),
                     26 => Some(4),
                     27 => Some(8),
@@ -463,6 +741,12 @@ impl<'a> RocketPackDecoder for RocketPackBytesDecoder<'a> {
 
         Ok(())
     }
+
+    fn alloc_vec<T>(&mut self, count: u64) -> Result<Vec<T>> {
+        // Every element takes at least one byte, so the buffer bounds the capacity worth reserving.
+        let capacity = count.min(self.remaining() as u64);
+        self.budget.alloc_vec(self.pos, capacity)
+    }
 }
 
 impl<'a> RocketPackBytesDecoder<'a> {
@@ -510,6 +794,7 @@ impl<'a> RocketPackBytesDecoder<'a> {
             (3, _) => return Ok(FieldType::String),
             (4, _) => return Ok(FieldType::Array),
             (5, _) => return Ok(FieldType::Map),
+            (6, _) => return Ok(FieldType::Tag),
             (7, 20..=21) => return Ok(FieldType::Bool),
             (7, 25) => return Ok(FieldType::F16),
             (7, 26) => return Ok(FieldType::F32),
@@ -520,6 +805,123 @@ impl<'a> RocketPackBytesDecoder<'a> {
         Ok(FieldType::Unknown { major, info })
     }
 
+    // Rejects heads whose argument or float is wider than needed, and map keys out of order.
+    fn check_canonical(&mut self) -> Result<()> {
+        if !self.strict {
+            return Ok(());
+        }
+
+        let position = self.pos;
+        let (major, info) = self.decompose(self.current_raw_byte()?);
+
+        let minimal = match (major, info) {
+            (7, 25) => {
+                let value = f16::from_bits(u16::from_be_bytes(self.peek_raw_fixed_bytes()?));
+                !value.is_nan() || value.to_bits() == 0x7e00
+            }
+            (7, 26) => narrow_to_f16(f32::from_be_bytes(self.peek_raw_fixed_bytes()?) as f64).is_none(),
+            (7, 27) => {
+                let value = f64::from_be_bytes(self.peek_raw_fixed_bytes()?);
+                narrow_to_f16(value).is_none() && narrow_to_f32(value).is_none()
+            }
+            (7, _) => true,
+            (_, 24) => u8::from_be_bytes(self.peek_raw_fixed_bytes()?) > 23,
+            (_, 25) => u16::from_be_bytes(self.peek_raw_fixed_bytes()?) > u8::MAX as u16,
+            (_, 26) => u32::from_be_bytes(self.peek_raw_fixed_bytes()?) > u16::MAX as u32,
+            (_, 27) => u64::from_be_bytes(self.peek_raw_fixed_bytes()?) > u32::MAX as u64,
+            _ => true,
+        };
+
+        if !minimal {
+            return Err(RocketPackDecoderError::NonCanonical {
+                position,
+                reason: "value is not in its shortest form",
+            });
+        }
+
+        self.track_item(major, info)
+    }
+
+    // Tracks the containers the head at the current position belongs to, and checks that the keys of every map
+    // strictly increase.
+    fn track_item(&mut self, major: u8, info: u8) -> Result<()> {
+        let position = self.pos;
+
+        // A tag is a prefix of the item that follows it.
+        if major == 6 {
+            self.tag_start.get_or_insert(position);
+            return Ok(());
+        }
+        let start = self.tag_start.take().unwrap_or(position);
+
+        if let Some(frame) = self.frames.last_mut() {
+            if frame.is_map {
+                if frame.remaining % 2 == 0 {
+                    frame.key_start = start;
+                } else {
+                    let key = &self.buf[frame.key_start..start];
+                    if let Some((previous_start, previous_end)) = frame.previous_key {
+                        let previous = &self.buf[previous_start..previous_end];
+                        if previous == key {
+                            return Err(RocketPackDecoderError::NonCanonical {
+                                position: frame.key_start,
+                                reason: "duplicate map key",
+                            });
+                        }
+                        if previous > key {
+                            return Err(RocketPackDecoderError::NonCanonical {
+                                position: frame.key_start,
+                                reason: "map keys are not sorted",
+                            });
+                        }
+                    }
+                    frame.previous_key = Some((frame.key_start, start));
+                }
+            }
+            frame.remaining -= 1;
+        }
+
+        let remaining = match major {
+            4 | 5 => {
+                let len = self.peek_raw_len(info)?.unwrap_or(0);
+                if major == 5 {
+                    len.checked_mul(2).ok_or(RocketPackDecoderError::LengthOverflow { position })?
+                } else {
+                    len
+                }
+            }
+            _ => 0,
+        };
+
+        if remaining > 0 {
+            self.frames.push(StrictFrame {
+                remaining,
+                is_map: major == 5,
+                key_start: position,
+                previous_key: None,
+            });
+            return Ok(());
+        }
+
+        // The item is complete, and so is every container whose last item it was.
+        while self.frames.last().is_some_and(|frame| frame.remaining == 0) {
+            self.frames.pop();
+        }
+
+        Ok(())
+    }
+
+    fn peek_raw_len(&self, info: u8) -> Result<Option<u64>> {
+        Ok(match info {
+            0..=23 => Some(info as u64),
+            24 => Some(u8::from_be_bytes(self.peek_raw_fixed_bytes()?) as u64),
+            25 => Some(u16::from_be_bytes(self.peek_raw_fixed_bytes()?) as u64),
+            26 => Some(u32::from_be_bytes(self.peek_raw_fixed_bytes()?) as u64),
+            27 => Some(u64::from_be_bytes(self.peek_raw_fixed_bytes()?)),
+            _ => None,
+        })
+    }
+
     pub(crate) fn read_raw_len(&mut self, info: u8) -> Result<Option<u64>> {
         Ok(match info {
             0..=23 => Some(info as u64),
@@ -545,6 +947,16 @@ impl<'a> RocketPackBytesDecoder<'a> {
         Ok(self.buf[self.pos + 1])
     }
 
+    // Reads the `N` bytes following the current initial byte without consuming anything.
+    fn peek_raw_fixed_bytes<const N: usize>(&self) -> Result<[u8; N]> {
+        if self.remaining() < N + 1 {
+            return Err(RocketPackDecoderError::UnexpectedEof);
+        }
+        let mut out = [0u8; N];
+        out.copy_from_slice(&self.buf[self.pos + 1..self.pos + 1 + N]);
+        Ok(out)
+    }
+
     fn read_raw_fixed_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
         if self.remaining() < N {
             return Err(RocketPackDecoderError::UnexpectedEof);
@@ -0,0 +1,233 @@
+use crate::RocketPackDecoderError;
+
+type Result<T> = std::result::Result<T, RocketPackDecoderError>;
+
+/// Resource limits applied while decoding, to keep untrusted input from exhausting memory or stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RocketPackDecoderLimits {
+    /// Maximum nesting depth of structs.
+    pub max_depth: usize,
+    /// Maximum element count of an array or entry count of a map.
+    pub max_collection_len: u64,
+    /// Maximum length of a single byte or text string.
+    pub max_bytes_len: u64,
+    /// Maximum number of bytes allocated for owned strings, byte buffers and collections during one decode.
+    pub max_total_allocation: usize,
+}
+
+impl RocketPackDecoderLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_collection_len: u64::MAX,
+            max_bytes_len: u64::MAX,
+            max_total_allocation: usize::MAX,
+        }
+    }
+}
+
+impl Default for RocketPackDecoderLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_collection_len: 1 << 24,
+            max_bytes_len: 64 * 1024 * 1024,
+            max_total_allocation: 256 * 1024 * 1024,
+        }
+    }
+}
+
+// Tracks how much of the limits a decoder has used so far.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DecoderBudget {
+    limits: RocketPackDecoderLimits,
+    depth: usize,
+    allocated: usize,
+}
+
+impl DecoderBudget {
+    pub fn new(limits: RocketPackDecoderLimits) -> Self {
+        Self { limits, depth: 0, allocated: 0 }
+    }
+
+    pub fn enter(&mut self, position: usize) -> Result<()> {
+        if self.depth >= self.limits.max_depth {
+            return Err(RocketPackDecoderError::DepthLimitExceeded {
+                position,
+                limit: self.limits.max_depth,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    pub fn check_collection_len(&self, position: usize, len: u64) -> Result<()> {
+        if len > self.limits.max_collection_len {
+            return Err(RocketPackDecoderError::CollectionLengthLimitExceeded {
+                position,
+                len,
+                limit: self.limits.max_collection_len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn check_bytes_len(&self, position: usize, len: u64) -> Result<()> {
+        if len > self.limits.max_bytes_len {
+            return Err(RocketPackDecoderError::BytesLengthLimitExceeded {
+                position,
+                len,
+                limit: self.limits.max_bytes_len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn allocate(&mut self, position: usize, len: usize) -> Result<()> {
+        let allocated = self.allocated.saturating_add(len);
+        if allocated > self.limits.max_total_allocation {
+            return Err(RocketPackDecoderError::AllocationLimitExceeded {
+                position,
+                limit: self.limits.max_total_allocation,
+            });
+        }
+        self.allocated = allocated;
+        Ok(())
+    }
+
+    pub fn alloc_vec<T>(&mut self, position: usize, capacity: u64) -> Result<Vec<T>> {
+        let capacity: usize = capacity.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position })?;
+        self.allocate(position, capacity.saturating_mul(size_of::<T>()))?;
+        Ok(Vec::with_capacity(capacity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+
+    use crate::{
+        RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackEncoder, RocketPackReadDecoder,
+        RocketPackStruct,
+    };
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct NestedTestStruct {
+        pub child: Option<Box<NestedTestStruct>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct CollectionTestStruct {
+        pub values: Vec<u64>,
+        pub names: Vec<String>,
+    }
+
+    fn nested(depth: usize) -> NestedTestStruct {
+        (0..depth).fold(NestedTestStruct { child: None }, |child, _| NestedTestStruct { child: Some(Box::new(child)) })
+    }
+
+    #[test]
+    fn depth_limit_test() -> TestResult {
+        let bytes = nested(10).export()?;
+        let limits = RocketPackDecoderLimits {
+            max_depth: 5,
+            ..Default::default()
+        };
+
+        let mut decoder = RocketPackBytesDecoder::with_limits(&bytes, limits);
+        let result = NestedTestStruct::unpack(&mut decoder);
+        assert!(matches!(result, Err(RocketPackDecoderError::DepthLimitExceeded { limit: 5, .. })));
+
+        let mut decoder = RocketPackReadDecoder::with_limits(bytes.as_slice(), limits);
+        let result = NestedTestStruct::unpack(&mut decoder);
+        assert!(matches!(result, Err(RocketPackDecoderError::DepthLimitExceeded { limit: 5, .. })));
+
+        assert_eq!(NestedTestStruct::import(&bytes)?, nested(10));
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_limit_test() -> TestResult {
+        let mut bytes = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_map(1)?;
+        encoder.write_u64(0)?;
+        encoder.write_array(u32::MAX as usize)?;
+
+        let mut decoder = RocketPackBytesDecoder::with_limits(&bytes, RocketPackDecoderLimits::default());
+        let result = CollectionTestStruct::unpack(&mut decoder);
+        assert!(matches!(
+            result,
+            Err(RocketPackDecoderError::CollectionLengthLimitExceeded { position: 2, len, .. }) if len == u32::MAX as u64
+        ));
+        assert!(matches!(
+            CollectionTestStruct::import(&bytes),
+            Err(RocketPackDecoderError::CollectionLengthLimitExceeded { .. })
+        ));
+
+        // `new` applies no limits, and a count far beyond the input only reserves what the buffer can hold.
+        let mut decoder = RocketPackBytesDecoder::new(&bytes);
+        let result = CollectionTestStruct::unpack(&mut decoder);
+        assert!(matches!(result, Err(RocketPackDecoderError::UnexpectedEof)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_limit_test() -> TestResult {
+        let mut bytes = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_string(&"a".repeat(100))?;
+
+        let limits = RocketPackDecoderLimits {
+            max_bytes_len: 10,
+            ..Default::default()
+        };
+
+        let mut decoder = RocketPackBytesDecoder::with_limits(&bytes, limits);
+        assert!(matches!(
+            decoder.read_string(),
+            Err(RocketPackDecoderError::BytesLengthLimitExceeded { position: 0, len: 100, limit: 10 })
+        ));
+
+        // The stream decoder rejects the length before reading the payload.
+        let mut decoder = RocketPackReadDecoder::with_limits(&bytes[..2], limits);
+        assert!(matches!(
+            decoder.read_string(),
+            Err(RocketPackDecoderError::BytesLengthLimitExceeded { position: 0, len: 100, limit: 10 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn allocation_limit_test() -> TestResult {
+        let value = CollectionTestStruct {
+            values: vec![1, 2, 3],
+            names: vec!["a".repeat(64), "b".repeat(64)],
+        };
+        let bytes = value.export()?;
+
+        let limits = RocketPackDecoderLimits {
+            max_total_allocation: 128,
+            ..Default::default()
+        };
+
+        let mut decoder = RocketPackBytesDecoder::with_limits(&bytes, limits);
+        let result = CollectionTestStruct::unpack(&mut decoder);
+        assert!(matches!(result, Err(RocketPackDecoderError::AllocationLimitExceeded { limit: 128, .. })));
+
+        let mut decoder = RocketPackReadDecoder::with_limits(bytes.as_slice(), limits);
+        let result = CollectionTestStruct::unpack(&mut decoder);
+        assert!(matches!(result, Err(RocketPackDecoderError::AllocationLimitExceeded { limit: 128, .. })));
+
+        assert_eq!(CollectionTestStruct::import(&bytes)?, value);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, rc::Rc};
+
+    use testresult::TestResult;
+
+    use crate::{RocketPackBytesEncoder, RocketPackDecoderError, RocketPackEncoder, RocketPackStruct, f16, primitive::Timestamp96};
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct DerivedTestStruct {
+        pub p0: bool,
+        pub p1: u8,
+        pub p2: u16,
+        pub p3: u32,
+        pub p4: u64,
+        pub p5: i8,
+        pub p6: i16,
+        pub p7: i32,
+        pub p8: i64,
+        pub p9: f32,
+        pub p10: f64,
+        pub p11: Vec<u8>,
+        pub p12: String,
+        pub p13: Vec<String>,
+        pub p14: BTreeMap<u32, String>,
+        pub p15: Option<Rc<DerivedTestStruct>>,
+        pub p16: [i64; 2],
+        pub p17: Vec<Option<u32>>,
+        pub p18: f16,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct DerivedTimestamp {
+        pub seconds: i64,
+        pub nanos: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct AttributeTestStruct {
+        #[rocketpack(tag = 10)]
+        pub name: String,
+        #[rocketpack(tag = 3, default)]
+        pub retries: u32,
+        #[rocketpack(skip)]
+        pub cache: Vec<u8>,
+        #[rocketpack(tag = 1)]
+        pub note: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct NameOnlyStruct {
+        #[rocketpack(tag = 10)]
+        pub name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct UnitStruct;
+
+    #[test]
+    fn normal_test() -> TestResult {
+        let mut value = DerivedTestStruct {
+            p0: true,
+            p1: 1,
+            p2: 2,
+            p3: 3,
+            p4: 4,
+            p5: 5,
+            p6: 6,
+            p7: 7,
+            p8: 8,
+            p9: 9.5,
+            p10: 10.5,
+            p11: vec![0xAA, 0xBB, 0xCC],
+            p12: "test".to_string(),
+            p13: vec!["test_0".to_string(), "test_1".to_string()],
+            p14: BTreeMap::from([(0_u32, "test_value_0".to_string()), (1_u32, "test_value_1".to_string())]),
+            p15: None,
+            p16: [-1, 1],
+            p17: vec![Some(1), None, Some(3)],
+            p18: f16::from_f32(18.5),
+        };
+        value.p15 = Some(Rc::new(value.clone()));
+
+        let exported = value.export()?;
+        let imported = DerivedTestStruct::import(exported.as_slice())?;
+        assert_eq!(imported, value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hand_written_compatibility_test() -> TestResult {
+        let derived = DerivedTimestamp { seconds: -1_234, nanos: 5_678 };
+        let hand_written = Timestamp96::new(-1_234, 5_678);
+
+        assert_eq!(derived.export()?, hand_written.export()?);
+
+        let imported = Timestamp96::import(derived.export()?.as_slice())?;
+        assert_eq!(imported.seconds, derived.seconds);
+        assert_eq!(imported.nanos, derived.nanos);
+
+        Ok(())
+    }
+
+    #[test]
+    fn attribute_test() -> TestResult {
+        let value = AttributeTestStruct {
+            name: "name".to_string(),
+            retries: 2,
+            cache: vec![1, 2, 3],
+            note: Some("note".to_string()),
+        };
+
+        let mut expected = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut expected);
+        encoder.write_map(3)?;
+        encoder.write_u64(1)?;
+        encoder.write_string("note")?;
+        encoder.write_u64(3)?;
+        encoder.write_u32(2)?;
+        encoder.write_u64(10)?;
+        encoder.write_string("name")?;
+
+        assert_eq!(value.export()?, expected);
+
+        let imported = AttributeTestStruct::import(expected.as_slice())?;
+        assert_eq!(imported, AttributeTestStruct { cache: Vec::new(), ..value });
+
+        let imported = AttributeTestStruct::import(NameOnlyStruct { name: "name".to_string() }.export()?.as_slice())?;
+        assert_eq!(
+            imported,
+            AttributeTestStruct {
+                name: "name".to_string(),
+                retries: 0,
+                cache: Vec::new(),
+                note: None,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_field_test() -> TestResult {
+        let result = NameOnlyStruct::import(UnitStruct.export()?.as_slice());
+        assert!(matches!(result, Err(RocketPackDecoderError::Other("missing field: name"))));
+
+        assert_eq!(UnitStruct::import(UnitStruct.export()?.as_slice())?, UnitStruct);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,704 @@
+use std::fmt::Write as _;
+
+use half::f16;
+use thiserror::Error;
+
+use crate::{FieldType, RocketPackBytesDecoder, RocketPackDecoder, RocketPackDecoderError, RocketPackDecoderLimits, rocket_pack_decoder_limits::DecoderBudget};
+
+// https://www.rfc-editor.org/rfc/rfc8949.html#name-diagnostic-notation
+
+#[derive(Error, Debug)]
+pub enum RocketPackDiagnosticError {
+    #[error("invalid diagnostic notation (position: {position}, reason: {reason})")]
+    InvalidSyntax { position: usize, reason: &'static str },
+}
+
+/// Renders one rocketpack value as single-line CBOR diagnostic notation, e.g. `{0: 1, 1: "abc", 2: [1.5_2, h'0a0b']}`.
+///
+/// Encoding indicators (`_0`..`_3`) mark floats narrower than `f64` and integers or lengths wider than needed,
+/// so `from_diagnostic` gives back the same bytes.
+pub fn to_diagnostic(bytes: &[u8]) -> Result<String, RocketPackDecoderError> {
+    let mut dumper = Dumper::new(bytes);
+    let mut out = String::new();
+    dumper.compact(&mut out)?;
+    Ok(out)
+}
+
+/// Renders one rocketpack value as indented diagnostic notation with one item per line, each annotated with
+/// its byte offset and `FieldType` in a `/ comment /`. The output still parses with `from_diagnostic`.
+pub fn dump_diagnostic(bytes: &[u8]) -> Result<String, RocketPackDecoderError> {
+    let mut dumper = Dumper::new(bytes);
+    let mut lines = Vec::new();
+    dumper.annotated(&mut lines, 0, String::new(), String::new(), Vec::new())?;
+
+    let width = lines.iter().map(|line| line.text.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for line in lines {
+        if line.notes.is_empty() {
+            writeln!(out, "{}", line.text).ok();
+        } else {
+            writeln!(out, "{:width$}  / {} /", line.text, line.notes.join(", ")).ok();
+        }
+    }
+    Ok(out)
+}
+
+/// Parses diagnostic notation, such as the output of `to_diagnostic` or `dump_diagnostic`, back into rocketpack bytes.
+///
+/// Integers and lengths use the shortest head unless an encoding indicator says otherwise, and floats without
+/// an indicator are written as `f64`.
+pub fn from_diagnostic(text: &str) -> Result<Vec<u8>, RocketPackDiagnosticError> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        depth: 0,
+        out: Vec::new(),
+    };
+    parser.value()?;
+    parser.skip_trivia()?;
+    if parser.pos < text.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(parser.out)
+}
+
+struct Head {
+    position: usize,
+    major: u8,
+    info: u8,
+    arg: u64,
+    field_type: FieldType,
+}
+
+impl Head {
+    // The encoding indicator when the head is wider than the shortest form of its argument.
+    fn indicator(&self) -> String {
+        if self.info < 24 || self.info == shortest_info(self.arg) {
+            return String::new();
+        }
+        format!("_{}", self.info - 24)
+    }
+}
+
+struct Line {
+    text: String,
+    notes: Vec<String>,
+}
+
+struct Dumper<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    budget: DecoderBudget,
+}
+
+impl<'a> Dumper<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            budget: DecoderBudget::new(RocketPackDecoderLimits::default()),
+        }
+    }
+
+    fn head(&mut self) -> Result<Head, RocketPackDecoderError> {
+        let position = self.pos;
+        let decoder = RocketPackBytesDecoder::new(&self.bytes[position..]);
+        let field_type = decoder.current_type().map_err(|e| e.with_offset(position))?;
+
+        let byte = self.bytes[position];
+        let (major, info) = (byte >> 5, byte & 0x1f);
+        let len = match info {
+            0..=23 => 0,
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(RocketPackDecoderError::MismatchFieldType { position, field_type }),
+        };
+
+        let arg_bytes = self.bytes.get(position + 1..position + 1 + len).ok_or(RocketPackDecoderError::UnexpectedEof)?;
+        let arg = if len == 0 {
+            info as u64
+        } else {
+            arg_bytes.iter().fold(0_u64, |acc, b| (acc << 8) | *b as u64)
+        };
+        self.pos += 1 + len;
+
+        Ok(Head {
+            position,
+            major,
+            info,
+            arg,
+            field_type,
+        })
+    }
+
+    fn payload(&mut self, head: &Head) -> Result<&'a [u8], RocketPackDecoderError> {
+        let len: usize = head.arg.try_into().map_err(|_| RocketPackDecoderError::LengthOverflow { position: head.position })?;
+        let end = self.pos.checked_add(len).ok_or(RocketPackDecoderError::LengthOverflow { position: head.position })?;
+        let payload = self.bytes.get(self.pos..end).ok_or(RocketPackDecoderError::UnexpectedEof)?;
+        self.pos = end;
+        Ok(payload)
+    }
+
+    // The text of a head that is not a container or a tag.
+    fn scalar(&mut self, head: &Head) -> Result<String, RocketPackDecoderError> {
+        let indicator = head.indicator();
+        Ok(match head.major {
+            0 => format!("{}{indicator}", head.arg),
+            1 => format!("{}{indicator}", -1 - head.arg as i128),
+            2 => format!("h'{}'{indicator}", hex::encode(self.payload(head)?)),
+            3 => {
+                let position = head.position;
+                let text = std::str::from_utf8(self.payload(head)?).map_err(|error| RocketPackDecoderError::Utf8 { position, error })?;
+                format!("{}{indicator}", serde_json::Value::from(text))
+            }
+            7 => match head.info {
+                20 => "false".to_string(),
+                21 => "true".to_string(),
+                22 => "null".to_string(),
+                23 => "undefined".to_string(),
+                25 => format!("{}_1", format_float(f16::from_bits(head.arg as u16).to_f64(), |v| format!("{:?}", v as f32))),
+                26 => format!("{}_2", format_float(f32::from_bits(head.arg as u32) as f64, |v| format!("{:?}", v as f32))),
+                27 => format_float(f64::from_bits(head.arg), |v| format!("{v:?}")),
+                _ => format!("simple({})", head.arg),
+            },
+            _ => unreachable!(),
+        })
+    }
+
+    fn compact(&mut self, out: &mut String) -> Result<(), RocketPackDecoderError> {
+        let head = self.head()?;
+        match head.major {
+            4 | 5 => {
+                let indicator = head.indicator();
+                let (open, close) = if head.major == 4 { ('[', ']') } else { ('{', '}') };
+                out.push(open);
+                if !indicator.is_empty() {
+                    write!(out, "{indicator} ").ok();
+                }
+
+                self.budget.enter(head.position)?;
+                for i in 0..head.arg {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.compact(out)?;
+                    if head.major == 5 {
+                        out.push_str(": ");
+                        self.compact(out)?;
+                    }
+                }
+                self.budget.leave();
+
+                out.push(close);
+            }
+            6 => {
+                write!(out, "{}{}(", head.arg, head.indicator()).ok();
+                self.budget.enter(head.position)?;
+                self.compact(out)?;
+                self.budget.leave();
+                out.push(')');
+            }
+            _ => out.push_str(&self.scalar(&head)?),
+        }
+        Ok(())
+    }
+
+    fn annotated(&mut self, lines: &mut Vec<Line>, depth: usize, prefix: String, suffix: String, mut notes: Vec<String>) -> Result<(), RocketPackDecoderError> {
+        let indent = "  ".repeat(depth);
+        let head = self.head()?;
+        notes.push(format!("@{} {}", head.position, head.field_type));
+
+        match head.major {
+            4 | 5 => {
+                let indicator = head.indicator();
+                let (open, close) = if head.major == 4 { ("[", "]") } else { ("{", "}") };
+                let open = if indicator.is_empty() { open.to_string() } else { format!("{open}{indicator}") };
+                lines.push(Line {
+                    text: format!("{indent}{prefix}{open}"),
+                    notes,
+                });
+
+                self.budget.enter(head.position)?;
+                for i in 0..head.arg {
+                    let separator = if i + 1 < head.arg { "," } else { "" }.to_string();
+                    if head.major == 4 {
+                        self.annotated(lines, depth + 1, String::new(), separator, Vec::new())?;
+                        continue;
+                    }
+
+                    // Scalar keys share a line with their value; other keys get lines of their own.
+                    let key_start = self.pos;
+                    let key = self.head()?;
+                    if matches!(key.major, 0..=3 | 7) {
+                        let text = self.scalar(&key)?;
+                        let note = format!("@{} {}", key.position, key.field_type);
+                        self.annotated(lines, depth + 1, format!("{text}: "), separator, vec![note])?;
+                    } else {
+                        self.pos = key_start;
+                        self.annotated(lines, depth + 1, String::new(), ":".to_string(), Vec::new())?;
+                        self.annotated(lines, depth + 1, String::new(), separator, Vec::new())?;
+                    }
+                }
+                self.budget.leave();
+
+                lines.push(Line {
+                    text: format!("{indent}{close}{suffix}"),
+                    notes: Vec::new(),
+                });
+            }
+            6 => {
+                let prefix = format!("{prefix}{}{}(", head.arg, head.indicator());
+                self.budget.enter(head.position)?;
+                self.annotated(lines, depth, prefix, format!("){suffix}"), notes)?;
+                self.budget.leave();
+            }
+            _ => {
+                let text = self.scalar(&head)?;
+                lines.push(Line {
+                    text: format!("{indent}{prefix}{text}{suffix}"),
+                    notes,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_float(value: f64, finite: impl Fn(f64) -> String) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value < 0.0 { "-Infinity" } else { "Infinity" }.to_string()
+    } else {
+        finite(value)
+    }
+}
+
+fn shortest_info(arg: u64) -> u8 {
+    match arg {
+        0..=23 => arg as u8,
+        24..=0xff => 24,
+        0x100..=0xffff => 25,
+        0x1_0000..=0xffff_ffff => 26,
+        _ => 27,
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    depth: usize,
+    out: Vec<u8>,
+}
+
+impl Parser<'_> {
+    const MAX_DEPTH: usize = 128;
+
+    fn error(&self, reason: &'static str) -> RocketPackDiagnosticError {
+        RocketPackDiagnosticError::InvalidSyntax { position: self.pos, reason }
+    }
+
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), RocketPackDiagnosticError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            if self.peek() != Some(b'/') {
+                return Ok(());
+            }
+            let Some(end) = self.text[self.pos + 1..].find('/') else {
+                return Err(self.error("unterminated comment"));
+            };
+            self.pos += end + 2;
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> Result<bool, RocketPackDiagnosticError> {
+        self.skip_trivia()?;
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn expect(&mut self, token: &str, reason: &'static str) -> Result<(), RocketPackDiagnosticError> {
+        if !self.eat(token)? {
+            return Err(self.error(reason));
+        }
+        Ok(())
+    }
+
+    // An optional `_0`..`_3` right after the current token.
+    fn indicator(&mut self) -> Result<Option<u8>, RocketPackDiagnosticError> {
+        let bytes = self.rest().as_bytes();
+        if bytes.first() != Some(&b'_') {
+            return Ok(None);
+        }
+        match bytes.get(1).copied() {
+            Some(c @ b'0'..=b'3') => {
+                self.pos += 2;
+                Ok(Some(c - b'0'))
+            }
+            _ => Err(self.error("unsupported encoding indicator")),
+        }
+    }
+
+    fn write_head(&mut self, major: u8, arg: u64, indicator: Option<u8>) -> Result<(), RocketPackDiagnosticError> {
+        let info = match indicator {
+            None => shortest_info(arg),
+            Some(n) => 24 + n,
+        };
+        if info != shortest_info(arg) && info < shortest_info(arg).max(24) {
+            return Err(self.error("encoding indicator too narrow for value"));
+        }
+
+        self.out.push((major << 5) | info);
+        match info {
+            24 => self.out.push(arg as u8),
+            25 => self.out.extend((arg as u16).to_be_bytes()),
+            26 => self.out.extend((arg as u32).to_be_bytes()),
+            27 => self.out.extend(arg.to_be_bytes()),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<(), RocketPackDiagnosticError> {
+        self.skip_trivia()?;
+        let Some(c) = self.peek() else {
+            return Err(self.error("unexpected end of input"));
+        };
+
+        match c {
+            b'[' | b'{' => self.container(c),
+            b'"' => self.string(),
+            b'h' if self.rest().starts_with("h'") => self.hex_bytes(),
+            b'-' | b'0'..=b'9' | b'N' | b'I' => self.number(),
+            _ => self.keyword(),
+        }
+    }
+
+    fn keyword(&mut self) -> Result<(), RocketPackDiagnosticError> {
+        for (keyword, byte) in [("false", 0xf4), ("true", 0xf5), ("null", 0xf6), ("undefined", 0xf7)] {
+            if self.rest().starts_with(keyword) {
+                self.pos += keyword.len();
+                self.out.push(byte);
+                return Ok(());
+            }
+        }
+
+        if self.eat("simple(")? {
+            let start = self.pos;
+            let digits = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+            self.pos += digits;
+            let value: u8 = self.text[start..self.pos].parse().map_err(|_| self.error("invalid simple value"))?;
+            match value {
+                0..=19 => self.out.push(0xe0 | value),
+                32.. => self.out.extend([0xf8, value]),
+                _ => return Err(self.error("invalid simple value")),
+            }
+            return self.expect(")", "expected ')'");
+        }
+
+        Err(self.error("unexpected character"))
+    }
+
+    fn container(&mut self, open: u8) -> Result<(), RocketPackDiagnosticError> {
+        self.pos += 1;
+        let indicator = self.indicator()?;
+        let (close, major) = if open == b'[' { ("]", 4) } else { ("}", 5) };
+
+        self.depth += 1;
+        if self.depth > Self::MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+
+        // Items are written to a separate buffer because the count comes first.
+        let outer = std::mem::take(&mut self.out);
+        let mut count = 0_u64;
+        if !self.eat(close)? {
+            loop {
+                self.value()?;
+                if major == 5 {
+                    self.expect(":", "expected ':'")?;
+                    self.value()?;
+                }
+                count += 1;
+
+                if self.eat(close)? {
+                    break;
+                }
+                self.expect(",", "expected ',' or closing bracket")?;
+            }
+        }
+        self.depth -= 1;
+
+        let items = std::mem::replace(&mut self.out, outer);
+        self.write_head(major, count, indicator)?;
+        self.out.extend(items);
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<(), RocketPackDiagnosticError> {
+        let start = self.pos;
+        let mut stream = serde_json::Deserializer::from_str(self.rest()).into_iter::<String>();
+        let value = match stream.next() {
+            Some(Ok(value)) => value,
+            _ => return Err(self.error("invalid string")),
+        };
+        self.pos = start + stream.byte_offset();
+
+        let indicator = self.indicator()?;
+        self.write_head(3, value.len() as u64, indicator)?;
+        self.out.extend(value.as_bytes());
+        Ok(())
+    }
+
+    fn hex_bytes(&mut self) -> Result<(), RocketPackDiagnosticError> {
+        self.pos += 2;
+        let Some(end) = self.rest().find('\'') else {
+            return Err(self.error("unterminated byte string"));
+        };
+        let digits: String = self.rest()[..end].chars().filter(|c| !c.is_whitespace()).collect();
+        let value = hex::decode(digits).map_err(|_| self.error("invalid hex in byte string"))?;
+        self.pos += end + 1;
+
+        let indicator = self.indicator()?;
+        self.write_head(2, value.len() as u64, indicator)?;
+        self.out.extend(value);
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<(), RocketPackDiagnosticError> {
+        let start = self.pos;
+        let len = self.rest().bytes().take_while(|c| c.is_ascii_alphanumeric() || matches!(c, b'-' | b'+' | b'.')).count();
+        self.pos += len;
+        let token = &self.text[start..self.pos];
+        let indicator = self.indicator()?;
+
+        let is_float = matches!(token, "NaN" | "Infinity" | "-Infinity") || token.contains(['.', 'e', 'E']);
+        if is_float {
+            return self.float(token, indicator);
+        }
+
+        let value: i128 = token.parse().map_err(|_| self.error("invalid number"))?;
+        let (major, arg) = if value >= 0 { (0, value) } else { (1, -1 - value) };
+        let arg: u64 = arg.try_into().map_err(|_| self.error("integer out of range"))?;
+
+        // An integer directly followed by `(` is a tag.
+        if self.peek() == Some(b'(') {
+            if major != 0 {
+                return Err(self.error("negative tag number"));
+            }
+            self.pos += 1;
+            self.write_head(6, arg, indicator)?;
+
+            self.depth += 1;
+            if self.depth > Self::MAX_DEPTH {
+                return Err(self.error("nesting too deep"));
+            }
+            self.value()?;
+            self.depth -= 1;
+
+            return self.expect(")", "expected ')'");
+        }
+
+        self.write_head(major, arg, indicator)
+    }
+
+    fn float(&mut self, token: &str, indicator: Option<u8>) -> Result<(), RocketPackDiagnosticError> {
+        let parse_f32 = |token: &str| match token {
+            "NaN" => Some(f32::NAN),
+            "Infinity" => Some(f32::INFINITY),
+            "-Infinity" => Some(f32::NEG_INFINITY),
+            _ => token.parse::<f32>().ok(),
+        };
+
+        match indicator {
+            Some(1) => {
+                let value = parse_f32(token).ok_or_else(|| self.error("invalid float"))?;
+                self.out.push(0xf9);
+                self.out.extend(f16::from_f32(value).to_bits().to_be_bytes());
+            }
+            Some(2) => {
+                let value = parse_f32(token).ok_or_else(|| self.error("invalid float"))?;
+                self.out.push(0xfa);
+                self.out.extend(value.to_bits().to_be_bytes());
+            }
+            None | Some(3) => {
+                let value = match token {
+                    "NaN" => f64::NAN,
+                    "Infinity" => f64::INFINITY,
+                    "-Infinity" => f64::NEG_INFINITY,
+                    _ => token.parse::<f64>().map_err(|_| self.error("invalid float"))?,
+                };
+                self.out.push(0xfb);
+                self.out.extend(value.to_bits().to_be_bytes());
+            }
+            _ => return Err(self.error("unsupported encoding indicator")),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+
+    use crate::{RocketPackBytesEncoder, RocketPackDecoderError, RocketPackEncoder, RocketPackStruct, f16};
+
+    use super::{RocketPackDiagnosticError, dump_diagnostic, from_diagnostic, to_diagnostic};
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct DiagnosticTestStruct {
+        pub id: u32,
+        pub name: String,
+        pub values: Vec<i32>,
+        pub payload: Vec<u8>,
+        pub ratio: f32,
+        pub child: Option<Box<DiagnosticTestStruct>>,
+    }
+
+    fn test_value() -> DiagnosticTestStruct {
+        DiagnosticTestStruct {
+            id: 1,
+            name: "a\"b".to_string(),
+            values: vec![-1, 300],
+            payload: vec![0x0a, 0x0b],
+            ratio: 1.5,
+            child: Some(Box::new(DiagnosticTestStruct {
+                id: 2,
+                name: String::new(),
+                values: vec![],
+                payload: vec![],
+                ratio: 0.1,
+                child: None,
+            })),
+        }
+    }
+
+    #[test]
+    fn to_diagnostic_test() -> TestResult {
+        let bytes = test_value().export()?;
+        assert_eq!(
+            to_diagnostic(&bytes)?,
+            r#"{0: 1, 1: "a\"b", 2: [-1, 300], 3: h'0a0b', 4: 1.5_2, 5: {0: 2, 1: "", 2: [], 3: h'', 4: 0.1_2}}"#
+        );
+        assert_eq!(from_diagnostic(&to_diagnostic(&bytes)?)?, bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dump_diagnostic_test() -> TestResult {
+        let mut bytes = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_map(2)?;
+        encoder.write_u64(0)?;
+        encoder.write_array(2)?;
+        encoder.write_string("abc")?;
+        encoder.write_tag(1)?;
+        encoder.write_i64(-500)?;
+        encoder.write_u64(1)?;
+        encoder.write_bool(true)?;
+
+        let expected = concat!(
+            "{            / @0 map /\n",
+            "  0: [       / @1 u8, @2 array /\n",
+            "    \"abc\",   / @3 string /\n",
+            "    1(-500)  / @7 tag, @8 i16 /\n",
+            "  ],\n",
+            "  1: true    / @11 u8, @12 bool /\n",
+            "}\n",
+        );
+        assert_eq!(dump_diagnostic(&bytes)?, expected);
+        assert_eq!(from_diagnostic(expected)?, bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_test() -> TestResult {
+        let cases = [
+            "0",
+            "-1",
+            "18446744073709551615",
+            "-18446744073709551616",
+            "1_0",
+            "24_3",
+            "[_0 1, 2]",
+            "{_1 }",
+            "\"\\u0000\u{3042}\"_1",
+            "h''_0",
+            "1.5_1",
+            "NaN_1",
+            "-Infinity_2",
+            "1e300",
+            "2(h'010000000000000000')",
+            "1001({1: 0, -9: 1})",
+            "1_1(0)",
+            "[null, undefined, simple(16), simple(255), false]",
+            "{[1]: {}}",
+        ];
+
+        for case in cases {
+            let bytes = from_diagnostic(case)?;
+            assert_eq!(to_diagnostic(&bytes)?, case, "hex: {}", hex::encode(&bytes));
+            assert_eq!(from_diagnostic(&dump_diagnostic(&bytes)?)?, bytes, "hex: {}", hex::encode(&bytes));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_diagnostic_test() -> TestResult {
+        let bytes = from_diagnostic(
+            r#"
+            / a hand-written test vector /
+            {
+                0: 1,           / id /
+                1: "a\"b",
+                2: [-1, 300],
+                3: h'0a 0b',
+                4: 1.5_2,
+                5: {0: 2, 1: "", 2: [], 3: h'', 4: 0.1_2}
+            }
+            "#,
+        )?;
+        assert_eq!(DiagnosticTestStruct::import(&bytes)?, test_value());
+        assert_eq!(bytes, test_value().export()?);
+
+        let bytes = from_diagnostic("1.5_1")?;
+        assert_eq!(bytes, [0xf9, 0x3e, 0x00]);
+        assert_eq!(f16::from_bits(0x3e00), f16::from_f32(1.5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn error_test() -> TestResult {
+        let cases = [("[1, 2", 5), ("{1 2}", 3), ("300_0", 5), ("1_4", 1), ("\"abc", 0), ("1 2", 2), ("[1,]", 3), ("/ open", 0)];
+        for (text, expected) in cases {
+            match from_diagnostic(text) {
+                Err(RocketPackDiagnosticError::InvalidSyntax { position, .. }) => assert_eq!(position, expected, "{text}"),
+                result => panic!("{text}: {result:?}"),
+            }
+        }
+
+        assert!(matches!(to_diagnostic(&[0x82, 0x01]), Err(RocketPackDecoderError::UnexpectedEof)));
+
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
 use std::io::Write;
 
+use half::f16;
+
 use thiserror::Error;
 
-use crate::RocketPackStruct;
+use crate::{
+    RocketPackStruct,
+    rocket_pack_tag::{TAG_NEGATIVE_BIGNUM, TAG_POSITIVE_BIGNUM, bignum_bytes},
+};
 
 type Result<T> = std::result::Result<T, RocketPackEncoderError>;
 
@@ -14,6 +19,10 @@ pub enum RocketPackEncoderError {
     IoError(#[from] std::io::Error),
     #[error("length overflow")]
     LengthOverflow { len: usize },
+    #[error("duplicate map key in canonical encoding")]
+    DuplicateMapKey,
+    #[error("container is missing items in canonical encoding (remaining: {remaining})")]
+    IncompleteContainer { remaining: usize },
 }
 
 pub trait RocketPackEncoder {
@@ -26,6 +35,7 @@ pub trait RocketPackEncoder {
     fn write_i16(&mut self, value: i16) -> Result<()>;
     fn write_i32(&mut self, value: i32) -> Result<()>;
     fn write_i64(&mut self, value: i64) -> Result<()>;
+    fn write_f16(&mut self, value: f16) -> Result<()>;
     fn write_f32(&mut self, value: f32) -> Result<()>;
     fn write_f64(&mut self, value: f64) -> Result<()>;
     fn write_bytes(&mut self, value: &[u8]) -> Result<()>;
@@ -33,22 +43,91 @@ pub trait RocketPackEncoder {
     fn write_array(&mut self, len: usize) -> Result<()>;
     fn write_map(&mut self, len: usize) -> Result<()>;
     fn write_null(&mut self) -> Result<()>;
+    fn write_tag(&mut self, tag: u64) -> Result<()>;
     fn write_struct<T: RocketPackStruct>(&mut self, value: &T) -> Result<()>;
+
+    /// Writes values that fit in 64 bits as plain integers and larger ones as a positive bignum (tag 2).
+    fn write_u128(&mut self, value: u128) -> Result<()> {
+        if let Ok(value) = u64::try_from(value) {
+            return self.write_u64(value);
+        }
+
+        self.write_tag(TAG_POSITIVE_BIGNUM)?;
+        self.write_bytes(&bignum_bytes(value))
+    }
+
+    /// Writes values in the `i64` range as plain integers and others as a positive or negative bignum (tag 2 or 3).
+    fn write_i128(&mut self, value: i128) -> Result<()> {
+        if let Ok(value) = i64::try_from(value) {
+            return self.write_i64(value);
+        }
+        if value >= 0 {
+            return self.write_u128(value as u128);
+        }
+
+        self.write_tag(TAG_NEGATIVE_BIGNUM)?;
+        self.write_bytes(&bignum_bytes((-1 - value) as u128))
+    }
 }
 
 pub struct RocketPackBytesEncoder<W: Write> {
     writer: W,
+    canonical: bool,
+    // Containers still being written in canonical mode, innermost last.
+    frames: Vec<Frame>,
+}
+
+enum Frame {
+    Array { remaining: usize },
+    Map(MapFrame),
+}
+
+// Map entries are buffered so they can be sorted by their encoded keys once the map is complete.
+struct MapFrame {
+    remaining: usize,
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+    key: Option<Vec<u8>>,
+    current: Vec<u8>,
 }
 
 impl<W: Write> RocketPackBytesEncoder<W> {
     pub fn new(writer: W) -> Self {
-        Self { writer }
+        Self {
+            writer,
+            canonical: false,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Creates an encoder that produces the deterministic encoding: shortest-form integers and lengths,
+    /// map entries sorted by their encoded keys without duplicates, and the narrowest float width that keeps the value.
+    pub fn new_canonical(writer: W) -> Self {
+        Self {
+            writer,
+            canonical: true,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Returns the writer after checking that every array and map received the items it declared.
+    /// A canonical map is only written once it is complete, so a short one would otherwise be silently dropped.
+    pub fn finish(self) -> Result<W> {
+        if let Some(frame) = self.frames.last() {
+            let remaining = match frame {
+                Frame::Array { remaining } => *remaining,
+                Frame::Map(map) => map.remaining,
+            };
+            return Err(RocketPackEncoderError::IncompleteContainer { remaining });
+        }
+
+        Ok(self.writer)
     }
 }
 
 impl<W: Write> RocketPackEncoder for RocketPackBytesEncoder<W> {
     fn write_bool(&mut self, value: bool) -> Result<()> {
-        self.write_raw_bytes(&[self.compose(7, if value { 21 } else { 20 })])
+        self.write_raw_bytes(&[self.compose(7, if value { 21 } else { 20 })])?;
+        self.end_item()
     }
 
     fn write_u8(&mut self, value: u8) -> Result<()> {
@@ -57,7 +136,7 @@ impl<W: Write> RocketPackEncoder for RocketPackBytesEncoder<W> {
         } else {
             self.write_raw_bytes(&[self.compose(0, 24), value])?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_u16(&mut self, value: u16) -> Result<()> {
@@ -69,7 +148,7 @@ impl<W: Write> RocketPackEncoder for RocketPackBytesEncoder<W> {
             self.write_raw_bytes(&[self.compose(0, 25)])?;
             self.write_raw_bytes(value.to_be_bytes().as_slice())?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_u32(&mut self, value: u32) -> Result<()> {
@@ -84,7 +163,7 @@ impl<W: Write> RocketPackEncoder for RocketPackBytesEncoder<W> {
             self.write_raw_bytes(&[self.compose(0, 26)])?;
             self.write_raw_bytes(value.to_be_bytes().as_slice())?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_u64(&mut self, value: u64) -> Result<()> {
@@ -102,113 +181,172 @@ impl<W: Write> RocketPackEncoder for RocketPackBytesEncoder<W> {
             self.write_raw_bytes(&[self.compose(0, 27)])?;
             self.write_raw_bytes(value.to_be_bytes().as_slice())?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_i8(&mut self, value: i8) -> Result<()> {
         if value >= 0 {
-            self.write_u8(value as u8)?;
+            return self.write_u8(value as u8);
+        }
+
+        let v = (-1 - value) as u8;
+        if v <= 23 {
+            self.write_raw_bytes(&[self.compose(1, v)])?;
         } else {
-            let v = (-1 - value) as u8;
-            if v <= 23 {
-                self.write_raw_bytes(&[self.compose(1, v)])?;
-            } else {
-                self.write_raw_bytes(&[self.compose(1, 24), v])?;
-            }
+            self.write_raw_bytes(&[self.compose(1, 24), v])?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_i16(&mut self, value: i16) -> Result<()> {
         if value >= 0 {
-            self.write_u16(value as u16)?;
+            return self.write_u16(value as u16);
+        }
+
+        let v = (-1 - value) as u16;
+        if v <= 23 {
+            self.write_raw_bytes(&[self.compose(1, v as u8)])?;
+        } else if v <= u8::MAX as u16 {
+            self.write_raw_bytes(&[self.compose(1, 24), v as u8])?;
         } else {
-            let v = (-1 - value) as u16;
-            if v <= 23 {
-                self.write_raw_bytes(&[self.compose(1, v as u8)])?;
-            } else if v <= u8::MAX as u16 {
-                self.write_raw_bytes(&[self.compose(1, 24), v as u8])?;
-            } else {
-                self.write_raw_bytes(&[self.compose(1, 25)])?;
-                self.write_raw_bytes(v.to_be_bytes().as_slice())?;
-            }
+            self.write_raw_bytes(&[self.compose(1, 25)])?;
+            self.write_raw_bytes(v.to_be_bytes().as_slice())?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_i32(&mut self, value: i32) -> Result<()> {
         if value >= 0 {
-            self.write_u32(value as u32)?;
+            return self.write_u32(value as u32);
+        }
+
+        let v = (-1 - value) as u32;
+        if v <= 23 {
+            self.write_raw_bytes(&[self.compose(1, v as u8)])?;
+        } else if v <= u8::MAX as u32 {
+            self.write_raw_bytes(&[self.compose(1, 24), v as u8])?;
+        } else if v <= u16::MAX as u32 {
+            self.write_raw_bytes(&[self.compose(1, 25)])?;
+            self.write_raw_bytes((v as u16).to_be_bytes().as_slice())?;
         } else {
-            let v = (-1 - value) as u32;
-            if v <= 23 {
-                self.write_raw_bytes(&[self.compose(1, v as u8)])?;
-            } else if v <= u8::MAX as u32 {
-                self.write_raw_bytes(&[self.compose(1, 24), v as u8])?;
-            } else if v <= u16::MAX as u32 {
-                self.write_raw_bytes(&[self.compose(1, 25)])?;
-                self.write_raw_bytes((v as u16).to_be_bytes().as_slice())?;
-            } else {
-                self.write_raw_bytes(&[self.compose(1, 26)])?;
-                self.write_raw_bytes(v.to_be_bytes().as_slice())?;
-            }
+            self.write_raw_bytes(&[self.compose(1, 26)])?;
+            self.write_raw_bytes(v.to_be_bytes().as_slice())?;
         }
-        Ok(())
+        self.end_item()
     }
 
     fn write_i64(&mut self, value: i64) -> Result<()> {
         if value >= 0 {
-            self.write_u64(value as u64)?;
+            return self.write_u64(value as u64);
+        }
+
+        let v = (-1 - value) as u64;
+        if v <= 23 {
+            self.write_raw_bytes(&[self.compose(1, v as u8)])?;
+        } else if v <= u8::MAX as u64 {
+            self.write_raw_bytes(&[self.compose(1, 24), v as u8])?;
+        } else if v <= u16::MAX as u64 {
+            self.write_raw_bytes(&[self.compose(1, 25)])?;
+            self.write_raw_bytes((v as u16).to_be_bytes().as_slice())?;
+        } else if v <= u32::MAX as u64 {
+            self.write_raw_bytes(&[self.compose(1, 26)])?;
+            self.write_raw_bytes((v as u32).to_be_bytes().as_slice())?;
         } else {
-            let v = (-1 - value) as u64;
-            if v <= 23 {
-                self.write_raw_bytes(&[self.compose(1, v as u8)])?;
-            } else if v <= u8::MAX as u64 {
-                self.write_raw_bytes(&[self.compose(1, 24), v as u8])?;
-            } else if v <= u16::MAX as u64 {
-                self.write_raw_bytes(&[self.compose(1, 25)])?;
-                self.write_raw_bytes((v as u16).to_be_bytes().as_slice())?;
-            } else if v <= u32::MAX as u64 {
-                self.write_raw_bytes(&[self.compose(1, 26)])?;
-                self.write_raw_bytes((v as u32).to_be_bytes().as_slice())?;
-            } else {
-                self.write_raw_bytes(&[self.compose(1, 27)])?;
-                self.write_raw_bytes(v.to_be_bytes().as_slice())?;
-            }
+            self.write_raw_bytes(&[self.compose(1, 27)])?;
+            self.write_raw_bytes(v.to_be_bytes().as_slice())?;
         }
-        Ok(())
+        self.end_item()
+    }
+
+    fn write_f16(&mut self, value: f16) -> Result<()> {
+        // Every NaN payload is written as the canonical quiet NaN in canonical mode.
+        let bits = if self.canonical && value.is_nan() { 0x7e00 } else { value.to_bits() };
+        self.write_raw_bytes(&[self.compose(7, 25)])?;
+        self.write_raw_bytes(bits.to_be_bytes().as_slice())?;
+        self.end_item()
     }
 
     fn write_f32(&mut self, value: f32) -> Result<()> {
+        if self.canonical
+            && let Some(v) = narrow_to_f16(value as f64)
+        {
+            self.write_raw_bytes(&[self.compose(7, 25)])?;
+            self.write_raw_bytes(v.to_be_bytes().as_slice())?;
+            return self.end_item();
+        }
+
         self.write_raw_bytes(&[self.compose(7, 26)])?;
-        self.write_raw_bytes(value.to_be_bytes().as_slice())
+        self.write_raw_bytes(value.to_be_bytes().as_slice())?;
+        self.end_item()
     }
 
     fn write_f64(&mut self, value: f64) -> Result<()> {
+        if self.canonical {
+            if let Some(v) = narrow_to_f16(value) {
+                self.write_raw_bytes(&[self.compose(7, 25)])?;
+                self.write_raw_bytes(v.to_be_bytes().as_slice())?;
+                return self.end_item();
+            }
+            if let Some(v) = narrow_to_f32(value) {
+                self.write_raw_bytes(&[self.compose(7, 26)])?;
+                self.write_raw_bytes(v.to_be_bytes().as_slice())?;
+                return self.end_item();
+            }
+        }
+
         self.write_raw_bytes(&[self.compose(7, 27)])?;
-        self.write_raw_bytes(value.to_be_bytes().as_slice())
+        self.write_raw_bytes(value.to_be_bytes().as_slice())?;
+        self.end_item()
     }
 
     fn write_bytes(&mut self, value: &[u8]) -> Result<()> {
         self.write_raw_len(2, value.len())?;
-        self.write_raw_bytes(value)
+        self.write_raw_bytes(value)?;
+        self.end_item()
     }
 
     fn write_string(&mut self, value: &str) -> Result<()> {
         self.write_raw_len(3, value.len())?;
-        self.write_raw_bytes(value.as_bytes())
+        self.write_raw_bytes(value.as_bytes())?;
+        self.end_item()
     }
 
     fn write_array(&mut self, len: usize) -> Result<()> {
-        self.write_raw_len(4, len)
+        self.write_raw_len(4, len)?;
+
+        if self.canonical && len > 0 {
+            self.frames.push(Frame::Array { remaining: len });
+            return Ok(());
+        }
+
+        self.end_item()
     }
 
     fn write_map(&mut self, len: usize) -> Result<()> {
-        self.write_raw_len(5, len)
+        // The header is written when the map is complete, together with its sorted entries.
+        if self.canonical && len > 0 {
+            let remaining = len.checked_mul(2).ok_or(RocketPackEncoderError::LengthOverflow { len })?;
+            self.frames.push(Frame::Map(MapFrame {
+                remaining,
+                entries: Vec::with_capacity(len),
+                key: None,
+                current: Vec::new(),
+            }));
+            return Ok(());
+        }
+
+        self.write_raw_len(5, len)?;
+        self.end_item()
     }
 
     fn write_null(&mut self) -> Result<()> {
-        self.write_raw_bytes(&[self.compose(7, 22)])
+        self.write_raw_bytes(&[self.compose(7, 22)])?;
+        self.end_item()
+    }
+
+    // A tag prefixes the next value, so it does not complete an item on its own.
+    fn write_tag(&mut self, tag: u64) -> Result<()> {
+        self.write_raw_head(6, tag)
     }
 
     fn write_struct<T: RocketPackStruct>(&mut self, value: &T) -> Result<()> {
@@ -224,7 +362,10 @@ impl<W: Write> RocketPackBytesEncoder<W> {
 
     pub(crate) fn write_raw_len(&mut self, major: u8, len: usize) -> Result<()> {
         let len: u64 = len.try_into().map_err(|_| RocketPackEncoderError::LengthOverflow { len })?;
+        self.write_raw_head(major, len)
+    }
 
+    fn write_raw_head(&mut self, major: u8, len: u64) -> Result<()> {
         if len <= 23 {
             self.write_raw_bytes(&[self.compose(major, len as u8)])?;
         } else if len <= u8::MAX as u64 {
@@ -243,7 +384,86 @@ impl<W: Write> RocketPackBytesEncoder<W> {
         Ok(())
     }
 
+    // Writes one item that was already encoded elsewhere.
+    pub(crate) fn write_encoded(&mut self, value: &[u8]) -> Result<()> {
+        self.write_raw_bytes(value)?;
+        self.end_item()
+    }
+
     fn write_raw_bytes(&mut self, value: &[u8]) -> Result<()> {
-        self.writer.write_all(value).map_err(RocketPackEncoderError::IoError)
+        let current = self.frames.iter_mut().rev().find_map(|frame| match frame {
+            Frame::Map(map) => Some(&mut map.current),
+            Frame::Array { .. } => None,
+        });
+
+        match current {
+            Some(buf) => {
+                buf.extend_from_slice(value);
+                Ok(())
+            }
+            None => self.writer.write_all(value).map_err(RocketPackEncoderError::IoError),
+        }
     }
+
+    // Marks one complete item in the innermost open container, closing every container it completes.
+    fn end_item(&mut self) -> Result<()> {
+        loop {
+            match self.frames.last_mut() {
+                None => return Ok(()),
+                Some(Frame::Array { remaining }) => {
+                    *remaining -= 1;
+                    if *remaining > 0 {
+                        return Ok(());
+                    }
+                    self.frames.pop();
+                }
+                Some(Frame::Map(map)) => {
+                    let item = std::mem::take(&mut map.current);
+                    match map.key.take() {
+                        None => map.key = Some(item),
+                        Some(key) => map.entries.push((key, item)),
+                    }
+
+                    map.remaining -= 1;
+                    if map.remaining > 0 {
+                        return Ok(());
+                    }
+
+                    let Some(Frame::Map(map)) = self.frames.pop() else {
+                        unreachable!();
+                    };
+                    self.write_sorted_map(map.entries)?;
+                }
+            }
+        }
+    }
+
+    fn write_sorted_map(&mut self, mut entries: Vec<(Vec<u8>, Vec<u8>)>) -> Result<()> {
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if entries.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(RocketPackEncoderError::DuplicateMapKey);
+        }
+
+        self.write_raw_len(5, entries.len())?;
+        for (key, value) in entries {
+            self.write_raw_bytes(&key)?;
+            self.write_raw_bytes(&value)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the half-precision bits when the conversion keeps the value exactly; NaN maps to the canonical quiet NaN.
+pub(crate) fn narrow_to_f16(value: f64) -> Option<u16> {
+    if value.is_nan() {
+        return Some(0x7e00);
+    }
+    let narrowed = f16::from_f64(value);
+    (narrowed.to_f64() == value).then(|| narrowed.to_bits())
+}
+
+pub(crate) fn narrow_to_f32(value: f64) -> Option<f32> {
+    let narrowed = value as f32;
+    (narrowed as f64 == value).then_some(narrowed)
 }
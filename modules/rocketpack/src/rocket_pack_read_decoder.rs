@@ -0,0 +1,463 @@
+use std::{cell::RefCell, io::Read};
+
+use half::f16;
+
+use crate::{FieldType, RocketPackBytesDecoder, RocketPackDecoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackStruct, rocket_pack_decoder_limits::DecoderBudget};
+
+type Result<T> = std::result::Result<T, RocketPackDecoderError>;
+
+/// Decodes rocketpack values incrementally from a `std::io::Read`.
+///
+/// Only the item being decoded is buffered, so wrap unbuffered sources such as files in a `BufReader`.
+/// Positions in errors are absolute offsets from the start of the stream.
+pub struct RocketPackReadDecoder<R: Read> {
+    // `current_type` takes `&self` but may need to pull the next head from the reader.
+    inner: RefCell<ReadBuffer<R>>,
+    scratch: Vec<u8>,
+    budget: DecoderBudget,
+}
+
+struct ReadBuffer<R: Read> {
+    reader: R,
+    // Unconsumed bytes of the next item; `pos` is the stream offset of `buf[0]`.
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> RocketPackReadDecoder<R> {
+    /// Creates a decoder without resource limits, for trusted input. Use `with_limits` for input from peers.
+    pub fn new(reader: R) -> Self {
+        Self::with_limits(reader, RocketPackDecoderLimits::unlimited())
+    }
+
+    pub fn with_limits(reader: R, limits: RocketPackDecoderLimits) -> Self {
+        Self {
+            inner: RefCell::new(ReadBuffer { reader, buf: Vec::new(), pos: 0 }),
+            scratch: Vec::new(),
+            budget: DecoderBudget::new(limits),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().reader
+    }
+
+    // Decodes the next item with a slice decoder over its buffered bytes.
+    fn decode_item<T>(&mut self, f: impl FnOnce(&mut RocketPackBytesDecoder<'_>) -> Result<T>) -> Result<T> {
+        let inner = self.inner.get_mut();
+        inner.fill_item(&self.budget)?;
+
+        let mut decoder = RocketPackBytesDecoder::with_budget(&inner.buf, self.budget);
+        let result = f(&mut decoder).map_err(|e| e.with_offset(inner.pos));
+        let consumed = decoder.position();
+        self.budget = decoder.budget();
+
+        inner.consume(consumed);
+        result
+    }
+}
+
+impl<R: Read> RocketPackDecoder for RocketPackReadDecoder<R> {
+    /// Returns the number of bytes read ahead from the stream; the length of the rest of the stream is unknown.
+    fn remaining(&self) -> usize {
+        self.inner.borrow().buf.len()
+    }
+
+    fn position(&self) -> usize {
+        self.inner.borrow().pos
+    }
+
+    fn current_type(&self) -> Result<FieldType> {
+        let mut inner = self.inner.borrow_mut();
+        inner.fill_head()?;
+        RocketPackBytesDecoder::new(&inner.buf).current_type().map_err(|e| e.with_offset(inner.pos))
+    }
+
+    fn current_len(&self) -> Result<u64> {
+        let mut inner = self.inner.borrow_mut();
+        inner.fill_head()?;
+        RocketPackBytesDecoder::new(&inner.buf).current_len().map_err(|e| e.with_offset(inner.pos))
+    }
+
+    fn read_bool(&mut self) -> Result<bool> {
+        self.decode_item(|decoder| decoder.read_bool())
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        self.decode_item(|decoder| decoder.read_u8())
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        self.decode_item(|decoder| decoder.read_u16())
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        self.decode_item(|decoder| decoder.read_u32())
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        self.decode_item(|decoder| decoder.read_u64())
+    }
+
+    fn read_i8(&mut self) -> Result<i8> {
+        self.decode_item(|decoder| decoder.read_i8())
+    }
+
+    fn read_i16(&mut self) -> Result<i16> {
+        self.decode_item(|decoder| decoder.read_i16())
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        self.decode_item(|decoder| decoder.read_i32())
+    }
+
+    fn read_i64(&mut self) -> Result<i64> {
+        self.decode_item(|decoder| decoder.read_i64())
+    }
+
+    fn read_f16(&mut self) -> Result<f16> {
+        self.decode_item(|decoder| decoder.read_f16())
+    }
+
+    fn read_f32(&mut self) -> Result<f32> {
+        self.decode_item(|decoder| decoder.read_f32())
+    }
+
+    fn read_f64(&mut self) -> Result<f64> {
+        self.decode_item(|decoder| decoder.read_f64())
+    }
+
+    fn read_bytes(&mut self) -> Result<&[u8]> {
+        let inner = self.inner.get_mut();
+        inner.fill_item(&self.budget)?;
+
+        // Move the item out of the read buffer so the returned slice outlives the next read.
+        self.scratch.clear();
+        self.scratch.append(&mut inner.buf);
+
+        let mut decoder = RocketPackBytesDecoder::with_budget(&self.scratch, self.budget);
+        let result = decoder.read_bytes_borrowed().map_err(|e| e.with_offset(inner.pos));
+        let consumed = decoder.position();
+        self.budget = decoder.budget();
+
+        inner.buf.extend_from_slice(&self.scratch[consumed..]);
+        inner.pos += consumed;
+        result
+    }
+
+    fn read_bytes_vec(&mut self) -> Result<Vec<u8>> {
+        self.decode_item(|decoder| decoder.read_bytes_vec())
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        self.decode_item(|decoder| decoder.read_string())
+    }
+
+    fn read_array(&mut self) -> Result<u64> {
+        self.decode_item(|decoder| decoder.read_array())
+    }
+
+    fn read_map(&mut self) -> Result<u64> {
+        self.decode_item(|decoder| decoder.read_map())
+    }
+
+    fn read_null(&mut self) -> Result<()> {
+        self.decode_item(|decoder| decoder.read_null())
+    }
+
+    fn read_tag(&mut self) -> Result<u64> {
+        self.decode_item(|decoder| decoder.read_tag())
+    }
+
+    fn read_struct<T: RocketPackStruct>(&mut self) -> Result<T> {
+        self.budget.enter(self.position())?;
+        let value = T::unpack(self)?;
+        self.budget.leave();
+        Ok(value)
+    }
+
+    fn skip_field(&mut self) -> Result<()> {
+        let inner = self.inner.get_mut();
+        let mut remain: u64 = 1;
+
+        while remain > 0 {
+            inner.fill_head()?;
+
+            let position = inner.pos;
+            let (major, info) = decompose(inner.buf[0]);
+            let field_type = RocketPackBytesDecoder::new(&inner.buf).current_type().map_err(|e| e.with_offset(position))?;
+
+            let len = match major {
+                0 | 1 => (info <= 28).then_some(0),
+                2..=6 => RocketPackBytesDecoder::new(&inner.buf[1..]).read_raw_len(info)?,
+                7 => matches!(info, 20..=22 | 25 | 26 | 27).then_some(0),
+                _ => None,
+            };
+            let Some(len) = len else {
+                return Err(RocketPackDecoderError::MismatchFieldType { position, field_type });
+            };
+
+            let head_len = inner.buf.len();
+            inner.consume(head_len);
+
+            match major {
+                2 | 3 => inner.discard(len)?,
+                4 => remain = remain.checked_add(len).ok_or(RocketPackDecoderError::LengthOverflow { position })?,
+                6 => remain = remain.checked_add(1).ok_or(RocketPackDecoderError::LengthOverflow { position })?,
+                5 => {
+                    let count = len.checked_mul(2).ok_or(RocketPackDecoderError::LengthOverflow { position })?;
+                    remain = remain.checked_add(count).ok_or(RocketPackDecoderError::LengthOverflow { position })?;
+                }
+                _ => {}
+            }
+
+            remain -= 1;
+        }
+
+        Ok(())
+    }
+
+    fn alloc_vec<T>(&mut self, count: u64) -> Result<Vec<T>> {
+        let position = self.position();
+        self.budget.alloc_vec(position, count)
+    }
+}
+
+impl<R: Read> ReadBuffer<R> {
+    // Buffers the head of the next item: the initial byte and its argument.
+    fn fill_head(&mut self) -> Result<()> {
+        self.fill(1)?;
+        let (major, info) = decompose(self.buf[0]);
+        self.fill(head_len(major, info))
+    }
+
+    // Buffers the whole next item, including the payload of a byte or text string.
+    fn fill_item(&mut self, budget: &DecoderBudget) -> Result<()> {
+        self.fill_head()?;
+
+        let (major, info) = decompose(self.buf[0]);
+        if major != 2 && major != 3 {
+            return Ok(());
+        }
+
+        let Some(len) = RocketPackBytesDecoder::new(&self.buf[1..]).read_raw_len(info)? else {
+            return Ok(());
+        };
+        // Check before buffering so a hostile length is rejected without reading the payload.
+        budget.check_bytes_len(self.pos, len)?;
+        let len = usize::try_from(len)
+            .ok()
+            .and_then(|len| len.checked_add(head_len(major, info)))
+            .ok_or(RocketPackDecoderError::LengthOverflow { position: self.pos })?;
+
+        self.fill(len)
+    }
+
+    fn fill(&mut self, len: usize) -> Result<()> {
+        let Some(want) = len.checked_sub(self.buf.len()).filter(|want| *want > 0) else {
+            return Ok(());
+        };
+
+        // `take` + `read_to_end` grows the buffer as data arrives instead of trusting the declared length.
+        let position = self.pos + self.buf.len();
+        let read = (&mut self.reader)
+            .take(want as u64)
+            .read_to_end(&mut self.buf)
+            .map_err(|error| RocketPackDecoderError::IoError { position, error })?;
+
+        if read < want {
+            return Err(RocketPackDecoderError::UnexpectedEof);
+        }
+
+        Ok(())
+    }
+
+    fn discard(&mut self, len: u64) -> Result<()> {
+        let position = self.pos;
+        let skipped = std::io::copy(&mut (&mut self.reader).take(len), &mut std::io::sink()).map_err(|error| RocketPackDecoderError::IoError { position, error })?;
+
+        if skipped < len {
+            return Err(RocketPackDecoderError::UnexpectedEof);
+        }
+
+        self.pos += skipped as usize;
+        Ok(())
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.buf.drain(..len);
+        self.pos += len;
+    }
+}
+
+#[inline]
+pub(crate) fn decompose(v: u8) -> (u8, u8) {
+    (v >> 5, v & 0b0001_1111)
+}
+
+// Length of the initial byte plus its argument.
+pub(crate) fn head_len(major: u8, info: u8) -> usize {
+    1 + match (major, info) {
+        (_, 24) => 1,
+        (_, 25) => 2,
+        (_, 26) => 4,
+        (_, 27) => 8,
+        (0 | 1, 28) => 16,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use testresult::TestResult;
+
+    use crate::{FieldType, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, RocketPackStruct};
+
+    use super::RocketPackReadDecoder;
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct StreamTestStruct {
+        pub id: i64,
+        pub name: String,
+        pub payload: Vec<u8>,
+        pub values: Vec<u32>,
+        pub note: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct StreamTestHeader {
+        pub id: i64,
+    }
+
+    // Returns at most one byte per call to exercise incremental reads.
+    struct OneByteReader<'a>(&'a [u8]);
+
+    impl Read for OneByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            if buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = *first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    fn test_value(id: i64) -> StreamTestStruct {
+        StreamTestStruct {
+            id,
+            name: format!("name_{id}"),
+            payload: vec![0xAA; 300],
+            values: vec![1, 300, 70_000],
+            note: Some("note".to_string()),
+        }
+    }
+
+    #[test]
+    fn normal_test() -> TestResult {
+        let mut bytes = test_value(1).export()?;
+        bytes.extend(test_value(-2).export()?);
+
+        let mut decoder = RocketPackReadDecoder::new(OneByteReader(&bytes));
+        assert_eq!(decoder.read_struct::<StreamTestStruct>()?, test_value(1));
+        assert_eq!(decoder.read_struct::<StreamTestStruct>()?, test_value(-2));
+        assert_eq!(decoder.position(), bytes.len());
+        assert!(matches!(decoder.current_type(), Err(RocketPackDecoderError::UnexpectedEof)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn primitive_test() -> TestResult {
+        let mut bytes = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_i16(-300)?;
+        encoder.write_bytes(&[1, 2, 3])?;
+        encoder.write_string("test")?;
+        encoder.write_array(2)?;
+        encoder.write_f64(1.5)?;
+        encoder.write_bool(true)?;
+        encoder.write_u128(u128::MAX)?;
+        encoder.write_i128(i128::MIN)?;
+
+        let mut decoder = RocketPackReadDecoder::new(bytes.as_slice());
+        assert_eq!(decoder.current_type()?, FieldType::I16);
+        assert_eq!(decoder.read_i16()?, -300);
+        assert_eq!(decoder.read_bytes()?, &[1, 2, 3]);
+        assert_eq!(decoder.read_string()?, "test");
+        assert_eq!(decoder.read_array()?, 2);
+        assert_eq!(decoder.read_f64()?, 1.5);
+        assert!(decoder.read_bool()?);
+        assert_eq!(decoder.read_u128()?, u128::MAX);
+        assert_eq!(decoder.read_i128()?, i128::MIN);
+
+        Ok(())
+    }
+
+    #[test]
+    fn current_len_test() -> TestResult {
+        let mut bytes = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_array(3)?;
+        encoder.write_u8(1)?;
+        encoder.write_u8(2)?;
+        encoder.write_u8(3)?;
+        // A text string head announcing 1000 bytes with no payload behind it.
+        bytes.extend_from_slice(&[0x79, 0x03, 0xe8]);
+
+        let mut decoder = RocketPackReadDecoder::new(OneByteReader(&bytes));
+        assert_eq!(decoder.current_len()?, 3);
+        assert_eq!(decoder.read_array()?, 3);
+        assert!(matches!(decoder.current_len(), Err(RocketPackDecoderError::MismatchFieldType { position: 1, .. })));
+        for _ in 0..3 {
+            decoder.read_u8()?;
+        }
+
+        // Only the head is read, so the missing payload is not noticed.
+        assert_eq!(decoder.current_len()?, 1000);
+        assert!(matches!(decoder.read_string(), Err(RocketPackDecoderError::UnexpectedEof)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn skip_field_test() -> TestResult {
+        let bytes = test_value(3).export()?;
+
+        // `StreamTestHeader` only knows tag 0, so every other field is skipped from the stream.
+        let mut decoder = RocketPackReadDecoder::new(OneByteReader(&bytes));
+        assert_eq!(decoder.read_struct::<StreamTestHeader>()?, StreamTestHeader { id: 3 });
+        assert_eq!(decoder.position(), bytes.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn error_position_test() -> TestResult {
+        let mut bytes = test_value(4).export()?;
+        let offset = bytes.len();
+        let mut encoder = RocketPackBytesEncoder::new(&mut bytes);
+        encoder.write_map(1)?;
+        encoder.write_u64(0)?;
+        encoder.write_string("not an integer")?;
+
+        let mut decoder = RocketPackReadDecoder::new(bytes.as_slice());
+        decoder.read_struct::<StreamTestStruct>()?;
+
+        let result = decoder.read_struct::<StreamTestHeader>();
+        assert!(matches!(
+            result,
+            Err(RocketPackDecoderError::MismatchFieldType { position, field_type: FieldType::String }) if position == offset + 2
+        ));
+
+        let mut decoder = RocketPackReadDecoder::new(&bytes[..offset - 1]);
+        assert!(matches!(decoder.read_struct::<StreamTestStruct>(), Err(RocketPackDecoderError::UnexpectedEof)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+
+/// Gives a generated type access to the schema it was generated from.
+///
+/// The Rust generator of `rocketpack-compiler` implements it for every struct and enum when `emit_schema: true` is set,
+/// and collects the descriptors of each package in a `SCHEMA_REGISTRY` static next to the types.
+pub trait RocketPackSchema {
+    const SCHEMA: ItemDescriptor;
+}
+
+/// A struct or enum of a schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemDescriptor {
+    /// The package path, e.g. `omnius::demo::v1`, or an empty string for sources without a package.
+    pub package: &'static str,
+    pub name: &'static str,
+    pub type_params: &'static [&'static str],
+    pub kind: ItemKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Struct { fields: &'static [FieldDescriptor] },
+    Enum { variants: &'static [VariantDescriptor] },
+}
+
+/// A field of a struct or of an enum variant. Tuple variant fields are tagged from 0 in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    pub tag: u32,
+    pub name: &'static str,
+    pub ty: TypeDescriptor,
+    /// The default value as written in schema syntax.
+    pub default: Option<&'static str>,
+}
+
+/// The type of a field, with aliases resolved. It displays in schema syntax, e.g. `Option<omnius::demo::v1::User>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeDescriptor {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F16,
+    F32,
+    F64,
+    String,
+    Bytes,
+    Option(&'static TypeDescriptor),
+    Vec(&'static TypeDescriptor),
+    Map(&'static TypeDescriptor, &'static TypeDescriptor),
+    Array(&'static TypeDescriptor, u64),
+    /// A struct or enum by its fully qualified name, e.g. `omnius::demo::v1::User`, which `SchemaRegistry::get` takes.
+    Named {
+        name: &'static str,
+        args: &'static [TypeDescriptor],
+    },
+    /// A type parameter of the enclosing generic item.
+    Param(&'static str),
+}
+
+impl fmt::Display for TypeDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => f.write_str("bool"),
+            Self::U8 => f.write_str("u8"),
+            Self::U16 => f.write_str("u16"),
+            Self::U32 => f.write_str("u32"),
+            Self::U64 => f.write_str("u64"),
+            Self::U128 => f.write_str("u128"),
+            Self::I8 => f.write_str("i8"),
+            Self::I16 => f.write_str("i16"),
+            Self::I32 => f.write_str("i32"),
+            Self::I64 => f.write_str("i64"),
+            Self::I128 => f.write_str("i128"),
+            Self::F16 => f.write_str("f16"),
+            Self::F32 => f.write_str("f32"),
+            Self::F64 => f.write_str("f64"),
+            Self::String => f.write_str("string"),
+            Self::Bytes => f.write_str("bytes"),
+            Self::Option(inner) => write!(f, "Option<{inner}>"),
+            Self::Vec(inner) => write!(f, "Vec<{inner}>"),
+            Self::Map(key, value) => write!(f, "Map<{key}, {value}>"),
+            Self::Array(inner, len) => write!(f, "[{inner}; {len}]"),
+            Self::Named { name, args } => {
+                f.write_str(name)?;
+                if args.is_empty() {
+                    return Ok(());
+                }
+                f.write_str("<")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                f.write_str(">")
+            }
+            Self::Param(name) => f.write_str(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantDescriptor {
+    pub tag: u32,
+    pub name: &'static str,
+    pub fields: &'static [FieldDescriptor],
+}
+
+impl ItemDescriptor {
+    /// Returns whether `full_name`, e.g. `omnius::demo::v1::User`, names this item.
+    pub fn is_named(&self, full_name: &str) -> bool {
+        if self.package.is_empty() {
+            return full_name == self.name;
+        }
+
+        full_name
+            .strip_suffix(self.name)
+            .and_then(|prefix| prefix.strip_suffix("::"))
+            .is_some_and(|package| package == self.package)
+    }
+
+    pub fn fields(&self) -> &'static [FieldDescriptor] {
+        match self.kind {
+            ItemKind::Struct { fields } => fields,
+            ItemKind::Enum { .. } => &[],
+        }
+    }
+
+    pub fn variants(&self) -> &'static [VariantDescriptor] {
+        match self.kind {
+            ItemKind::Struct { .. } => &[],
+            ItemKind::Enum { variants } => variants,
+        }
+    }
+}
+
+/// The items of one package, across all of its source files.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaRegistry {
+    items: &'static [&'static ItemDescriptor],
+}
+
+impl SchemaRegistry {
+    pub const fn new(items: &'static [&'static ItemDescriptor]) -> Self {
+        Self { items }
+    }
+
+    pub fn items(&self) -> &'static [&'static ItemDescriptor] {
+        self.items
+    }
+
+    /// Looks up an item by its fully qualified name, e.g. `omnius::demo::v1::User`.
+    pub fn get(&self, full_name: &str) -> Option<&'static ItemDescriptor> {
+        self.items.iter().copied().find(|item| item.is_named(full_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER: ItemDescriptor = ItemDescriptor {
+        package: "omnius::demo",
+        name: "User",
+        type_params: &[],
+        kind: ItemKind::Struct {
+            fields: &[FieldDescriptor {
+                tag: 1,
+                name: "name",
+                ty: TypeDescriptor::String,
+                default: None,
+            }],
+        },
+    };
+
+    const STATUS: ItemDescriptor = ItemDescriptor {
+        package: "",
+        name: "Status",
+        type_params: &[],
+        kind: ItemKind::Enum {
+            variants: &[VariantDescriptor {
+                tag: 1,
+                name: "Active",
+                fields: &[],
+            }],
+        },
+    };
+
+    static REGISTRY: SchemaRegistry = SchemaRegistry::new(&[&USER, &STATUS]);
+
+    #[test]
+    fn registry_get_test() {
+        assert_eq!(REGISTRY.get("omnius::demo::User"), Some(&USER));
+        assert_eq!(REGISTRY.get("Status"), Some(&STATUS));
+        assert_eq!(REGISTRY.get("User"), None);
+        assert_eq!(REGISTRY.get("demo::User"), None);
+        assert_eq!(REGISTRY.get("omnius::demoUser"), None);
+
+        assert_eq!(USER.fields()[0].name, "name");
+        assert!(USER.variants().is_empty());
+        assert_eq!(STATUS.variants()[0].name, "Active");
+    }
+
+    #[test]
+    fn type_descriptor_display_test() {
+        const PAGE: TypeDescriptor = TypeDescriptor::Named {
+            name: "omnius::demo::Page",
+            args: &[TypeDescriptor::Param("T"), TypeDescriptor::Array(&TypeDescriptor::U8, 4)],
+        };
+
+        assert_eq!(TypeDescriptor::Option(&TypeDescriptor::Vec(&TypeDescriptor::Bytes)).to_string(), "Option<Vec<bytes>>");
+        assert_eq!(
+            TypeDescriptor::Map(&TypeDescriptor::String, &PAGE).to_string(),
+            "Map<string, omnius::demo::Page<T, [u8; 4]>>"
+        );
+    }
+}
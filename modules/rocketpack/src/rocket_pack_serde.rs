@@ -0,0 +1,1150 @@
+use std::io::Write;
+
+use serde::{de, ser};
+use thiserror::Error;
+
+use crate::{
+    FieldType, RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackEncoder, RocketPackEncoderError,
+};
+
+type Result<T> = std::result::Result<T, RocketPackSerdeError>;
+
+// The encoding of `null`, which struct fields leave out the same way `None` fields are left out.
+const NULL: &[u8] = &[0xf6];
+
+#[derive(Error, Debug)]
+pub enum RocketPackSerdeError {
+    #[error(transparent)]
+    Encoder(#[from] RocketPackEncoderError),
+    #[error(transparent)]
+    Decoder(#[from] RocketPackDecoderError),
+    #[error("duplicate tag {tag}")]
+    DuplicateTag { tag: u64 },
+    #[error("{0}")]
+    Message(String),
+}
+
+impl ser::Error for RocketPackSerdeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl de::Error for RocketPackSerdeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+/// Serializes `value` into rocketpack bytes.
+///
+/// Structs become maps keyed by field position, or by the field name when it is renamed to an integer
+/// (`#[serde(rename = "1")]`); two fields ending up with the same tag are an error.
+/// Enums are written like generated enums: a single-entry map from the variant tag to its payload.
+///
+/// The output reads back with `RocketPackStruct::import` as long as byte fields are marked with
+/// `#[serde(with = "omnius_core_rocketpack::serde_bytes")]`: serde hands `Vec<u8>` over as a sequence, which becomes an
+/// array, while `RocketPackStruct` writes a byte string.
+pub fn to_vec<T: serde::Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    value.serialize(&mut RocketPackSerializer::new(&mut bytes))?;
+    Ok(bytes)
+}
+
+/// Deserializes a value from rocketpack bytes, borrowing strings and byte strings from `bytes` where the type allows it.
+/// Decoding stays within the default `RocketPackDecoderLimits`.
+pub fn from_slice<'de, T: serde::Deserialize<'de>>(bytes: &'de [u8]) -> Result<T> {
+    T::deserialize(&mut RocketPackDeserializer::with_limits(bytes, RocketPackDecoderLimits::default()))
+}
+
+fn tag_of(index: usize, name: &str) -> u64 {
+    name.parse().unwrap_or(index as u64)
+}
+
+fn name_of(names: &'static [&'static str], tag: u64) -> Result<Option<&'static str>> {
+    let mut matches = names.iter().enumerate().filter(|(index, name)| tag_of(*index, name) == tag);
+    let found = matches.next().map(|(_, name)| *name);
+    if matches.next().is_some() {
+        return Err(RocketPackSerdeError::DuplicateTag { tag });
+    }
+    Ok(found)
+}
+
+/// Writes `Vec<u8>` and `&[u8]` fields as byte strings, the way `RocketPackStruct` does, instead of arrays:
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Blob {
+///     #[serde(with = "omnius_core_rocketpack::serde_bytes")]
+///     payload: Vec<u8>,
+/// }
+/// ```
+pub mod serde_bytes {
+    use serde::{Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> de::Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a byte string")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<Vec<u8>, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> std::result::Result<Vec<u8>, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Vec<u8>, A::Error> {
+            let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(b) = seq.next_element()? {
+                out.push(b);
+            }
+            Ok(out)
+        }
+    }
+}
+
+pub struct RocketPackSerializer<W: Write> {
+    encoder: RocketPackBytesEncoder<W>,
+}
+
+impl<W: Write> RocketPackSerializer<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            encoder: RocketPackBytesEncoder::new(writer),
+        }
+    }
+
+    fn begin_variant(&mut self, variant_index: u32, variant: &str) -> Result<()> {
+        self.encoder.write_map(1)?;
+        self.encoder.write_u64(tag_of(variant_index as usize, variant))?;
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> ser::Serializer for &'a mut RocketPackSerializer<W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+    type SerializeSeq = SeqSerializer<'a, W>;
+    type SerializeTuple = SeqSerializer<'a, W>;
+    type SerializeTupleStruct = SeqSerializer<'a, W>;
+    type SerializeTupleVariant = StructSerializer<'a, W>;
+    type SerializeMap = MapSerializer<'a, W>;
+    type SerializeStruct = StructSerializer<'a, W>;
+    type SerializeStructVariant = StructSerializer<'a, W>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        Ok(self.encoder.write_bool(v)?)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        Ok(self.encoder.write_i8(v)?)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        Ok(self.encoder.write_i16(v)?)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        Ok(self.encoder.write_i32(v)?)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        Ok(self.encoder.write_i64(v)?)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        Ok(self.encoder.write_i128(v)?)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        Ok(self.encoder.write_u8(v)?)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        Ok(self.encoder.write_u16(v)?)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        Ok(self.encoder.write_u32(v)?)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        Ok(self.encoder.write_u64(v)?)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        Ok(self.encoder.write_u128(v)?)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        Ok(self.encoder.write_f32(v)?)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        Ok(self.encoder.write_f64(v)?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        Ok(self.encoder.write_string(v.encode_utf8(&mut [0; 4]))?)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        Ok(self.encoder.write_string(v)?)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        Ok(self.encoder.write_bytes(v)?)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Ok(self.encoder.write_null()?)
+    }
+
+    fn serialize_some<T: serde::Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(self.encoder.write_null()?)
+    }
+
+    // A struct without fields, like a generated empty message.
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(self.encoder.write_map(0)?)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, variant: &'static str) -> Result<()> {
+        self.begin_variant(variant_index, variant)?;
+        Ok(self.encoder.write_map(0)?)
+    }
+
+    fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(self, name: &'static str, variant_index: u32, variant: &'static str, value: &T) -> Result<()> {
+        let mut fields = ser::Serializer::serialize_tuple_variant(self, name, variant_index, variant, 1)?;
+        ser::SerializeTupleVariant::serialize_field(&mut fields, value)?;
+        ser::SerializeTupleVariant::end(fields)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        SeqSerializer::new(self, len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        SeqSerializer::new(self, Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct> {
+        SeqSerializer::new(self, Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, variant_index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant> {
+        self.begin_variant(variant_index, variant)?;
+        Ok(StructSerializer::new(self))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        MapSerializer::new(self, len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(StructSerializer::new(self))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, variant_index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant> {
+        self.begin_variant(variant_index, variant)?;
+        Ok(StructSerializer::new(self))
+    }
+}
+
+// Elements are written straight through when the length is known up front and buffered otherwise.
+pub struct SeqSerializer<'a, W: Write> {
+    ser: &'a mut RocketPackSerializer<W>,
+    pending: Option<(usize, Vec<u8>)>,
+}
+
+impl<'a, W: Write> SeqSerializer<'a, W> {
+    fn new(ser: &'a mut RocketPackSerializer<W>, len: Option<usize>) -> Result<Self> {
+        let pending = match len {
+            Some(len) => {
+                ser.encoder.write_array(len)?;
+                None
+            }
+            None => Some((0, Vec::new())),
+        };
+        Ok(Self { ser, pending })
+    }
+
+    fn element<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match self.pending.as_mut() {
+            Some((count, buf)) => {
+                value.serialize(&mut RocketPackSerializer::new(buf))?;
+                *count += 1;
+                Ok(())
+            }
+            None => value.serialize(&mut *self.ser),
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        if let Some((count, buf)) = self.pending {
+            self.ser.encoder.write_array(count)?;
+            self.ser.encoder.write_encoded(&buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeSeq for SeqSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<W: Write> ser::SerializeTuple for SeqSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<W: Write> ser::SerializeTupleStruct for SeqSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+pub struct MapSerializer<'a, W: Write> {
+    ser: &'a mut RocketPackSerializer<W>,
+    pending: Option<(usize, Vec<u8>)>,
+}
+
+impl<'a, W: Write> MapSerializer<'a, W> {
+    fn new(ser: &'a mut RocketPackSerializer<W>, len: Option<usize>) -> Result<Self> {
+        let pending = match len {
+            Some(len) => {
+                ser.encoder.write_map(len)?;
+                None
+            }
+            None => Some((0, Vec::new())),
+        };
+        Ok(Self { ser, pending })
+    }
+
+    fn item<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match self.pending.as_mut() {
+            Some((_, buf)) => value.serialize(&mut RocketPackSerializer::new(buf)),
+            None => value.serialize(&mut *self.ser),
+        }
+    }
+}
+
+impl<W: Write> ser::SerializeMap for MapSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_key<T: serde::Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        if let Some((count, _)) = self.pending.as_mut() {
+            *count += 1;
+        }
+        self.item(key)
+    }
+
+    fn serialize_value<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<()> {
+        if let Some((count, buf)) = self.pending {
+            self.ser.encoder.write_map(count)?;
+            self.ser.encoder.write_encoded(&buf)?;
+        }
+        Ok(())
+    }
+}
+
+// Null fields are left out of the map and its entry count comes first, so the entries of a struct are written to one
+// buffer that is copied to the outer encoder at the end.
+pub struct StructSerializer<'a, W: Write> {
+    ser: &'a mut RocketPackSerializer<W>,
+    index: usize,
+    tags: Vec<u64>,
+    count: usize,
+    entries: Vec<u8>,
+}
+
+impl<'a, W: Write> StructSerializer<'a, W> {
+    fn new(ser: &'a mut RocketPackSerializer<W>) -> Self {
+        Self {
+            ser,
+            index: 0,
+            tags: Vec::new(),
+            count: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn next_tag(&mut self, key: &str) -> Result<u64> {
+        let tag = tag_of(self.index, key);
+        self.index += 1;
+
+        if self.tags.contains(&tag) {
+            return Err(RocketPackSerdeError::DuplicateTag { tag });
+        }
+        self.tags.push(tag);
+        Ok(tag)
+    }
+
+    fn field<T: serde::Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        let tag = self.next_tag(key)?;
+
+        let start = self.entries.len();
+        RocketPackSerializer::new(&mut self.entries).encoder.write_u64(tag)?;
+        let value_start = self.entries.len();
+        value.serialize(&mut RocketPackSerializer::new(&mut self.entries))?;
+
+        if &self.entries[value_start..] == NULL {
+            self.entries.truncate(start);
+        } else {
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        self.ser.encoder.write_map(self.count)?;
+        self.ser.encoder.write_encoded(&self.entries)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStruct for StructSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        self.next_tag(key)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<W: Write> ser::SerializeStructVariant for StructSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        self.next_tag(key)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+// Tuple variant fields are keyed by position, like generated tuple variants.
+impl<W: Write> ser::SerializeTupleVariant for StructSerializer<'_, W> {
+    type Ok = ();
+    type Error = RocketPackSerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.field("", value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+pub struct RocketPackDeserializer<'de> {
+    decoder: RocketPackBytesDecoder<'de>,
+}
+
+impl<'de> RocketPackDeserializer<'de> {
+    /// Creates a deserializer without resource limits, like `RocketPackBytesDecoder::new`.
+    pub fn new(bytes: &'de [u8]) -> Self {
+        Self {
+            decoder: RocketPackBytesDecoder::new(bytes),
+        }
+    }
+
+    pub fn with_limits(bytes: &'de [u8], limits: RocketPackDecoderLimits) -> Self {
+        Self {
+            decoder: RocketPackBytesDecoder::with_limits(bytes, limits),
+        }
+    }
+
+    // Runs `f` one level deeper so the decoder's depth limit applies to nested containers.
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.decoder.enter()?;
+        let value = f(self)?;
+        self.decoder.leave();
+        Ok(value)
+    }
+
+    fn is_null(&self) -> Result<bool> {
+        Ok(self.decoder.current_type()? == FieldType::Unknown { major: 7, info: 22 })
+    }
+
+    fn skip_entries(&mut self, count: u64) -> Result<()> {
+        for _ in 0..count {
+            self.decoder.skip_field()?;
+            self.decoder.skip_field()?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut RocketPackDeserializer<'de> {
+    type Error = RocketPackSerdeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let position = self.decoder.position();
+        match self.decoder.current_type()? {
+            FieldType::Bool => visitor.visit_bool(self.decoder.read_bool()?),
+            // `U8` also covers the one-byte negative integers, which `read_i16` reads either way.
+            FieldType::U8 => match self.decoder.read_i16()? {
+                v if v < 0 => visitor.visit_i64(v as i64),
+                v => visitor.visit_u64(v as u64),
+            },
+            FieldType::U16 | FieldType::U32 | FieldType::U64 => visitor.visit_u64(self.decoder.read_u64()?),
+            FieldType::I8 | FieldType::I16 | FieldType::I32 | FieldType::I64 => visitor.visit_i64(self.decoder.read_i64()?),
+            FieldType::F16 | FieldType::F32 | FieldType::F64 => visitor.visit_f64(self.decoder.read_f64()?),
+            FieldType::Bytes => visitor.visit_borrowed_bytes(self.decoder.read_bytes_borrowed()?),
+            FieldType::String => visitor.visit_borrowed_str(self.decoder.read_str()?),
+            FieldType::Array => self.deserialize_seq(visitor),
+            FieldType::Map => self.deserialize_map(visitor),
+            // Tags carry no meaning for serde types, so the tagged value is read in their place.
+            FieldType::Tag => {
+                self.decoder.read_tag()?;
+                self.nested(|de| de.deserialize_any(visitor))
+            }
+            FieldType::Unknown { major: 7, info: 22 } => {
+                self.decoder.read_null()?;
+                visitor.visit_unit()
+            }
+            field_type => Err(RocketPackDecoderError::MismatchFieldType { position, field_type }.into()),
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.decoder.read_bool()?)
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(self.decoder.read_i8()?)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i16(self.decoder.read_i16()?)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.decoder.read_i32()?)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.decoder.read_i64()?)
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i128(self.decoder.read_i128()?)
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.decoder.read_u8()?)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(self.decoder.read_u16()?)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.decoder.read_u32()?)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.decoder.read_u64()?)
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u128(self.decoder.read_u128()?)
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(self.decoder.read_f32()?)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(self.decoder.read_f64()?)
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.decoder.read_str()?;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(de::Error::invalid_value(de::Unexpected::Str(value), &"a single character")),
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self.decoder.read_str()?)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_bytes(self.decoder.read_bytes_borrowed()?)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_null()? {
+            self.decoder.read_null()?;
+            return visitor.visit_none();
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.decoder.read_null()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        if self.is_null()? {
+            return self.deserialize_unit(visitor);
+        }
+        let count = self.decoder.read_map()?;
+        self.skip_entries(count)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // Byte strings are also accepted here, so `Vec<u8>` fields read what `RocketPackStruct` writes for them.
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.decoder.current_type()? == FieldType::Bytes {
+            let bytes = self.decoder.read_bytes_borrowed()?;
+            return visitor.visit_seq(de::value::SeqDeserializer::new(bytes.iter().copied()));
+        }
+
+        let count = self.decoder.read_array()?;
+        self.nested(|de| visitor.visit_seq(SeqAccess { de, remaining: count }))
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let count = self.decoder.read_map()?;
+        self.nested(|de| visitor.visit_map(MapAccess { de, remaining: count }))
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        let count = self.decoder.read_map()?;
+        self.nested(|de| visitor.visit_map(StructAccess { de, remaining: count, fields }))
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(self, _name: &'static str, variants: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        let count = self.decoder.read_map()?;
+        if count != 1 {
+            return Err(de::Error::invalid_length(count as usize, &"a map with a single variant"));
+        }
+        self.nested(|de| visitor.visit_enum(EnumAccess { de, variants }))
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.decoder.skip_field()?;
+        visitor.visit_unit()
+    }
+}
+
+struct SeqAccess<'a, 'de> {
+    de: &'a mut RocketPackDeserializer<'de>,
+    remaining: u64,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_, 'de> {
+    type Error = RocketPackSerdeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        usize::try_from(self.remaining).ok()
+    }
+}
+
+struct MapAccess<'a, 'de> {
+    de: &'a mut RocketPackDeserializer<'de>,
+    remaining: u64,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_, 'de> {
+    type Error = RocketPackSerdeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        usize::try_from(self.remaining).ok()
+    }
+}
+
+// Maps integer tags back to field names; unknown tags are handed over as names serde does not know, so they are ignored.
+struct StructAccess<'a, 'de> {
+    de: &'a mut RocketPackDeserializer<'de>,
+    remaining: u64,
+    fields: &'static [&'static str],
+}
+
+impl<'de> de::MapAccess<'de> for StructAccess<'_, 'de> {
+    type Error = RocketPackSerdeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        let tag = self.de.decoder.read_u64()?;
+        match name_of(self.fields, tag)? {
+            Some(name) => seed.deserialize(de::value::StrDeserializer::<RocketPackSerdeError>::new(name)).map(Some),
+            None => seed.deserialize(de::value::StringDeserializer::<RocketPackSerdeError>::new(tag.to_string())).map(Some),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        usize::try_from(self.remaining).ok()
+    }
+}
+
+struct EnumAccess<'a, 'de> {
+    de: &'a mut RocketPackDeserializer<'de>,
+    variants: &'static [&'static str],
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for EnumAccess<'a, 'de> {
+    type Error = RocketPackSerdeError;
+    type Variant = &'a mut RocketPackDeserializer<'de>;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let tag = self.de.decoder.read_u64()?;
+        let value = match name_of(self.variants, tag)? {
+            Some(name) => seed.deserialize(de::value::StrDeserializer::<RocketPackSerdeError>::new(name))?,
+            None => seed.deserialize(de::value::U64Deserializer::<RocketPackSerdeError>::new(tag))?,
+        };
+        Ok((value, self.de))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut RocketPackDeserializer<'de> {
+    type Error = RocketPackSerdeError;
+
+    fn unit_variant(self) -> Result<()> {
+        let count = self.decoder.read_map()?;
+        self.skip_entries(count)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        let count = self.decoder.read_map()?;
+        let mut access = IndexedAccess::new(self, count, 1);
+        let value = de::SeqAccess::next_element_seed(&mut access, seed)?;
+        access.finish()?;
+        value.ok_or_else(|| de::Error::invalid_length(0, &"a newtype variant"))
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        let count = self.decoder.read_map()?;
+        let mut access = IndexedAccess::new(self, count, len);
+        let value = visitor.visit_seq(&mut access)?;
+        access.finish()?;
+        Ok(value)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+// Reads a map keyed by position as a sequence; positions that are missing, like `None` fields, read as unit.
+struct IndexedAccess<'a, 'de> {
+    de: &'a mut RocketPackDeserializer<'de>,
+    remaining: u64,
+    index: u64,
+    len: u64,
+    key: Option<u64>,
+}
+
+impl<'a, 'de> IndexedAccess<'a, 'de> {
+    fn new(de: &'a mut RocketPackDeserializer<'de>, remaining: u64, len: usize) -> Self {
+        Self {
+            de,
+            remaining,
+            index: 0,
+            len: len as u64,
+            key: None,
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.key.is_some() {
+            self.de.decoder.skip_field()?;
+        }
+        self.de.skip_entries(self.remaining)
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for IndexedAccess<'_, 'de> {
+    type Error = RocketPackSerdeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        if self.key.is_none() && self.remaining > 0 {
+            self.remaining -= 1;
+            self.key = Some(self.de.decoder.read_u64()?);
+        }
+
+        let value = if self.key == Some(self.index) {
+            self.key = None;
+            seed.deserialize(&mut *self.de)?
+        } else {
+            seed.deserialize(de::value::UnitDeserializer::<RocketPackSerdeError>::new())?
+        };
+
+        self.index += 1;
+        Ok(Some(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        usize::try_from(self.len - self.index).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+    use testresult::TestResult;
+
+    use crate::{EmptyRocketMessage, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackStruct, RocketPackValue};
+
+    use super::{RocketPackDeserializer, RocketPackSerdeError, from_slice, to_vec};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct SerdeTestStruct {
+        pub id: u32,
+        pub delta: i64,
+        pub name: String,
+        pub note: Option<String>,
+        pub labels: Vec<String>,
+        pub values: BTreeMap<u32, String>,
+        pub ratio: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct DerivedTestStruct {
+        pub id: u32,
+        pub delta: i64,
+        pub name: String,
+        pub note: Option<String>,
+        pub labels: Vec<String>,
+        pub values: BTreeMap<u32, String>,
+        pub ratio: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct RenamedTestStruct {
+        #[serde(rename = "5")]
+        pub id: u32,
+        #[serde(rename = "2")]
+        pub name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum SerdeTestEnum {
+        Unit,
+        Newtype(Option<String>),
+        Tuple(u8, Option<String>, bool),
+        Record { id: u32, name: String },
+    }
+
+    #[test]
+    fn interop_test() -> TestResult {
+        let serde_value = SerdeTestStruct {
+            id: 1,
+            delta: -300,
+            name: "test".to_string(),
+            note: None,
+            labels: vec!["a".to_string(), "b".to_string()],
+            values: BTreeMap::from([(0, "x".to_string()), (7, "y".to_string())]),
+            ratio: 0.5,
+        };
+        let derived_value = DerivedTestStruct {
+            id: 1,
+            delta: -300,
+            name: "test".to_string(),
+            note: None,
+            labels: vec!["a".to_string(), "b".to_string()],
+            values: BTreeMap::from([(0, "x".to_string()), (7, "y".to_string())]),
+            ratio: 0.5,
+        };
+
+        let bytes = to_vec(&serde_value)?;
+        assert_eq!(bytes, derived_value.export()?);
+        assert_eq!(DerivedTestStruct::import(&bytes)?, derived_value);
+        assert_eq!(from_slice::<SerdeTestStruct>(&derived_value.export()?)?, serde_value);
+
+        let serde_value = SerdeTestStruct {
+            note: Some("note".to_string()),
+            ..serde_value
+        };
+        let derived_value = DerivedTestStruct {
+            note: Some("note".to_string()),
+            ..derived_value
+        };
+        assert_eq!(to_vec(&serde_value)?, derived_value.export()?);
+        assert_eq!(from_slice::<SerdeTestStruct>(&derived_value.export()?)?, serde_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_test() -> TestResult {
+        #[derive(Debug, PartialEq, RocketPackStruct)]
+        struct DerivedBytes {
+            pub payload: Vec<u8>,
+            pub chunks: Vec<Vec<u8>>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct SerdeBytes {
+            #[serde(with = "crate::serde_bytes")]
+            pub payload: Vec<u8>,
+            pub chunks: Vec<SerdeChunk>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(transparent)]
+        struct SerdeChunk(#[serde(with = "crate::serde_bytes")] Vec<u8>);
+
+        let derived_value = DerivedBytes {
+            payload: vec![1, 2, 3],
+            chunks: vec![vec![4], vec![]],
+        };
+        let serde_value = SerdeBytes {
+            payload: vec![1, 2, 3],
+            chunks: vec![SerdeChunk(vec![4]), SerdeChunk(vec![])],
+        };
+
+        let bytes = to_vec(&serde_value)?;
+        assert_eq!(bytes, derived_value.export()?);
+        assert_eq!(DerivedBytes::import(&bytes)?, derived_value);
+        assert_eq!(from_slice::<SerdeBytes>(&derived_value.export()?)?, serde_value);
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct PlainBytes {
+            pub payload: Vec<u8>,
+        }
+
+        // Without `serde_bytes`, `Vec<u8>` serializes as an array, which `RocketPackStruct` does not read as bytes, but
+        // the byte strings `RocketPackStruct` writes still deserialize.
+        let plain = PlainBytes { payload: vec![1, 2, 3] };
+        assert!(DerivedBytes::import(&to_vec(&plain)?).is_err());
+        assert_eq!(from_slice::<PlainBytes>(&derived_value.export()?)?, plain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_tag_test() -> TestResult {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Colliding {
+            pub first: u8,
+            #[serde(rename = "0")]
+            pub second: u8,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct CollidingSkipped {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub first: Option<u8>,
+            #[serde(rename = "0")]
+            pub second: u8,
+        }
+
+        let result = to_vec(&Colliding { first: 1, second: 2 });
+        assert!(matches!(result, Err(RocketPackSerdeError::DuplicateTag { tag: 0 })));
+        let result = to_vec(&CollidingSkipped { first: None, second: 2 });
+        assert!(matches!(result, Err(RocketPackSerdeError::DuplicateTag { tag: 0 })));
+
+        let bytes = RocketPackValue::Map(vec![(RocketPackValue::UInt(0), RocketPackValue::UInt(1))]).export()?;
+        let result = from_slice::<Colliding>(&bytes);
+        assert!(matches!(result, Err(RocketPackSerdeError::DuplicateTag { tag: 0 })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rename_test() -> TestResult {
+        let value = RenamedTestStruct { id: 3, name: "a".to_string() };
+        let bytes = to_vec(&value)?;
+        assert_eq!(
+            RocketPackValue::import(&bytes)?,
+            RocketPackValue::Map(vec![
+                (RocketPackValue::UInt(5), RocketPackValue::UInt(3)),
+                (RocketPackValue::UInt(2), RocketPackValue::String("a".to_string())),
+            ])
+        );
+        assert_eq!(from_slice::<RenamedTestStruct>(&bytes)?, value);
+
+        // Tags the type does not know are skipped.
+        let bytes = RocketPackValue::Map(vec![
+            (RocketPackValue::UInt(2), RocketPackValue::String("a".to_string())),
+            (RocketPackValue::UInt(9), RocketPackValue::Array(vec![RocketPackValue::Null])),
+            (RocketPackValue::UInt(5), RocketPackValue::UInt(3)),
+        ])
+        .export()?;
+        assert_eq!(from_slice::<RenamedTestStruct>(&bytes)?, value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn enum_test() -> TestResult {
+        assert_eq!(hex::encode(to_vec(&SerdeTestEnum::Unit)?), "a100a0");
+        assert_eq!(hex::encode(to_vec(&SerdeTestEnum::Newtype(None))?), "a101a0");
+
+        let cases = [
+            SerdeTestEnum::Unit,
+            SerdeTestEnum::Newtype(None),
+            SerdeTestEnum::Newtype(Some("a".to_string())),
+            SerdeTestEnum::Tuple(1, None, true),
+            SerdeTestEnum::Tuple(1, Some("b".to_string()), false),
+            SerdeTestEnum::Record { id: 2, name: "c".to_string() },
+        ];
+        for case in cases {
+            let bytes = to_vec(&case)?;
+            assert_eq!(from_slice::<SerdeTestEnum>(&bytes)?, case);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_message_test() -> TestResult {
+        let bytes = to_vec(&EmptyRocketMessage)?;
+        assert_eq!(bytes, vec![0xa0]);
+        assert_eq!(from_slice::<EmptyRocketMessage>(&bytes)?, EmptyRocketMessage);
+
+        Ok(())
+    }
+
+    #[test]
+    fn depth_limit_test() -> TestResult {
+        // 0x81 is a one-element array header, so this nests arrays 1000 deep.
+        let mut bytes = vec![0x81; 1000];
+        bytes.push(0x00);
+
+        let limits = RocketPackDecoderLimits {
+            max_depth: 64,
+            ..Default::default()
+        };
+        let mut deserializer = RocketPackDeserializer::with_limits(&bytes, limits);
+        let result = <serde_json::Value as Deserialize>::deserialize(&mut deserializer);
+        assert!(matches!(
+            result,
+            Err(RocketPackSerdeError::Decoder(RocketPackDecoderError::DepthLimitExceeded { limit: 64, .. }))
+        ));
+
+        Ok(())
+    }
+}
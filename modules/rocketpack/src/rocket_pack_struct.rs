@@ -1,4 +1,4 @@
-use crate::{RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, RocketPackEncoderError};
+use crate::{RocketPackBytesDecoder, RocketPackBytesEncoder, RocketPackDecoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackEncoder, RocketPackEncoderError};
 
 #[allow(clippy::len_without_is_empty)]
 pub trait RocketPackStruct {
@@ -8,11 +8,12 @@ pub trait RocketPackStruct {
     where
         Self: Sized;
 
+    /// Decodes `bytes` within the default `RocketPackDecoderLimits`.
     fn import(bytes: &[u8]) -> std::result::Result<Self, RocketPackDecoderError>
     where
         Self: Sized,
     {
-        let mut decoder = RocketPackBytesDecoder::new(bytes);
+        let mut decoder = RocketPackBytesDecoder::with_limits(bytes, RocketPackDecoderLimits::default());
         Self::unpack(&mut decoder)
     }
 
@@ -22,6 +23,24 @@ pub trait RocketPackStruct {
         Self::pack(&mut encoder, self)?;
         Ok(bytes)
     }
+
+    /// Decodes `bytes`, rejecting anything that is not in the canonical encoding.
+    fn import_strict(bytes: &[u8]) -> std::result::Result<Self, RocketPackDecoderError>
+    where
+        Self: Sized,
+    {
+        let mut decoder = RocketPackBytesDecoder::new_strict(bytes);
+        Self::unpack(&mut decoder)
+    }
+
+    /// Encodes `self` in the canonical encoding, which is byte-identical for equal values and suitable for signing.
+    fn export_canonical(&self) -> std::result::Result<Vec<u8>, RocketPackEncoderError> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut encoder = RocketPackBytesEncoder::new_canonical(&mut bytes);
+        Self::pack(&mut encoder, self)?;
+        encoder.finish()?;
+        Ok(bytes)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,32 @@
+use crate::RocketPackDecoderError;
+
+// https://www.iana.org/assignments/cbor-tags/cbor-tags.xhtml
+
+/// Epoch-based date/time: an integer or float number of seconds since 1970-01-01T00:00Z.
+pub const TAG_EPOCH_DATETIME: u64 = 1;
+/// Unsigned bignum: a big-endian byte string.
+pub const TAG_POSITIVE_BIGNUM: u64 = 2;
+/// Negative bignum: a big-endian byte string holding `-1 - n`.
+pub const TAG_NEGATIVE_BIGNUM: u64 = 3;
+/// Extended time (RFC 9581): a map with the seconds under key `1` and the nanoseconds under key `-9`.
+pub const TAG_EXTENDED_TIME: u64 = 1001;
+
+// Big-endian bytes of `value` without leading zeros, as bignums are written in preferred serialization.
+pub(crate) fn bignum_bytes(value: u128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+pub(crate) fn bignum_from_bytes(position: usize, bytes: &[u8]) -> Result<u128, RocketPackDecoderError> {
+    // Leading zeros are allowed by CBOR, so only the significant bytes have to fit.
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    if bytes.len() > 16 {
+        return Err(RocketPackDecoderError::LengthOverflow { position });
+    }
+
+    let mut buf = [0_u8; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Ok(u128::from_be_bytes(buf))
+}
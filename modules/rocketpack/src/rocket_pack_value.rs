@@ -0,0 +1,452 @@
+use std::fmt;
+
+#[cfg(feature = "json")]
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL};
+
+use crate::{FieldType, RocketPackDecoder, RocketPackDecoderError, RocketPackEncoder, RocketPackEncoderError, RocketPackStruct, f16};
+
+/// A rocketpack value decoded without knowing its schema.
+///
+/// Negative integers are limited to the `i64` range. Floats are split into `F16`, `F32` and `F64` instead of a single
+/// `Float` variant so they keep the width they were encoded with, and exporting a decoded value writes the same bytes a
+/// typed struct would. `as_float` reads any of the three as an `f64`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RocketPackValue {
+    Bool(bool),
+    UInt(u64),
+    NInt(i64),
+    F16(f16),
+    F32(f32),
+    F64(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<RocketPackValue>),
+    Map(Vec<(RocketPackValue, RocketPackValue)>),
+    Null,
+    Tagged(u64, Box<RocketPackValue>),
+}
+
+impl RocketPackStruct for RocketPackValue {
+    fn pack(encoder: &mut impl RocketPackEncoder, value: &Self) -> std::result::Result<(), RocketPackEncoderError> {
+        match value {
+            Self::Bool(v) => encoder.write_bool(*v),
+            Self::UInt(v) => encoder.write_u64(*v),
+            Self::NInt(v) => encoder.write_i64(*v),
+            Self::F16(v) => encoder.write_f16(*v),
+            Self::F32(v) => encoder.write_f32(*v),
+            Self::F64(v) => encoder.write_f64(*v),
+            Self::Bytes(v) => encoder.write_bytes(v),
+            Self::String(v) => encoder.write_string(v),
+            Self::Array(values) => {
+                encoder.write_array(values.len())?;
+                for v in values {
+                    encoder.write_struct(v)?;
+                }
+                Ok(())
+            }
+            Self::Map(entries) => {
+                encoder.write_map(entries.len())?;
+                for (key, value) in entries {
+                    encoder.write_struct(key)?;
+                    encoder.write_struct(value)?;
+                }
+                Ok(())
+            }
+            Self::Null => encoder.write_null(),
+            Self::Tagged(tag, v) => {
+                encoder.write_tag(*tag)?;
+                encoder.write_struct(v.as_ref())
+            }
+        }
+    }
+
+    // Nested values go through `read_struct` so the decoder's depth limit applies.
+    fn unpack(decoder: &mut impl RocketPackDecoder) -> std::result::Result<Self, RocketPackDecoderError>
+    where
+        Self: Sized,
+    {
+        let position = decoder.position();
+        let field_type = decoder.current_type()?;
+
+        Ok(match field_type {
+            FieldType::Bool => Self::Bool(decoder.read_bool()?),
+            // `U8` also covers the one-byte negative integers, which `read_i16` reads either way.
+            FieldType::U8 => match decoder.read_i16()? {
+                v if v < 0 => Self::NInt(v as i64),
+                v => Self::UInt(v as u64),
+            },
+            FieldType::U16 | FieldType::U32 | FieldType::U64 => Self::UInt(decoder.read_u64()?),
+            FieldType::I8 | FieldType::I16 | FieldType::I32 | FieldType::I64 => Self::NInt(decoder.read_i64()?),
+            FieldType::F16 => Self::F16(decoder.read_f16()?),
+            FieldType::F32 => Self::F32(decoder.read_f32()?),
+            FieldType::F64 => Self::F64(decoder.read_f64()?),
+            FieldType::Bytes => Self::Bytes(decoder.read_bytes_vec()?),
+            FieldType::String => Self::String(decoder.read_string()?),
+            FieldType::Array => {
+                let count = decoder.read_array()?;
+                let mut values = decoder.alloc_vec(count)?;
+                for _ in 0..count {
+                    values.push(decoder.read_struct::<Self>()?);
+                }
+                Self::Array(values)
+            }
+            FieldType::Map => {
+                let count = decoder.read_map()?;
+                let mut entries = decoder.alloc_vec(count)?;
+                for _ in 0..count {
+                    let key = decoder.read_struct::<Self>()?;
+                    let value = decoder.read_struct::<Self>()?;
+                    entries.push((key, value));
+                }
+                Self::Map(entries)
+            }
+            FieldType::Tag => {
+                let tag = decoder.read_tag()?;
+                Self::Tagged(tag, Box::new(decoder.read_struct::<Self>()?))
+            }
+            FieldType::Unknown { major: 7, info: 22 } => {
+                decoder.read_null()?;
+                Self::Null
+            }
+            field_type => return Err(RocketPackDecoderError::MismatchFieldType { position, field_type }),
+        })
+    }
+}
+
+impl RocketPackValue {
+    /// Returns the value of a float of any width, widened to `f64`.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::F16(v) => Some(f64::from(*v)),
+            Self::F32(v) => Some(*v as f64),
+            Self::F64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn fmt_with_indent(&self, f: &mut fmt::Formatter<'_>, depth: usize, pretty: bool) -> fmt::Result {
+        let (open_sep, sep, close_sep) = if pretty {
+            (
+                format!("\n{}", "  ".repeat(depth + 1)),
+                format!(",\n{}", "  ".repeat(depth + 1)),
+                format!("\n{}", "  ".repeat(depth)),
+            )
+        } else {
+            (String::new(), ", ".to_string(), String::new())
+        };
+
+        match self {
+            Self::Bool(v) => write!(f, "{v}"),
+            Self::UInt(v) => write!(f, "{v}"),
+            Self::NInt(v) => write!(f, "{v}"),
+            Self::F16(v) => fmt_float(f, f64::from(*v), &f32::from(*v)),
+            Self::F32(v) => fmt_float(f, *v as f64, v),
+            Self::F64(v) => fmt_float(f, *v, v),
+            Self::Bytes(v) => {
+                write!(f, "h'")?;
+                for b in v {
+                    write!(f, "{b:02x}")?;
+                }
+                write!(f, "'")
+            }
+            Self::String(v) => write!(f, "{v:?}"),
+            Self::Array(values) if values.is_empty() => write!(f, "[]"),
+            Self::Array(values) => {
+                write!(f, "[{open_sep}")?;
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{sep}")?;
+                    }
+                    v.fmt_with_indent(f, depth + 1, pretty)?;
+                }
+                write!(f, "{close_sep}]")
+            }
+            Self::Map(entries) if entries.is_empty() => write!(f, "{{}}"),
+            Self::Map(entries) => {
+                write!(f, "{{{open_sep}")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{sep}")?;
+                    }
+                    key.fmt_with_indent(f, depth + 1, pretty)?;
+                    write!(f, ": ")?;
+                    value.fmt_with_indent(f, depth + 1, pretty)?;
+                }
+                write!(f, "{close_sep}}}")
+            }
+            Self::Null => write!(f, "null"),
+            Self::Tagged(tag, v) => {
+                write!(f, "{tag}(")?;
+                v.fmt_with_indent(f, depth, pretty)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+// Writes finite floats with the shortest representation of their own width, so an `f32` of 0.1 stays `0.1`.
+fn fmt_float(f: &mut fmt::Formatter<'_>, value: f64, shortest: &dyn fmt::Debug) -> fmt::Result {
+    if value.is_nan() {
+        write!(f, "NaN")
+    } else if value.is_infinite() {
+        write!(f, "{}Infinity", if value < 0.0 { "-" } else { "" })
+    } else {
+        write!(f, "{shortest:?}")
+    }
+}
+
+/// Formats the value in CBOR diagnostic notation; `{:#}` spreads containers over indented lines.
+impl fmt::Display for RocketPackValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0, f.alternate())
+    }
+}
+
+/// Byte strings become unpadded URL-safe base64, non-string map keys are rendered with `Display`,
+/// tagged values become `{"tag": n, "value": v}` and non-finite floats become `null`.
+#[cfg(feature = "json")]
+impl From<RocketPackValue> for serde_json::Value {
+    fn from(value: RocketPackValue) -> Self {
+        match value {
+            RocketPackValue::Bool(v) => serde_json::Value::Bool(v),
+            RocketPackValue::UInt(v) => serde_json::Value::from(v),
+            RocketPackValue::NInt(v) => serde_json::Value::from(v),
+            RocketPackValue::F16(v) => json_float(f64::from(v)),
+            RocketPackValue::F32(v) => json_float(v as f64),
+            RocketPackValue::F64(v) => json_float(v),
+            RocketPackValue::Bytes(v) => serde_json::Value::String(BASE64_URL.encode(v)),
+            RocketPackValue::String(v) => serde_json::Value::String(v),
+            RocketPackValue::Array(values) => serde_json::Value::Array(values.into_iter().map(Into::into).collect()),
+            RocketPackValue::Map(entries) => serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let key = match key {
+                            RocketPackValue::String(key) => key,
+                            key => key.to_string(),
+                        };
+                        (key, value.into())
+                    })
+                    .collect(),
+            ),
+            RocketPackValue::Null => serde_json::Value::Null,
+            RocketPackValue::Tagged(tag, v) => serde_json::json!({ "tag": tag, "value": serde_json::Value::from(*v) }),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_float(value: f64) -> serde_json::Value {
+    serde_json::Number::from_f64(value).map_or(serde_json::Value::Null, serde_json::Value::Number)
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Value> for RocketPackValue {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Self::Null,
+            serde_json::Value::Bool(v) => Self::Bool(v),
+            serde_json::Value::Number(v) => {
+                if let Some(v) = v.as_u64() {
+                    Self::UInt(v)
+                } else if let Some(v) = v.as_i64() {
+                    Self::NInt(v)
+                } else {
+                    Self::F64(v.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            serde_json::Value::String(v) => Self::String(v),
+            serde_json::Value::Array(values) => Self::Array(values.into_iter().map(Into::into).collect()),
+            serde_json::Value::Object(entries) => Self::Map(entries.into_iter().map(|(key, value)| (Self::String(key), value.into())).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testresult::TestResult;
+
+    use crate::{RocketPackBytesDecoder, RocketPackDecoderError, RocketPackDecoderLimits, RocketPackStruct, f16};
+
+    use super::RocketPackValue;
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct ValueTestStruct {
+        pub id: u32,
+        pub delta: i16,
+        pub name: String,
+        pub payload: Vec<u8>,
+        pub labels: Vec<Option<String>>,
+        pub ratio: f64,
+    }
+
+    #[test]
+    fn roundtrip_test() -> TestResult {
+        let expected = ValueTestStruct {
+            id: 300,
+            delta: -2,
+            name: "abc".to_string(),
+            payload: vec![0x0a, 0x0b],
+            labels: vec![Some("x".to_string()), None],
+            ratio: 1.5,
+        };
+        let bytes = expected.export()?;
+
+        let value = RocketPackValue::import(&bytes)?;
+        assert_eq!(
+            value,
+            RocketPackValue::Map(vec![
+                (RocketPackValue::UInt(0), RocketPackValue::UInt(300)),
+                (RocketPackValue::UInt(1), RocketPackValue::NInt(-2)),
+                (RocketPackValue::UInt(2), RocketPackValue::String("abc".to_string())),
+                (RocketPackValue::UInt(3), RocketPackValue::Bytes(vec![0x0a, 0x0b])),
+                (
+                    RocketPackValue::UInt(4),
+                    RocketPackValue::Array(vec![RocketPackValue::String("x".to_string()), RocketPackValue::Null])
+                ),
+                (RocketPackValue::UInt(5), RocketPackValue::F64(1.5)),
+            ])
+        );
+
+        assert_eq!(value.export()?, bytes);
+        assert_eq!(ValueTestStruct::import(&value.export()?)?, expected);
+
+        Ok(())
+    }
+
+    #[derive(Debug, Clone, PartialEq, RocketPackStruct)]
+    struct FloatTestStruct {
+        pub half: f16,
+        pub single: f32,
+        pub double: f64,
+    }
+
+    #[test]
+    fn float_width_test() -> TestResult {
+        let expected = FloatTestStruct {
+            half: f16::from_f32(0.5),
+            single: 1.5,
+            double: 0.1,
+        };
+        let bytes = expected.export()?;
+
+        let value = RocketPackValue::import(&bytes)?;
+        assert_eq!(
+            value,
+            RocketPackValue::Map(vec![
+                (RocketPackValue::UInt(0), RocketPackValue::F16(f16::from_f32(0.5))),
+                (RocketPackValue::UInt(1), RocketPackValue::F32(1.5)),
+                (RocketPackValue::UInt(2), RocketPackValue::F64(0.1)),
+            ])
+        );
+        assert_eq!(value.export()?, bytes);
+        assert_eq!(FloatTestStruct::import(&value.export()?)?, expected);
+
+        assert_eq!(RocketPackValue::F16(f16::from_f32(0.5)).as_float(), Some(0.5));
+        assert_eq!(RocketPackValue::F32(1.5).as_float(), Some(1.5));
+        assert_eq!(RocketPackValue::F64(0.1).as_float(), Some(0.1));
+        assert_eq!(RocketPackValue::UInt(1).as_float(), None);
+
+        assert_eq!(RocketPackValue::F32(0.1).to_string(), "0.1");
+        assert_eq!(RocketPackValue::F16(f16::INFINITY).to_string(), "Infinity");
+
+        Ok(())
+    }
+
+    #[test]
+    fn tagged_test() -> TestResult {
+        let value = RocketPackValue::Tagged(
+            1,
+            Box::new(RocketPackValue::Array(vec![
+                RocketPackValue::NInt(-1),
+                RocketPackValue::NInt(-1000),
+                RocketPackValue::NInt(i64::MIN),
+                RocketPackValue::UInt(u64::MAX),
+                RocketPackValue::Bool(false),
+            ])),
+        );
+        let bytes = value.export()?;
+        assert_eq!(bytes[0], 0xc1);
+        assert_eq!(RocketPackValue::import(&bytes)?, value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn display_test() -> TestResult {
+        let value = RocketPackValue::Map(vec![
+            (RocketPackValue::UInt(1), RocketPackValue::String("abc".to_string())),
+            (
+                RocketPackValue::UInt(2),
+                RocketPackValue::Array(vec![RocketPackValue::UInt(1), RocketPackValue::NInt(-2), RocketPackValue::Bytes(vec![0x0a, 0x0b])]),
+            ),
+            (RocketPackValue::UInt(3), RocketPackValue::Tagged(1, Box::new(RocketPackValue::F64(1.0)))),
+            (RocketPackValue::UInt(4), RocketPackValue::Array(vec![])),
+        ]);
+
+        assert_eq!(value.to_string(), r#"{1: "abc", 2: [1, -2, h'0a0b'], 3: 1(1.0), 4: []}"#);
+        assert_eq!(
+            format!("{value:#}"),
+            "{\n  1: \"abc\",\n  2: [\n    1,\n    -2,\n    h'0a0b'\n  ],\n  3: 1(1.0),\n  4: []\n}"
+        );
+        assert_eq!(RocketPackValue::F64(f64::NEG_INFINITY).to_string(), "-Infinity");
+        assert_eq!(RocketPackValue::Null.to_string(), "null");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_test() -> TestResult {
+        let value = RocketPackValue::Map(vec![
+            (RocketPackValue::UInt(1), RocketPackValue::Bytes(vec![0xff, 0xfe])),
+            (RocketPackValue::String("name".to_string()), RocketPackValue::NInt(-3)),
+            (RocketPackValue::UInt(2), RocketPackValue::Tagged(1, Box::new(RocketPackValue::F64(f64::NAN)))),
+        ]);
+
+        let json: serde_json::Value = value.into();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "1": "__4",
+                "name": -3,
+                "2": { "tag": 1, "value": null },
+            })
+        );
+
+        let value: RocketPackValue = serde_json::json!({ "a": [1, -1, 0.5, true, null, "s"] }).into();
+        assert_eq!(
+            value,
+            RocketPackValue::Map(vec![(
+                RocketPackValue::String("a".to_string()),
+                RocketPackValue::Array(vec![
+                    RocketPackValue::UInt(1),
+                    RocketPackValue::NInt(-1),
+                    RocketPackValue::F64(0.5),
+                    RocketPackValue::Bool(true),
+                    RocketPackValue::Null,
+                    RocketPackValue::String("s".to_string()),
+                ])
+            )])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn depth_limit_test() -> TestResult {
+        // 0x81 is a one-element array header, so this nests arrays 1000 deep.
+        let mut bytes = vec![0x81; 1000];
+        bytes.push(0x00);
+
+        let limits = RocketPackDecoderLimits {
+            max_depth: 64,
+            ..Default::default()
+        };
+        let mut decoder = RocketPackBytesDecoder::with_limits(&bytes, limits);
+        let result = RocketPackValue::unpack(&mut decoder);
+        assert!(matches!(result, Err(RocketPackDecoderError::DepthLimitExceeded { limit: 64, .. })));
+
+        Ok(())
+    }
+}